@@ -54,7 +54,7 @@ fn bench_window_operator(c: &mut Criterion) {
     let budget = MemoryBudgetImpl::new(4 * 1024 * 1024);
     c.bench_function("window_op", |b| {
         b.iter(|| {
-            let _ = window.eval_block(&[batch.clone()], &budget).unwrap();
+            let _ = window.eval_block(std::slice::from_ref(&batch), &budget).unwrap();
         })
     });
 }
@@ -8,6 +8,10 @@ use emsqrt_te::plan_te;
 use std::fs;
 use std::path::PathBuf;
 
+mod profile;
+mod report;
+mod serve;
+
 #[derive(Parser)]
 #[command(name = "emsqrt")]
 #[command(about = "EM-√: External-Memory ETL Engine with hard peak-RAM guarantees", long_about = None)]
@@ -17,6 +21,7 @@ struct Cli {
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Execute a pipeline from a YAML file
     Run {
@@ -75,6 +80,82 @@ enum Commands {
         /// Maximum parallel tasks (overrides config)
         #[arg(long)]
         max_parallel: Option<usize>,
+
+        /// Dump sample rows produced by this plan step (operator id; see
+        /// 'emsqrt explain' for ids)
+        #[arg(long)]
+        debug_step: Option<u64>,
+
+        /// How many rows --debug-step prints (default 20)
+        #[arg(long, default_value = "20")]
+        dump_rows: usize,
+
+        /// Write the run manifest (JSON) to this path
+        #[arg(long)]
+        manifest_out: Option<PathBuf>,
+
+        /// Cache planning output in this directory, keyed by pipeline hash
+        /// (also via EMSQRT_PLAN_CACHE_DIR); repeat runs skip planning
+        #[arg(long)]
+        plan_cache: Option<PathBuf>,
+    },
+
+    /// Execute a pipeline and verify end-to-end record counts/checksums
+    Verify {
+        /// Path to the pipeline YAML file
+        #[arg(short, long)]
+        pipeline: PathBuf,
+
+        /// Expected number of records delivered to sinks
+        #[arg(long)]
+        expect_records: Option<u64>,
+
+        /// Expected outputs digest (hex) from a previous run
+        #[arg(long)]
+        expect_digest: Option<String>,
+    },
+
+    /// Profile a CSV/JSONL source: per-column stats in one pass
+    Profile {
+        /// Source file (CSV or JSONL, optionally compressed)
+        source: String,
+
+        /// Maximum rows to read
+        #[arg(long, default_value = "1000000")]
+        limit: usize,
+    },
+
+    /// Render a saved run manifest as a self-contained HTML report
+    Report {
+        /// Manifest JSON written by 'emsqrt run --manifest-out'
+        #[arg(long)]
+        manifest: PathBuf,
+
+        /// Output HTML file
+        #[arg(long)]
+        out: PathBuf,
+    },
+
+    /// Compare two run manifests for source schema drift
+    Drift {
+        /// Manifest JSON from the earlier run
+        #[arg(long)]
+        old: PathBuf,
+
+        /// Manifest JSON from the newer run
+        #[arg(long)]
+        new: PathBuf,
+    },
+
+    /// Run a pipeline and compare its output against a stored golden record
+    Golden {
+        /// Path to the pipeline YAML file
+        #[arg(short, long)]
+        pipeline: PathBuf,
+
+        /// Record the current output as the new golden baseline
+        #[arg(long)]
+        update: bool,
     },
 
     /// Validate a pipeline YAML file (syntax check)
@@ -84,6 +165,38 @@ enum Commands {
         pipeline: PathBuf,
     },
 
+    /// Generate operator documentation from the registry
+    Operators,
+
+    /// List source/sink connectors and their capabilities
+    Connectors,
+
+    /// Migrate an older pipeline YAML file to the current format
+    Migrate {
+        /// Path to the pipeline YAML file
+        #[arg(short, long)]
+        pipeline: PathBuf,
+
+        /// Rewrite the file in place (a .bak copy is kept)
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Serve a web UI for plan and run inspection
+    Serve {
+        /// Path to the pipeline YAML file
+        #[arg(short, long)]
+        pipeline: PathBuf,
+
+        /// Engine config JSON, re-read on every request (hot reload)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Port to listen on (localhost only)
+        #[arg(long, default_value = "7979")]
+        port: u16,
+    },
+
     /// Show execution plan for a pipeline (EXPLAIN)
     Explain {
         /// Path to the pipeline YAML file
@@ -115,6 +228,10 @@ fn main() {
             spill_retry_initial_ms,
             spill_retry_max_ms,
             max_parallel,
+            debug_step,
+            dump_rows,
+            manifest_out,
+            plan_cache,
         } => {
             if let Err(e) = run_pipeline(
                 &pipeline,
@@ -131,11 +248,49 @@ fn main() {
                 spill_retry_initial_ms,
                 spill_retry_max_ms,
                 max_parallel,
+                debug_step,
+                dump_rows,
+                manifest_out,
+                plan_cache,
             ) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
+        Commands::Verify {
+            pipeline,
+            expect_records,
+            expect_digest,
+        } => {
+            if let Err(e) = verify_pipeline(&pipeline, expect_records, expect_digest) {
+                eprintln!("Verification failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Profile { source, limit } => {
+            if let Err(e) = profile::profile(&source, limit) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Report { manifest, out } => {
+            if let Err(e) = report::report(&manifest, &out) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Drift { old, new } => {
+            if let Err(e) = drift_check(&old, &new) {
+                eprintln!("Drift check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Golden { pipeline, update } => {
+            if let Err(e) = golden_pipeline(&pipeline, update) {
+                eprintln!("Golden check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
         Commands::Validate { pipeline } => {
             if let Err(e) = validate_pipeline(&pipeline) {
                 eprintln!("Validation failed: {}", e);
@@ -143,6 +298,43 @@ fn main() {
             }
             println!("✓ Pipeline is valid");
         }
+        Commands::Migrate { pipeline, write } => {
+            if let Err(e) = migrate_pipeline_file(&pipeline, write) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Connectors => {
+            let engine = Engine::new(EngineConfig::default());
+            match engine {
+                Ok(engine) => {
+                    println!("{:<12} capabilities", "connector");
+                    for (name, capabilities) in engine.connector_list() {
+                        println!("{:<12} {}", name, capabilities);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Operators => {
+            print!(
+                "{}",
+                emsqrt_operators::registry::Registry::new().docs_markdown()
+            );
+        }
+        Commands::Serve {
+            pipeline,
+            config,
+            port,
+        } => {
+            if let Err(e) = serve::serve(&pipeline, config.as_ref(), port) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
         Commands::Explain {
             pipeline,
             memory_cap,
@@ -155,6 +347,7 @@ fn main() {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_pipeline(
     pipeline_path: &PathBuf,
     memory_cap: Option<usize>,
@@ -170,22 +363,16 @@ fn run_pipeline(
     spill_retry_initial_ms: Option<u64>,
     spill_retry_max_ms: Option<u64>,
     max_parallel: Option<usize>,
+    debug_step: Option<u64>,
+    dump_rows: usize,
+    manifest_out: Option<PathBuf>,
+    plan_cache: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Read YAML file
     let yaml_content = fs::read_to_string(pipeline_path)?;
 
     // Parse pipeline
     let parsed = parse_yaml_pipeline(&yaml_content)?;
-    let logical_plan = parsed.plan.clone();
-
-    // Optimize
-    let optimized = rules::optimize(logical_plan);
-
-    // Lower to physical plan
-    let phys_prog = lower_to_physical(&optimized);
-
-    // Estimate work
-    let work = estimate_work(&optimized, None);
 
     // Create config
     let mut config = EngineConfig::from_env();
@@ -229,9 +416,46 @@ fn run_pipeline(
     if let Some(parallel) = max_parallel {
         config.max_parallel_tasks = parallel;
     }
-    // Plan TE execution
-    let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
-        .map_err(|e| format!("TE planning failed: {}", e))?;
+    config.debug_step = debug_step;
+    config.debug_dump_rows = dump_rows;
+
+    // Plan (or restore from the cold-start cache, keyed by the pipeline
+    // text and the memory cap that shaped TE block sizing).
+    let cache_dir = plan_cache.or_else(|| std::env::var("EMSQRT_PLAN_CACHE_DIR").ok().map(PathBuf::from));
+    let cache_file = cache_dir.as_ref().map(|dir| {
+        let key = emsqrt_core::hash::hash_str(&format!(
+            "{}|mem_cap={}",
+            yaml_content, config.mem_cap_bytes
+        ));
+        dir.join(format!("{}.plan.json", key.to_hex()))
+    });
+
+    let (phys_prog, te) = match cache_file
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| {
+            serde_json::from_str::<(emsqrt_planner::PhysicalProgram, emsqrt_te::TePlan)>(&text)
+                .ok()
+        }) {
+        Some(cached) => {
+            println!("  (plan restored from cache)");
+            cached
+        }
+        None => {
+            let optimized = rules::optimize(parsed.plan.clone());
+            let phys_prog = lower_to_physical(&optimized);
+            let work = estimate_work(&optimized, None);
+            let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
+                .map_err(|e| format!("TE planning failed: {}", e))?;
+            if let Some(path) = &cache_file {
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                let _ = fs::write(path, serde_json::to_string(&(&phys_prog, &te))?);
+            }
+            (phys_prog, te)
+        }
+    };
 
     // Execute
     let mut engine =
@@ -245,6 +469,172 @@ fn run_pipeline(
     );
     println!("  Plan hash: {}", manifest.plan_hash);
 
+    if let Some(path) = manifest_out {
+        fs::write(&path, serde_json::to_string_pretty(&manifest)?)?;
+        println!("  Manifest: {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Compare two saved manifests and report source schema drift.
+fn drift_check(old: &PathBuf, new: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let old_manifest: emsqrt_core::manifest::RunManifest =
+        serde_json::from_str(&fs::read_to_string(old)?)?;
+    let new_manifest: emsqrt_core::manifest::RunManifest =
+        serde_json::from_str(&fs::read_to_string(new)?)?;
+
+    let drift = new_manifest.schema_drift_from(&old_manifest);
+    if drift.is_empty() {
+        println!("✓ No source schema drift");
+        Ok(())
+    } else {
+        for report in &drift {
+            println!("  {}", report);
+        }
+        Err(format!("{} schema change(s) detected", drift.len()).into())
+    }
+}
+
+fn migrate_pipeline_file(
+    pipeline_path: &PathBuf,
+    write: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let yaml_content = fs::read_to_string(pipeline_path)?;
+    let (migrated, changes) = emsqrt_planner::migrate_pipeline(&yaml_content)?;
+
+    if changes.is_empty() {
+        println!("✓ Pipeline is already in the current format");
+        return Ok(());
+    }
+
+    for change in &changes {
+        println!("  {}", change);
+    }
+
+    // Make sure the migrated document actually parses before emitting it.
+    parse_yaml_pipeline(&migrated)?;
+
+    if write {
+        let backup = pipeline_path.with_extension("yaml.bak");
+        fs::copy(pipeline_path, &backup)?;
+        fs::write(pipeline_path, &migrated)?;
+        println!("✓ Migrated in place (backup at {})", backup.display());
+    } else {
+        println!("--- migrated pipeline ---");
+        print!("{}", migrated);
+    }
+    Ok(())
+}
+
+fn verify_pipeline(
+    pipeline_path: &PathBuf,
+    expect_records: Option<u64>,
+    expect_digest: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let yaml_content = fs::read_to_string(pipeline_path)?;
+    let parsed = parse_yaml_pipeline(&yaml_content)?;
+    let optimized = rules::optimize(parsed.plan.clone());
+    let phys_prog = lower_to_physical(&optimized);
+    let work = estimate_work(&optimized, None);
+
+    let mut config = EngineConfig::from_env();
+    apply_pipeline_config(&mut config, &parsed.config);
+    let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
+        .map_err(|e| format!("TE planning failed: {}", e))?;
+
+    let mut engine =
+        Engine::new(config).map_err(|e| -> Box<dyn std::error::Error> { Box::new(e) })?;
+    let manifest = engine.run(&phys_prog, &te)?;
+
+    let rows_read: u64 = manifest.source_checkpoints.iter().map(|c| c.rows_read).sum();
+    let digest = manifest
+        .outputs_digest
+        .map(|d| d.to_hex())
+        .unwrap_or_else(|| "(no output)".to_string());
+
+    println!("✓ Pipeline executed");
+    println!("  Records read:    {}", rows_read);
+    println!("  Records written: {}", manifest.records_written);
+    println!("  Outputs digest:  {}", digest);
+
+    if let Some(expected) = expect_records {
+        if manifest.records_written != expected {
+            return Err(format!(
+                "record count mismatch: expected {}, wrote {}",
+                expected, manifest.records_written
+            )
+            .into());
+        }
+        println!("✓ Record count matches ({})", expected);
+    }
+    if let Some(expected) = expect_digest {
+        if !digest.eq_ignore_ascii_case(&expected) {
+            return Err(format!(
+                "digest mismatch: expected {}, got {}",
+                expected, digest
+            )
+            .into());
+        }
+        println!("✓ Outputs digest matches");
+    }
+    Ok(())
+}
+
+/// Run the pipeline and compare (or record) its golden output summary,
+/// stored next to the pipeline as `<name>.golden.json`.
+fn golden_pipeline(
+    pipeline_path: &PathBuf,
+    update: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let yaml_content = fs::read_to_string(pipeline_path)?;
+    let parsed = parse_yaml_pipeline(&yaml_content)?;
+    let optimized = rules::optimize(parsed.plan.clone());
+    let phys_prog = lower_to_physical(&optimized);
+    let work = estimate_work(&optimized, None);
+
+    let mut config = EngineConfig::from_env();
+    apply_pipeline_config(&mut config, &parsed.config);
+    let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
+        .map_err(|e| format!("TE planning failed: {}", e))?;
+
+    let mut engine =
+        Engine::new(config).map_err(|e| -> Box<dyn std::error::Error> { Box::new(e) })?;
+    let manifest = engine.run(&phys_prog, &te)?;
+
+    let current = serde_json::json!({
+        "records_written": manifest.records_written,
+        "outputs_digest": manifest.outputs_digest.map(|d| d.to_hex()),
+    });
+
+    let golden_path = pipeline_path.with_extension("golden.json");
+    if update {
+        fs::write(&golden_path, serde_json::to_string_pretty(&current)?)?;
+        println!("✓ Recorded golden output at {}", golden_path.display());
+        println!("  {}", current);
+        return Ok(());
+    }
+
+    let golden_text = fs::read_to_string(&golden_path).map_err(|e| {
+        format!(
+            "no golden record at {} ({}); run with --update to create one",
+            golden_path.display(),
+            e
+        )
+    })?;
+    let golden: serde_json::Value = serde_json::from_str(&golden_text)?;
+
+    if golden != current {
+        return Err(format!(
+            "output changed:
+  golden:  {}
+  current: {}",
+            golden, current
+        )
+        .into());
+    }
+
+    println!("✓ Output matches golden record ({})", golden_path.display());
     Ok(())
 }
 
@@ -295,6 +685,25 @@ fn explain_pipeline(
         println!("  Max Frontier: {} blocks", max_frontier);
     }
     println!();
+    let split = emsqrt_exec::runtime::planned_budget_split(&phys_prog.bindings, memory_cap);
+    if !split.is_empty() {
+        println!("Planned Memory Split (with per-operator budgets):");
+        for (op, key, share) in &split {
+            println!(
+                "  Op {} ({}) -> {} bytes ({:.2} MB)",
+                op.get(),
+                key,
+                share,
+                *share as f64 / 1_048_576.0
+            );
+        }
+        println!(
+            "  shared pool -> {} bytes ({:.2} MB)",
+            memory_cap - split.iter().map(|(_, _, s)| s).sum::<usize>(),
+            (memory_cap - split.iter().map(|(_, _, s)| s).sum::<usize>()) as f64 / 1_048_576.0
+        );
+        println!();
+    }
     println!("Block Execution Order:");
     for (i, block) in te.order.iter().enumerate() {
         println!(
@@ -0,0 +1,149 @@
+//! Data profiling (`emsqrt profile <source>`): per-column counts, null
+//! rates, approximate distinct counts (HyperLogLog), numeric min/max/mean,
+//! and example values, computed in one streaming pass.
+
+use std::collections::HashMap;
+
+use emsqrt_core::types::Scalar;
+use emsqrt_io::readers::csv::CsvReader;
+use emsqrt_io::readers::jsonl::JsonlReader;
+use emsqrt_operators::sketch::Hll;
+
+const BATCH_ROWS: usize = 10_000;
+
+#[derive(Default)]
+struct ColumnProfile {
+    rows: u64,
+    nulls: u64,
+    distinct: Option<Hll>,
+    min: Option<f64>,
+    max: Option<f64>,
+    sum: f64,
+    numeric_rows: u64,
+    example: Option<String>,
+}
+
+impl ColumnProfile {
+    fn observe(&mut self, value: &Scalar) {
+        self.rows += 1;
+        let text = match value {
+            Scalar::Null => {
+                self.nulls += 1;
+                return;
+            }
+            Scalar::Str(s) => s.clone(),
+            other => format!("{:?}", other),
+        };
+
+        self.distinct
+            .get_or_insert_with(Hll::new)
+            .insert(text.as_bytes());
+        if self.example.is_none() {
+            self.example = Some(text.clone());
+        }
+
+        let numeric = match value {
+            Scalar::I32(v) => Some(*v as f64),
+            Scalar::I64(v) => Some(*v as f64),
+            Scalar::F32(v) => Some(*v as f64),
+            Scalar::F64(v) => Some(*v),
+            Scalar::Str(s) => s.parse::<f64>().ok(),
+            _ => None,
+        };
+        if let Some(v) = numeric {
+            self.numeric_rows += 1;
+            self.sum += v;
+            self.min = Some(self.min.map_or(v, |m| m.min(v)));
+            self.max = Some(self.max.map_or(v, |m| m.max(v)));
+        }
+    }
+}
+
+pub fn profile(source: &str, limit: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let mut profiles: HashMap<String, ColumnProfile> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut total_rows = 0usize;
+
+    fn observe_batch(
+        batch: &emsqrt_core::types::RowBatch,
+        profiles: &mut HashMap<String, ColumnProfile>,
+        order: &mut Vec<String>,
+        total_rows: &mut usize,
+    ) {
+        for column in &batch.columns {
+            if !order.contains(&column.name) {
+                order.push(column.name.clone());
+            }
+            let profile = profiles.entry(column.name.clone()).or_default();
+            for value in &column.values {
+                profile.observe(value);
+            }
+        }
+        *total_rows += batch.num_rows();
+    }
+
+    let is_jsonl = {
+        let stripped = emsqrt_io::decompress::strip_compression_extension(source);
+        stripped.ends_with(".jsonl") || stripped.ends_with(".ndjson")
+    };
+
+    if is_jsonl {
+        let mut reader = JsonlReader::from_path(source)?;
+        while total_rows < limit {
+            let take = BATCH_ROWS.min(limit - total_rows);
+            match reader.next_batch(take)? {
+                Some(batch) if batch.num_rows() > 0 => {
+                    observe_batch(&batch, &mut profiles, &mut order, &mut total_rows)
+                }
+                _ => break,
+            }
+        }
+    } else {
+        let mut reader = CsvReader::from_path(source, true)?;
+        while total_rows < limit {
+            let take = BATCH_ROWS.min(limit - total_rows);
+            match reader.next_batch(take)? {
+                Some(batch) if batch.num_rows() > 0 => {
+                    observe_batch(&batch, &mut profiles, &mut order, &mut total_rows)
+                }
+                _ => break,
+            }
+        }
+    }
+
+    println!("Profile of {} ({} rows)", source, total_rows);
+    println!();
+    println!(
+        "{:<20} {:>10} {:>8} {:>10} {:>12} {:>12} {:>12}  example",
+        "column", "rows", "null%", "~distinct", "min", "max", "mean"
+    );
+    for name in &order {
+        let p = &profiles[name];
+        let null_pct = if p.rows > 0 {
+            100.0 * p.nulls as f64 / p.rows as f64
+        } else {
+            0.0
+        };
+        let fmt_num = |v: Option<f64>| match v {
+            Some(v) => format!("{:.4}", v),
+            None => "-".to_string(),
+        };
+        let mean = if p.numeric_rows > 0 {
+            Some(p.sum / p.numeric_rows as f64)
+        } else {
+            None
+        };
+        println!(
+            "{:<20} {:>10} {:>7.1}% {:>10} {:>12} {:>12} {:>12}  {}",
+            name,
+            p.rows,
+            null_pct,
+            p.distinct.as_ref().map(|h| h.estimate()).unwrap_or(0),
+            fmt_num(p.min),
+            fmt_num(p.max),
+            fmt_num(mean),
+            p.example.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
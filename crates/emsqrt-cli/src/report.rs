@@ -0,0 +1,110 @@
+//! HTML run report generation (`emsqrt report`).
+//!
+//! Renders a saved run manifest as a self-contained HTML page: run summary,
+//! per-source checkpoints, and recorded source schemas. No external assets,
+//! so the file can be attached to tickets or archived as-is.
+
+use std::path::PathBuf;
+
+use emsqrt_core::manifest::RunManifest;
+
+pub fn report(
+    manifest_path: &PathBuf,
+    out_path: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest: RunManifest =
+        serde_json::from_str(&std::fs::read_to_string(manifest_path)?)?;
+    let html = render(&manifest);
+    std::fs::write(out_path, html)?;
+    println!("✓ Report written to {}", out_path.display());
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn render(manifest: &RunManifest) -> String {
+    let duration_ms = manifest.finished_ms.saturating_sub(manifest.started_ms);
+    let outputs_digest = manifest
+        .outputs_digest
+        .map(|d| d.to_hex())
+        .unwrap_or_else(|| "(no output)".to_string());
+
+    let mut checkpoints = String::new();
+    for cp in &manifest.source_checkpoints {
+        checkpoints.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&cp.source),
+            cp.rows_read,
+            cp.byte_offset
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ));
+    }
+
+    let mut schemas = String::new();
+    for schema in &manifest.source_schemas {
+        let columns: Vec<String> = schema
+            .columns
+            .iter()
+            .map(|(name, dtype)| format!("{}: {}", escape(name), escape(dtype)))
+            .collect();
+        schemas.push_str(&format!(
+            "<tr><td>{}</td><td><code>{}</code></td></tr>\n",
+            escape(&schema.source),
+            columns.join(", ")
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>EM-&#8730; run {id}</title>
+<style>
+  body {{ font-family: ui-monospace, monospace; margin: 2rem; color: #222; }}
+  h1 {{ font-size: 1.3rem; }}
+  table {{ border-collapse: collapse; margin-bottom: 1.5rem; }}
+  th, td {{ border: 1px solid #ccc; padding: 0.3rem 0.8rem; text-align: left; }}
+  th {{ background: #f2f2f2; }}
+  code {{ font-size: 0.85em; }}
+</style>
+</head>
+<body>
+<h1>EM-&#8730; run report</h1>
+
+<table>
+  <tr><th>Run id</th><td>{id}</td></tr>
+  <tr><th>Engine version</th><td>{version}</td></tr>
+  <tr><th>Duration</th><td>{duration_ms} ms</td></tr>
+  <tr><th>Records written</th><td>{records}</td></tr>
+  <tr><th>Plan hash</th><td><code>{plan_hash}</code></td></tr>
+  <tr><th>Outputs digest</th><td><code>{outputs}</code></td></tr>
+</table>
+
+<h2>Source checkpoints</h2>
+<table>
+  <tr><th>Source</th><th>Rows read</th><th>Byte offset</th></tr>
+{checkpoints}</table>
+
+<h2>Source schemas</h2>
+<table>
+  <tr><th>Source</th><th>Columns</th></tr>
+{schemas}</table>
+</body>
+</html>
+"#,
+        id = manifest.id.0,
+        version = escape(&manifest.engine_version),
+        duration_ms = duration_ms,
+        records = manifest.records_written,
+        plan_hash = manifest.plan_hash.to_hex(),
+        outputs = outputs_digest,
+        checkpoints = checkpoints,
+        schemas = schemas,
+    )
+}
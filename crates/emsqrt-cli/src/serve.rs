@@ -0,0 +1,202 @@
+//! Minimal web UI for plan and run inspection (`emsqrt serve`).
+//!
+//! A hand-rolled HTTP/1.1 server (no framework dependency) exposing:
+//! - `GET /`          embedded single-page UI
+//! - `GET /api/plan`  logical plan, operator bindings, work estimate, TE order
+//! - `POST /api/run`  execute the pipeline and return the run manifest
+//!
+//! Connections are handled sequentially; this is an inspection tool, not a
+//! production server.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+
+use emsqrt_core::config::EngineConfig;
+use emsqrt_exec::Engine;
+use emsqrt_planner::{estimate_work, lower_to_physical, parse_yaml_pipeline, rules};
+use emsqrt_te::plan_te;
+
+const INDEX_HTML: &str = include_str!("serve_index.html");
+
+pub fn serve(
+    pipeline_path: &PathBuf,
+    config_path: Option<&PathBuf>,
+    port: u16,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Serving plan inspector at http://127.0.0.1:{}/", port);
+    println!("  Pipeline: {}", pipeline_path.display());
+    if let Some(config) = config_path {
+        println!("  Config:   {} (hot-reloaded per request)", config.display());
+    }
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        if let Err(e) = handle_connection(&mut stream, pipeline_path, config_path) {
+            eprintln!("request error: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Effective engine config: environment first, then the optional JSON config
+/// file overlaid. Re-read on every request, so edits apply without a server
+/// restart (hot reload).
+fn load_config(config_path: Option<&PathBuf>) -> Result<EngineConfig, Box<dyn std::error::Error>> {
+    match config_path {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)?;
+            let config: EngineConfig = serde_json::from_str(&text)
+                .map_err(|e| format!("invalid engine config {}: {}", path.display(), e))?;
+            Ok(config)
+        }
+        None => Ok(EngineConfig::from_env()),
+    }
+}
+
+fn handle_connection(
+    stream: &mut TcpStream,
+    pipeline_path: &PathBuf,
+    config_path: Option<&PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+
+    // Drain headers (we only need Content-Length for request bodies).
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some(value) = line
+            .to_ascii_lowercase()
+            .strip_prefix("content-length:")
+            .map(|v| v.trim().to_string())
+        {
+            content_length = value.parse().unwrap_or(0);
+        }
+    }
+    if content_length > 0 {
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+    }
+
+    match (method, path) {
+        ("GET", "/") => respond(stream, 200, "text/html", INDEX_HTML),
+        ("GET", "/api/plan") => match plan_json(pipeline_path, config_path) {
+            Ok(json) => respond(stream, 200, "application/json", &json),
+            Err(e) => respond_error(stream, &e.to_string()),
+        },
+        ("GET", "/api/config") => match load_config(config_path) {
+            Ok(config) => match serde_json::to_string_pretty(&config) {
+                Ok(json) => respond(stream, 200, "application/json", &json),
+                Err(e) => respond_error(stream, &e.to_string()),
+            },
+            Err(e) => respond_error(stream, &e.to_string()),
+        },
+        ("POST", "/api/run") => match run_json(pipeline_path, config_path) {
+            Ok(json) => respond(stream, 200, "application/json", &json),
+            Err(e) => respond_error(stream, &e.to_string()),
+        },
+        _ => respond(stream, 404, "text/plain", "not found"),
+    }
+}
+
+fn plan_json(
+    pipeline_path: &PathBuf,
+    config_path: Option<&PathBuf>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let yaml_content = std::fs::read_to_string(pipeline_path)?;
+    let parsed = parse_yaml_pipeline(&yaml_content)?;
+    let optimized = rules::optimize(parsed.plan.clone());
+    let phys_prog = lower_to_physical(&optimized);
+    let work = estimate_work(&optimized, None);
+    let config = load_config(config_path)?;
+    let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
+        .map_err(|e| format!("TE planning failed: {}", e))?;
+
+    let te_order: Vec<serde_json::Value> = te
+        .order
+        .iter()
+        .map(|b| {
+            serde_json::json!({
+                "block": b.id.get(),
+                "op": b.op.get(),
+                "deps": b.deps.iter().map(|d| d.get()).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    let json = serde_json::json!({
+        "pipeline": pipeline_path.display().to_string(),
+        "logical": optimized,
+        "bindings": phys_prog.bindings,
+        "work": {
+            "total_rows": work.total_rows,
+            "total_bytes": work.total_bytes,
+            "max_fan_in": work.max_fan_in,
+        },
+        "te": {
+            "rows_per_block": te.block_size.rows_per_block,
+            "order": te_order,
+        },
+    });
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+fn run_json(
+    pipeline_path: &PathBuf,
+    config_path: Option<&PathBuf>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let yaml_content = std::fs::read_to_string(pipeline_path)?;
+    let parsed = parse_yaml_pipeline(&yaml_content)?;
+    let optimized = rules::optimize(parsed.plan.clone());
+    let phys_prog = lower_to_physical(&optimized);
+    let work = estimate_work(&optimized, None);
+    let config = load_config(config_path)?;
+    let te = plan_te(&phys_prog.plan, &work, config.mem_cap_bytes)
+        .map_err(|e| format!("TE planning failed: {}", e))?;
+
+    let mut engine = Engine::new(config)?;
+    let manifest = engine.run(&phys_prog, &te)?;
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+fn respond_error(stream: &mut TcpStream, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    respond(stream, 500, "application/json", &body)
+}
@@ -64,6 +64,8 @@ pub fn row_batch_to_arrow(batch: &RowBatch) -> Result<RecordBatch, String> {
                     name: c.name.clone(),
                     data_type: DataType::Utf8, // Placeholder
                     nullable: true,
+                    default: None,
+                    provenance: None,
                 })
                 .collect(),
         };
@@ -120,6 +122,8 @@ fn batch_to_schema(batch: &RowBatch) -> Schema {
                 name: c.name.clone(),
                 data_type,
                 nullable: true,
+                default: None,
+                provenance: None,
             }
         })
         .collect();
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EngineConfig {
     /// Hard memory cap (in bytes). The engine and operators must *never* exceed this.
     pub mem_cap_bytes: usize,
@@ -16,9 +17,34 @@ pub struct EngineConfig {
     /// Optional seed for deterministic shuffles/partitioning.
     pub seed: Option<u64>,
 
+    /// Hash function for row partitioning ("blake3" or "fnv1a").
+    pub hash_function: String,
+
+    /// Use approximate, memory-bounded aggregation sketches (HyperLogLog for
+    /// distinct counts, t-digest for quantiles) instead of exact state.
+    pub approx_aggregates: bool,
+
     /// Execution parallelism. The scheduler must respect this when launching tasks.
     pub max_parallel_tasks: usize,
 
+    /// When set, blocks running longer than this many milliseconds get a
+    /// second speculative attempt; the first result wins.
+    pub speculative_straggler_ms: Option<u64>,
+
+    /// Write file sinks into a staging directory and publish them with an
+    /// atomic rename only after the run succeeds.
+    pub atomic_sinks: bool,
+
+    /// Carve per-operator sub-budgets for pipeline breakers (half the cap
+    /// split evenly across them) so one greedy operator can't starve the
+    /// others. `emsqrt explain` shows the planned split.
+    pub per_operator_budgets: bool,
+
+    /// Debug tap: dump sample rows produced by this plan step (operator id).
+    pub debug_step: Option<u64>,
+    /// How many rows the debug tap prints in total (default 20).
+    pub debug_dump_rows: usize,
+
     /// Directory for spill files (legacy local-path configuration).
     pub spill_dir: String,
 
@@ -27,12 +53,34 @@ pub struct EngineConfig {
 
     /// Cloud credential hints / overrides.
     pub spill_aws_region: Option<String>,
+    /// Custom S3-compatible endpoint (MinIO, Ceph RGW, localstack, ...).
+    pub spill_aws_endpoint: Option<String>,
+    /// Use path-style addressing (required by most S3-compatible stores).
+    pub spill_aws_path_style: bool,
     pub spill_aws_access_key_id: Option<String>,
     pub spill_aws_secret_access_key: Option<String>,
     pub spill_aws_session_token: Option<String>,
     pub spill_gcs_service_account_path: Option<String>,
     pub spill_azure_access_key: Option<String>,
 
+    /// Optional cap on total on-disk spill bytes.
+    pub spill_disk_budget_bytes: Option<u64>,
+
+    /// Spill compression codec ("none", "zstd", "lz4"); codecs other than
+    /// "none" require the matching cargo feature.
+    pub spill_codec: String,
+
+    /// Compression level for codecs that support one (zstd).
+    pub spill_codec_level: Option<i32>,
+
+    /// When set, spill writes happen on a background thread with at most
+    /// this many encoded bytes in flight.
+    pub spill_background_inflight_bytes: Option<u64>,
+
+    /// Local disk cache tier for cloud spill backends.
+    pub spill_cache_dir: Option<String>,
+    pub spill_cache_bytes: Option<u64>,
+
     /// Retry policy for spill storage.
     pub spill_retry_max_retries: usize,
     pub spill_retry_initial_backoff_ms: u64,
@@ -46,15 +94,30 @@ impl Default for EngineConfig {
             block_size_hint: None,
             max_spill_concurrency: 4,
             seed: None,
+            hash_function: "blake3".to_string(),
+            approx_aggregates: false,
             max_parallel_tasks: 4,
+            speculative_straggler_ms: None,
+            atomic_sinks: false,
+            per_operator_budgets: false,
+            debug_step: None,
+            debug_dump_rows: 20,
             spill_dir: "/tmp/emsqrt-spill".to_string(),
             spill_uri: None,
             spill_aws_region: None,
+            spill_aws_endpoint: None,
+            spill_aws_path_style: false,
             spill_aws_access_key_id: None,
             spill_aws_secret_access_key: None,
             spill_aws_session_token: None,
             spill_gcs_service_account_path: None,
             spill_azure_access_key: None,
+            spill_disk_budget_bytes: None,
+            spill_codec: "none".to_string(),
+            spill_codec_level: None,
+            spill_background_inflight_bytes: None,
+            spill_cache_dir: None,
+            spill_cache_bytes: None,
             spill_retry_max_retries: 3,
             spill_retry_initial_backoff_ms: 200,
             spill_retry_max_backoff_ms: 5_000,
@@ -66,7 +129,12 @@ impl Default for EngineConfig {
 pub struct StorageConfig {
     pub uri: Option<String>,
     pub root: String,
+    /// Local disk cache tier for remote spill (directory + byte budget).
+    pub local_cache_dir: Option<String>,
+    pub local_cache_bytes: Option<u64>,
     pub aws_region: Option<String>,
+    pub aws_endpoint: Option<String>,
+    pub aws_path_style: bool,
     pub aws_access_key_id: Option<String>,
     pub aws_secret_access_key: Option<String>,
     pub aws_session_token: Option<String>,
@@ -123,12 +191,40 @@ impl EngineConfig {
             }
         }
 
+        if let Ok(s) = std::env::var("EMSQRT_HASH_FUNCTION") {
+            cfg.hash_function = s;
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_APPROX_AGGREGATES") {
+            if let Ok(v) = s.parse::<bool>() {
+                cfg.approx_aggregates = v;
+            }
+        }
+
         if let Ok(s) = std::env::var("EMSQRT_MAX_PARALLEL_TASKS") {
             if let Ok(v) = s.parse::<usize>() {
                 cfg.max_parallel_tasks = v;
             }
         }
 
+        if let Ok(s) = std::env::var("EMSQRT_ATOMIC_SINKS") {
+            if let Ok(v) = s.parse::<bool>() {
+                cfg.atomic_sinks = v;
+            }
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_PER_OPERATOR_BUDGETS") {
+            if let Ok(v) = s.parse::<bool>() {
+                cfg.per_operator_budgets = v;
+            }
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPECULATIVE_STRAGGLER_MS") {
+            if let Ok(v) = s.parse::<u64>() {
+                cfg.speculative_straggler_ms = Some(v);
+            }
+        }
+
         if let Ok(s) = std::env::var("EMSQRT_SPILL_DIR") {
             cfg.spill_dir = s;
         }
@@ -141,6 +237,16 @@ impl EngineConfig {
             cfg.spill_aws_region = Some(s);
         }
 
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_AWS_ENDPOINT") {
+            cfg.spill_aws_endpoint = Some(s);
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_AWS_PATH_STYLE") {
+            if let Ok(v) = s.parse::<bool>() {
+                cfg.spill_aws_path_style = v;
+            }
+        }
+
         if let Ok(s) = std::env::var("EMSQRT_SPILL_AWS_ACCESS_KEY_ID") {
             cfg.spill_aws_access_key_id = Some(s);
         }
@@ -161,6 +267,38 @@ impl EngineConfig {
             cfg.spill_azure_access_key = Some(s);
         }
 
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_DISK_BUDGET_BYTES") {
+            if let Ok(v) = s.parse::<u64>() {
+                cfg.spill_disk_budget_bytes = Some(v);
+            }
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_CODEC") {
+            cfg.spill_codec = s;
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_CODEC_LEVEL") {
+            if let Ok(v) = s.parse::<i32>() {
+                cfg.spill_codec_level = Some(v);
+            }
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_BG_INFLIGHT_BYTES") {
+            if let Ok(v) = s.parse::<u64>() {
+                cfg.spill_background_inflight_bytes = Some(v);
+            }
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_CACHE_DIR") {
+            cfg.spill_cache_dir = Some(s);
+        }
+
+        if let Ok(s) = std::env::var("EMSQRT_SPILL_CACHE_BYTES") {
+            if let Ok(v) = s.parse::<u64>() {
+                cfg.spill_cache_bytes = Some(v);
+            }
+        }
+
         if let Ok(s) = std::env::var("EMSQRT_SPILL_RETRY_MAX_RETRIES") {
             if let Ok(v) = s.parse::<usize>() {
                 cfg.spill_retry_max_retries = v;
@@ -203,7 +341,11 @@ impl EngineConfig {
         StorageConfig {
             uri: self.spill_uri.clone(),
             root,
+            local_cache_dir: self.spill_cache_dir.clone(),
+            local_cache_bytes: self.spill_cache_bytes,
             aws_region: self.spill_aws_region.clone(),
+            aws_endpoint: self.spill_aws_endpoint.clone(),
+            aws_path_style: self.spill_aws_path_style,
             aws_access_key_id: self.spill_aws_access_key_id.clone(),
             aws_secret_access_key: self.spill_aws_secret_access_key.clone(),
             aws_session_token: self.spill_aws_session_token.clone(),
@@ -25,6 +25,32 @@ pub enum Aggregation {
     Avg(String),
     Min(String),
     Max(String),
+    /// Exact by default; t-digest approximation when approximate aggregation
+    /// is enabled in `EngineConfig`.
+    Median(String),
+    /// Sample standard deviation (n - 1 denominator).
+    Stddev(String),
+    /// Sample variance (n - 1 denominator).
+    Variance(String),
+    /// Exact by default; HyperLogLog approximation when approximate
+    /// aggregation is enabled in `EngineConfig`.
+    CountDistinct(String),
+    First(String),
+    Last(String),
+    StringAgg {
+        column: String,
+        delimiter: String,
+    },
+    /// Sample covariance / Pearson correlation of two numeric columns.
+    Covar(String, String),
+    Corr(String, String),
+    /// Conditional aggregate: only rows matching `predicate` are fed to the
+    /// inner aggregation (SQL `agg FILTER (WHERE predicate)`). The predicate
+    /// uses the same expression syntax as `Filter`.
+    Filtered {
+        agg: Box<Aggregation>,
+        predicate: String,
+    },
     // TODO: distinct, multi-agg per group, etc.
 }
 
@@ -70,11 +96,46 @@ pub enum LogicalPlan {
         alias: String,
         delimiter: Option<String>,
     },
+    /// Enrich rows from a small reference file: the reference is loaded into
+    /// memory and matched on `key` (input) == `ref_key` (reference).
+    /// `columns` selects reference columns to append (empty = all but the key).
+    Lookup {
+        input: Box<LogicalPlan>,
+        source: String,
+        key: String,
+        ref_key: String,
+        columns: Vec<String>,
+    },
+    /// TTL/retention: keep rows whose `column` timestamp is within
+    /// `max_age` (e.g. "30d", "24h") of `as_of` (defaults to wall clock at
+    /// execution; pin it for reproducible runs).
+    Retention {
+        input: Box<LogicalPlan>,
+        column: String,
+        max_age: String,
+        as_of: Option<String>,
+    },
+    /// Row sampling: `fraction` is Bernoulli (seeded from `EngineConfig.seed`),
+    /// `rows` is reservoir sampling with bounded memory. Exactly one should be
+    /// set; `rows` wins if both are.
+    Sample {
+        input: Box<LogicalPlan>,
+        fraction: Option<f64>,
+        rows: Option<usize>,
+    },
     Sink {
         input: Box<LogicalPlan>,
         destination: String, // e.g., "s3://bucket/out/"
         format: String,      // "parquet", "csv", ...
     },
+    /// Terminal sink that routes each row to the first destination whose
+    /// predicate matches; unmatched rows go to `default` (or are dropped).
+    RoutedSink {
+        input: Box<LogicalPlan>,
+        routes: Vec<(String, String)>, // (predicate, destination)
+        default: Option<String>,
+        format: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,7 +209,11 @@ impl LogicalPlan {
             | Aggregate { .. }
             | Window { .. }
             | Lateral { .. }
-            | Sink { .. } => 1,
+            | Lookup { .. }
+            | Retention { .. }
+            | Sample { .. }
+            | Sink { .. }
+            | RoutedSink { .. } => 1,
             Join { .. } => 2,
         }
     }
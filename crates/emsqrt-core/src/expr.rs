@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::schema::DataType;
 use crate::types::{RowBatch, Scalar};
 
 /// Binary operators for expressions.
@@ -68,6 +69,127 @@ impl UnaryOp {
     }
 }
 
+/// Built-in scalar functions callable from expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExprFunc {
+    /// First non-null argument, or null.
+    Coalesce,
+    /// Null when both arguments are equal, else the first argument.
+    NullIf,
+    /// Second argument when the first is null (two-argument COALESCE).
+    IfNull,
+    /// SPLIT_PART(text, delimiter, n): the n-th (1-based) field after
+    /// splitting on the delimiter, or null when out of range.
+    SplitPart,
+    /// CONCAT(a, b, ...): concatenate arguments as text, skipping nulls.
+    Concat,
+    /// CONCAT_WS(delimiter, a, b, ...): like CONCAT with a delimiter between
+    /// non-null arguments.
+    ConcatWs,
+    /// FORMAT(template, a, b, ...): substitute `{}` placeholders in order;
+    /// null arguments render as empty strings.
+    Format,
+    /// UPPER(text) / LOWER(text): case conversion.
+    Upper,
+    Lower,
+    /// TRIM / LTRIM / RTRIM(text): strip whitespace.
+    Trim,
+    Ltrim,
+    Rtrim,
+    /// LENGTH(text): character count as Int64.
+    Length,
+    /// SUBSTR(text, start [, len]): 1-based substring.
+    Substr,
+    /// REPLACE(text, from, to): replace all occurrences.
+    Replace,
+    /// REVERSE(text): reversed characters.
+    Reverse,
+    /// LEFT(text, n) / RIGHT(text, n): first/last n characters.
+    Left,
+    Right,
+    /// CONTAINS / STARTS_WITH / ENDS_WITH(text, needle): boolean tests.
+    Contains,
+    StartsWith,
+    EndsWith,
+    /// TO_TIMESTAMP(text): parse `YYYY-MM-DD[ HH:MM:SS[.mmm]]` into Date64.
+    ToTimestamp,
+    /// DATE_PART('year'|'month'|'day'|'hour'|'minute'|'second'|'dow'|'epoch', ts).
+    DatePart,
+    /// DATE_TRUNC('year'|'month'|'day'|'hour'|'minute'|'second', ts).
+    DateTrunc,
+    /// ZORDER(a, b, ...): interleave the arguments' order-preserving bits
+    /// into one Int64 space-filling-curve key, so sorting by it clusters
+    /// rows that are close in every dimension.
+    ZOrder,
+    /// REGEXP_MATCH(text, pattern): whether the regex matches anywhere in
+    /// the text. Patterns are cached per thread after first compilation.
+    RegexpMatch,
+}
+
+impl ExprFunc {
+    /// Look up a function by (uppercase) name.
+    pub fn lookup(name: &str) -> Option<Self> {
+        match name {
+            "COALESCE" => Some(ExprFunc::Coalesce),
+            "NULLIF" => Some(ExprFunc::NullIf),
+            "IFNULL" => Some(ExprFunc::IfNull),
+            "SPLIT_PART" => Some(ExprFunc::SplitPart),
+            "CONCAT" => Some(ExprFunc::Concat),
+            "CONCAT_WS" => Some(ExprFunc::ConcatWs),
+            "FORMAT" => Some(ExprFunc::Format),
+            "UPPER" => Some(ExprFunc::Upper),
+            "LOWER" => Some(ExprFunc::Lower),
+            "TRIM" => Some(ExprFunc::Trim),
+            "LTRIM" => Some(ExprFunc::Ltrim),
+            "RTRIM" => Some(ExprFunc::Rtrim),
+            "LENGTH" => Some(ExprFunc::Length),
+            "SUBSTR" | "SUBSTRING" => Some(ExprFunc::Substr),
+            "REPLACE" => Some(ExprFunc::Replace),
+            "REVERSE" => Some(ExprFunc::Reverse),
+            "LEFT" => Some(ExprFunc::Left),
+            "RIGHT" => Some(ExprFunc::Right),
+            "CONTAINS" => Some(ExprFunc::Contains),
+            "STARTS_WITH" => Some(ExprFunc::StartsWith),
+            "ENDS_WITH" => Some(ExprFunc::EndsWith),
+            "TO_TIMESTAMP" | "TO_DATE" => Some(ExprFunc::ToTimestamp),
+            "DATE_PART" => Some(ExprFunc::DatePart),
+            "DATE_TRUNC" => Some(ExprFunc::DateTrunc),
+            "ZORDER" => Some(ExprFunc::ZOrder),
+            "REGEXP_MATCH" => Some(ExprFunc::RegexpMatch),
+            _ => None,
+        }
+    }
+
+    /// Expected argument count: (min, max). `None` max means unbounded.
+    fn arity(&self) -> (usize, Option<usize>) {
+        match self {
+            ExprFunc::Coalesce => (1, None),
+            ExprFunc::NullIf | ExprFunc::IfNull => (2, Some(2)),
+            ExprFunc::SplitPart => (3, Some(3)),
+            ExprFunc::Concat => (1, None),
+            ExprFunc::ConcatWs | ExprFunc::Format => (2, None),
+            ExprFunc::Upper
+            | ExprFunc::Lower
+            | ExprFunc::Trim
+            | ExprFunc::Ltrim
+            | ExprFunc::Rtrim
+            | ExprFunc::Length
+            | ExprFunc::Reverse => (1, Some(1)),
+            ExprFunc::Substr => (2, Some(3)),
+            ExprFunc::Replace => (3, Some(3)),
+            ExprFunc::Left
+            | ExprFunc::Right
+            | ExprFunc::Contains
+            | ExprFunc::StartsWith
+            | ExprFunc::EndsWith => (2, Some(2)),
+            ExprFunc::ToTimestamp => (1, Some(1)),
+            ExprFunc::DatePart | ExprFunc::DateTrunc => (2, Some(2)),
+            ExprFunc::ZOrder => (2, Some(8)),
+            ExprFunc::RegexpMatch => (2, Some(2)),
+        }
+    }
+}
+
 /// Expression AST for SQL-like expressions.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
@@ -83,6 +205,16 @@ pub enum Expr {
     },
     /// Unary operation: OP arg
     UnaryOp { op: UnaryOp, arg: Box<Expr> },
+    /// Null-handling function call: COALESCE(a, b, ...), NULLIF(a, b),
+    /// IFNULL(a, b).
+    Func { func: ExprFunc, args: Vec<Expr> },
+    /// Conditional: CASE WHEN cond THEN value [WHEN ...] [ELSE value] END.
+    /// Branches are evaluated in order; without a matching branch (and no
+    /// ELSE) the result is null.
+    Case {
+        branches: Vec<(Expr, Expr)>,
+        else_expr: Option<Box<Expr>>,
+    },
 }
 
 impl Expr {
@@ -95,6 +227,12 @@ impl Expr {
     pub fn parse(expr_str: &str) -> Result<Self, String> {
         let expr_str = expr_str.trim();
 
+        // CASE WHEN ... THEN ... [ELSE ...] END is self-delimiting; handle it
+        // before operator splitting so conditions can contain comparisons.
+        if expr_str.starts_with("CASE ") || expr_str == "CASE" {
+            return Self::parse_case(expr_str);
+        }
+
         // Parse with operator precedence: logical operators last (lowest precedence)
         // This allows expressions like "age > 20 AND price < 15" to be parsed correctly
 
@@ -110,8 +248,8 @@ impl Expr {
         let mut best_op_str: Option<&str> = None;
 
         for (op_str, op) in &logical_ops {
-            if let Some(pos) = expr_str.rfind(op_str) {
-                if best_pos.map_or(true, |best| pos > best) {
+            if let Some(pos) = find_top_level(expr_str, op_str, true) {
+                if best_pos.is_none_or(|best| pos > best) {
                     best_pos = Some(pos);
                     best_op = Some(*op);
                     best_op_str = Some(op_str);
@@ -136,7 +274,7 @@ impl Expr {
 
         // Then, try comparison operators
         for op_str in &["==", "!=", "<=", ">=", "<", ">"] {
-            if let Some(pos) = expr_str.find(op_str) {
+            if let Some(pos) = find_top_level(expr_str, op_str, false) {
                 let left_str = expr_str[..pos].trim();
                 let right_str = expr_str[pos + op_str.len()..].trim();
 
@@ -155,7 +293,7 @@ impl Expr {
 
         // Finally, try arithmetic operators (highest precedence)
         for op_str in &["+", "-", "*", "/"] {
-            if let Some(pos) = expr_str.find(op_str) {
+            if let Some(pos) = find_top_level(expr_str, op_str, false) {
                 let left_str = expr_str[..pos].trim();
                 let right_str = expr_str[pos + op_str.len()..].trim();
 
@@ -176,10 +314,63 @@ impl Expr {
         Self::parse_atom(expr_str)
     }
 
-    /// Parse an atomic expression (column or literal).
+    /// Parse a CASE expression: `CASE WHEN cond THEN value [WHEN ...] [ELSE value] END`.
+    ///
+    /// Keywords must be uppercase (matching AND/OR elsewhere); nested CASE
+    /// expressions are not supported.
+    fn parse_case(expr_str: &str) -> Result<Self, String> {
+        let body = expr_str
+            .strip_prefix("CASE")
+            .and_then(|s| s.trim_end().strip_suffix("END"))
+            .ok_or_else(|| format!("CASE expression must end with END: '{}'", expr_str))?
+            .trim();
+
+        // Split off the optional ELSE branch first.
+        let (when_part, else_expr) = match body.rfind(" ELSE ") {
+            Some(pos) => {
+                let else_str = body[pos + " ELSE ".len()..].trim();
+                (
+                    body[..pos].trim(),
+                    Some(Box::new(Self::parse(else_str)?)),
+                )
+            }
+            None => (body, None),
+        };
+
+        let mut branches = Vec::new();
+        for clause in when_part.split("WHEN ") {
+            let clause = clause.trim();
+            if clause.is_empty() {
+                continue;
+            }
+            let (cond_str, value_str) = clause
+                .split_once(" THEN ")
+                .ok_or_else(|| format!("WHEN clause missing THEN: '{}'", clause))?;
+            branches.push((Self::parse(cond_str)?, Self::parse(value_str)?));
+        }
+
+        if branches.is_empty() {
+            return Err(format!(
+                "CASE expression needs at least one WHEN clause: '{}'",
+                expr_str
+            ));
+        }
+
+        Ok(Expr::Case {
+            branches,
+            else_expr,
+        })
+    }
+
+    /// Parse an atomic expression (function call, column, or literal).
     fn parse_atom(atom_str: &str) -> Result<Self, String> {
         let atom_str = atom_str.trim();
 
+        // Function call: NAME(arg, arg, ...) spanning the whole atom.
+        if let Some(expr) = Self::parse_function(atom_str)? {
+            return Ok(expr);
+        }
+
         // Try to parse as literal first
         if let Ok(scalar) = parse_literal(atom_str) {
             return Ok(Expr::Literal(scalar));
@@ -189,6 +380,42 @@ impl Expr {
         Ok(Expr::Column(atom_str.to_string()))
     }
 
+    /// Try to parse `NAME(args...)` as a built-in function call. Returns
+    /// `Ok(None)` if the string isn't shaped like a known call.
+    fn parse_function(s: &str) -> Result<Option<Self>, String> {
+        let Some(open) = s.find('(') else {
+            return Ok(None);
+        };
+        if !s.ends_with(')') {
+            return Ok(None);
+        }
+        let Some(func) = ExprFunc::lookup(s[..open].trim()) else {
+            return Ok(None);
+        };
+
+        let args_str = &s[open + 1..s.len() - 1];
+        let mut args = Vec::new();
+        for part in split_top_level(args_str, ',') {
+            let part = part.trim();
+            if part.is_empty() {
+                return Err(format!("empty argument in function call '{}'", s));
+            }
+            args.push(Self::parse(part)?);
+        }
+
+        let (min, max) = func.arity();
+        if args.len() < min || max.is_some_and(|m| args.len() > m) {
+            return Err(format!(
+                "wrong number of arguments for {:?} in '{}': got {}",
+                func,
+                s,
+                args.len()
+            ));
+        }
+
+        Ok(Some(Expr::Func { func, args }))
+    }
+
     /// Evaluate an expression against a row in a RowBatch.
     ///
     /// Returns the resulting Scalar value.
@@ -225,6 +452,27 @@ impl Expr {
                 let arg_val = arg.evaluate(batch, row_idx)?;
                 evaluate_unary_op(*op, &arg_val)
             }
+            Expr::Func { func, args } => {
+                let values: Vec<Scalar> = args
+                    .iter()
+                    .map(|a| a.evaluate(batch, row_idx))
+                    .collect::<Result<_, _>>()?;
+                evaluate_function(*func, &values)
+            }
+            Expr::Case {
+                branches,
+                else_expr,
+            } => {
+                for (cond, value) in branches {
+                    if cond.evaluate_bool(batch, row_idx)? {
+                        return value.evaluate(batch, row_idx);
+                    }
+                }
+                match else_expr {
+                    Some(e) => e.evaluate(batch, row_idx),
+                    None => Ok(Scalar::Null),
+                }
+            }
         }
     }
 
@@ -235,6 +483,198 @@ impl Expr {
         let scalar = self.evaluate(batch, row_idx)?;
         scalar_to_bool(&scalar)
     }
+
+    /// Schema-aware type check: resolve column references against `schema`
+    /// and verify operator/function argument types, returning the inferred
+    /// result type (`None` = statically unknown, which never errors).
+    ///
+    /// This is the plan-time half of expression validation; row evaluation
+    /// still re-checks dynamically for mixed-type prototype columns.
+    pub fn check_types(&self, schema: &crate::schema::Schema) -> Result<Option<DataType>, String> {
+        use DataType::*;
+
+        fn numeric(dt: &DataType) -> bool {
+            matches!(dt, Int32 | Int64 | Float32 | Float64)
+        }
+
+        match self {
+            Expr::Column(name) => {
+                let field = schema
+                    .fields
+                    .iter()
+                    .find(|f| &f.name == name)
+                    .ok_or_else(|| {
+                        let available: Vec<&str> =
+                            schema.fields.iter().map(|f| f.name.as_str()).collect();
+                        format!(
+                            "column '{}' not in input schema. Available columns: {:?}",
+                            name, available
+                        )
+                    })?;
+                Ok(Some(field.data_type.clone()))
+            }
+            Expr::Literal(Scalar::Null) => Ok(None),
+            Expr::Literal(scalar) => Ok(Some(scalar.data_type())),
+            Expr::BinaryOp { op, left, right } => {
+                let lhs = left.check_types(schema)?;
+                let rhs = right.check_types(schema)?;
+                match op {
+                    BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                        Ok(Some(Boolean))
+                    }
+                    BinOp::And | BinOp::Or => Ok(Some(Boolean)),
+                    BinOp::Add if lhs == Some(Utf8) && rhs == Some(Utf8) => Ok(Some(Utf8)),
+                    BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div => {
+                        for dt in [&lhs, &rhs].into_iter().flatten() {
+                            if !numeric(dt) {
+                                return Err(format!(
+                                    "arithmetic on non-numeric type {:?} in '{:?}'",
+                                    dt, self
+                                ));
+                            }
+                        }
+                        Ok(match (lhs, rhs) {
+                            (Some(Float64), _) | (_, Some(Float64)) => Some(Float64),
+                            (Some(Float32), _) | (_, Some(Float32)) => Some(Float64),
+                            (Some(Int64), _) | (_, Some(Int64)) => Some(Int64),
+                            (Some(Int32), Some(Int32)) => Some(Int32),
+                            _ => None,
+                        })
+                    }
+                }
+            }
+            Expr::UnaryOp { arg, .. } => {
+                arg.check_types(schema)?;
+                Ok(Some(Boolean))
+            }
+            Expr::Func { func, args } => {
+                let arg_types: Vec<Option<DataType>> = args
+                    .iter()
+                    .map(|a| a.check_types(schema))
+                    .collect::<Result<_, _>>()?;
+
+                let require_text = |idx: usize| -> Result<(), String> {
+                    match &arg_types[idx] {
+                        Some(Utf8) | None => Ok(()),
+                        Some(other) => Err(format!(
+                            "{:?} expects a string argument, got {:?}",
+                            func, other
+                        )),
+                    }
+                };
+                let require_int = |idx: usize| -> Result<(), String> {
+                    match &arg_types[idx] {
+                        Some(Int32) | Some(Int64) | None => Ok(()),
+                        Some(other) => Err(format!(
+                            "{:?} expects an integer argument, got {:?}",
+                            func, other
+                        )),
+                    }
+                };
+
+                match func {
+                    ExprFunc::Coalesce => Ok(arg_types.into_iter().flatten().next()),
+                    ExprFunc::NullIf | ExprFunc::IfNull => Ok(arg_types[0].clone()),
+                    ExprFunc::SplitPart => {
+                        require_text(0)?;
+                        require_text(1)?;
+                        require_int(2)?;
+                        Ok(Some(Utf8))
+                    }
+                    ExprFunc::Concat | ExprFunc::ConcatWs | ExprFunc::Format => Ok(Some(Utf8)),
+                    ExprFunc::Upper
+                    | ExprFunc::Lower
+                    | ExprFunc::Trim
+                    | ExprFunc::Ltrim
+                    | ExprFunc::Rtrim
+                    | ExprFunc::Reverse => {
+                        require_text(0)?;
+                        Ok(Some(Utf8))
+                    }
+                    ExprFunc::Length => {
+                        require_text(0)?;
+                        Ok(Some(Int64))
+                    }
+                    ExprFunc::Substr => {
+                        require_text(0)?;
+                        require_int(1)?;
+                        if arg_types.len() > 2 {
+                            require_int(2)?;
+                        }
+                        Ok(Some(Utf8))
+                    }
+                    ExprFunc::Replace => {
+                        require_text(0)?;
+                        require_text(1)?;
+                        require_text(2)?;
+                        Ok(Some(Utf8))
+                    }
+                    ExprFunc::Left | ExprFunc::Right => {
+                        require_text(0)?;
+                        require_int(1)?;
+                        Ok(Some(Utf8))
+                    }
+                    ExprFunc::Contains
+                    | ExprFunc::StartsWith
+                    | ExprFunc::EndsWith
+                    | ExprFunc::RegexpMatch => {
+                        require_text(0)?;
+                        require_text(1)?;
+                        Ok(Some(Boolean))
+                    }
+                    ExprFunc::ToTimestamp => {
+                        match &arg_types[0] {
+                            Some(Utf8) | Some(Date64) | None => {}
+                            Some(other) => {
+                                return Err(format!(
+                                    "TO_TIMESTAMP expects a string or timestamp, got {:?}",
+                                    other
+                                ));
+                            }
+                        }
+                        Ok(Some(Date64))
+                    }
+                    ExprFunc::DatePart => {
+                        require_text(0)?;
+                        Ok(Some(Int64))
+                    }
+                    ExprFunc::DateTrunc => {
+                        require_text(0)?;
+                        Ok(Some(Date64))
+                    }
+                    ExprFunc::ZOrder => Ok(Some(Int64)),
+                }
+            }
+            Expr::Case {
+                branches,
+                else_expr,
+            } => {
+                let mut result: Option<DataType> = None;
+                let mut mixed = false;
+                for (cond, value) in branches {
+                    cond.check_types(schema)?;
+                    let vt = value.check_types(schema)?;
+                    match (&result, vt) {
+                        (None, Some(dt)) if !mixed => result = Some(dt),
+                        (Some(existing), Some(dt)) if *existing != dt => {
+                            result = None;
+                            mixed = true;
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(else_expr) = else_expr {
+                    let vt = else_expr.check_types(schema)?;
+                    if let (Some(existing), Some(dt)) = (&result, vt) {
+                        if *existing != dt {
+                            result = None;
+                        }
+                    }
+                }
+                Ok(result)
+            }
+        }
+    }
 }
 
 /// Parse a literal string into a Scalar value.
@@ -477,6 +917,432 @@ fn evaluate_binary_op(op: BinOp, left: &Scalar, right: &Scalar) -> Result<Scalar
     }
 }
 
+/// Evaluate a built-in scalar function over already-evaluated arguments.
+fn evaluate_function(func: ExprFunc, args: &[Scalar]) -> Result<Scalar, String> {
+    match func {
+        ExprFunc::Coalesce => Ok(args
+            .iter()
+            .find(|v| !matches!(v, Scalar::Null))
+            .cloned()
+            .unwrap_or(Scalar::Null)),
+        ExprFunc::NullIf => {
+            if scalar_eq(&args[0], &args[1]) {
+                Ok(Scalar::Null)
+            } else {
+                Ok(args[0].clone())
+            }
+        }
+        ExprFunc::IfNull => {
+            if matches!(args[0], Scalar::Null) {
+                Ok(args[1].clone())
+            } else {
+                Ok(args[0].clone())
+            }
+        }
+        ExprFunc::SplitPart => {
+            let text = match &args[0] {
+                Scalar::Null => return Ok(Scalar::Null),
+                Scalar::Str(s) => s,
+                other => {
+                    return Err(format!("SPLIT_PART expects a string, got {:?}", other));
+                }
+            };
+            let delim = match &args[1] {
+                Scalar::Str(s) if !s.is_empty() => s,
+                other => {
+                    return Err(format!(
+                        "SPLIT_PART delimiter must be a non-empty string, got {:?}",
+                        other
+                    ));
+                }
+            };
+            let n = match &args[2] {
+                Scalar::I32(i) => *i as i64,
+                Scalar::I64(i) => *i,
+                other => {
+                    return Err(format!("SPLIT_PART index must be an integer, got {:?}", other));
+                }
+            };
+            if n < 1 {
+                return Err(format!("SPLIT_PART index is 1-based, got {}", n));
+            }
+            Ok(text
+                .split(delim.as_str())
+                .nth(n as usize - 1)
+                .map(|part| Scalar::Str(part.to_string()))
+                .unwrap_or(Scalar::Null))
+        }
+        ExprFunc::Concat => {
+            let joined: String = args.iter().filter_map(scalar_display).collect();
+            Ok(Scalar::Str(joined))
+        }
+        ExprFunc::ConcatWs => {
+            let delim = match &args[0] {
+                Scalar::Str(s) => s.clone(),
+                other => {
+                    return Err(format!(
+                        "CONCAT_WS delimiter must be a string, got {:?}",
+                        other
+                    ));
+                }
+            };
+            let parts: Vec<String> = args[1..].iter().filter_map(scalar_display).collect();
+            Ok(Scalar::Str(parts.join(&delim)))
+        }
+        ExprFunc::Format => {
+            let template = match &args[0] {
+                Scalar::Str(s) => s.clone(),
+                other => {
+                    return Err(format!("FORMAT template must be a string, got {:?}", other));
+                }
+            };
+            let placeholders = template.matches("{}").count();
+            if placeholders != args.len() - 1 {
+                return Err(format!(
+                    "FORMAT template has {} placeholders but {} arguments were given",
+                    placeholders,
+                    args.len() - 1
+                ));
+            }
+            let mut out = String::with_capacity(template.len());
+            let mut rest = template.as_str();
+            for arg in &args[1..] {
+                let (before, after) = rest.split_once("{}").expect("placeholder counted above");
+                out.push_str(before);
+                if let Some(text) = scalar_display(arg) {
+                    out.push_str(&text);
+                }
+                rest = after;
+            }
+            out.push_str(rest);
+            Ok(Scalar::Str(out))
+        }
+        ExprFunc::Upper
+        | ExprFunc::Lower
+        | ExprFunc::Trim
+        | ExprFunc::Ltrim
+        | ExprFunc::Rtrim
+        | ExprFunc::Length
+        | ExprFunc::Reverse => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            Ok(match func {
+                ExprFunc::Upper => Scalar::Str(text.to_uppercase()),
+                ExprFunc::Lower => Scalar::Str(text.to_lowercase()),
+                ExprFunc::Trim => Scalar::Str(text.trim().to_string()),
+                ExprFunc::Ltrim => Scalar::Str(text.trim_start().to_string()),
+                ExprFunc::Rtrim => Scalar::Str(text.trim_end().to_string()),
+                ExprFunc::Length => Scalar::I64(text.chars().count() as i64),
+                _ => Scalar::Str(text.chars().rev().collect()),
+            })
+        }
+        ExprFunc::Substr => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let start = int_arg(func, &args[1])?;
+            if start < 1 {
+                return Err(format!("SUBSTR start is 1-based, got {}", start));
+            }
+            let len = match args.get(2) {
+                Some(v) => {
+                    let l = int_arg(func, v)?;
+                    if l < 0 {
+                        return Err(format!("SUBSTR length must be non-negative, got {}", l));
+                    }
+                    Some(l as usize)
+                }
+                None => None,
+            };
+            let chars = text.chars().skip(start as usize - 1);
+            let result: String = match len {
+                Some(l) => chars.take(l).collect(),
+                None => chars.collect(),
+            };
+            Ok(Scalar::Str(result))
+        }
+        ExprFunc::Replace => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let Some(from) = text_arg(func, &args[1])? else {
+                return Ok(Scalar::Null);
+            };
+            let Some(to) = text_arg(func, &args[2])? else {
+                return Ok(Scalar::Null);
+            };
+            if from.is_empty() {
+                return Ok(Scalar::Str(text.to_string()));
+            }
+            Ok(Scalar::Str(text.replace(from, to)))
+        }
+        ExprFunc::Left | ExprFunc::Right => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let n = int_arg(func, &args[1])?;
+            if n < 0 {
+                return Err(format!("{:?} count must be non-negative, got {}", func, n));
+            }
+            let n = n as usize;
+            let count = text.chars().count();
+            let result: String = match func {
+                ExprFunc::Left => text.chars().take(n).collect(),
+                _ => text.chars().skip(count.saturating_sub(n)).collect(),
+            };
+            Ok(Scalar::Str(result))
+        }
+        ExprFunc::Contains | ExprFunc::StartsWith | ExprFunc::EndsWith => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let Some(needle) = text_arg(func, &args[1])? else {
+                return Ok(Scalar::Null);
+            };
+            Ok(Scalar::Bool(match func {
+                ExprFunc::Contains => text.contains(needle),
+                ExprFunc::StartsWith => text.starts_with(needle),
+                _ => text.ends_with(needle),
+            }))
+        }
+        ExprFunc::ToTimestamp => match &args[0] {
+            Scalar::Null => Ok(Scalar::Null),
+            Scalar::Date64(ms) => Ok(Scalar::Date64(*ms)),
+            Scalar::Str(s) => match crate::time::parse_datetime(s) {
+                Some(ms) => Ok(Scalar::Date64(ms)),
+                None => Err(format!("TO_TIMESTAMP could not parse '{}'", s)),
+            },
+            other => Err(format!("TO_TIMESTAMP expects a string, got {:?}", other)),
+        },
+        ExprFunc::DatePart | ExprFunc::DateTrunc => {
+            let Some(unit) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let millis = match &args[1] {
+                Scalar::Null => return Ok(Scalar::Null),
+                Scalar::Date64(ms) => *ms,
+                Scalar::Str(s) => crate::time::parse_datetime(s)
+                    .ok_or_else(|| format!("{:?} could not parse '{}'", func, s))?,
+                other => {
+                    return Err(format!("{:?} expects a date/time, got {:?}", func, other));
+                }
+            };
+            match func {
+                ExprFunc::DatePart => crate::time::extract_part(millis, unit)
+                    .map(Scalar::I64)
+                    .ok_or_else(|| format!("unknown DATE_PART unit '{}'", unit)),
+                _ => crate::time::truncate(millis, unit)
+                    .map(Scalar::Date64)
+                    .ok_or_else(|| format!("unknown DATE_TRUNC unit '{}'", unit)),
+            }
+        }
+        ExprFunc::ZOrder => {
+            if args.iter().any(|a| matches!(a, Scalar::Null)) {
+                return Ok(Scalar::Null);
+            }
+            let dims: Vec<u64> = args.iter().map(order_preserving_bits).collect();
+
+            // Interleave the top (64 / n) bits of each dimension, MSB first,
+            // round-robin across dimensions.
+            let bits_per_dim = 64 / dims.len();
+            let mut key = 0u64;
+            let mut out_bit = 63i32;
+            for bit in 0..bits_per_dim {
+                for dim in &dims {
+                    if out_bit < 0 {
+                        break;
+                    }
+                    let b = (dim >> (63 - bit)) & 1;
+                    key |= b << out_bit;
+                    out_bit -= 1;
+                }
+            }
+            // Flip the sign bit so i64 ordering matches unsigned key order.
+            Ok(Scalar::I64((key ^ (1u64 << 63)) as i64))
+        }
+        ExprFunc::RegexpMatch => {
+            let Some(text) = text_arg(func, &args[0])? else {
+                return Ok(Scalar::Null);
+            };
+            let Some(pattern) = text_arg(func, &args[1])? else {
+                return Ok(Scalar::Null);
+            };
+            let regex = compiled_regex(pattern)?;
+            Ok(Scalar::Bool(regex.is_match(text)))
+        }
+    }
+}
+
+/// Per-thread cache of compiled regexes: patterns come from parsed
+/// expressions, so the set is small, but evaluation is per row.
+fn compiled_regex(pattern: &str) -> Result<std::rc::Rc<regex_lite::Regex>, String> {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    thread_local! {
+        static CACHE: RefCell<HashMap<String, Rc<regex_lite::Regex>>> =
+            RefCell::new(HashMap::new());
+    }
+
+    CACHE.with(|cache| {
+        if let Some(regex) = cache.borrow().get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = Rc::new(
+            regex_lite::Regex::new(pattern)
+                .map_err(|e| format!("invalid regex '{}': {}", pattern, e))?,
+        );
+        cache.borrow_mut().insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    })
+}
+
+/// Map a scalar to a u64 whose unsigned order matches `scalar_cmp` within
+/// the same type: sign-flipped integers, IEEE-754 bit transform for floats,
+/// big-endian byte prefix for text/binary.
+fn order_preserving_bits(s: &Scalar) -> u64 {
+    match s {
+        Scalar::Null => 0,
+        Scalar::Bool(b) => (*b as u64) << 63,
+        Scalar::I32(v) => (*v as i64 as u64) ^ (1u64 << 63),
+        Scalar::I64(v) => (*v as u64) ^ (1u64 << 63),
+        Scalar::Date64(v) => (*v as u64) ^ (1u64 << 63),
+        Scalar::F32(v) => order_preserving_f64(*v as f64),
+        Scalar::F64(v) => order_preserving_f64(*v),
+        Scalar::Str(v) => {
+            let mut bytes = [0u8; 8];
+            for (i, b) in v.as_bytes().iter().take(8).enumerate() {
+                bytes[i] = *b;
+            }
+            u64::from_be_bytes(bytes)
+        }
+        Scalar::Bin(v) => {
+            let mut bytes = [0u8; 8];
+            for (i, b) in v.iter().take(8).enumerate() {
+                bytes[i] = *b;
+            }
+            u64::from_be_bytes(bytes)
+        }
+    }
+}
+
+fn order_preserving_f64(v: f64) -> u64 {
+    let bits = v.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+/// String argument accessor: `None` for null, error for non-string scalars.
+fn text_arg(func: ExprFunc, arg: &Scalar) -> Result<Option<&str>, String> {
+    match arg {
+        Scalar::Null => Ok(None),
+        Scalar::Str(s) => Ok(Some(s.as_str())),
+        other => Err(format!("{:?} expects a string argument, got {:?}", func, other)),
+    }
+}
+
+/// Integer argument accessor.
+fn int_arg(func: ExprFunc, arg: &Scalar) -> Result<i64, String> {
+    match arg {
+        Scalar::I32(i) => Ok(*i as i64),
+        Scalar::I64(i) => Ok(*i),
+        other => Err(format!(
+            "{:?} expects an integer argument, got {:?}",
+            func, other
+        )),
+    }
+}
+
+/// Text rendering of a scalar for string functions; nulls render as `None`.
+fn scalar_display(s: &Scalar) -> Option<String> {
+    match s {
+        Scalar::Null => None,
+        Scalar::Str(v) => Some(v.clone()),
+        Scalar::Bool(v) => Some(v.to_string()),
+        Scalar::I32(v) => Some(v.to_string()),
+        Scalar::I64(v) => Some(v.to_string()),
+        Scalar::F32(v) => Some(v.to_string()),
+        Scalar::F64(v) => Some(v.to_string()),
+        Scalar::Bin(v) => Some(format!("{:?}", v)),
+        Scalar::Date64(ms) => Some(crate::time::format_datetime(*ms)),
+    }
+}
+
+/// Find `needle` in `s` at paren depth zero and outside quotes. `from_right`
+/// returns the rightmost match (for left-associative operators).
+fn find_top_level(s: &str, needle: &str, from_right: bool) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let nlen = needle.len();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut found = None;
+
+    let mut i = 0;
+    while i + nlen <= s.len() {
+        let b = bytes[i];
+        match quote {
+            Some(q) => {
+                if b == q {
+                    quote = None;
+                }
+            }
+            None => match b {
+                b'\'' | b'"' => quote = Some(b),
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => {
+                    if depth == 0 && &s[i..i + nlen] == needle {
+                        if !from_right {
+                            return Some(i);
+                        }
+                        found = Some(i);
+                    }
+                }
+            },
+        }
+        i += 1;
+    }
+    found
+}
+
+/// Split `s` on `sep` at paren depth zero and outside quotes.
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {
+                    if depth == 0 && c == sep {
+                        parts.push(&s[start..i]);
+                        start = i + sep.len_utf8();
+                    }
+                }
+            },
+        }
+    }
+    if start < s.len() || !parts.is_empty() {
+        parts.push(&s[start..]);
+    }
+    parts
+}
+
 /// Evaluate a unary operation.
 fn evaluate_unary_op(op: UnaryOp, arg: &Scalar) -> Result<Scalar, String> {
     match op {
@@ -517,6 +1383,7 @@ fn scalar_eq(a: &Scalar, b: &Scalar) -> bool {
         (F64(x), I64(y)) => (x - (*y as f64)).abs() < f64::EPSILON,
         (Str(x), Str(y)) => x == y,
         (Bin(x), Bin(y)) => x == y,
+        (Date64(x), Date64(y)) => x == y,
         _ => false,
     }
 }
@@ -552,6 +1419,7 @@ fn scalar_cmp(a: &Scalar, b: &Scalar) -> std::cmp::Ordering {
         (F64(x), I64(y)) => x.partial_cmp(&(*y as f64)).unwrap_or(Ordering::Equal),
         (Str(x), Str(y)) => x.cmp(y),
         (Bin(x), Bin(y)) => x.cmp(y),
+        (Date64(x), Date64(y)) => x.cmp(y),
         _ => {
             // Mixed types: compare by type order
             let a_order = scalar_type_order(a);
@@ -573,6 +1441,7 @@ fn scalar_type_order(s: &Scalar) -> u8 {
         F64(_) => 5,
         Str(_) => 6,
         Bin(_) => 7,
+        Date64(_) => 8,
     }
 }
 
@@ -588,5 +1457,6 @@ fn scalar_to_bool(scalar: &Scalar) -> Result<bool, String> {
         F64(f) => Ok(*f != 0.0),
         Str(s) => Ok(!s.is_empty()),
         Bin(b) => Ok(!b.is_empty()),
+        Date64(ms) => Ok(*ms != 0),
     }
 }
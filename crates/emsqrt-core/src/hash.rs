@@ -41,3 +41,64 @@ pub fn hash_serde<T: Serialize>(v: &T) -> Result<Hash256, crate::error::Error> {
     let bytes = serde_json::to_vec(v).map_err(|e| crate::error::Error::Hash(e.to_string()))?;
     Ok(hash_bytes(&bytes))
 }
+
+/// Hash functions available for row partitioning. BLAKE3 is the default;
+/// FNV-1a is a cheap non-cryptographic alternative for hot paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, serde::Deserialize)]
+pub enum HashKind {
+    #[default]
+    Blake3,
+    Fnv1a,
+}
+
+impl HashKind {
+    /// Parse a config string ("blake3", "fnv1a").
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "blake3" => Ok(HashKind::Blake3),
+            "fnv1a" | "fnv" => Ok(HashKind::Fnv1a),
+            other => Err(format!("unknown hash function '{}'", other)),
+        }
+    }
+}
+
+/// Seeded row hasher used for partitioning. The same (kind, seed) pair
+/// produces the same partition assignment on every run, so repartitioned
+/// spill layouts are stable across replays.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RowHasher {
+    pub kind: HashKind,
+    pub seed: u64,
+}
+
+impl RowHasher {
+    pub fn new(kind: HashKind, seed: u64) -> Self {
+        Self { kind, seed }
+    }
+
+    /// Hash pre-encoded key bytes to a u64.
+    ///
+    /// With the default (Blake3, seed 0) this matches the engine's historical
+    /// unseeded partitioning, so existing layouts stay valid.
+    pub fn hash_u64(&self, bytes: &[u8]) -> u64 {
+        match self.kind {
+            HashKind::Blake3 => {
+                let mut h = Hasher::new();
+                if self.seed != 0 {
+                    h.update(&self.seed.to_le_bytes());
+                }
+                h.update(bytes);
+                let out = h.finalize();
+                u64::from_le_bytes(out.as_bytes()[0..8].try_into().unwrap())
+            }
+            HashKind::Fnv1a => {
+                let mut hash = 0xcbf2_9ce4_8422_2325u64 ^ self.seed;
+                for &b in bytes {
+                    hash ^= b as u64;
+                    hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+                }
+                hash
+            }
+        }
+    }
+}
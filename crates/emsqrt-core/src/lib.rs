@@ -25,6 +25,7 @@ pub mod manifest;
 pub mod prelude;
 pub mod schema;
 pub mod stats;
+pub mod time;
 pub mod types;
 
 #[cfg(feature = "arrow")]
@@ -12,6 +12,28 @@ use crate::hash::Hash256;
 #[serde(transparent)]
 pub struct ManifestId(pub Uuid);
 
+/// Schema seen at one source during a run, recorded so later runs can
+/// detect drift (added/removed/retyped columns).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSchemaRecord {
+    pub source: String,
+    /// (column name, data type) pairs in declaration order.
+    pub columns: Vec<(String, String)>,
+}
+
+/// Final read position of one source, recorded so a later run can resume
+/// ingestion where this one stopped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceCheckpoint {
+    /// Source URI/path as given in the plan.
+    pub source: String,
+    /// Data rows consumed (excluding headers).
+    pub rows_read: u64,
+    /// Byte offset of the next unread record, when the reader can provide it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_offset: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunManifest {
     pub id: ManifestId,
@@ -34,6 +56,18 @@ pub struct RunManifest {
     /// Milliseconds since Unix epoch (UTC).
     pub started_ms: u64,
     pub finished_ms: u64,
+
+    /// Per-source read checkpoints for resumable ingestion.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_checkpoints: Vec<SourceCheckpoint>,
+
+    /// Total records delivered to sinks during this run.
+    #[serde(default)]
+    pub records_written: u64,
+
+    /// Per-source schemas for drift detection between runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_schemas: Vec<SourceSchemaRecord>,
 }
 
 impl RunManifest {
@@ -47,6 +81,9 @@ impl RunManifest {
             outputs_digest: None,
             started_ms,
             finished_ms: started_ms,
+            source_checkpoints: Vec::new(),
+            records_written: 0,
+            source_schemas: Vec::new(),
         }
     }
 
@@ -55,4 +92,42 @@ impl RunManifest {
         self.outputs_digest = outputs_digest;
         self
     }
+
+    /// Human-readable schema differences between this run's sources and a
+    /// previous run's. Empty = no drift.
+    pub fn schema_drift_from(&self, previous: &RunManifest) -> Vec<String> {
+        let mut reports = Vec::new();
+        for current in &self.source_schemas {
+            let Some(old) = previous
+                .source_schemas
+                .iter()
+                .find(|s| s.source == current.source)
+            else {
+                continue; // source not present in the previous run
+            };
+
+            for (name, dtype) in &current.columns {
+                match old.columns.iter().find(|(n, _)| n == name) {
+                    None => reports.push(format!(
+                        "{}: column '{}' added ({})",
+                        current.source, name, dtype
+                    )),
+                    Some((_, old_type)) if old_type != dtype => reports.push(format!(
+                        "{}: column '{}' changed type {} -> {}",
+                        current.source, name, old_type, dtype
+                    )),
+                    _ => {}
+                }
+            }
+            for (name, dtype) in &old.columns {
+                if !current.columns.iter().any(|(n, _)| n == name) {
+                    reports.push(format!(
+                        "{}: column '{}' removed (was {})",
+                        current.source, name, dtype
+                    ));
+                }
+            }
+        }
+        reports
+    }
 }
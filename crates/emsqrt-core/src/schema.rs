@@ -3,6 +3,8 @@
 //! The `types.rs` module contains lightweight `Scalar`/`Column` placeholders.
 //! In `emsqrt-operators`, you'll likely convert to Arrow arrays for execution.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::stats::SchemaStats;
@@ -26,6 +28,15 @@ pub struct Field {
     pub name: String,
     pub data_type: DataType,
     pub nullable: bool,
+    /// Scan-time default: used when the source is missing this column or a
+    /// value fails to parse. Also serves as a generated constant for columns
+    /// absent from the source entirely.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Where this column came from, e.g. "scan:data.csv" or "map:x * 2".
+    /// Filled in during lowering; purely informational.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
 }
 
 impl Field {
@@ -34,8 +45,23 @@ impl Field {
             name: name.into(),
             data_type,
             nullable,
+            default: None,
+            provenance: None,
         }
     }
+
+    /// Attach a scan-time default value (kept as source text; parsed per
+    /// `data_type` at read time).
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Attach a provenance note describing where the column came from.
+    pub fn with_provenance(mut self, provenance: impl Into<String>) -> Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +70,9 @@ pub struct Schema {
     /// Optional column statistics for cost estimation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stats: Option<SchemaStats>,
+    /// Free-form schema annotations (owner, description, source system, ...).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl PartialEq for Schema {
@@ -58,11 +87,22 @@ impl Schema {
         Self {
             fields,
             stats: None,
+            metadata: BTreeMap::new(),
         }
     }
 
     pub fn new_with_stats(fields: Vec<Field>, stats: Option<SchemaStats>) -> Self {
-        Self { fields, stats }
+        Self {
+            fields,
+            stats,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Attach one metadata annotation (builder style).
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
     }
 
     pub fn field(&self, idx: usize) -> Option<&Field> {
@@ -75,7 +75,9 @@ impl ColumnStats {
     ///
     /// Used when combining stats from multiple partitions or batches.
     pub fn merge(&self, other: &ColumnStats) -> ColumnStats {
-        let merged = ColumnStats {
+        
+
+        ColumnStats {
             min: match (&self.min, &other.min) {
                 (Some(a), Some(b)) => {
                     if scalar_cmp(a, b).is_le() {
@@ -103,9 +105,7 @@ impl ColumnStats {
             null_count: self.null_count + other.null_count,
             distinct_count: None, // Merging distinct counts is complex, set to None
             total_count: self.total_count + other.total_count,
-        };
-
-        merged
+        }
     }
 
     /// Get the number of non-null values.
@@ -136,8 +136,8 @@ impl ColumnStats {
         match (min_val, max_val) {
             (Some(min), Some(max)) => {
                 // Range predicate: estimate based on overlap
-                if scalar_cmp(min, &self.max.as_ref().unwrap()).is_gt()
-                    || scalar_cmp(max, &self.min.as_ref().unwrap()).is_lt()
+                if scalar_cmp(min, self.max.as_ref().unwrap()).is_gt()
+                    || scalar_cmp(max, self.min.as_ref().unwrap()).is_lt()
                 {
                     return 0.0; // No overlap
                 }
@@ -165,7 +165,7 @@ impl ColumnStats {
                     if let Some(threshold) = scalar_to_f64(min) {
                         if max_val > min_val {
                             let selectivity = (max_val - threshold) / (max_val - min_val);
-                            return selectivity.max(0.0).min(1.0);
+                            return selectivity.clamp(0.0, 1.0);
                         }
                     }
                 }
@@ -192,7 +192,7 @@ impl ColumnStats {
                     if let Some(threshold) = scalar_to_f64(max) {
                         if max_val > min_val {
                             let selectivity = (threshold - min_val) / (max_val - min_val);
-                            return selectivity.max(0.0).min(1.0);
+                            return selectivity.clamp(0.0, 1.0);
                         }
                     }
                 }
@@ -252,7 +252,7 @@ impl SchemaStats {
     pub fn get_or_create(&mut self, column_name: String) -> &mut ColumnStats {
         self.column_stats
             .entry(column_name)
-            .or_insert_with(ColumnStats::new)
+            .or_default()
     }
 
     /// Merge statistics from another SchemaStats into this one.
@@ -342,5 +342,6 @@ fn scalar_type_order(s: &Scalar) -> u8 {
         F64(_) => 5,
         Str(_) => 6,
         Bin(_) => 7,
+        Date64(_) => 8,
     }
 }
@@ -0,0 +1,189 @@
+//! Minimal civil date/time helpers for the `Scalar::Date64` type.
+//!
+//! Date64 values are milliseconds since the Unix epoch (UTC), matching
+//! Arrow's Date64 convention. No timezone handling; everything is UTC.
+//! Conversion algorithms follow Howard Hinnant's `days_from_civil` /
+//! `civil_from_days`.
+
+pub const MILLIS_PER_SECOND: i64 = 1_000;
+pub const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+pub const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+pub const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+
+/// Days since the Unix epoch for a civil (proleptic Gregorian) date.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // [0, 399]
+    let mp = (month as i64 + 9) % 12; // March = 0
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil date (year, month, day) for days since the Unix epoch.
+pub fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    (if month <= 2 { y + 1 } else { y }, month, day)
+}
+
+/// Broken-down UTC timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CivilDateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub millisecond: u32,
+}
+
+/// Break epoch milliseconds into civil UTC components.
+pub fn civil_from_millis(millis: i64) -> CivilDateTime {
+    let days = millis.div_euclid(MILLIS_PER_DAY);
+    let time = millis.rem_euclid(MILLIS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    CivilDateTime {
+        year,
+        month,
+        day,
+        hour: (time / MILLIS_PER_HOUR) as u32,
+        minute: ((time % MILLIS_PER_HOUR) / MILLIS_PER_MINUTE) as u32,
+        second: ((time % MILLIS_PER_MINUTE) / MILLIS_PER_SECOND) as u32,
+        millisecond: (time % MILLIS_PER_SECOND) as u32,
+    }
+}
+
+/// Day of week for epoch milliseconds: 0 = Sunday ... 6 = Saturday.
+pub fn day_of_week(millis: i64) -> u32 {
+    let days = millis.div_euclid(MILLIS_PER_DAY);
+    ((days + 4).rem_euclid(7)) as u32
+}
+
+/// Parse `YYYY-MM-DD`, optionally followed by ` HH:MM:SS` or `THH:MM:SS`
+/// (with optional `.mmm` fraction and trailing `Z`), into epoch milliseconds.
+pub fn parse_datetime(s: &str) -> Option<i64> {
+    let s = s.trim().trim_end_matches('Z');
+    let (date_part, time_part) = match s.find(['T', ' ']) {
+        Some(pos) => (&s[..pos], Some(&s[pos + 1..])),
+        None => (s, None),
+    };
+
+    let mut date_fields = date_part.split('-');
+    let year: i64 = date_fields.next()?.parse().ok()?;
+    let month: u32 = date_fields.next()?.parse().ok()?;
+    let day: u32 = date_fields.next()?.parse().ok()?;
+    if date_fields.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut millis = days_from_civil(year, month, day) * MILLIS_PER_DAY;
+
+    if let Some(time) = time_part {
+        let (hms, frac) = match time.split_once('.') {
+            Some((h, f)) => (h, Some(f)),
+            None => (time, None),
+        };
+        let mut time_fields = hms.split(':');
+        let hour: i64 = time_fields.next()?.parse().ok()?;
+        let minute: i64 = time_fields.next()?.parse().ok()?;
+        let second: i64 = match time_fields.next() {
+            Some(v) => v.parse().ok()?,
+            None => 0,
+        };
+        if time_fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        millis += hour * MILLIS_PER_HOUR + minute * MILLIS_PER_MINUTE + second * MILLIS_PER_SECOND;
+        if let Some(frac) = frac {
+            // Interpret up to three fraction digits as milliseconds.
+            let digits: String = frac.chars().take(3).collect();
+            let scale = 10i64.pow(3 - digits.len() as u32);
+            millis += digits.parse::<i64>().ok()? * scale;
+        }
+    }
+
+    Some(millis)
+}
+
+/// Render epoch milliseconds as `YYYY-MM-DD` (midnight) or
+/// `YYYY-MM-DD HH:MM:SS[.mmm]`.
+pub fn format_datetime(millis: i64) -> String {
+    let c = civil_from_millis(millis);
+    if c.hour == 0 && c.minute == 0 && c.second == 0 && c.millisecond == 0 {
+        format!("{:04}-{:02}-{:02}", c.year, c.month, c.day)
+    } else if c.millisecond == 0 {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            c.year, c.month, c.day, c.hour, c.minute, c.second
+        )
+    } else {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+            c.year, c.month, c.day, c.hour, c.minute, c.second, c.millisecond
+        )
+    }
+}
+
+/// Truncate epoch milliseconds to the start of the given unit.
+pub fn truncate(millis: i64, unit: &str) -> Option<i64> {
+    let c = civil_from_millis(millis);
+    let day_start = days_from_civil(c.year, c.month, c.day) * MILLIS_PER_DAY;
+    match unit {
+        "year" => Some(days_from_civil(c.year, 1, 1) * MILLIS_PER_DAY),
+        "month" => Some(days_from_civil(c.year, c.month, 1) * MILLIS_PER_DAY),
+        "day" => Some(day_start),
+        "hour" => Some(day_start + c.hour as i64 * MILLIS_PER_HOUR),
+        "minute" => {
+            Some(day_start + c.hour as i64 * MILLIS_PER_HOUR + c.minute as i64 * MILLIS_PER_MINUTE)
+        }
+        "second" => Some(
+            day_start
+                + c.hour as i64 * MILLIS_PER_HOUR
+                + c.minute as i64 * MILLIS_PER_MINUTE
+                + c.second as i64 * MILLIS_PER_SECOND,
+        ),
+        _ => None,
+    }
+}
+
+/// Extract a named part from epoch milliseconds.
+pub fn extract_part(millis: i64, part: &str) -> Option<i64> {
+    let c = civil_from_millis(millis);
+    match part {
+        "year" => Some(c.year),
+        "month" => Some(c.month as i64),
+        "day" => Some(c.day as i64),
+        "hour" => Some(c.hour as i64),
+        "minute" => Some(c.minute as i64),
+        "second" => Some(c.second as i64),
+        "millisecond" => Some(c.millisecond as i64),
+        "dow" => Some(day_of_week(millis) as i64),
+        "epoch" => Some(millis.div_euclid(MILLIS_PER_SECOND)),
+        _ => None,
+    }
+}
+
+/// Parse a duration like "90s", "15m", "24h", "30d" into milliseconds.
+pub fn parse_duration_ms(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(s.len().checked_sub(1)?);
+    let value: i64 = number.trim().parse().ok()?;
+    let multiplier = match unit {
+        "s" => MILLIS_PER_SECOND,
+        "m" => MILLIS_PER_MINUTE,
+        "h" => MILLIS_PER_HOUR,
+        "d" => MILLIS_PER_DAY,
+        _ => return None,
+    };
+    value.checked_mul(multiplier)
+}
@@ -17,7 +17,9 @@ pub enum Scalar {
     F64(f64),
     Str(String),
     Bin(Vec<u8>),
-    // TODO: Add Date/Time/Decimal, etc.
+    /// Milliseconds since the Unix epoch (UTC); see `crate::time`.
+    Date64(i64),
+    // TODO: Add Decimal, etc.
 }
 
 impl Scalar {
@@ -31,6 +33,7 @@ impl Scalar {
             Scalar::F64(_) => DataType::Float64,
             Scalar::Str(_) => DataType::Utf8,
             Scalar::Bin(_) => DataType::Binary,
+            Scalar::Date64(_) => DataType::Date64,
         }
     }
 }
@@ -118,6 +121,17 @@ impl RowBatch {
         &self,
         hash_keys: &[String],
         num_partitions: usize,
+    ) -> Result<Vec<usize>, String> {
+        self.hash_columns_with(hash_keys, num_partitions, &crate::hash::RowHasher::default())
+    }
+
+    /// Like `hash_columns`, but with a pluggable, seeded hash function so
+    /// partition assignment stays stable across runs for a given seed.
+    pub fn hash_columns_with(
+        &self,
+        hash_keys: &[String],
+        num_partitions: usize,
+        hasher: &crate::hash::RowHasher,
     ) -> Result<Vec<usize>, String> {
         let num_rows = self.num_rows();
         if num_rows == 0 {
@@ -137,19 +151,32 @@ impl RowBatch {
 
         // Compute hash for each row
         let mut result = Vec::with_capacity(num_rows);
+        let mut key_bytes = Vec::new();
         for row_idx in 0..num_rows {
-            let mut hasher = blake3::Hasher::new();
+            key_bytes.clear();
             for &col_idx in &key_indices {
-                hash_scalar(&self.columns[col_idx].values[row_idx], &mut hasher);
+                encode_scalar_for_hash(&self.columns[col_idx].values[row_idx], &mut key_bytes);
             }
-            let hash = hasher.finalize();
-            let hash_u64 = u64::from_le_bytes(hash.as_bytes()[0..8].try_into().unwrap());
+            let hash_u64 = hasher.hash_u64(&key_bytes);
             result.push((hash_u64 as usize) % num_partitions);
         }
 
         Ok(result)
     }
 
+    /// Borrow a zero-copy view over rows `[offset, offset + len)`, clamped
+    /// to the batch bounds.
+    pub fn slice(&self, offset: usize, len: usize) -> BatchSlice<'_> {
+        let rows = self.num_rows();
+        let offset = offset.min(rows);
+        let len = len.min(rows - offset);
+        BatchSlice {
+            batch: self,
+            offset,
+            len,
+        }
+    }
+
     /// Concatenate two RowBatches side-by-side (for join results).
     ///
     /// All rows from `left` and `right` must have the same count.
@@ -184,6 +211,372 @@ impl RowBatch {
     }
 }
 
+
+
+/// Borrowed, zero-copy view over a contiguous row range of a `RowBatch`.
+/// No scalar values are cloned until `to_batch` materializes the slice.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchSlice<'a> {
+    batch: &'a RowBatch,
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> BatchSlice<'a> {
+    pub fn num_rows(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.batch.columns.len()
+    }
+
+    pub fn column_name(&self, col: usize) -> Option<&'a str> {
+        self.batch.columns.get(col).map(|c| c.name.as_str())
+    }
+
+    /// Value at (column, row-within-slice), by reference.
+    pub fn value(&self, col: usize, row: usize) -> Option<&'a Scalar> {
+        if row >= self.len {
+            return None;
+        }
+        self.batch.columns.get(col)?.values.get(self.offset + row)
+    }
+
+    /// Borrowed values of one column within the slice.
+    pub fn column_values(&self, col: usize) -> Option<&'a [Scalar]> {
+        self.batch
+            .columns
+            .get(col)
+            .map(|c| &c.values[self.offset..self.offset + self.len])
+    }
+
+    /// Narrow this slice further (relative offset, clamped to bounds).
+    pub fn slice(&self, offset: usize, len: usize) -> BatchSlice<'a> {
+        let offset = offset.min(self.len);
+        let len = len.min(self.len - offset);
+        BatchSlice {
+            batch: self.batch,
+            offset: self.offset + offset,
+            len,
+        }
+    }
+
+    /// Materialize the view into an owned batch (this is where the copy
+    /// happens).
+    pub fn to_batch(&self) -> RowBatch {
+        RowBatch {
+            columns: self
+                .batch
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: c.values[self.offset..self.offset + self.len].to_vec(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Typed columnar storage: one contiguous vector per column instead of
+/// per-value `Scalar` boxing. Nulls are tracked in `TypedColumn::validity`;
+/// slots for null values hold an arbitrary default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ColumnData {
+    Bool(Vec<bool>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Str(Vec<String>),
+    Bin(Vec<Vec<u8>>),
+    Date64(Vec<i64>),
+}
+
+impl ColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Bool(v) => v.len(),
+            ColumnData::I32(v) => v.len(),
+            ColumnData::I64(v) => v.len(),
+            ColumnData::F32(v) => v.len(),
+            ColumnData::F64(v) => v.len(),
+            ColumnData::Str(v) => v.len(),
+            ColumnData::Bin(v) => v.len(),
+            ColumnData::Date64(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn data_type(&self) -> DataType {
+        match self {
+            ColumnData::Bool(_) => DataType::Boolean,
+            ColumnData::I32(_) => DataType::Int32,
+            ColumnData::I64(_) => DataType::Int64,
+            ColumnData::F32(_) => DataType::Float32,
+            ColumnData::F64(_) => DataType::Float64,
+            ColumnData::Str(_) => DataType::Utf8,
+            ColumnData::Bin(_) => DataType::Binary,
+            ColumnData::Date64(_) => DataType::Date64,
+        }
+    }
+
+    fn with_capacity(data_type: &DataType, capacity: usize) -> Result<Self, String> {
+        Ok(match data_type {
+            DataType::Boolean => ColumnData::Bool(Vec::with_capacity(capacity)),
+            DataType::Int32 => ColumnData::I32(Vec::with_capacity(capacity)),
+            DataType::Int64 => ColumnData::I64(Vec::with_capacity(capacity)),
+            DataType::Float32 => ColumnData::F32(Vec::with_capacity(capacity)),
+            DataType::Float64 => ColumnData::F64(Vec::with_capacity(capacity)),
+            DataType::Utf8 => ColumnData::Str(Vec::with_capacity(capacity)),
+            DataType::Binary => ColumnData::Bin(Vec::with_capacity(capacity)),
+            DataType::Date64 => ColumnData::Date64(Vec::with_capacity(capacity)),
+            other => return Err(format!("unsupported columnar type {:?}", other)),
+        })
+    }
+
+    /// Append one scalar. Nulls push the type's default (validity tracks them).
+    fn push_scalar(&mut self, value: &Scalar) -> Result<(), String> {
+        match (self, value) {
+            (ColumnData::Bool(v), Scalar::Bool(x)) => v.push(*x),
+            (ColumnData::Bool(v), Scalar::Null) => v.push(false),
+            (ColumnData::I32(v), Scalar::I32(x)) => v.push(*x),
+            (ColumnData::I32(v), Scalar::Null) => v.push(0),
+            (ColumnData::I64(v), Scalar::I64(x)) => v.push(*x),
+            (ColumnData::I64(v), Scalar::I32(x)) => v.push(*x as i64),
+            (ColumnData::I64(v), Scalar::Null) => v.push(0),
+            (ColumnData::F32(v), Scalar::F32(x)) => v.push(*x),
+            (ColumnData::F32(v), Scalar::Null) => v.push(0.0),
+            (ColumnData::F64(v), Scalar::F64(x)) => v.push(*x),
+            (ColumnData::F64(v), Scalar::F32(x)) => v.push(*x as f64),
+            (ColumnData::F64(v), Scalar::I32(x)) => v.push(*x as f64),
+            (ColumnData::F64(v), Scalar::I64(x)) => v.push(*x as f64),
+            (ColumnData::F64(v), Scalar::Null) => v.push(0.0),
+            (ColumnData::Str(v), Scalar::Str(x)) => v.push(x.clone()),
+            (ColumnData::Str(v), Scalar::Null) => v.push(String::new()),
+            (ColumnData::Bin(v), Scalar::Bin(x)) => v.push(x.clone()),
+            (ColumnData::Bin(v), Scalar::Null) => v.push(Vec::new()),
+            (ColumnData::Date64(v), Scalar::Date64(x)) => v.push(*x),
+            (ColumnData::Date64(v), Scalar::Null) => v.push(0),
+            (data, value) => {
+                return Err(format!(
+                    "type mismatch: cannot store {:?} in {:?} column",
+                    value,
+                    data.data_type()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read one slot back out as a scalar (ignoring validity).
+    fn get_scalar(&self, idx: usize) -> Scalar {
+        match self {
+            ColumnData::Bool(v) => Scalar::Bool(v[idx]),
+            ColumnData::I32(v) => Scalar::I32(v[idx]),
+            ColumnData::I64(v) => Scalar::I64(v[idx]),
+            ColumnData::F32(v) => Scalar::F32(v[idx]),
+            ColumnData::F64(v) => Scalar::F64(v[idx]),
+            ColumnData::Str(v) => Scalar::Str(v[idx].clone()),
+            ColumnData::Bin(v) => Scalar::Bin(v[idx].clone()),
+            ColumnData::Date64(v) => Scalar::Date64(v[idx]),
+        }
+    }
+}
+
+
+/// Packed validity bitmap: one bit per row (1 = valid, 0 = null), stored in
+/// 64-bit words. Replaces per-row `Scalar::Null` sentinels in the columnar
+/// representation at 1/8th the memory of a `Vec<bool>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Default for Bitmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bitmap {
+    pub fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// A bitmap of `len` bits, all valid. Bits beyond `len` stay zero so
+    /// later `push(false)` calls land on clear bits.
+    pub fn all_valid(len: usize) -> Self {
+        let mut words = vec![u64::MAX; len.div_ceil(64)];
+        if !len.is_multiple_of(64) {
+            if let Some(last) = words.last_mut() {
+                *last = (1u64 << (len % 64)) - 1;
+            }
+        }
+        Self { words, len }
+    }
+
+    pub fn from_bools(bools: &[bool]) -> Self {
+        let mut bitmap = Self::new();
+        for &b in bools {
+            bitmap.push(b);
+        }
+        bitmap
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, valid: bool) {
+        let word = self.len / 64;
+        if word == self.words.len() {
+            self.words.push(0);
+        }
+        if valid {
+            self.words[word] |= 1u64 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    pub fn get(&self, idx: usize) -> bool {
+        debug_assert!(idx < self.len);
+        self.words[idx / 64] & (1u64 << (idx % 64)) != 0
+    }
+
+    pub fn set(&mut self, idx: usize, valid: bool) {
+        debug_assert!(idx < self.len);
+        if valid {
+            self.words[idx / 64] |= 1u64 << (idx % 64);
+        } else {
+            self.words[idx / 64] &= !(1u64 << (idx % 64));
+        }
+    }
+
+    /// Number of null (zero) bits.
+    pub fn null_count(&self) -> usize {
+        let mut valid: usize = 0;
+        for (i, word) in self.words.iter().enumerate() {
+            let mut w = *word;
+            // Mask out bits beyond len in the last word.
+            if (i + 1) * 64 > self.len {
+                w &= (1u64 << (self.len % 64)) - 1;
+            }
+            valid += w.count_ones() as usize;
+        }
+        self.len - valid
+    }
+}
+
+/// A typed column: contiguous data plus an optional validity mask
+/// (`false` = null). `validity: None` means all values are valid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypedColumn {
+    pub name: String,
+    pub data: ColumnData,
+    pub validity: Option<Bitmap>,
+}
+
+impl TypedColumn {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn is_valid(&self, idx: usize) -> bool {
+        self.validity.as_ref().is_none_or(|v| v.get(idx))
+    }
+
+    /// Convert a scalar column, using `data_type` for the array layout.
+    pub fn from_column(col: &Column, data_type: &DataType) -> Result<Self, String> {
+        let mut data = ColumnData::with_capacity(data_type, col.len())?;
+        let mut validity: Option<Bitmap> = None;
+
+        for (idx, value) in col.values.iter().enumerate() {
+            data.push_scalar(value).map_err(|e| {
+                format!("column '{}' row {}: {}", col.name, idx, e)
+            })?;
+            if matches!(value, Scalar::Null) && validity.is_none() {
+                validity = Some(Bitmap::all_valid(idx));
+            }
+            if let Some(mask) = &mut validity {
+                mask.push(!matches!(value, Scalar::Null));
+            }
+        }
+
+        Ok(Self {
+            name: col.name.clone(),
+            data,
+            validity,
+        })
+    }
+
+    /// Infer the array type from the first non-null value (Utf8 when the
+    /// column is entirely null).
+    pub fn from_column_inferred(col: &Column) -> Result<Self, String> {
+        let data_type = col
+            .values
+            .iter()
+            .find(|v| !matches!(v, Scalar::Null))
+            .map(|v| v.data_type())
+            .unwrap_or(DataType::Utf8);
+        Self::from_column(col, &data_type)
+    }
+
+    /// Read one slot as a scalar, honouring validity.
+    pub fn get(&self, idx: usize) -> Scalar {
+        if self.is_valid(idx) {
+            self.data.get_scalar(idx)
+        } else {
+            Scalar::Null
+        }
+    }
+
+    /// Convert back to the scalar representation.
+    pub fn to_column(&self) -> Column {
+        Column {
+            name: self.name.clone(),
+            values: (0..self.len()).map(|i| self.get(i)).collect(),
+        }
+    }
+}
+
+impl RowBatch {
+    /// Convert all columns to typed arrays, inferring each column's type.
+    pub fn to_typed(&self) -> Result<Vec<TypedColumn>, String> {
+        self.columns
+            .iter()
+            .map(TypedColumn::from_column_inferred)
+            .collect()
+    }
+
+    /// Rebuild a scalar `RowBatch` from typed columns.
+    pub fn from_typed(columns: &[TypedColumn]) -> RowBatch {
+        RowBatch {
+            columns: columns.iter().map(|c| c.to_column()).collect(),
+        }
+    }
+}
+
 /// Compare two scalar tuples lexicographically for sorting.
 fn scalar_tuple_cmp(a: &[Scalar], b: &[Scalar]) -> std::cmp::Ordering {
     use std::cmp::Ordering;
@@ -234,6 +627,7 @@ fn scalar_cmp(a: &Scalar, b: &Scalar) -> std::cmp::Ordering {
         }
         (Str(x), Str(y)) => x.cmp(y),
         (Bin(x), Bin(y)) => x.cmp(y),
+        (Date64(x), Date64(y)) => x.cmp(y),
         // Mixed types: order by variant order
         _ => scalar_type_order(a).cmp(&scalar_type_order(b)),
     }
@@ -251,38 +645,42 @@ fn scalar_type_order(s: &Scalar) -> u8 {
         F64(_) => 5,
         Str(_) => 6,
         Bin(_) => 7,
+        Date64(_) => 8,
     }
 }
 
-/// Hash a scalar value into a hasher.
-fn hash_scalar(scalar: &Scalar, hasher: &mut blake3::Hasher) {
+/// Encode a scalar into bytes for hashing (type discriminant + value).
+fn encode_scalar_for_hash(scalar: &Scalar, out: &mut Vec<u8>) {
     use Scalar::*;
 
     // Write type discriminant first
-    hasher.update(&[scalar_type_order(scalar)]);
+    out.push(scalar_type_order(scalar));
 
     match scalar {
         Null => {}
         Bool(b) => {
-            hasher.update(&[*b as u8]);
+            out.push(*b as u8);
         }
         I32(i) => {
-            hasher.update(&i.to_le_bytes());
+            out.extend_from_slice(&i.to_le_bytes());
         }
         I64(i) => {
-            hasher.update(&i.to_le_bytes());
+            out.extend_from_slice(&i.to_le_bytes());
         }
         F32(f) => {
-            hasher.update(&f.to_bits().to_le_bytes());
+            out.extend_from_slice(&f.to_bits().to_le_bytes());
         }
         F64(f) => {
-            hasher.update(&f.to_bits().to_le_bytes());
+            out.extend_from_slice(&f.to_bits().to_le_bytes());
         }
         Str(s) => {
-            hasher.update(s.as_bytes());
+            out.extend_from_slice(s.as_bytes());
         }
         Bin(b) => {
-            hasher.update(b);
+            out.extend_from_slice(b);
+        }
+        Date64(ms) => {
+            out.extend_from_slice(&ms.to_le_bytes());
         }
     }
 }
@@ -0,0 +1,179 @@
+//! Registry of source/sink connectors with capability flags.
+//!
+//! Built-in file formats stay on their fast paths in the runtime; this
+//! registry is the extension point for everything else (databases, queues,
+//! key-value stores). A connector claims URIs via `matches` and the engine
+//! consults the registry for any source/destination its built-in paths
+//! don't recognize. `emsqrt connectors` lists the table.
+
+use emsqrt_core::prelude::Schema;
+
+use crate::runtime::{BatchSink, RowBatchProvider};
+
+/// What a connector can do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Usable as a scan source.
+    pub read: bool,
+    /// Usable as a sink destination.
+    pub write: bool,
+    /// Streams incrementally (vs. whole-dataset reads/writes).
+    pub streaming: bool,
+    /// Supports random access / point lookups.
+    pub random_access: bool,
+}
+
+impl Capabilities {
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.read {
+            parts.push("read");
+        }
+        if self.write {
+            parts.push("write");
+        }
+        if self.streaming {
+            parts.push("streaming");
+        }
+        if self.random_access {
+            parts.push("random-access");
+        }
+        parts.join(", ")
+    }
+}
+
+/// A pluggable source/sink backend.
+pub trait Connector: Send + Sync {
+    /// Stable connector name.
+    fn name(&self) -> &'static str;
+
+    fn capabilities(&self) -> Capabilities;
+
+    /// Does this connector handle the given URI/destination?
+    fn matches(&self, uri: &str) -> bool;
+
+    /// Open a scan source for a matched URI. Default: not readable.
+    fn open_source(
+        &self,
+        uri: &str,
+        _schema: &Schema,
+    ) -> Result<Box<dyn RowBatchProvider>, String> {
+        Err(format!("connector '{}' cannot read '{}'", self.name(), uri))
+    }
+
+    /// Open a sink for a matched destination. Default: not writable.
+    fn open_sink(&self, uri: &str, _format: &str) -> Result<Box<dyn BatchSink>, String> {
+        Err(format!("connector '{}' cannot write '{}'", self.name(), uri))
+    }
+}
+
+/// Capability-only descriptor for the engine's built-in paths, so the
+/// listing covers everything even though their construction is hardwired.
+struct Builtin {
+    name: &'static str,
+    capabilities: Capabilities,
+    prefixes: &'static [&'static str],
+}
+
+impl Connector for Builtin {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+    fn matches(&self, uri: &str) -> bool {
+        self.prefixes.iter().any(|p| uri.starts_with(p))
+    }
+}
+
+pub struct ConnectorRegistry {
+    connectors: Vec<Box<dyn Connector>>,
+}
+
+impl Default for ConnectorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectorRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            connectors: Vec::new(),
+        };
+        // Built-in descriptors (handled directly by the runtime).
+        registry.register(Builtin {
+            name: "csv",
+            capabilities: Capabilities {
+                read: true,
+                write: true,
+                streaming: true,
+                random_access: false,
+            },
+            prefixes: &[], // selected by extension, not scheme
+        });
+        registry.register(Builtin {
+            name: "jsonl",
+            capabilities: Capabilities {
+                read: true,
+                write: true,
+                streaming: true,
+                random_access: false,
+            },
+            prefixes: &[],
+        });
+        registry.register(Builtin {
+            name: "parquet",
+            capabilities: Capabilities {
+                read: true,
+                write: true,
+                streaming: true,
+                random_access: false,
+            },
+            prefixes: &[],
+        });
+        registry.register(Builtin {
+            name: "memory",
+            capabilities: Capabilities {
+                read: true,
+                write: true,
+                streaming: true,
+                random_access: false,
+            },
+            prefixes: &["mem://", "callback://"],
+        });
+        registry
+    }
+
+    pub fn register(&mut self, connector: impl Connector + 'static) {
+        self.connectors.push(Box::new(connector));
+    }
+
+    /// Connector claiming this URI with read capability.
+    pub fn find_source(&self, uri: &str) -> Option<&dyn Connector> {
+        self.connectors
+            .iter()
+            .map(|c| c.as_ref())
+            .find(|c| c.capabilities().read && c.matches(uri))
+    }
+
+    /// Connector claiming this URI with write capability.
+    pub fn find_sink(&self, uri: &str) -> Option<&dyn Connector> {
+        self.connectors
+            .iter()
+            .map(|c| c.as_ref())
+            .find(|c| c.capabilities().write && c.matches(uri))
+    }
+
+    /// (name, capability summary) for every registered connector.
+    pub fn list(&self) -> Vec<(&'static str, String)> {
+        let mut out: Vec<(&'static str, String)> = self
+            .connectors
+            .iter()
+            .map(|c| (c.name(), c.capabilities().summary()))
+            .collect();
+        out.sort_by_key(|(name, _)| *name);
+        out
+    }
+}
@@ -0,0 +1,187 @@
+//! JDBC-style database sources: a generic SQL driver abstraction plugged
+//! into the connector registry.
+//!
+//! A `SqlDriver` executes a query against a DSN and returns text rows — the
+//! lowest common denominator every database client can produce. The
+//! `DatabaseConnector` wraps one driver, claims `<driver>://` URIs of the
+//! form `<driver>://<dsn>/<table>`, and converts rows to the declared scan
+//! schema's types. Concrete drivers (SQLite, Postgres, ...) register per
+//! scheme, mirroring how JDBC dispatches on the URL.
+
+use std::sync::Arc;
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::runtime::RowBatchProvider;
+
+/// Text-level SQL driver: the minimal surface a database client must offer.
+pub trait SqlDriver: Send + Sync {
+    /// Scheme this driver serves (e.g. "sqlite", "postgres").
+    fn scheme(&self) -> &'static str;
+
+    /// Run a query against `dsn`; returns column names plus rows of
+    /// nullable text values.
+    #[allow(clippy::type_complexity)]
+    fn query(
+        &self,
+        dsn: &str,
+        sql: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<Option<String>>>), String>;
+}
+
+/// Connector adapting a `SqlDriver` to the source side of the registry.
+pub struct DatabaseConnector {
+    driver: Arc<dyn SqlDriver>,
+}
+
+impl DatabaseConnector {
+    pub fn new(driver: impl SqlDriver + 'static) -> Self {
+        Self {
+            driver: Arc::new(driver),
+        }
+    }
+
+    /// Split `<driver>://<dsn>/<table>` into (dsn, table). The table is the
+    /// final path component; everything before it belongs to the DSN.
+    fn parse_uri<'a>(&self, uri: &'a str) -> Result<(&'a str, &'a str), String> {
+        let rest = uri
+            .strip_prefix(self.driver.scheme())
+            .and_then(|r| r.strip_prefix("://"))
+            .ok_or_else(|| format!("not a {} URI: '{}'", self.driver.scheme(), uri))?;
+        rest.rsplit_once('/')
+            .filter(|(dsn, table)| !dsn.is_empty() && !table.is_empty())
+            .ok_or_else(|| {
+                format!(
+                    "expected {}://<dsn>/<table>, got '{}'",
+                    self.driver.scheme(),
+                    uri
+                )
+            })
+    }
+}
+
+impl Connector for DatabaseConnector {
+    fn name(&self) -> &'static str {
+        self.driver.scheme()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: true,
+            write: false,
+            streaming: false, // whole result set per query
+            random_access: true,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with(self.driver.scheme()) && uri[self.driver.scheme().len()..].starts_with("://")
+    }
+
+    fn open_source(
+        &self,
+        uri: &str,
+        schema: &Schema,
+    ) -> Result<Box<dyn RowBatchProvider>, String> {
+        let (dsn, table) = self.parse_uri(uri)?;
+
+        // Project only the declared columns when a schema is given.
+        let column_list = if schema.fields.is_empty() {
+            "*".to_string()
+        } else {
+            schema
+                .fields
+                .iter()
+                .map(|f| f.name.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let sql = format!("SELECT {} FROM {}", column_list, table);
+
+        Ok(Box::new(DatabaseSource {
+            driver: self.driver.clone(),
+            dsn: dsn.to_string(),
+            sql,
+            schema: schema.clone(),
+            done: false,
+        }))
+    }
+}
+
+/// Lazily runs the query on first pull and emits one batch.
+struct DatabaseSource {
+    driver: Arc<dyn SqlDriver>,
+    dsn: String,
+    sql: String,
+    schema: Schema,
+    done: bool,
+}
+
+impl RowBatchProvider for DatabaseSource {
+    fn next_batch(&mut self) -> Option<RowBatch> {
+        if self.done {
+            return None;
+        }
+        self.done = true;
+
+        let (names, rows) = match self.driver.query(&self.dsn, &self.sql) {
+            Ok(result) => result,
+            Err(e) => {
+                // RowBatchProvider has no error channel; surface loudly and
+                // end the stream so the run fails downstream on row counts.
+                eprintln!("database source query failed: {}", e);
+                return None;
+            }
+        };
+
+        let mut columns: Vec<Column> = names
+            .iter()
+            .map(|name| Column {
+                name: name.clone(),
+                values: Vec::with_capacity(rows.len()),
+            })
+            .collect();
+
+        for row in rows {
+            for (idx, value) in row.into_iter().enumerate() {
+                let Some(column) = columns.get_mut(idx) else {
+                    continue;
+                };
+                let scalar = match value {
+                    None => Scalar::Null,
+                    Some(text) => {
+                        // Parse per the declared type when the schema names
+                        // this column; raw text otherwise.
+                        match self.schema.fields.iter().find(|f| f.name == column.name) {
+                            Some(field) => typed_text(&field.data_type, &text),
+                            None => Scalar::Str(text),
+                        }
+                    }
+                };
+                column.values.push(scalar);
+            }
+        }
+
+        Some(RowBatch { columns })
+    }
+}
+
+fn typed_text(data_type: &emsqrt_core::schema::DataType, text: &str) -> Scalar {
+    use emsqrt_core::schema::DataType;
+    match data_type {
+        DataType::Boolean => text
+            .parse::<bool>()
+            .map(Scalar::Bool)
+            .unwrap_or_else(|_| Scalar::Bool(text == "1")),
+        DataType::Int32 => text.parse().map(Scalar::I32).unwrap_or(Scalar::Null),
+        DataType::Int64 => text.parse().map(Scalar::I64).unwrap_or(Scalar::Null),
+        DataType::Float32 => text.parse().map(Scalar::F32).unwrap_or(Scalar::Null),
+        DataType::Float64 => text.parse().map(Scalar::F64).unwrap_or(Scalar::Null),
+        DataType::Date64 => emsqrt_core::time::parse_datetime(text)
+            .map(Scalar::Date64)
+            .unwrap_or(Scalar::Null),
+        _ => Scalar::Str(text.to_string()),
+    }
+}
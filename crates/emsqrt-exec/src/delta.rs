@@ -0,0 +1,367 @@
+//! Delta Lake table sink (behind the `parquet` feature).
+//!
+//! Writes a minimal but spec-conformant Delta table: Parquet data files
+//! plus JSON commits in `_delta_log/` (protocol + metaData on the first
+//! commit, an `add` action per file), enough for Spark/DuckDB/polars
+//! readers to consume the table. Re-opening an existing table resumes from
+//! the highest committed version instead of clobbering commit 0, and
+//! `delta://dir?partition=col1,col2` lays data files out hive-style with
+//! the partition columns recorded in partitionValues rather than the
+//! files. Iceberg emits a clear "not supported yet" error rather than
+//! pretending.
+
+use std::path::PathBuf;
+
+use emsqrt_core::prelude::{DataType, Schema};
+use emsqrt_core::types::RowBatch;
+
+use crate::connectors::{Capabilities, Connector};
+use crate::runtime::BatchSink;
+
+pub struct DeltaConnector;
+
+impl Connector for DeltaConnector {
+    fn name(&self) -> &'static str {
+        "delta"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: false,
+            write: true,
+            streaming: true,
+            random_access: false,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("delta://") || uri.starts_with("iceberg://")
+    }
+
+    fn open_sink(
+        &self,
+        uri: &str,
+        _format: &str,
+    ) -> Result<Box<dyn crate::runtime::BatchSink>, String> {
+        if uri.starts_with("iceberg://") {
+            return Err(
+                "Iceberg table sinks are not supported yet; use delta://<table dir>".to_string(),
+            );
+        }
+        let rest = uri
+            .strip_prefix("delta://")
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| format!("expected delta://<table dir>, got '{}'", uri))?;
+        let (dir, query) = match rest.split_once('?') {
+            Some((dir, query)) => (dir, Some(query)),
+            None => (rest, None),
+        };
+
+        let mut partition_columns = Vec::new();
+        for pair in query.unwrap_or("").split('&').filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some(("partition", columns)) => {
+                    partition_columns = columns
+                        .split(',')
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty())
+                        .collect();
+                }
+                _ => return Err(format!("unknown delta URI option '{}'", pair)),
+            }
+        }
+
+        let table_dir = PathBuf::from(dir);
+        // Appending to an existing table continues its version sequence;
+        // protocol + metaData were committed by whoever created it.
+        let version = next_version(&table_dir);
+        Ok(Box::new(DeltaSink {
+            table_dir,
+            committed_metadata: version > 0,
+            version,
+            partition_columns,
+        }))
+    }
+}
+
+/// Highest committed version in `_delta_log` plus one, or 0 for a fresh
+/// table (or one whose log holds nothing parseable).
+fn next_version(table_dir: &std::path::Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(table_dir.join("_delta_log")) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name();
+            let name = name.to_str()?;
+            name.strip_suffix(".json")?.parse::<u64>().ok()
+        })
+        .max()
+        .map(|v| v + 1)
+        .unwrap_or(0)
+}
+
+struct DeltaSink {
+    table_dir: PathBuf,
+    version: u64,
+    committed_metadata: bool,
+    /// Hive-style partition columns (from `?partition=col1,col2`): encoded
+    /// in the data-file paths and partitionValues, excluded from the files.
+    partition_columns: Vec<String>,
+}
+
+/// Spark-style schemaString type names for the metaData action.
+fn delta_type(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int32 => "integer",
+        DataType::Int64 => "long",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        DataType::Date64 => "timestamp",
+        DataType::Binary => "binary",
+        _ => "string",
+    }
+}
+
+fn schema_string(schema: &Schema) -> String {
+    let fields: Vec<serde_json::Value> = schema
+        .fields
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "name": f.name,
+                "type": delta_type(&f.data_type),
+                "nullable": f.nullable,
+                "metadata": {}
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "struct", "fields": fields }).to_string()
+}
+
+/// Infer a schema from a batch (column types from first non-null values).
+pub(crate) fn batch_schema(batch: &RowBatch) -> Schema {
+    use emsqrt_core::prelude::Field;
+    use emsqrt_core::types::Scalar;
+    Schema::new(
+        batch
+            .columns
+            .iter()
+            .map(|c| {
+                let data_type = c
+                    .values
+                    .iter()
+                    .find(|v| !matches!(v, Scalar::Null))
+                    .map(|v| v.data_type())
+                    .unwrap_or(DataType::Utf8);
+                Field::new(c.name.clone(), data_type, true)
+            })
+            .collect(),
+    )
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl DeltaSink {
+    /// Write one Parquet data file and return its add action. `rows` of
+    /// None means the whole batch; partitioned writes pass the group's row
+    /// indices and strip the partition columns from the file contents.
+    fn write_data_file(
+        &self,
+        batch: &RowBatch,
+        relative_dir: &str,
+        rows: Option<&[usize]>,
+        partition_values: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        use emsqrt_core::types::Column;
+
+        let data_batch = RowBatch {
+            columns: batch
+                .columns
+                .iter()
+                .filter(|c| !self.partition_columns.contains(&c.name))
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: match rows {
+                        Some(rows) => rows.iter().map(|&r| c.values[r].clone()).collect(),
+                        None => c.values.clone(),
+                    },
+                })
+                .collect(),
+        };
+
+        let data_name = format!(
+            "{}part-{:05}-{}.parquet",
+            relative_dir,
+            self.version,
+            uuid_like()
+        );
+        let data_path = self.table_dir.join(&data_name);
+        if let Some(parent) = data_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("create partition dir: {}", e))?;
+        }
+        let schema = batch_schema(&data_batch);
+        let mut writer = emsqrt_io::writers::parquet::ParquetWriter::from_emsqrt_schema(
+            data_path
+                .to_str()
+                .ok_or_else(|| "non-utf8 table path".to_string())?,
+            &schema,
+        )
+        .map_err(|e| format!("delta data file: {}", e))?;
+        writer
+            .write_row_batch(&data_batch)
+            .map_err(|e| format!("delta data write: {}", e))?;
+        writer
+            .close()
+            .map_err(|e| format!("delta data close: {}", e))?;
+        let size = std::fs::metadata(&data_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        Ok(serde_json::json!({
+            "add": {
+                "path": data_name,
+                "partitionValues": partition_values,
+                "size": size,
+                "modificationTime": now_millis(),
+                "dataChange": true,
+            }
+        }))
+    }
+}
+
+impl BatchSink for DeltaSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let log_dir = self.table_dir.join("_delta_log");
+        std::fs::create_dir_all(&log_dir).map_err(|e| format!("create _delta_log: {}", e))?;
+
+        // Data files for this batch: one for an unpartitioned table, one
+        // per partition-value combination otherwise.
+        let mut adds = Vec::new();
+        if self.partition_columns.is_empty() {
+            adds.push(self.write_data_file(batch, "", None, serde_json::json!({}))?);
+        } else {
+            let partition_idx: Vec<usize> = self
+                .partition_columns
+                .iter()
+                .map(|name| {
+                    batch
+                        .columns
+                        .iter()
+                        .position(|c| &c.name == name)
+                        .ok_or_else(|| format!("delta partition column '{}' not found", name))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut groups: std::collections::BTreeMap<Vec<String>, Vec<usize>> =
+                std::collections::BTreeMap::new();
+            for row in 0..batch.num_rows() {
+                let values: Vec<String> = partition_idx
+                    .iter()
+                    .map(|&idx| partition_value(&batch.columns[idx].values[row]))
+                    .collect();
+                groups.entry(values).or_default().push(row);
+            }
+
+            for (values, rows) in groups {
+                let relative_dir: String = self
+                    .partition_columns
+                    .iter()
+                    .zip(&values)
+                    .map(|(name, value)| format!("{}={}/", name, value))
+                    .collect();
+                let partition_values: serde_json::Map<String, serde_json::Value> = self
+                    .partition_columns
+                    .iter()
+                    .zip(&values)
+                    .map(|(name, value)| {
+                        (name.clone(), serde_json::Value::String(value.clone()))
+                    })
+                    .collect();
+                adds.push(self.write_data_file(
+                    batch,
+                    &relative_dir,
+                    Some(&rows),
+                    serde_json::Value::Object(partition_values),
+                )?);
+            }
+        }
+
+        // Commit: protocol + metaData first time, then the add actions.
+        let mut commit = String::new();
+        if !self.committed_metadata {
+            commit.push_str(
+                &serde_json::json!({
+                    "protocol": { "minReaderVersion": 1, "minWriterVersion": 2 }
+                })
+                .to_string(),
+            );
+            commit.push('\n');
+            commit.push_str(
+                &serde_json::json!({
+                    "metaData": {
+                        "id": uuid_like(),
+                        "format": { "provider": "parquet", "options": {} },
+                        "schemaString": schema_string(&batch_schema(batch)),
+                        "partitionColumns": self.partition_columns,
+                        "configuration": {},
+                        "createdTime": now_millis(),
+                    }
+                })
+                .to_string(),
+            );
+            commit.push('\n');
+            self.committed_metadata = true;
+        }
+        for add in adds {
+            commit.push_str(&add.to_string());
+            commit.push('\n');
+        }
+
+        let commit_path = log_dir.join(format!("{:020}.json", self.version));
+        std::fs::write(&commit_path, commit).map_err(|e| format!("delta commit: {}", e))?;
+        self.version += 1;
+        Ok(())
+    }
+}
+
+/// Hive-safe rendering of one partition value for the directory path.
+fn partition_value(scalar: &emsqrt_core::types::Scalar) -> String {
+    use emsqrt_core::types::Scalar;
+    let raw = match scalar {
+        Scalar::Null => return "__HIVE_DEFAULT_PARTITION__".to_string(),
+        Scalar::Str(s) => s.clone(),
+        other => format!("{:?}", other),
+    };
+    raw.chars()
+        .map(|c| if c.is_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect()
+}
+
+/// Pseudo-UUID from the engine's hash helper (no uuid dependency here).
+fn uuid_like() -> String {
+    let hash = blake3::hash(
+        format!("{}-{:?}", now_millis(), std::thread::current().id()).as_bytes(),
+    );
+    let hex = hash.to_hex();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
@@ -0,0 +1,218 @@
+//! DuckDB interop source/sink connector (behind the `duckdb` feature).
+//!
+//! Mirrors the SQLite connector: drives the `duckdb` CLI in JSON mode
+//! rather than linking the (large) embedded engine, so the feature costs
+//! nothing at build time beyond this module. URIs take the form
+//! `duckdb://<db file>/<table>`.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::RowBatch;
+
+use crate::connectors::{Capabilities, Connector};
+use crate::database::{DatabaseConnector, SqlDriver};
+use crate::runtime::{BatchSink, RowBatchProvider};
+use crate::sqlite::{column_type, sql_literal};
+
+/// SQL driver backed by the `duckdb` CLI.
+pub struct DuckDbDriver {
+    duckdb_cmd: String,
+}
+
+impl Default for DuckDbDriver {
+    fn default() -> Self {
+        Self {
+            duckdb_cmd: "duckdb".to_string(),
+        }
+    }
+}
+
+impl SqlDriver for DuckDbDriver {
+    fn scheme(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn query(
+        &self,
+        dsn: &str,
+        sql: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<Option<String>>>), String> {
+        let output = Command::new(&self.duckdb_cmd)
+            .arg("-json")
+            .arg("-readonly")
+            .arg(dsn)
+            .arg("-c")
+            .arg(sql)
+            .output()
+            .map_err(|e| format!("spawn {}: {}", self.duckdb_cmd, e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "duckdb query failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        if text.trim().is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_str(text.trim()).map_err(|e| format!("duckdb json: {}", e))?;
+
+        let names: Vec<String> = rows
+            .first()
+            .map(|row| row.keys().cloned().collect())
+            .unwrap_or_default();
+        let data = rows
+            .into_iter()
+            .map(|row| {
+                names
+                    .iter()
+                    .map(|name| match row.get(name) {
+                        None | Some(serde_json::Value::Null) => None,
+                        Some(serde_json::Value::String(s)) => Some(s.clone()),
+                        Some(other) => Some(other.to_string()),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok((names, data))
+    }
+}
+
+/// Full DuckDB connector: reads via the generic database source, writes via
+/// batched INSERT transactions.
+pub struct DuckDbConnector {
+    source: DatabaseConnector,
+    duckdb_cmd: String,
+}
+
+impl Default for DuckDbConnector {
+    fn default() -> Self {
+        Self::with_command("duckdb")
+    }
+}
+
+impl DuckDbConnector {
+    pub fn with_command(cmd: impl Into<String>) -> Self {
+        let cmd = cmd.into();
+        Self {
+            source: DatabaseConnector::new(DuckDbDriver {
+                duckdb_cmd: cmd.clone(),
+            }),
+            duckdb_cmd: cmd,
+        }
+    }
+}
+
+impl Connector for DuckDbConnector {
+    fn name(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: true,
+            write: true,
+            streaming: false,
+            random_access: true,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("duckdb://")
+    }
+
+    fn open_source(
+        &self,
+        uri: &str,
+        schema: &Schema,
+    ) -> Result<Box<dyn RowBatchProvider>, String> {
+        self.source.open_source(uri, schema)
+    }
+
+    fn open_sink(&self, uri: &str, _format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let rest = uri
+            .strip_prefix("duckdb://")
+            .ok_or_else(|| format!("not a duckdb URI: '{}'", uri))?;
+        let (db, table) = rest
+            .rsplit_once('/')
+            .filter(|(db, table)| !db.is_empty() && !table.is_empty())
+            .ok_or_else(|| format!("expected duckdb://<db file>/<table>, got '{}'", uri))?;
+        Ok(Box::new(DuckDbSink {
+            duckdb_cmd: self.duckdb_cmd.clone(),
+            db: db.to_string(),
+            table: table.to_string(),
+            table_created: false,
+        }))
+    }
+}
+
+struct DuckDbSink {
+    duckdb_cmd: String,
+    db: String,
+    table: String,
+    table_created: bool,
+}
+
+impl BatchSink for DuckDbSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let mut script = String::from("BEGIN;\n");
+        if !self.table_created {
+            let columns: Vec<String> = batch
+                .columns
+                .iter()
+                .map(|c| format!("\"{}\" {}", c.name, column_type(&c.values)))
+                .collect();
+            script.push_str(&format!(
+                "CREATE TABLE IF NOT EXISTS \"{}\" ({});\n",
+                self.table,
+                columns.join(", ")
+            ));
+            self.table_created = true;
+        }
+        for row in 0..batch.num_rows() {
+            let values: Vec<String> = batch
+                .columns
+                .iter()
+                .map(|c| sql_literal(&c.values[row]))
+                .collect();
+            script.push_str(&format!(
+                "INSERT INTO \"{}\" VALUES ({});\n",
+                self.table,
+                values.join(", ")
+            ));
+        }
+        script.push_str("COMMIT;\n");
+
+        let mut child = Command::new(&self.duckdb_cmd)
+            .arg(&self.db)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("spawn {}: {}", self.duckdb_cmd, e))?;
+        child
+            .stdin
+            .take()
+            .expect("stdin piped")
+            .write_all(script.as_bytes())
+            .map_err(|e| format!("duckdb stdin: {}", e))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("duckdb wait: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "duckdb insert failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(())
+    }
+}
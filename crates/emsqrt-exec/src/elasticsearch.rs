@@ -0,0 +1,274 @@
+//! Elasticsearch/OpenSearch bulk sink connector.
+//!
+//! Destinations of the form `es://host:port/<index>` (also
+//! `elasticsearch://` / `opensearch://`) send each batch as
+//! `POST /<index>/_bulk` requests of NDJSON action/document pairs. The
+//! index may embed a column reference (`events-{region}`) resolved per
+//! row; the sink format string can name a column used as the document
+//! `_id` (file-format values mean auto-assigned ids).
+//!
+//! Transient failures — connection errors, 429 Too Many Requests, and
+//! 5xx — retry with exponential backoff. Permanent failures (other 4xx,
+//! exhausted retries, or per-item errors in a 200 response) are routed to
+//! the dead-letter file named by `?dead_letter=<path>` when configured,
+//! letting the run continue; without one they abort the run.
+
+use emsqrt_core::types::{RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::http;
+use crate::runtime::BatchSink;
+
+pub struct ElasticsearchConnector;
+
+const SCHEMES: &[&str] = &["es://", "elasticsearch://", "opensearch://"];
+
+impl Connector for ElasticsearchConnector {
+    fn name(&self) -> &'static str {
+        "elasticsearch"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: false,
+            write: true,
+            streaming: true,
+            random_access: false,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        SCHEMES.iter().any(|s| uri.starts_with(s))
+    }
+
+    fn open_sink(&self, uri: &str, format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let rest = SCHEMES
+            .iter()
+            .find_map(|s| uri.strip_prefix(s))
+            .ok_or_else(|| format!("not an elasticsearch URI: '{}'", uri))?;
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (host, port, path) = http::parse_target(rest, 9200)?;
+        let index = path.trim_matches('/').to_string();
+        if index.is_empty() {
+            return Err(format!("expected es://host:port/<index>, got '{}'", uri));
+        }
+
+        let mut dead_letter = None;
+        for pair in query.unwrap_or("").split('&').filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some(("dead_letter", path)) => dead_letter = Some(path.to_string()),
+                _ => return Err(format!("unknown elasticsearch URI option '{}'", pair)),
+            }
+        }
+
+        let id_column = match format {
+            "" | "csv" | "jsonl" | "parquet" => None,
+            column => Some(column.to_string()),
+        };
+
+        Ok(Box::new(ElasticsearchSink {
+            host,
+            port,
+            index,
+            id_column,
+            dead_letter,
+        }))
+    }
+}
+
+/// Retry attempts per bulk request after the first try.
+const MAX_RETRIES: usize = 3;
+const INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+struct ElasticsearchSink {
+    host: String,
+    port: u16,
+    /// Index name, optionally embedding a `{column}` reference resolved per
+    /// row ("events-{region}").
+    index: String,
+    id_column: Option<String>,
+    /// NDJSON file receiving permanently failed bulk bodies (with errors)
+    /// instead of aborting the run.
+    dead_letter: Option<String>,
+}
+
+fn scalar_json(value: &Scalar) -> serde_json::Value {
+    match value {
+        Scalar::Null => serde_json::Value::Null,
+        Scalar::Bool(b) => serde_json::Value::Bool(*b),
+        Scalar::I32(v) => serde_json::Value::from(*v),
+        Scalar::I64(v) => serde_json::Value::from(*v),
+        Scalar::F32(v) => serde_json::Value::from(*v as f64),
+        Scalar::F64(v) => serde_json::Value::from(*v),
+        Scalar::Str(s) => serde_json::Value::String(s.clone()),
+        Scalar::Date64(ms) => {
+            serde_json::Value::String(emsqrt_core::time::format_datetime(*ms))
+        }
+        Scalar::Bin(b) => serde_json::Value::String(format!("[binary {} bytes]", b.len())),
+    }
+}
+
+impl ElasticsearchSink {
+    /// Resolve the target index for one row, expanding a `{column}`
+    /// reference when the configured index has one.
+    fn index_for_row(&self, batch: &RowBatch, row: usize) -> Result<String, String> {
+        let (Some(open), Some(close)) = (self.index.find('{'), self.index.find('}')) else {
+            return Ok(self.index.clone());
+        };
+        let column_name = &self.index[open + 1..close];
+        let column = batch
+            .columns
+            .iter()
+            .find(|c| c.name == column_name)
+            .ok_or_else(|| format!("es index column '{}' not found", column_name))?;
+        let value = match &column.values[row] {
+            Scalar::Str(s) => s.clone(),
+            Scalar::Null => "null".to_string(),
+            other => format!("{:?}", other),
+        };
+        Ok(format!(
+            "{}{}{}",
+            &self.index[..open],
+            value.to_lowercase(),
+            &self.index[close + 1..]
+        ))
+    }
+
+    /// One bulk POST with retry/backoff on transient failures (connection
+    /// errors, 429, 5xx). Returns the permanent-failure description on
+    /// giving up.
+    fn bulk_with_retry(&self, index: &str, body: &str) -> Result<(), String> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut last_error = String::new();
+
+        for attempt in 0..=MAX_RETRIES {
+            match http::request(
+                &self.host,
+                self.port,
+                "POST",
+                &format!("/{}/_bulk", index),
+                "application/x-ndjson",
+                body.as_bytes(),
+            ) {
+                Ok((status, response)) if status == 200 || status == 201 => {
+                    // Bulk responses are 200 even when individual items fail;
+                    // item errors are permanent.
+                    let parsed: serde_json::Value = serde_json::from_slice(&response)
+                        .map_err(|e| format!("es bulk response json: {}", e))?;
+                    if parsed["errors"].as_bool() == Some(true) {
+                        return Err(format!(
+                            "es bulk reported item errors: {}",
+                            String::from_utf8_lossy(&response)
+                        ));
+                    }
+                    return Ok(());
+                }
+                Ok((429, _)) => {
+                    last_error = "429 Too Many Requests".to_string();
+                }
+                Ok((status, response)) if status >= 500 => {
+                    last_error = format!("server error {}", status);
+                    let _ = response;
+                }
+                Ok((status, response)) => {
+                    // Other 4xx: permanent, no point retrying.
+                    return Err(format!(
+                        "es bulk rejected with {}: {}",
+                        status,
+                        String::from_utf8_lossy(&response)
+                    ));
+                }
+                Err(e) => {
+                    last_error = e;
+                }
+            }
+            if attempt < MAX_RETRIES {
+                std::thread::sleep(backoff);
+                backoff *= 2;
+            }
+        }
+        Err(format!(
+            "es bulk failed after {} attempts: {}",
+            MAX_RETRIES + 1,
+            last_error
+        ))
+    }
+
+    /// Route a permanently failed bulk body to the dead-letter file, or
+    /// surface the error when no dead-letter channel is configured.
+    fn dead_letter_or_fail(&self, index: &str, body: &str, error: String) -> Result<(), String> {
+        let Some(path) = &self.dead_letter else {
+            return Err(error);
+        };
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("open dead-letter '{}': {}", path, e))?;
+        let entry = serde_json::json!({
+            "index": index,
+            "error": error,
+            "bulk_body": body,
+        });
+        writeln!(file, "{}", entry).map_err(|e| format!("dead-letter write: {}", e))?;
+        Ok(())
+    }
+}
+
+impl BatchSink for ElasticsearchSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let id_idx = match &self.id_column {
+            Some(name) => Some(
+                batch
+                    .columns
+                    .iter()
+                    .position(|c| &c.name == name)
+                    .ok_or_else(|| format!("es id column '{}' not found", name))?,
+            ),
+            None => None,
+        };
+
+        // NDJSON bulk bodies, one per resolved target index.
+        let mut bodies: std::collections::BTreeMap<String, String> =
+            std::collections::BTreeMap::new();
+        for row in 0..batch.num_rows() {
+            let index = self.index_for_row(batch, row)?;
+            let body = bodies.entry(index).or_default();
+
+            let action = match id_idx {
+                Some(idx) => {
+                    let id = match &batch.columns[idx].values[row] {
+                        Scalar::Str(s) => s.clone(),
+                        other => format!("{:?}", other),
+                    };
+                    serde_json::json!({ "index": { "_id": id } })
+                }
+                None => serde_json::json!({ "index": {} }),
+            };
+            body.push_str(&action.to_string());
+            body.push('\n');
+
+            let mut doc = serde_json::Map::new();
+            for column in &batch.columns {
+                doc.insert(column.name.clone(), scalar_json(&column.values[row]));
+            }
+            body.push_str(&serde_json::Value::Object(doc).to_string());
+            body.push('\n');
+        }
+
+        for (index, body) in bodies {
+            if let Err(error) = self.bulk_with_retry(&index, &body) {
+                self.dead_letter_or_fail(&index, &body, error)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Minimal HTTP/1.1 client shared by the HTTP-speaking connectors
+//! (Elasticsearch bulk sink, webhook sink). One request per connection,
+//! no TLS — point these connectors at plain-HTTP endpoints or a local
+//! proxy.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+/// (status code, response body).
+pub(crate) type Response = (u16, Vec<u8>);
+
+pub(crate) fn request(
+    host: &str,
+    port: u16,
+    method: &str,
+    path: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<Response, String> {
+    let mut stream = TcpStream::connect((host, port))
+        .map_err(|e| format!("connect {}:{}: {}", host, port, e))?;
+
+    let head = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {host}:{port}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|_| stream.write_all(body))
+        .map_err(|e| format!("send: {}", e))?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| format!("recv: {}", e))?;
+
+    let header_end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| "malformed HTTP response".to_string())?;
+    let status: u16 = String::from_utf8_lossy(&response[..header_end])
+        .lines()
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| "malformed status line".to_string())?;
+
+    Ok((status, response[header_end + 4..].to_vec()))
+}
+
+/// Parse `host[:port]/path` with a default port.
+pub(crate) fn parse_target(rest: &str, default_port: u16) -> Result<(String, u16, String), String> {
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], rest[pos..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((h, p)) => (
+            h.to_string(),
+            p.parse::<u16>().map_err(|e| format!("bad port: {}", e))?,
+        ),
+        None => (authority.to_string(), default_port),
+    };
+    Ok((host, port, path))
+}
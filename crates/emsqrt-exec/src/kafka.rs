@@ -0,0 +1,390 @@
+//! Kafka source and sink connectors.
+//!
+//! Drives `kcat` (kafkacat) rather than binding librdkafka, matching the
+//! other CLI-backed connectors: no build dependency, and the requirement
+//! surfaces on first use. URIs take the form
+//! `kafka://<broker[:port]>/<topic>[?offset=N][&count=M]`.
+//!
+//! - Source: consumes a bounded range (`offset`/`count`, defaulting to
+//!   beginning-to-current-end) by streaming the consumer's stdout in
+//!   chunk-sized batches, so memory stays capped regardless of topic size.
+//!   Each message is one JSON object mapped onto the declared schema.
+//! - Sink: streams rows as JSON lines into a long-lived `kcat -P` producer;
+//!   the sink format string can name a column used as the message key.
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::runtime::{BatchSink, RowBatchProvider};
+
+pub struct KafkaConnector {
+    kcat_cmd: String,
+}
+
+impl Default for KafkaConnector {
+    fn default() -> Self {
+        Self::with_command("kcat")
+    }
+}
+
+impl KafkaConnector {
+    /// Use a different kcat binary (tests/sandboxes).
+    pub fn with_command(cmd: impl Into<String>) -> Self {
+        Self {
+            kcat_cmd: cmd.into(),
+        }
+    }
+
+    fn parse_uri<'a>(&self, uri: &'a str) -> Result<KafkaTarget<'a>, String> {
+        let rest = uri
+            .strip_prefix("kafka://")
+            .ok_or_else(|| format!("not a kafka URI: '{}'", uri))?;
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (broker, topic) = rest
+            .split_once('/')
+            .filter(|(broker, topic)| !broker.is_empty() && !topic.is_empty())
+            .ok_or_else(|| format!("expected kafka://<broker>/<topic>, got '{}'", uri))?;
+
+        let mut offset = None;
+        let mut count = None;
+        for pair in query.unwrap_or("").split('&').filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some(("offset", v)) => {
+                    offset = Some(v.parse::<u64>().map_err(|e| {
+                        format!("bad kafka offset '{}': {}", v, e)
+                    })?);
+                }
+                Some(("count", v)) => {
+                    count = Some(v.parse::<u64>().map_err(|e| {
+                        format!("bad kafka count '{}': {}", v, e)
+                    })?);
+                }
+                _ => return Err(format!("unknown kafka URI option '{}'", pair)),
+            }
+        }
+        Ok(KafkaTarget {
+            broker,
+            topic,
+            offset,
+            count,
+        })
+    }
+}
+
+/// Parsed kafka URI: broker, topic, and the bounded offset range.
+struct KafkaTarget<'a> {
+    broker: &'a str,
+    topic: &'a str,
+    /// Starting offset (default: beginning).
+    offset: Option<u64>,
+    /// Maximum messages to consume (default: up to the current end).
+    count: Option<u64>,
+}
+
+impl Connector for KafkaConnector {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: true,
+            write: true,
+            streaming: true,
+            random_access: false,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("kafka://")
+    }
+
+    fn open_source(
+        &self,
+        uri: &str,
+        schema: &Schema,
+    ) -> Result<Box<dyn RowBatchProvider>, String> {
+        let target = self.parse_uri(uri)?;
+        Ok(Box::new(KafkaSource {
+            kcat_cmd: self.kcat_cmd.clone(),
+            broker: target.broker.to_string(),
+            topic: target.topic.to_string(),
+            offset: target.offset,
+            count: target.count,
+            schema: schema.clone(),
+            consumer: None,
+            done: false,
+        }))
+    }
+
+    fn open_sink(&self, uri: &str, format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let target = self.parse_uri(uri)?;
+        // The sink format string names the message-key column; file-format
+        // values ("csv", ...) mean keyless messages.
+        let key_column = match format {
+            "" | "csv" | "jsonl" | "parquet" => None,
+            column => Some(column.to_string()),
+        };
+        Ok(Box::new(KafkaSink {
+            kcat_cmd: self.kcat_cmd.clone(),
+            broker: target.broker.to_string(),
+            topic: target.topic.to_string(),
+            key_column,
+            producer: None,
+        }))
+    }
+}
+
+/// Messages per emitted batch from the consumer.
+const KAFKA_CHUNK_ROWS: usize = 10_000;
+
+/// Streams a bounded offset range of the topic as chunk-sized batches: the
+/// consumer process stays open across `next_batch` calls and its stdout is
+/// read incrementally, so memory is capped at one chunk.
+struct KafkaSource {
+    kcat_cmd: String,
+    broker: String,
+    topic: String,
+    offset: Option<u64>,
+    count: Option<u64>,
+    schema: Schema,
+    consumer: Option<(Child, std::io::BufReader<std::process::ChildStdout>)>,
+    done: bool,
+}
+
+impl KafkaSource {
+    fn spawn_consumer(&mut self) -> Result<(), String> {
+        let offset = self
+            .offset
+            .map(|o| o.to_string())
+            .unwrap_or_else(|| "beginning".to_string());
+        let mut command = Command::new(&self.kcat_cmd);
+        command.args(["-C", "-b", &self.broker, "-t", &self.topic, "-o", &offset]);
+        if let Some(count) = self.count {
+            command.args(["-c", &count.to_string()]);
+        } else {
+            command.arg("-e"); // stop at the current end of the topic
+        }
+        let mut child = command
+            .arg("-q")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("spawn {}: {}", self.kcat_cmd, e))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "kafka consumer stdout closed".to_string())?;
+        self.consumer = Some((child, std::io::BufReader::new(stdout)));
+        Ok(())
+    }
+}
+
+impl RowBatchProvider for KafkaSource {
+    fn next_batch(&mut self) -> Option<RowBatch> {
+        use std::io::BufRead;
+
+        if self.done {
+            return None;
+        }
+        if self.consumer.is_none() {
+            if let Err(e) = self.spawn_consumer() {
+                eprintln!("kafka source consume failed: {}", e);
+                self.done = true;
+                return None;
+            }
+        }
+        let (_, reader) = self.consumer.as_mut().expect("spawned above");
+
+        // One JSON object per message; schema-declared columns are
+        // extracted, others ignored. Non-JSON messages land in a "value"
+        // column when declared.
+        let mut columns: Vec<Column> = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| Column {
+                name: f.name.clone(),
+                values: Vec::new(),
+            })
+            .collect();
+
+        let mut rows = 0usize;
+        let mut line = String::new();
+        while rows < KAFKA_CHUNK_ROWS {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("kafka source read error: {}", e);
+                    self.done = true;
+                    break;
+                }
+            }
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(trimmed) {
+                Ok(object) => {
+                    for (idx, field) in self.schema.fields.iter().enumerate() {
+                        let value = object
+                            .get(&field.name)
+                            .map(json_to_scalar)
+                            .unwrap_or(Scalar::Null);
+                        columns[idx].values.push(value);
+                    }
+                }
+                Err(_) => {
+                    for (idx, field) in self.schema.fields.iter().enumerate() {
+                        columns[idx].values.push(if field.name == "value" {
+                            Scalar::Str(trimmed.to_string())
+                        } else {
+                            Scalar::Null
+                        });
+                    }
+                }
+            }
+            rows += 1;
+        }
+
+        if self.done {
+            if let Some((mut child, _)) = self.consumer.take() {
+                let _ = child.wait();
+            }
+        }
+        if rows == 0 {
+            return None;
+        }
+        Some(RowBatch { columns })
+    }
+}
+
+fn json_to_scalar(value: &serde_json::Value) -> Scalar {
+    match value {
+        serde_json::Value::Null => Scalar::Null,
+        serde_json::Value::Bool(b) => Scalar::Bool(*b),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Scalar::I64(i)
+            } else {
+                Scalar::F64(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        serde_json::Value::String(s) => Scalar::Str(s.clone()),
+        other => Scalar::Str(other.to_string()),
+    }
+}
+
+fn scalar_json(value: &Scalar) -> serde_json::Value {
+    match value {
+        Scalar::Null => serde_json::Value::Null,
+        Scalar::Bool(b) => serde_json::Value::Bool(*b),
+        Scalar::I32(v) => serde_json::Value::from(*v),
+        Scalar::I64(v) => serde_json::Value::from(*v),
+        Scalar::F32(v) => serde_json::Value::from(*v as f64),
+        Scalar::F64(v) => serde_json::Value::from(*v),
+        Scalar::Str(s) => serde_json::Value::String(s.clone()),
+        Scalar::Date64(ms) => {
+            serde_json::Value::String(emsqrt_core::time::format_datetime(*ms))
+        }
+        Scalar::Bin(b) => serde_json::Value::String(format!("[binary {} bytes]", b.len())),
+    }
+}
+
+/// Separator between message key and value on the producer's stdin
+/// (kcat's -K option); the ASCII unit separator never appears in JSON.
+const KEY_SEPARATOR: char = '\x1f';
+
+/// Streams rows into one long-lived kcat producer process.
+struct KafkaSink {
+    kcat_cmd: String,
+    broker: String,
+    topic: String,
+    /// Column whose value becomes the message key (keyless when unset).
+    key_column: Option<String>,
+    producer: Option<Child>,
+}
+
+impl BatchSink for KafkaSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let key_idx = match &self.key_column {
+            Some(name) => Some(
+                batch
+                    .columns
+                    .iter()
+                    .position(|c| &c.name == name)
+                    .ok_or_else(|| format!("kafka key column '{}' not found", name))?,
+            ),
+            None => None,
+        };
+
+        if self.producer.is_none() {
+            let mut command = Command::new(&self.kcat_cmd);
+            command.args(["-P", "-b", &self.broker, "-t", &self.topic]);
+            if self.key_column.is_some() {
+                command.args(["-K", &KEY_SEPARATOR.to_string()]);
+            }
+            let child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("spawn {}: {}", self.kcat_cmd, e))?;
+            self.producer = Some(child);
+        }
+        let stdin = self
+            .producer
+            .as_mut()
+            .and_then(|c| c.stdin.as_mut())
+            .ok_or_else(|| "kafka producer stdin closed".to_string())?;
+
+        for row in 0..batch.num_rows() {
+            let mut object = serde_json::Map::new();
+            for column in &batch.columns {
+                object.insert(column.name.clone(), scalar_json(&column.values[row]));
+            }
+            let payload = serde_json::Value::Object(object).to_string();
+            match key_idx {
+                Some(idx) => {
+                    let key = match &batch.columns[idx].values[row] {
+                        Scalar::Str(s) => s.clone(),
+                        other => format!("{:?}", other),
+                    };
+                    writeln!(stdin, "{}{}{}", key, KEY_SEPARATOR, payload)
+                        .map_err(|e| format!("kafka produce: {}", e))?;
+                }
+                None => {
+                    writeln!(stdin, "{}", payload)
+                        .map_err(|e| format!("kafka produce: {}", e))?;
+                }
+            }
+        }
+        stdin.flush().map_err(|e| format!("kafka flush: {}", e))?;
+        Ok(())
+    }
+}
+
+impl Drop for KafkaSink {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.producer.take() {
+            drop(child.stdin.take()); // EOF lets kcat flush and exit
+            let _ = child.wait();
+        }
+    }
+}
@@ -5,10 +5,41 @@
 //! Next steps: parallel block scheduling with bounded channels, real sources/sinks,
 //! and spill-aware operators.
 
+pub mod connectors;
+pub mod database;
+#[cfg(feature = "parquet")]
+pub mod delta;
+pub mod elasticsearch;
 pub mod failpoints;
+pub mod grpc;
+#[cfg(feature = "grpc")]
+pub mod grpc_pb;
+pub(crate) mod http;
+pub mod kafka;
 pub mod metrics;
+pub mod redis;
 pub mod replay;
+pub mod results;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
 pub mod runtime;
+pub mod sqlite;
+pub mod webhook;
 pub mod scheduler;
 
-pub use runtime::{Engine, ExecError};
+pub use connectors::{Capabilities, Connector, ConnectorRegistry};
+pub use database::{DatabaseConnector, SqlDriver};
+#[cfg(feature = "parquet")]
+pub use delta::DeltaConnector;
+pub use elasticsearch::ElasticsearchConnector;
+pub use grpc::GrpcConnector;
+pub use kafka::KafkaConnector;
+pub use redis::RedisConnector;
+pub use webhook::WebhookConnector;
+pub use sqlite::{SqliteConnector, SqliteDriver};
+#[cfg(feature = "duckdb")]
+pub use duckdb::{DuckDbConnector, DuckDbDriver};
+pub use results::ResultSet;
+pub use runtime::{
+    BatchSink, CallbackSink, ChannelSink, Engine, ExecError, MemorySource, RowBatchProvider,
+};
@@ -0,0 +1,184 @@
+//! Redis sink connector for key-value enrichment tables.
+//!
+//! Speaks RESP directly over TCP (the protocol is trivial; no client crate
+//! needed). Destinations take the form `redis://host:port/<key prefix>`;
+//! each row is written as `SET <prefix>:<key> <row as JSON>` so downstream
+//! services can enrich by point lookup. The sink format string names the
+//! key column ("csv" and other file formats fall back to the first column).
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use emsqrt_core::types::{RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::runtime::BatchSink;
+
+pub struct RedisConnector;
+
+impl Connector for RedisConnector {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: false,
+            write: true,
+            streaming: true,
+            random_access: true,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("redis://")
+    }
+
+    fn open_sink(&self, uri: &str, format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let rest = uri
+            .strip_prefix("redis://")
+            .ok_or_else(|| format!("not a redis URI: '{}'", uri))?;
+        let (authority, prefix) = match rest.split_once('/') {
+            Some((a, p)) => (a, p.to_string()),
+            None => (rest, String::new()),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((h, p)) => (
+                h.to_string(),
+                p.parse::<u16>()
+                    .map_err(|e| format!("bad redis port: {}", e))?,
+            ),
+            None => (authority.to_string(), 6379),
+        };
+
+        // The sink's format string names the key column; file-format values
+        // ("csv", "parquet", ...) mean "unspecified".
+        let key_column = match format {
+            "" | "csv" | "jsonl" | "parquet" => None,
+            column => Some(column.to_string()),
+        };
+
+        Ok(Box::new(RedisSink {
+            host,
+            port,
+            prefix,
+            key_column,
+            connection: None,
+        }))
+    }
+}
+
+struct RedisSink {
+    host: String,
+    port: u16,
+    prefix: String,
+    key_column: Option<String>,
+    connection: Option<BufReader<TcpStream>>,
+}
+
+/// Encode one RESP command (array of bulk strings).
+fn resp_command(parts: &[&str]) -> Vec<u8> {
+    let mut out = format!("*{}\r\n", parts.len()).into_bytes();
+    for part in parts {
+        out.extend_from_slice(format!("${}\r\n", part.len()).as_bytes());
+        out.extend_from_slice(part.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+fn scalar_json(value: &Scalar) -> serde_json::Value {
+    match value {
+        Scalar::Null => serde_json::Value::Null,
+        Scalar::Bool(b) => serde_json::Value::Bool(*b),
+        Scalar::I32(v) => serde_json::Value::from(*v),
+        Scalar::I64(v) => serde_json::Value::from(*v),
+        Scalar::F32(v) => serde_json::Value::from(*v as f64),
+        Scalar::F64(v) => serde_json::Value::from(*v),
+        Scalar::Str(s) => serde_json::Value::String(s.clone()),
+        Scalar::Date64(ms) => {
+            serde_json::Value::String(emsqrt_core::time::format_datetime(*ms))
+        }
+        Scalar::Bin(b) => serde_json::Value::String(format!("[binary {} bytes]", b.len())),
+    }
+}
+
+fn key_text(value: &Scalar) -> String {
+    match value {
+        Scalar::Str(s) => s.clone(),
+        Scalar::Null => "null".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+impl RedisSink {
+    fn connect(&mut self) -> Result<&mut BufReader<TcpStream>, String> {
+        if self.connection.is_none() {
+            let stream = TcpStream::connect((self.host.as_str(), self.port))
+                .map_err(|e| format!("redis connect {}:{}: {}", self.host, self.port, e))?;
+            self.connection = Some(BufReader::new(stream));
+        }
+        Ok(self.connection.as_mut().expect("connected above"))
+    }
+}
+
+impl BatchSink for RedisSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let key_idx = match &self.key_column {
+            Some(name) => batch
+                .columns
+                .iter()
+                .position(|c| &c.name == name)
+                .ok_or_else(|| format!("redis key column '{}' not found", name))?,
+            None => 0,
+        };
+
+        // Render rows before borrowing the connection.
+        let mut commands = Vec::with_capacity(batch.num_rows());
+        for row in 0..batch.num_rows() {
+            let key_value = key_text(&batch.columns[key_idx].values[row]);
+            let key = if self.prefix.is_empty() {
+                key_value
+            } else {
+                format!("{}:{}", self.prefix, key_value)
+            };
+
+            let mut object = serde_json::Map::new();
+            for column in &batch.columns {
+                object.insert(column.name.clone(), scalar_json(&column.values[row]));
+            }
+            let payload = serde_json::Value::Object(object).to_string();
+            commands.push((key, payload));
+        }
+
+        let connection = self.connect()?;
+
+        // Pipeline every SET, then collect one reply per command.
+        for (key, payload) in &commands {
+            let command = resp_command(&["SET", key, payload]);
+            connection
+                .get_mut()
+                .write_all(&command)
+                .map_err(|e| format!("redis send: {}", e))?;
+        }
+        connection
+            .get_mut()
+            .flush()
+            .map_err(|e| format!("redis flush: {}", e))?;
+
+        for _ in 0..commands.len() {
+            let mut reply = String::new();
+            connection
+                .read_line(&mut reply)
+                .map_err(|e| format!("redis reply: {}", e))?;
+            if reply.starts_with('-') {
+                return Err(format!("redis error reply: {}", reply.trim_end()));
+            }
+        }
+        Ok(())
+    }
+}
@@ -25,8 +25,8 @@ pub fn hash_te(te: &TePlan) -> Result<Hash256, ExecError> {
 
 fn xor_hashes(a: Hash256, b: Hash256) -> Hash256 {
     let mut out = [0u8; 32];
-    for i in 0..32 {
-        out[i] = a.0[i] ^ b.0[i];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = a.0[i] ^ b.0[i];
     }
     Hash256(out)
 }
@@ -0,0 +1,110 @@
+//! Materialized result sets with pagination, for serving query results to
+//! interactive callers (query-as-a-service).
+//!
+//! Collect engine output (e.g. from `Engine::register_channel_sink`) into a
+//! `ResultSet`, then hand out fixed-size pages without re-running the
+//! pipeline.
+
+use emsqrt_core::types::{Column, RowBatch};
+
+/// Buffered pipeline output, addressable by row ranges.
+#[derive(Debug, Clone, Default)]
+pub struct ResultSet {
+    batches: Vec<RowBatch>,
+    total_rows: usize,
+}
+
+impl ResultSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drain a channel sink's receiver into a result set. Returns once the
+    /// engine drops the sending side (i.e. the run finished).
+    pub fn collect(rx: std::sync::mpsc::Receiver<RowBatch>) -> Self {
+        let mut set = Self::new();
+        for batch in rx {
+            set.push(batch);
+        }
+        set
+    }
+
+    pub fn from_batches(batches: Vec<RowBatch>) -> Self {
+        let mut set = Self::new();
+        for batch in batches {
+            set.push(batch);
+        }
+        set
+    }
+
+    pub fn push(&mut self, batch: RowBatch) {
+        if batch.num_rows() == 0 {
+            return;
+        }
+        self.total_rows += batch.num_rows();
+        self.batches.push(batch);
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.total_rows
+    }
+
+    pub fn num_pages(&self, page_size: usize) -> usize {
+        if page_size == 0 {
+            return 0;
+        }
+        self.total_rows.div_ceil(page_size)
+    }
+
+    /// Rows `[offset, offset + limit)` as one batch, crossing underlying
+    /// batch boundaries as needed. Out-of-range requests are clamped; a
+    /// fully out-of-range offset yields an empty batch with the result
+    /// columns intact.
+    pub fn rows(&self, offset: usize, limit: usize) -> RowBatch {
+        let columns: Vec<Column> = self
+            .batches
+            .first()
+            .map(|b| {
+                b.columns
+                    .iter()
+                    .map(|c| Column {
+                        name: c.name.clone(),
+                        values: Vec::new(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut out = RowBatch { columns };
+
+        let mut remaining_skip = offset;
+        let mut remaining_take = limit;
+
+        for batch in &self.batches {
+            if remaining_take == 0 {
+                break;
+            }
+            let rows = batch.num_rows();
+            if remaining_skip >= rows {
+                remaining_skip -= rows;
+                continue;
+            }
+            let start = remaining_skip;
+            let take = (rows - start).min(remaining_take);
+            remaining_skip = 0;
+            remaining_take -= take;
+
+            for (out_col, in_col) in out.columns.iter_mut().zip(&batch.columns) {
+                out_col
+                    .values
+                    .extend(in_col.values[start..start + take].iter().cloned());
+            }
+        }
+
+        out
+    }
+
+    /// Page `index` (0-based) of `page_size` rows.
+    pub fn page(&self, index: usize, page_size: usize) -> RowBatch {
+        self.rows(index.saturating_mul(page_size), page_size)
+    }
+}
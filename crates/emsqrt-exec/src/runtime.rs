@@ -47,12 +47,80 @@ pub enum ExecError {
     Storage(String),
 }
 
+/// Pull-based batch provider for in-memory sources registered by embedders.
+/// Return `None` when exhausted.
+pub trait RowBatchProvider: Send {
+    fn next_batch(&mut self) -> Option<RowBatch>;
+}
+
+/// Simplest provider: a fixed list of batches, yielded in order.
+pub struct MemorySource {
+    batches: std::collections::VecDeque<RowBatch>,
+}
+
+impl MemorySource {
+    pub fn new(batches: Vec<RowBatch>) -> Self {
+        Self {
+            batches: batches.into(),
+        }
+    }
+}
+
+impl RowBatchProvider for MemorySource {
+    fn next_batch(&mut self) -> Option<RowBatch> {
+        self.batches.pop_front()
+    }
+}
+
+/// Push-based batch consumer for sinks registered by embedders.
+pub trait BatchSink: Send {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String>;
+}
+
+/// Adapter so embedders can register a closure as a sink.
+pub struct CallbackSink<F: FnMut(&RowBatch) -> Result<(), String> + Send>(pub F);
+
+impl<F: FnMut(&RowBatch) -> Result<(), String> + Send> BatchSink for CallbackSink<F> {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        (self.0)(batch)
+    }
+}
+
+/// Sink that forwards batches over a bounded channel. `write_batch` blocks
+/// when the channel is full, so a slow consumer backpressures the engine
+/// instead of batches piling up in memory.
+pub struct ChannelSink {
+    tx: std::sync::mpsc::SyncSender<RowBatch>,
+}
+
+impl BatchSink for ChannelSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        self.tx
+            .send(batch.clone())
+            .map_err(|_| "channel sink receiver dropped".to_string())
+    }
+}
+
 /// Engine owns the memory budget, operator registry, and spill manager.
 pub struct Engine {
     _cfg: EngineConfig,
     budget: MemoryBudgetImpl,
     registry: Registry,
     spill_mgr: Arc<Mutex<SpillManager>>,
+    /// In-memory sources registered by embedders, addressed as `mem://<name>`.
+    mem_sources: HashMap<String, Arc<Mutex<Box<dyn RowBatchProvider>>>>,
+    /// Callback sinks registered by embedders, addressed as `callback://<name>`.
+    callback_sinks: HashMap<String, Arc<Mutex<Box<dyn BatchSink>>>>,
+    /// Pluggable source/sink connectors consulted for URIs the built-in
+    /// paths don't recognize.
+    connectors: crate::connectors::ConnectorRegistry,
+    /// (staging path, final path) pairs published atomically when a run
+    /// with `atomic_sinks` succeeds.
+    staged_sinks: Mutex<Vec<(String, String)>>,
+    /// Warm-start cache: stateless operator instances keyed by
+    /// (bindings hash, op id), reused across runs of the same program.
+    /// Sources and sinks carry per-run state and are always rebuilt.
+    op_cache: Mutex<HashMap<(u64, u64), Arc<dyn Operator>>>,
 }
 
 impl Engine {
@@ -63,17 +131,193 @@ impl Engine {
         // Create spill manager with configured storage backend
         let storage = build_storage_from_config(&storage_cfg)
             .map_err(|e| ExecError::Storage(e.to_string()))?;
-        let codec = Codec::None; // Default to no compression; can be made configurable
-        let spill_mgr = SpillManager::new(storage, codec, storage_cfg.root.clone());
+        let codec = Codec::parse(&cfg.spill_codec)
+            .map_err(|e| ExecError::Storage(e.to_string()))?;
+        let mut spill_mgr = SpillManager::new(storage, codec, storage_cfg.root.clone());
+        spill_mgr.set_disk_budget(cfg.spill_disk_budget_bytes);
+        spill_mgr.set_codec_level(cfg.spill_codec_level);
+        if let Some(inflight) = cfg.spill_background_inflight_bytes {
+            spill_mgr.enable_background_writes(inflight);
+        }
 
         Ok(Self {
             _cfg: cfg,
             budget: MemoryBudgetImpl::new(cap),
             registry: Registry::new(),
             spill_mgr: Arc::new(Mutex::new(spill_mgr)),
+            mem_sources: HashMap::new(),
+            callback_sinks: HashMap::new(),
+            staged_sinks: Mutex::new(Vec::new()),
+            op_cache: Mutex::new(HashMap::new()),
+            connectors: {
+                let mut connectors = crate::connectors::ConnectorRegistry::new();
+                // SQLite ships by default; it only needs the sqlite3 CLI,
+                // and that requirement surfaces on first use.
+                connectors.register(crate::sqlite::SqliteConnector::default());
+                connectors.register(crate::redis::RedisConnector);
+                connectors.register(crate::elasticsearch::ElasticsearchConnector);
+                connectors.register(crate::kafka::KafkaConnector::default());
+                connectors.register(crate::webhook::WebhookConnector);
+                connectors.register(crate::grpc::GrpcConnector);
+                #[cfg(feature = "parquet")]
+                connectors.register(crate::delta::DeltaConnector);
+                #[cfg(feature = "duckdb")]
+                connectors.register(crate::duckdb::DuckDbConnector::default());
+                connectors
+            },
         })
     }
 
+    /// Register a source/sink connector; scans and sinks whose URI matches
+    /// it are routed through the connector instead of the built-in paths.
+    pub fn register_connector(
+        &mut self,
+        connector: impl crate::connectors::Connector + 'static,
+    ) {
+        self.connectors.register(connector);
+    }
+
+    /// Connector listing for diagnostics (`emsqrt connectors`).
+    pub fn connector_list(&self) -> Vec<(&'static str, String)> {
+        self.connectors.list()
+    }
+
+    /// Number of warm operator instances currently cached across runs.
+    pub fn warm_operator_count(&self) -> usize {
+        self.op_cache.lock().unwrap().len()
+    }
+
+    /// Test hook: run one operator through the engine's block execution
+    /// path (retries + speculation) without planning a full program.
+    #[doc(hidden)]
+    pub fn execute_for_test(
+        &self,
+        op: Arc<dyn Operator>,
+        inputs: Vec<RowBatch>,
+    ) -> Result<RowBatch, OpError> {
+        self.execute_block_with_retry(op, inputs, "test block", 3, &self.budget)
+    }
+
+    /// Register an in-memory source. A scan whose source is `mem://<name>`
+    /// pulls batches from the provider instead of reading a file.
+    pub fn register_source(&mut self, name: &str, provider: impl RowBatchProvider + 'static) {
+        self.mem_sources
+            .insert(name.to_string(), Arc::new(Mutex::new(Box::new(provider))));
+    }
+
+    /// Register a callback sink. A sink whose destination is
+    /// `callback://<name>` hands every batch to the callback instead of
+    /// writing a file.
+    pub fn register_sink(&mut self, name: &str, sink: impl BatchSink + 'static) {
+        self.callback_sinks
+            .insert(name.to_string(), Arc::new(Mutex::new(Box::new(sink))));
+    }
+
+    /// Register a sink that yields batches as an async `Stream` (addressed
+    /// as `callback://<name>`). The producing side blocks the engine thread
+    /// when `capacity` batches are buffered, so async consumers still
+    /// backpressure execution.
+    #[cfg(feature = "async")]
+    pub fn register_stream_sink(
+        &mut self,
+        name: &str,
+        capacity: usize,
+    ) -> futures::channel::mpsc::Receiver<RowBatch> {
+        use futures::SinkExt;
+
+        struct StreamSink {
+            tx: futures::channel::mpsc::Sender<RowBatch>,
+        }
+
+        impl BatchSink for StreamSink {
+            fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+                futures::executor::block_on(self.tx.send(batch.clone()))
+                    .map_err(|_| "stream sink receiver dropped".to_string())
+            }
+        }
+
+        let (tx, rx) = futures::channel::mpsc::channel(capacity);
+        self.register_sink(name, StreamSink { tx });
+        rx
+    }
+
+    /// Build one sink operator for a single destination (file or
+    /// `callback://<name>`), shared by the plain and fan-out paths.
+    fn build_sink(&self, destination: &str, format: &str) -> Result<Box<dyn Operator>, ExecError> {
+        // Registered connectors claim non-file destinations first.
+        if !destination.starts_with("callback://") {
+            if let Some(connector) = self.connectors.find_sink(destination) {
+                let sink = connector
+                    .open_sink(destination, format)
+                    .map_err(ExecError::Registry)?;
+                return Ok(Box::new(CallbackSinkOp {
+                    sink: Arc::new(Mutex::new(sink)),
+                }));
+            }
+        }
+
+        if let Some(name) = destination.strip_prefix("callback://") {
+            let sink = self.callback_sinks.get(name).cloned().ok_or_else(|| {
+                ExecError::Registry(format!("no callback sink registered under '{}'", name))
+            })?;
+            return Ok(Box::new(CallbackSinkOp { sink }));
+        }
+
+        // Console sinks: handy for piping pipeline output into shell tools.
+        if destination == "stdout" || destination == "stderr" {
+            return Ok(Box::new(StdStreamSinkOp {
+                stderr: destination == "stderr",
+                format: format.to_string(),
+                wrote_header: std::sync::Mutex::new(false),
+            }));
+        }
+        // Atomic commits: plain file destinations write into a staging
+        // directory and are renamed into place after the run succeeds.
+        let destination = if self._cfg.atomic_sinks && !destination.contains("://") {
+            let (dir, name) = destination
+                .rsplit_once('/')
+                .unwrap_or((".", destination));
+            let staging = format!("{}/.emsqrt-staging/{}", dir, name);
+            if let Some(parent) = std::path::Path::new(&staging).parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| ExecError::Storage(format!("create staging dir: {}", e)))?;
+            }
+            let _ = std::fs::remove_file(&staging); // stale from a failed run
+            self.staged_sinks
+                .lock()
+                .unwrap()
+                .push((staging.clone(), destination.to_string()));
+            staging
+        } else {
+            destination.to_string()
+        };
+
+        Ok(Box::new(SinkOp {
+            destination,
+            format: format.to_string(),
+            mem_cap_bytes: self._cfg.mem_cap_bytes,
+            writer_initialized: std::sync::Arc::new(std::sync::Mutex::new(false)),
+            sorted_runs_writer: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            partition_counts: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
+            rotation: std::sync::Arc::new(std::sync::Mutex::new((0, 0))),
+            #[cfg(feature = "parquet")]
+            parquet_writer: std::sync::Arc::new(std::sync::Mutex::new(None)),
+        }))
+    }
+
+    /// Register a bounded channel sink (addressed as `callback://<name>`)
+    /// and return the consuming end. At most `capacity` batches are buffered;
+    /// beyond that the engine blocks until the consumer catches up.
+    pub fn register_channel_sink(
+        &mut self,
+        name: &str,
+        capacity: usize,
+    ) -> std::sync::mpsc::Receiver<RowBatch> {
+        let (tx, rx) = std::sync::mpsc::sync_channel(capacity);
+        self.register_sink(name, ChannelSink { tx });
+        rx
+    }
+
     /// Execute a prepared `PhysicalProgram` under `TePlan` and return a manifest.
     pub fn run(
         &mut self,
@@ -89,11 +333,70 @@ impl Engine {
         // Merge hashes (simple xor of bytes) to capture bindings+plan.
         let plan_hash = xor_hashes(plan_hash, bindings_hash);
 
-        // Instantiate operator table keyed by OpId.
-        let mut ops: HashMap<u64, Box<dyn Operator>> = HashMap::new();
+        // Fresh staging bookkeeping for this run.
+        self.staged_sinks.lock().unwrap().clear();
+
+        // Per-operator sub-budgets: pipeline breakers each get a reserved
+        // slice of the cap so one greedy operator can't starve the others.
+        // The reservations live for the run and return to the root budget
+        // when this map drops.
+        let mut sub_budgets: HashMap<u64, emsqrt_mem::SubBudget> = HashMap::new();
+        if self._cfg.per_operator_budgets {
+            for (op_id, _, share) in
+                planned_budget_split(&program.bindings, self._cfg.mem_cap_bytes)
+            {
+                let sub = self.budget.reserve(share, "operator_sub_budget").ok_or_else(|| {
+                    ExecError::Invalid(format!(
+                        "cannot reserve {} bytes for operator {}: budget exhausted",
+                        share, op_id
+                    ))
+                })?;
+                sub_budgets.insert(op_id.get(), sub);
+            }
+        }
+
+        // Instantiate operator table keyed by OpId. Operators are Arc'd so
+        // speculative attempts can run detached on worker threads.
+        let mut ops: HashMap<u64, Arc<dyn Operator>> = HashMap::new();
+        // Source read positions, harvested into the manifest after the run.
+        let mut source_positions: Vec<(String, Arc<Mutex<usize>>)> = Vec::new();
+        // End-to-end sink accounting: record count + content checksum.
+        let sink_records = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let sink_hasher = Arc::new(Mutex::new(blake3::Hasher::new()));
         for (op_id, binding) in &program.bindings {
-            let key = binding.key.as_str();
+            // Resolve deprecated operator keys so old plans keep running.
+            let key = match self.registry.resolve(binding.key.as_str()) {
+                Some((canonical, deprecated)) => {
+                    if deprecated {
+                        eprintln!(
+                            "warning: operator key '{}' is deprecated; use '{}'",
+                            binding.key, canonical
+                        );
+                    }
+                    canonical
+                }
+                None => binding.key.as_str(),
+            };
             let config = &binding.config;
+
+            // Warm start: stateless operators built for this exact program
+            // are reused from the cache. Sources and sinks carry per-run
+            // state (read positions, writer handles), and lookup caches its
+            // loaded reference table — reusing it would serve stale data
+            // after the reference file changes on disk — so none of those
+            // are cached.
+            let cacheable = !matches!(key, "source" | "sink" | "route_sink" | "lookup");
+            let cache_key = (
+                u64::from_le_bytes(bindings_hash.0[0..8].try_into().unwrap()),
+                op_id.get(),
+            );
+            if cacheable {
+                if let Some(cached) = self.op_cache.lock().unwrap().get(&cache_key) {
+                    ops.insert(op_id.get(), cached.clone());
+                    continue;
+                }
+            }
+
             let inst: Box<dyn Operator> = match key {
                 "source" => {
                     let source_uri =
@@ -114,10 +417,43 @@ impl Engine {
                         Schema::new(vec![])
                     };
 
+                    // Registered connectors claim non-file URIs first.
+                    if !source_uri.starts_with("mem://") {
+                        if let Some(connector) = self.connectors.find_source(source_uri) {
+                            let provider = connector
+                                .open_source(source_uri, &schema)
+                                .map_err(ExecError::Registry)?;
+                            ops.insert(
+                                op_id.get(),
+                                Arc::new(MemorySourceOp {
+                                    schema,
+                                    provider: Arc::new(Mutex::new(provider)),
+                                }),
+                            );
+                            continue;
+                        }
+                    }
+
+                    // mem:// sources dispatch to a registered provider.
+                    if let Some(name) = source_uri.strip_prefix("mem://") {
+                        let provider =
+                            self.mem_sources.get(name).cloned().ok_or_else(|| {
+                                ExecError::Registry(format!(
+                                    "no in-memory source registered under '{}'",
+                                    name
+                                ))
+                            })?;
+                        ops.insert(op_id.get(), Arc::new(MemorySourceOp { schema, provider }));
+                        continue;
+                    }
+
+                    let file_position = Arc::new(Mutex::new(0));
+                    source_positions.push((source_uri.to_string(), file_position.clone()));
                     Box::new(SourceOp {
                         source_uri: source_uri.to_string(),
                         schema,
-                        file_position: Arc::new(Mutex::new(0)),
+                        file_position,
+                        csv_state: Arc::new(Mutex::new(None)),
                         #[cfg(feature = "parquet")]
                         parquet_reader: Arc::new(Mutex::new(None)),
                     })
@@ -132,13 +468,26 @@ impl Engine {
                         .and_then(|v| v.as_str())
                         .unwrap_or("csv");
 
-                    Box::new(SinkOp {
-                        destination: destination.to_string(),
-                        format: format.to_string(),
-                        writer_initialized: std::sync::Arc::new(std::sync::Mutex::new(false)),
-                        #[cfg(feature = "parquet")]
-                        parquet_writer: std::sync::Arc::new(std::sync::Mutex::new(None)),
-                    })
+                    // Fan-out: ';'-separated destinations each get their own
+                    // sink; every batch is written to all of them.
+                    let parts: Vec<&str> = destination
+                        .split(';')
+                        .map(str::trim)
+                        .filter(|p| !p.is_empty())
+                        .collect();
+                    let mut sinks: Vec<Box<dyn Operator>> = Vec::with_capacity(parts.len());
+                    for part in &parts {
+                        sinks.push(self.build_sink(part, format)?);
+                    }
+                    match sinks.len() {
+                        0 => {
+                            return Err(ExecError::Registry(
+                                "sink operator has no destination".into(),
+                            ));
+                        }
+                        1 => sinks.pop().expect("len checked"),
+                        _ => Box::new(FanoutSinkOp { sinks }),
+                    }
                 }
                 "filter" => {
                     let mut op = emsqrt_operators::filter::Filter::default();
@@ -158,12 +507,21 @@ impl Engine {
                     Box::new(op)
                 }
                 "map" => {
-                    // Map currently doesn't use config, but we could parse renames here
-                    Box::new(emsqrt_operators::map::Map::default())
+                    let op = match config.get("expr").and_then(|v| v.as_str()) {
+                        Some(expr) if !expr.trim().is_empty() => {
+                            emsqrt_operators::map::Map::parse_config(expr)
+                                .map_err(ExecError::Registry)?
+                        }
+                        _ => emsqrt_operators::map::Map::default(),
+                    };
+                    Box::new(op)
                 }
                 "aggregate" => {
-                    let mut op = emsqrt_operators::agregate::Aggregate::default();
-                    op.spill_mgr = Some(self.spill_mgr.clone());
+                    let mut op = emsqrt_operators::agregate::Aggregate {
+                        spill_mgr: Some(self.spill_mgr.clone()),
+                        approx: self._cfg.approx_aggregates,
+                        ..Default::default()
+                    };
                     // Parse group_by and aggs from config if provided
                     if let Some(group_by) = config.get("group_by").and_then(|v| v.as_array()) {
                         op.group_by = group_by
@@ -180,8 +538,10 @@ impl Engine {
                     Box::new(op)
                 }
                 "sort_external" => {
-                    let mut op = emsqrt_operators::sort::external::ExternalSort::default();
-                    op.spill_mgr = Some(self.spill_mgr.clone());
+                    let mut op = emsqrt_operators::sort::external::ExternalSort {
+                        spill_mgr: Some(self.spill_mgr.clone()),
+                        ..Default::default()
+                    };
                     // Parse sort keys from config if provided
                     if let Some(keys) = config.get("by").and_then(|v| v.as_array()) {
                         op.by = keys
@@ -191,9 +551,30 @@ impl Engine {
                     }
                     Box::new(op)
                 }
+                "sort_partitioned" => {
+                    let mut op = emsqrt_operators::sort::partitioned::PartitionedSort {
+                        spill_mgr: Some(self.spill_mgr.clone()),
+                        ..Default::default()
+                    };
+                    if let Some(keys) = config.get("by").and_then(|v| v.as_array()) {
+                        op.by = keys
+                            .iter()
+                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                            .collect();
+                    }
+                    Box::new(op)
+                }
                 "join_hash" => {
-                    let mut op = emsqrt_operators::join::hash::HashJoin::default();
-                    op.spill_mgr = Some(self.spill_mgr.clone());
+                    let hash_kind = emsqrt_core::hash::HashKind::parse(&self._cfg.hash_function)
+                        .map_err(ExecError::Registry)?;
+                    let mut op = emsqrt_operators::join::hash::HashJoin {
+                        spill_mgr: Some(self.spill_mgr.clone()),
+                        hasher: emsqrt_core::hash::RowHasher::new(
+                            hash_kind,
+                            self._cfg.seed.unwrap_or(0),
+                        ),
+                        ..Default::default()
+                    };
                     // Parse join keys from config if provided
                     if let Some(on) = config.get("on").and_then(|v| v.as_array()) {
                         op.on = on
@@ -213,6 +594,203 @@ impl Engine {
                     if let Some(join_type) = config.get("join_type").and_then(|v| v.as_str()) {
                         op.join_type = join_type.to_string();
                     }
+                    if let Some(projection) = config.get("projection").and_then(|v| v.as_array()) {
+                        op.projection = Some(
+                            projection
+                                .iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect(),
+                        );
+                    }
+                    if let Some(filter) = config.get("residual_filter").and_then(|v| v.as_str()) {
+                        op.residual_filter = Some(filter.to_string());
+                    }
+                    Box::new(op)
+                }
+                "route_sink" => {
+                    let format = config
+                        .get("format")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("csv");
+                    let mut routes = Vec::new();
+                    if let Some(entries) = config.get("routes").and_then(|v| v.as_array()) {
+                        for entry in entries {
+                            let pair = entry.as_array().ok_or_else(|| {
+                                ExecError::Registry("route entry must be [when, destination]".into())
+                            })?;
+                            let (when, destination) = match (
+                                pair.first().and_then(|v| v.as_str()),
+                                pair.get(1).and_then(|v| v.as_str()),
+                            ) {
+                                (Some(w), Some(d)) => (w.to_string(), d),
+                                _ => {
+                                    return Err(ExecError::Registry(
+                                        "route entry must be [when, destination]".into(),
+                                    ));
+                                }
+                            };
+                            routes.push((when, self.build_sink(destination, format)?));
+                        }
+                    }
+                    if routes.is_empty() {
+                        return Err(ExecError::Registry(
+                            "route_sink needs at least one route".into(),
+                        ));
+                    }
+                    let default = config
+                        .get("default")
+                        .and_then(|v| v.as_str())
+                        .map(|d| self.build_sink(d, format))
+                        .transpose()?;
+                    ops.insert(
+                        op_id.get(),
+                        Arc::new(CountingSinkOp {
+                            inner: Arc::new(RoutedSinkOp { routes, default }),
+                            records: sink_records.clone(),
+                            hasher: sink_hasher.clone(),
+                        }),
+                    );
+                    continue;
+                }
+                "lookup" => {
+                    let source = config
+                        .get("source")
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| {
+                            ExecError::Registry("lookup operator missing 'source' in config".into())
+                        })?
+                        .to_string();
+                    let key = config
+                        .get("key")
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| {
+                            ExecError::Registry("lookup operator missing 'key' in config".into())
+                        })?
+                        .to_string();
+                    let ref_key = config
+                        .get("ref_key")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(key.as_str())
+                        .to_string();
+                    let columns = json_to_vec_strings(config.get("columns"));
+
+                    Box::new(LookupOp {
+                        source,
+                        key,
+                        ref_key,
+                        columns,
+                        table: Arc::new(Mutex::new(None)),
+                    })
+                }
+                "window_dedup" => {
+                    let mut op = emsqrt_operators::dedup::WindowDedup::default();
+                    if let Some(key) = config.get("key").and_then(|v| v.as_str()) {
+                        op.key = key.to_string();
+                    }
+                    if let Some(ts) = config.get("ts_column").and_then(|v| v.as_str()) {
+                        op.ts_column = ts.to_string();
+                    }
+                    if let Some(window) = config.get("window").and_then(|v| v.as_str()) {
+                        op.window = window.to_string();
+                    }
+                    Box::new(op)
+                }
+                "approx_dedup" => {
+                    let mut op = emsqrt_operators::dedup::ApproxDedup::default();
+                    if let Some(column) = config.get("column").and_then(|v| v.as_str()) {
+                        op.column = column.to_string();
+                    }
+                    if let Some(threshold) = config.get("threshold").and_then(|v| v.as_f64()) {
+                        op.threshold = threshold;
+                    }
+                    if let Some(shingle) = config.get("shingle_len").and_then(|v| v.as_u64()) {
+                        op.shingle_len = shingle as usize;
+                    }
+                    Box::new(op)
+                }
+                "scd2_merge" => {
+                    let mut op = emsqrt_operators::scd::Scd2Merge::default();
+                    if let Some(key) = config.get("key").and_then(|v| v.as_str()) {
+                        op.key = key.to_string();
+                    }
+                    op.compare = json_to_vec_strings(config.get("compare"));
+                    if let Some(as_of) = config.get("as_of").and_then(|v| v.as_str()) {
+                        op.as_of = Some(as_of.to_string());
+                    }
+                    Box::new(op)
+                }
+                "one_hot" => {
+                    let mut op = emsqrt_operators::features::OneHot::default();
+                    if let Some(column) = config.get("column").and_then(|v| v.as_str()) {
+                        op.column = column.to_string();
+                    }
+                    op.categories = json_to_vec_strings(config.get("categories"));
+                    if let Some(max) = config.get("max_categories").and_then(|v| v.as_u64()) {
+                        op.max_categories = max as usize;
+                    }
+                    Box::new(op)
+                }
+                "feature_hash" => {
+                    let mut op = emsqrt_operators::features::FeatureHash {
+                        seed: self._cfg.seed.unwrap_or(0),
+                        ..Default::default()
+                    };
+                    if let Some(column) = config.get("column").and_then(|v| v.as_str()) {
+                        op.column = column.to_string();
+                    }
+                    if let Some(buckets) = config.get("buckets").and_then(|v| v.as_u64()) {
+                        op.buckets = buckets as usize;
+                    }
+                    Box::new(op)
+                }
+                "histogram" => {
+                    let mut op = emsqrt_operators::histogram::Histogram::default();
+                    if let Some(column) = config.get("column").and_then(|v| v.as_str()) {
+                        op.column = column.to_string();
+                    }
+                    if let Some(bins) = config.get("bins").and_then(|v| v.as_u64()) {
+                        op.bins = bins as usize;
+                    }
+                    op.min = config.get("min").and_then(|v| v.as_f64());
+                    op.max = config.get("max").and_then(|v| v.as_f64());
+                    Box::new(op)
+                }
+                "train_test_split" => {
+                    let mut op = emsqrt_operators::split::TrainTestSplit {
+                        seed: self._cfg.seed.unwrap_or(0),
+                        ..Default::default()
+                    };
+                    if let Some(key) = config.get("key").and_then(|v| v.as_str()) {
+                        op.key = key.to_string();
+                    }
+                    if let Some(fraction) = config.get("test_fraction").and_then(|v| v.as_f64()) {
+                        op.test_fraction = fraction;
+                    }
+                    if let Some(mode) = config.get("mode").and_then(|v| v.as_str()) {
+                        op.mode = emsqrt_operators::split::SplitMode::parse(mode)
+                            .map_err(ExecError::Registry)?;
+                    }
+                    Box::new(op)
+                }
+                "retention" => {
+                    let mut op = emsqrt_operators::retention::RetentionFilter::default();
+                    if let Some(column) = config.get("column").and_then(|v| v.as_str()) {
+                        op.column = column.to_string();
+                    }
+                    if let Some(max_age) = config.get("max_age").and_then(|v| v.as_str()) {
+                        op.max_age = max_age.to_string();
+                    }
+                    if let Some(as_of) = config.get("as_of").and_then(|v| v.as_str()) {
+                        op.as_of = Some(as_of.to_string());
+                    }
+                    Box::new(op)
+                }
+                "sample" => {
+                    let op = emsqrt_operators::sample::Sample {
+                        fraction: config.get("fraction").and_then(|v| v.as_f64()),
+                        rows: config.get("rows").and_then(|v| v.as_u64()).map(|v| v as usize),
+                        seed: self._cfg.seed,
+                    };
                     Box::new(op)
                 }
                 "window" => {
@@ -251,59 +829,215 @@ impl Engine {
                     ExecError::Registry(format!("unknown operator key '{other}'"))
                 })?,
             };
+            // Every sink is wrapped so records and content are checksummed
+            // end to end regardless of destination kind.
+            let inst: Arc<dyn Operator> = if inst.name() == "sink" {
+                Arc::new(CountingSinkOp {
+                    inner: Arc::from(inst),
+                    records: sink_records.clone(),
+                    hasher: sink_hasher.clone(),
+                })
+            } else {
+                Arc::from(inst)
+            };
+            if cacheable {
+                self.op_cache
+                    .lock()
+                    .unwrap()
+                    .insert(cache_key, inst.clone());
+            }
             ops.insert(op_id.get(), inst);
         }
 
-        // Map: BlockId → RowBatch result
+        // Map: BlockId → RowBatch result, plus remaining consumer counts so
+        // shared blocks (e.g. deduplicated scans) feed every dependent.
         let mut results: HashMap<u64, RowBatch> = HashMap::new();
+        let mut consumers: HashMap<u64, usize> = HashMap::new();
+        for b in &te.order {
+            for dep in &b.deps {
+                *consumers.entry(dep.get()).or_insert(0) += 1;
+            }
+        }
 
         // Start manifest
         let now_ms = now_millis();
         let mut manifest = RunManifest::new(plan_hash, te_hash, now_ms);
 
-        // Sequential TE order (starter).
+        // Debug tap budget: how many sample rows are still to be dumped.
+        let mut debug_rows_left = if self._cfg.debug_step.is_some() {
+            self._cfg.debug_dump_rows
+        } else {
+            0
+        };
+
+        // Per-operator parallelism limits: derived from each operator's plan
+        // (exclusive pipeline breakers and sources/sinks run alone) combined
+        // with the configured task cap via scheduler::effective_parallelism.
+        let block_schemas: HashMap<u64, Schema> = te
+            .order
+            .iter()
+            .map(|b| (b.id.get(), b.schema.clone()))
+            .collect();
+        let mut op_limits: HashMap<u64, usize> = HashMap::new();
         for b in &te.order {
-            // Gather input batches from deps in order.
-            let mut inputs: Vec<RowBatch> = Vec::with_capacity(b.deps.len());
-            for dep in &b.deps {
-                let key = dep.get();
-                let batch = results.remove(&key).ok_or_else(|| {
-                    ExecError::Invalid(format!("missing dependency block result for {}", key))
-                })?;
-                inputs.push(batch);
+            op_limits.entry(b.op.get()).or_insert_with(|| {
+                let dep_schemas: Vec<Schema> = b
+                    .deps
+                    .iter()
+                    .filter_map(|d| block_schemas.get(&d.get()).cloned())
+                    .collect();
+                ops.get(&b.op.get())
+                    .and_then(|op| op.plan(&dep_schemas).ok())
+                    .map(|plan| {
+                        crate::scheduler::effective_parallelism(
+                            self._cfg.max_parallel_tasks,
+                            &plan,
+                        )
+                    })
+                    // Sources and sinks don't plan at exec time; they carry
+                    // per-run state, so they run strictly sequentially.
+                    .unwrap_or(1)
+            });
+        }
+
+        // TE order, executed in windows of mutually independent blocks.
+        // Window size is bounded by each member operator's parallelism
+        // limit; a window of one is plain sequential execution.
+        let mut next_block = 0usize;
+        while next_block < te.order.len() {
+            // Grow the window while each candidate's deps are already
+            // materialized (i.e. not produced inside the window) and every
+            // involved operator stays under its limit.
+            let mut window_end = next_block + 1;
+            {
+                let mut op_counts: HashMap<u64, usize> = HashMap::new();
+                op_counts.insert(te.order[next_block].op.get(), 1);
+                let mut produced: std::collections::HashSet<u64> =
+                    std::collections::HashSet::new();
+                produced.insert(te.order[next_block].id.get());
+
+                while window_end < te.order.len()
+                    && window_end - next_block < self._cfg.max_parallel_tasks.max(1)
+                {
+                    let candidate = &te.order[window_end];
+                    let op_key = candidate.op.get();
+                    let limit = op_limits.get(&op_key).copied().unwrap_or(1);
+                    let count = op_counts.get(&op_key).copied().unwrap_or(0);
+                    let independent = candidate
+                        .deps
+                        .iter()
+                        .all(|d| !produced.contains(&d.get()));
+                    if !independent || count >= limit {
+                        break;
+                    }
+                    op_counts.insert(op_key, count + 1);
+                    produced.insert(candidate.id.get());
+                    window_end += 1;
+                }
             }
+            let window = &te.order[next_block..window_end];
+            next_block = window_end;
+
+            // Gather each window member's inputs up front (their deps are
+            // all materialized already).
+            let mut staged: Vec<(&emsqrt_te::TeBlock, Vec<RowBatch>, String)> = Vec::new();
+            for b in window {
+                let mut inputs: Vec<RowBatch> = Vec::with_capacity(b.deps.len());
+                for dep in &b.deps {
+                    let key = dep.get();
+                    let remaining = consumers.get_mut(&key).ok_or_else(|| {
+                        ExecError::Invalid(format!("missing consumer count for {}", key))
+                    })?;
+                    *remaining -= 1;
+                    let batch = if *remaining == 0 {
+                        results.remove(&key)
+                    } else {
+                        results.get(&key).cloned()
+                    }
+                    .ok_or_else(|| {
+                        ExecError::Invalid(format!("missing dependency block result for {}", key))
+                    })?;
+                    inputs.push(batch);
+                }
 
-            // Dispatch to the operator by op id.
-            let op = ops.get(&b.op.get()).ok_or_else(|| {
-                ExecError::Invalid(format!("no operator bound for op id {}", b.op))
-            })?;
+                let op = ops.get(&b.op.get()).ok_or_else(|| {
+                    ExecError::Invalid(format!("no operator bound for op id {}", b.op))
+                })?;
 
-            // Calculate input sizes for error context
-            let input_rows: usize = inputs.iter().map(|batch| batch.num_rows()).sum();
-            let input_bytes: usize = inputs
-                .iter()
-                .map(|batch| {
-                    batch
-                        .columns
-                        .iter()
-                        .map(|col| col.values.len() * 8)
-                        .sum::<usize>()
-                })
-                .sum();
-
-            // Build error context with operator and block information
-            let operator_name = op.name();
-            let context = format!(
-                "operator '{}' (op_id={}, block_id={}, input_rows={}, input_bytes={})",
-                operator_name,
-                b.op.get(),
-                b.id.get(),
-                input_rows,
-                input_bytes
-            );
+                // Calculate input sizes for error context
+                let input_rows: usize = inputs.iter().map(|batch| batch.num_rows()).sum();
+                let input_bytes: usize = inputs
+                    .iter()
+                    .map(|batch| {
+                        batch
+                            .columns
+                            .iter()
+                            .map(|col| col.values.len() * 8)
+                            .sum::<usize>()
+                    })
+                    .sum();
+                let context = format!(
+                    "operator '{}' (op_id={}, block_id={}, input_rows={}, input_bytes={})",
+                    op.name(),
+                    b.op.get(),
+                    b.id.get(),
+                    input_rows,
+                    input_bytes
+                );
+                staged.push((b, inputs, context));
+            }
 
-            // Try to execute with retry logic for recoverable errors
-            let out = match self.execute_block_with_retry(op.as_ref(), &inputs, &context, 3) {
+            // Evaluate the window: scoped threads when it has more than one
+            // member, inline otherwise.
+            let evaluated: Vec<(u64, Result<RowBatch, OpError>, String, u64)> =
+                if staged.len() == 1 {
+                    let (b, inputs, context) = staged.pop().expect("one member");
+                    let op = ops.get(&b.op.get()).expect("resolved above").clone();
+                    let block_budget: &dyn emsqrt_core::budget::MemoryBudget<
+                        Guard = emsqrt_mem::guard::BudgetGuardImpl,
+                    > = match sub_budgets.get(&b.op.get()) {
+                        Some(sub) => sub,
+                        None => &self.budget,
+                    };
+                    let result =
+                        self.execute_block_with_retry(op, inputs, &context, 3, block_budget);
+                    vec![(b.id.get(), result, context, b.op.get())]
+                } else {
+                    let engine: &Engine = self;
+                    let sub_budgets = &sub_budgets;
+                    let ops = &ops;
+                    std::thread::scope(|scope| {
+                        let handles: Vec<_> = staged
+                            .into_iter()
+                            .map(|(b, inputs, context)| {
+                                let op = ops.get(&b.op.get()).expect("resolved above").clone();
+                                scope.spawn(move || {
+                                    let block_budget: &dyn emsqrt_core::budget::MemoryBudget<
+                                        Guard = emsqrt_mem::guard::BudgetGuardImpl,
+                                    > = match sub_budgets.get(&b.op.get()) {
+                                        Some(sub) => sub,
+                                        None => &engine.budget,
+                                    };
+                                    let result = engine.execute_block_with_retry(
+                                        op,
+                                        inputs,
+                                        &context,
+                                        3,
+                                        block_budget,
+                                    );
+                                    (b.id.get(), result, context, b.op.get())
+                                })
+                            })
+                            .collect();
+                        handles
+                            .into_iter()
+                            .map(|h| h.join().expect("block worker panicked"))
+                            .collect()
+                    })
+                };
+
+            for (block_id, result, context, op_id) in evaluated {
+            let out = match result {
                 Ok(batch) => batch,
                 Err(e) => {
                     // Enhance error with context and suggestions
@@ -317,78 +1051,281 @@ impl Engine {
                             }
                         }
                     }
+                    // Failed run: drop staged outputs (including rotation
+                    // parts) so partial results never appear at the final
+                    // destinations.
+                    for (staging, destination) in
+                        self.staged_sinks.lock().unwrap().drain(..)
+                    {
+                        for (from, _) in staged_outputs(&staging, &destination) {
+                            let _ = std::fs::remove_file(&from);
+                        }
+                    }
                     return Err(ExecError::Operator(error_msg));
                 }
             };
 
+            // Debug tap: sample rows flowing out of the requested step.
+            if self._cfg.debug_step == Some(op_id) && debug_rows_left > 0 {
+                let take = debug_rows_left.min(out.num_rows());
+                for row in 0..take {
+                    let rendered: Vec<String> = out
+                        .columns
+                        .iter()
+                        .map(|c| format!("{}={:?}", c.name, c.values[row]))
+                        .collect();
+                    eprintln!(
+                        "[debug] step {} block {} row {}: {}",
+                        op_id,
+                        block_id,
+                        row,
+                        rendered.join(", ")
+                    );
+                }
+                debug_rows_left -= take;
+            }
+
             // Store the result for this block (downstream deps will consume/remove it).
-            results.insert(b.id.get(), out);
+            results.insert(block_id, out);
 
             #[cfg(feature = "tracing")]
-            tracing::trace!(block = %b.id.get(), op = %b.op.get(), deps = b.deps.len(), "executed block");
+            tracing::trace!(block = %block_id, op = %op_id, "executed block");
+            }
         }
 
-        // TODO: compute outputs digest (e.g., sinks) once sinks actually write data.
-        let outputs_digest = None;
+        // Outputs digest: checksum of everything delivered to sinks.
+        manifest.records_written = sink_records.load(std::sync::atomic::Ordering::Relaxed);
+        let outputs_digest = if manifest.records_written > 0 {
+            let hasher = sink_hasher.lock().unwrap();
+            Some(Hash256(hasher.finalize().into()))
+        } else {
+            None
+        };
+
+        // Record per-source schemas for drift detection between runs.
+        manifest.source_schemas = program
+            .bindings
+            .values()
+            .filter(|b| b.key == "source")
+            .filter_map(|b| {
+                let source = b.config.get("source")?.as_str()?.to_string();
+                let schema: Schema =
+                    serde_json::from_value(b.config.get("schema")?.clone()).ok()?;
+                Some(emsqrt_core::manifest::SourceSchemaRecord {
+                    source,
+                    columns: schema
+                        .fields
+                        .iter()
+                        .map(|f| (f.name.clone(), format!("{:?}", f.data_type)))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        // Record final source read positions for resumable ingestion.
+        manifest.source_checkpoints = source_positions
+            .into_iter()
+            .map(|(source, pos)| emsqrt_core::manifest::SourceCheckpoint {
+                source,
+                rows_read: *pos.lock().unwrap() as u64,
+                byte_offset: None,
+            })
+            .collect();
+
+        // Atomic publish: move staged sink files onto their destinations.
+        // Rotating sinks (csv:rotate=...) produce numbered part files from
+        // the staged path rather than the path itself, so every staged
+        // output is published by prefix, not just the literal name.
+        for (staging, destination) in self.staged_sinks.lock().unwrap().drain(..) {
+            for (from, to) in staged_outputs(&staging, &destination) {
+                std::fs::rename(&from, &to).map_err(|e| {
+                    ExecError::Storage(format!(
+                        "failed to publish '{}' -> '{}': {}",
+                        from, to, e
+                    ))
+                })?;
+            }
+        }
 
         manifest = manifest.finish(now_millis(), outputs_digest);
+
+        // End-of-run lifecycle: spill segments are scoped to one run.
+        if let Ok(mut spill_mgr) = self.spill_mgr.lock() {
+            spill_mgr
+                .flush_writes()
+                .map_err(|e| ExecError::Storage(e.to_string()))?;
+            let _ = spill_mgr.cleanup_all();
+        }
+
         Ok(manifest)
     }
 
     /// Execute a block with retry logic for recoverable errors.
     ///
-    /// Retries up to `max_retries` times for recoverable errors.
+    /// Retries up to `max_retries` times for recoverable errors. When
+    /// `EngineConfig.speculative_straggler_ms` is set, a block that exceeds
+    /// the threshold gets a second detached attempt; the first result wins
+    /// (evaluation is deterministic, so both attempts agree).
     fn execute_block_with_retry(
         &self,
-        op: &dyn Operator,
-        inputs: &[RowBatch],
+        op: Arc<dyn Operator>,
+        inputs: Vec<RowBatch>,
         context: &str,
         max_retries: u32,
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let mut last_error = None;
-
-        for attempt in 0..=max_retries {
-            match op.eval_block(inputs, &self.budget) {
-                Ok(batch) => return Ok(batch),
-                Err(e) => {
-                    if e.is_recoverable() && attempt < max_retries {
-                        // Exponential backoff: wait 2^attempt milliseconds
-                        let delay_ms = 2_u64.pow(attempt);
-                        std::thread::sleep(std::time::Duration::from_millis(delay_ms));
-                        last_error = Some(e);
-                        continue;
-                    } else {
-                        // Non-recoverable or max retries reached
-                        return Err(e.with_context(context));
-                    }
-                }
+        // Speculation is only safe for pure operators: sources advance read
+        // state and sinks write output, so duplicate attempts would double
+        // their side effects. Each speculative attempt still runs the full
+        // recoverable-error retry loop — flaky storage is exactly the case
+        // both mechanisms exist for. (Speculative attempts evaluate against
+        // the shared root budget: sub-budgets aren't shareable across the
+        // detached attempt threads.)
+        if let Some(threshold_ms) = self._cfg.speculative_straggler_ms {
+            if !matches!(op.name(), "source" | "sink") {
+                return self
+                    .execute_block_speculative(op, inputs, context, threshold_ms, max_retries)
+                    .map_err(|e| e.with_context(context));
             }
         }
 
-        // Should not reach here, but handle gracefully
-        match last_error {
-            Some(e) => Err(e.with_context(context)),
-            None => Err(
-                OpError::Exec(format!("execution failed after {} retries", max_retries))
-                    .with_context(context),
-            ),
-        }
+        eval_retrying(op.as_ref(), &inputs, budget, max_retries)
+            .map_err(|e| e.with_context(context))
     }
-}
-
-// --- helpers ---
 
-fn now_millis() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
+    /// Speculative execution: run the block on a worker thread; if it takes
+    /// longer than `threshold_ms`, launch a second attempt and take whichever
+    /// finishes first. The losing attempt runs to completion detached and is
+    /// discarded. Attempts evaluate with the usual recoverable-error retries.
+    fn execute_block_speculative(
+        &self,
+        op: Arc<dyn Operator>,
+        inputs: Vec<RowBatch>,
+        context: &str,
+        threshold_ms: u64,
+        max_retries: u32,
+    ) -> Result<RowBatch, OpError> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let inputs = Arc::new(inputs);
+
+        let spawn_attempt = |attempt: u32| {
+            let op = op.clone();
+            let inputs = inputs.clone();
+            let budget = self.budget.clone();
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                let result = eval_retrying(op.as_ref(), &inputs, &budget, max_retries);
+                let _ = tx.send((attempt, result));
+            });
+        };
+
+        spawn_attempt(0);
+
+        let first = match rx.recv_timeout(std::time::Duration::from_millis(threshold_ms)) {
+            Ok((_, result)) => return result.map_err(|e| e.with_context(context)),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                // Straggler: launch the backup attempt.
+                #[cfg(feature = "tracing")]
+                tracing::debug!(context, "launching speculative attempt for straggler block");
+                spawn_attempt(1);
+                rx.recv()
+            }
+            Err(e) => {
+                return Err(OpError::Exec(format!("speculative channel error: {e}"))
+                    .with_context(context));
+            }
+        };
+
+        match first {
+            Ok((_, result)) => result.map_err(|e| e.with_context(context)),
+            Err(_) => Err(OpError::Exec("all speculative attempts died".into())
+                .with_context(context)),
+        }
+    }
+}
+
+// --- helpers ---
+
+/// Operator keys that hold large working state and receive their own
+/// sub-budget under `per_operator_budgets` (pipeline breakers).
+pub const HEAVY_OPERATOR_KEYS: &[&str] = &[
+    "aggregate",
+    "sort_external",
+    "sort_partitioned",
+    "join_hash",
+    "join_merge",
+    "window",
+    "scd2_merge",
+    "histogram",
+];
+
+/// The planned per-operator memory split: half the cap divided evenly
+/// across the heavy operators in `bindings` (the other half stays shared
+/// for streaming operators and spill buffers). Empty when there are none.
+pub fn planned_budget_split(
+    bindings: &std::collections::BTreeMap<emsqrt_core::id::OpId, emsqrt_planner::physical::OperatorBinding>,
+    mem_cap_bytes: usize,
+) -> Vec<(emsqrt_core::id::OpId, String, usize)> {
+    let heavy: Vec<_> = bindings
+        .iter()
+        .filter(|(_, b)| HEAVY_OPERATOR_KEYS.contains(&b.key.as_str()))
+        .collect();
+    if heavy.is_empty() {
+        return Vec::new();
+    }
+    let share = (mem_cap_bytes / 2) / heavy.len();
+    heavy
+        .into_iter()
+        .map(|(op, b)| (*op, b.key.clone(), share))
+        .collect()
+}
+
+/// Evaluate one block, retrying recoverable errors with exponential backoff.
+/// Shared by the plain and speculative execution paths.
+fn eval_retrying(
+    op: &dyn Operator,
+    inputs: &[RowBatch],
+    budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    max_retries: u32,
+) -> Result<RowBatch, OpError> {
+    let mut last_error = None;
+
+    for attempt in 0..=max_retries {
+        match op.eval_block(inputs, budget) {
+            Ok(batch) => return Ok(batch),
+            Err(e) => {
+                if e.is_recoverable() && attempt < max_retries {
+                    // Exponential backoff: wait 2^attempt milliseconds
+                    let delay_ms = 2_u64.pow(attempt);
+                    std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+                    last_error = Some(e);
+                    continue;
+                } else {
+                    // Non-recoverable or max retries reached
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    // Should not reach here, but handle gracefully
+    Err(last_error.unwrap_or_else(|| {
+        OpError::Exec(format!("execution failed after {} retries", max_retries))
+    }))
+}
+
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as u64
 }
 
 fn xor_hashes(a: Hash256, b: Hash256) -> Hash256 {
     let mut out = [0u8; 32];
-    for i in 0..32 {
-        out[i] = a.0[i] ^ b.0[i];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = a.0[i] ^ b.0[i];
     }
     Hash256(out)
 }
@@ -441,6 +1378,48 @@ fn parse_window_functions(value: Option<&serde_json::Value>) -> Vec<WindowFnSpec
     specs
 }
 
+/// Rough in-memory size of a batch for sizing heuristics.
+#[cfg(feature = "parquet")]
+fn estimate_batch_bytes(batch: &RowBatch) -> u64 {
+    use emsqrt_core::types::Scalar;
+    batch
+        .columns
+        .iter()
+        .flat_map(|c| c.values.iter())
+        .map(|v| match v {
+            Scalar::Str(s) => 24 + s.len() as u64,
+            Scalar::Bin(b) => 24 + b.len() as u64,
+            _ => 16,
+        })
+        .sum()
+}
+
+/// Parse a raw source string as the given type; unparsable or empty values
+/// become null (strings pass through, but empty strings count as missing so
+/// defaults can apply).
+fn parse_typed_value(data_type: &emsqrt_core::schema::DataType, value: &str) -> emsqrt_core::types::Scalar {
+    use emsqrt_core::schema::DataType;
+    use emsqrt_core::types::Scalar;
+
+    if value.is_empty() {
+        return Scalar::Null;
+    }
+    match data_type {
+        DataType::Int32 => value.parse::<i32>().map(Scalar::I32).unwrap_or(Scalar::Null),
+        DataType::Int64 => value.parse::<i64>().map(Scalar::I64).unwrap_or(Scalar::Null),
+        DataType::Float32 => value.parse::<f32>().map(Scalar::F32).unwrap_or(Scalar::Null),
+        DataType::Float64 => value.parse::<f64>().map(Scalar::F64).unwrap_or(Scalar::Null),
+        DataType::Boolean => value
+            .parse::<bool>()
+            .map(Scalar::Bool)
+            .unwrap_or(Scalar::Null),
+        DataType::Date64 => emsqrt_core::time::parse_datetime(value)
+            .map(Scalar::Date64)
+            .unwrap_or(Scalar::Null),
+        _ => Scalar::Str(value.to_string()),
+    }
+}
+
 // --- placeholder source/sink operators (until real IO is wired) ---
 
 /// Detect file format from URI/path (by extension or explicit format parameter).
@@ -454,7 +1433,8 @@ fn detect_file_format(uri: &str, format_param: Option<&str>) -> &'static str {
         }
     }
 
-    // Detect by file extension
+    // Detect by file extension (ignoring a compression suffix)
+    let uri = emsqrt_io::decompress::strip_compression_extension(uri);
     if uri.ends_with(".parquet") || uri.ends_with(".parq") {
         return "parquet";
     }
@@ -463,11 +1443,124 @@ fn detect_file_format(uri: &str, format_param: Option<&str>) -> &'static str {
     "csv"
 }
 
+/// Expand a source path into concrete files: a literal path stays as-is, a
+/// directory yields its files, and `*`/`?` wildcards in the final component
+/// are matched against the directory listing. Results are sorted for
+/// deterministic scan order.
+fn expand_source(path: &str) -> Result<Vec<String>, OpError> {
+    let has_wildcard = path
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains('*') || name.contains('?'));
+
+    if has_wildcard {
+        let (dir, pattern) = match path.rsplit_once('/') {
+            Some((dir, pattern)) => (dir.to_string(), pattern.to_string()),
+            None => (".".to_string(), path.to_string()),
+        };
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| OpError::Exec(format!("failed to list '{}': {}", dir, e)))?;
+        let mut files: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                wildcard_match(&pattern, &name).then(|| format!("{}/{}", dir, name))
+            })
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            return Err(OpError::Exec(format!("no files match '{}'", path)));
+        }
+        return Ok(files);
+    }
+
+    let meta = std::fs::metadata(path)
+        .map_err(|e| OpError::Exec(format!("failed to open source '{}': {}", path, e)))?;
+    if meta.is_dir() {
+        let mut files: Vec<String> = std::fs::read_dir(path)
+            .map_err(|e| OpError::Exec(format!("failed to list '{}': {}", path, e)))?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .map(|e| e.path().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            return Err(OpError::Exec(format!("source directory '{}' is empty", path)));
+        }
+        return Ok(files);
+    }
+    Ok(vec![path.to_string()])
+}
+
+/// Glob-lite matcher: `*` matches any run of characters, `?` exactly one.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    fn rec(p: &[u8], n: &[u8]) -> bool {
+        match (p.first(), n.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                rec(&p[1..], n) || (!n.is_empty() && rec(p, &n[1..]))
+            }
+            (Some(b'?'), Some(_)) => rec(&p[1..], &n[1..]),
+            (Some(a), Some(b)) if a == b => rec(&p[1..], &n[1..]),
+            _ => false,
+        }
+    }
+    rec(pattern.as_bytes(), name.as_bytes())
+}
+
+/// Per-file reader plus that file's schema-to-column index mapping.
+struct OpenCsvFile {
+    path: String,
+    reader: ::csv::Reader<Box<dyn std::io::Read + Send>>,
+    /// Schema field -> CSV column index (None = synthesized/defaulted).
+    col_indices: Vec<Option<usize>>,
+}
+
+/// What to do with a malformed row: keep it with nulls, drop it (optionally
+/// into a rejects file), or abort the run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MalformedPolicy {
+    Null,
+    Skip,
+    Error,
+}
+
+impl MalformedPolicy {
+    fn from_schema_metadata(schema: &Schema) -> Result<Self, OpError> {
+        match schema.metadata.get("csv.on_malformed").map(String::as_str) {
+            None | Some("null") => Ok(MalformedPolicy::Null),
+            Some("skip") => Ok(MalformedPolicy::Skip),
+            Some("error") => Ok(MalformedPolicy::Error),
+            Some(other) => Err(OpError::Exec(format!(
+                "unknown on_malformed policy '{}' (expected null|skip|error)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Persistent CSV read state: files are consumed in order and each reader
+/// stays open across blocks, so every block streams the next chunk instead
+/// of re-scanning (which made multi-block reads quadratic).
+struct CsvSourceState {
+    files: Vec<String>,
+    next_file: usize,
+    current: Option<OpenCsvFile>,
+    /// Open rejects file, when a rejects path is configured.
+    rejects: Option<std::fs::File>,
+}
+
+/// Rows streamed per block from a CSV source.
+const CSV_CHUNK_ROWS: usize = 10_000;
+
 struct SourceOp {
     source_uri: String,
     schema: Schema,
-    // Track file position for multi-block reading (CSV)
+    // Track rows consumed (manifest checkpoints)
     file_position: Arc<Mutex<usize>>,
+    // Streaming CSV reader (initialized on first read, reused per block)
+    csv_state: Arc<Mutex<Option<CsvSourceState>>>,
     // Parquet reader (initialized on first read, reused for subsequent blocks)
     #[cfg(feature = "parquet")]
     parquet_reader: Arc<Mutex<Option<emsqrt_io::readers::parquet::ParquetReader>>>,
@@ -503,6 +1596,17 @@ impl Operator for SourceOp {
         // Detect file format
         let _format = detect_file_format(file_path, None);
 
+        // Scanning Parquet without the feature is a configuration error, not
+        // something to mis-parse as CSV.
+        #[cfg(not(feature = "parquet"))]
+        if _format == "parquet" {
+            return Err(OpError::Exec(format!(
+                "source '{}' is a Parquet file but EM-\u{221a} was built without the \
+`parquet` feature; rebuild with `--features parquet`",
+                file_path
+            )));
+        }
+
         // Handle Parquet files
         #[cfg(feature = "parquet")]
         if _format == "parquet" {
@@ -552,40 +1656,104 @@ impl Operator for SourceOp {
             }
         }
 
-        // Read CSV file with provided schema (default/fallback)
+        // Read CSV file with provided schema (streaming; default/fallback)
         use emsqrt_core::types::{Column, Scalar};
-        use std::fs::File;
-
-        let file = File::open(file_path).map_err(|e| {
-            OpError::Exec(format!("failed to open CSV file '{}': {}", file_path, e))
-        })?;
 
-        let mut rdr = ::csv::ReaderBuilder::new()
-            .has_headers(true)
-            .flexible(true)
-            .from_reader(file);
+        let mut state_guard = self.csv_state.lock().unwrap();
 
-        // Build column index mapping from schema field names
-        let headers = rdr
-            .headers()
-            .map_err(|e| OpError::Exec(format!("failed to read CSV headers: {}", e)))?;
+        // Expand the source (literal path, directory, or glob) once; later
+        // blocks continue from where the stream stopped.
+        if state_guard.is_none() {
+            let rejects = match self.schema.metadata.get("csv.rejects") {
+                Some(path) => Some(
+                    std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)
+                        .map_err(|e| {
+                            OpError::Exec(format!("failed to open rejects file '{}': {}", path, e))
+                        })?,
+                ),
+                None => None,
+            };
+            *state_guard = Some(CsvSourceState {
+                files: expand_source(file_path)?,
+                next_file: 0,
+                current: None,
+                rejects,
+            });
+        }
+        let state = state_guard.as_mut().expect("initialized above");
+
+        // Open the next file in sequence, resolving the schema against its
+        // headers (column order may differ between files).
+        fn open_next(
+            state: &mut CsvSourceState,
+            schema: &Schema,
+        ) -> Result<bool, OpError> {
+            let Some(path) = state.files.get(state.next_file).cloned() else {
+                return Ok(false); // all files consumed
+            };
+            state.next_file += 1;
 
-        let col_indices: Vec<Option<usize>> = self
-            .schema
-            .fields
-            .iter()
-            .map(|field| headers.iter().position(|h| h.trim() == field.name.trim()))
-            .collect();
+            // Compressed inputs (.gz/.zst/.bz2) are decompressed on the fly.
+            let mut file = emsqrt_io::decompress::open_maybe_compressed(&path).map_err(|e| {
+                OpError::Exec(format!("failed to open CSV file '{}': {}", path, e))
+            })?;
+            // Dialect options (delimiter/quote/comment/headers/escape/
+            // null token/skip rows) travel as schema metadata from the
+            // scan step.
+            let dialect = emsqrt_io::readers::csv::CsvDialect::from_schema_metadata(schema);
+            dialect.skip_leading_rows(&mut file).map_err(|e| {
+                OpError::Exec(format!("failed to skip leading rows of '{}': {}", path, e))
+            })?;
+            let has_headers = dialect.has_headers;
+            let mut builder = ::csv::ReaderBuilder::new();
+            dialect.configure(&mut builder);
+            let mut reader = builder.from_reader(file);
+
+            let col_indices: Vec<Option<usize>> = if let Some(names) = &dialect.headers {
+                // Explicit names override the file's header row (still
+                // consumed by the csv reader when has_headers is set).
+                schema
+                    .fields
+                    .iter()
+                    .map(|field| names.iter().position(|h| h.trim() == field.name.trim()))
+                    .collect()
+            } else if has_headers {
+                let headers = reader
+                    .headers()
+                    .map_err(|e| OpError::Exec(format!("failed to read CSV headers: {}", e)))?;
+                schema
+                    .fields
+                    .iter()
+                    .map(|field| headers.iter().position(|h| h.trim() == field.name.trim()))
+                    .collect()
+            } else {
+                // Headerless: fields map to columns positionally.
+                (0..schema.fields.len()).map(Some).collect()
+            };
 
-        // Verify all required columns are found
-        for (field, col_idx_opt) in self.schema.fields.iter().zip(col_indices.iter()) {
-            if col_idx_opt.is_none() {
-                return Err(OpError::Exec(format!(
-                    "CSV file missing required column '{}'. Available columns: {:?}",
-                    field.name,
-                    headers.iter().collect::<Vec<_>>()
-                )));
+            // Virtual metadata columns are synthesized, never read from the file.
+            for (field, col_idx_opt) in schema.fields.iter().zip(col_indices.iter()) {
+                let synthesized = matches!(
+                    field.name.as_str(),
+                    "_file" | "_line" | "_byte_offset"
+                ) || field.default.is_some();
+                if col_idx_opt.is_none() && !synthesized {
+                    return Err(OpError::Exec(format!(
+                        "CSV file '{}' missing required column '{}'",
+                        path, field.name
+                    )));
+                }
             }
+
+            state.current = Some(OpenCsvFile {
+                path,
+                reader,
+                col_indices,
+            });
+            Ok(true)
         }
 
         // Initialize columns based on schema
@@ -599,93 +1767,115 @@ impl Operator for SourceOp {
             })
             .collect();
 
-        // Read rows and populate columns
-        // Skip rows that were already read by previous blocks
-        let mut file_pos = self.file_position.lock().unwrap();
-        let skip_rows = *file_pos;
+        let policy = MalformedPolicy::from_schema_metadata(&self.schema)?;
+        let null_token = self.schema.metadata.get("csv.null_token").cloned();
 
-        // Skip header + already-read rows
         let mut row_count = 0;
-        let mut skipped = 0;
-        for result in rdr.records() {
-            // Skip rows that were read in previous blocks
-            if skipped < skip_rows {
-                skipped += 1;
-                continue; // Skip this row, don't process it
+        let mut record = ::csv::StringRecord::new();
+        while row_count < CSV_CHUNK_ROWS {
+            if state.current.is_none() && !open_next(state, &self.schema)? {
+                break; // every file consumed
+            }
+            let open = state.current.as_mut().expect("opened above");
+
+            let got = open
+                .reader
+                .read_record(&mut record)
+                .map_err(|e| OpError::Exec(format!("failed to read CSV record: {}", e)))?;
+            if !got {
+                // End of this file: advance to the next one.
+                state.current = None;
+                continue;
             }
 
-            // We've skipped enough - now process this row
-
-            let record =
-                result.map_err(|e| OpError::Exec(format!("failed to read CSV record: {}", e)))?;
-
+            // Record position for metadata virtual columns (1-based line,
+            // byte offset of the record start).
+            let (line, byte_offset) = record
+                .position()
+                .map(|p| (p.line() as i64, p.byte() as i64))
+                .unwrap_or((0, 0));
+
+            // Parse the full row first so the malformed policy can reject it
+            // atomically (a malformed row is one where a non-nullable field
+            // without a default fails to parse).
+            let mut parsed: Vec<Scalar> = Vec::with_capacity(self.schema.fields.len());
+            let mut malformed: Option<String> = None;
             for (col_idx, field) in self.schema.fields.iter().enumerate() {
-                let value = if let Some(csv_col_idx) = col_indices[col_idx] {
+                if matches!(field.name.as_str(), "_file" | "_line" | "_byte_offset") {
+                    parsed.push(match field.name.as_str() {
+                        "_file" => Scalar::Str(open.path.clone()),
+                        "_line" => Scalar::I64(line),
+                        _ => Scalar::I64(byte_offset),
+                    });
+                    continue;
+                }
+
+                let mut value = if let Some(csv_col_idx) = open.col_indices[col_idx] {
                     record.get(csv_col_idx).unwrap_or("")
                 } else {
                     ""
                 };
+                if null_token.as_deref() == Some(value) {
+                    value = "";
+                }
 
-                // Parse value based on schema type
-                let scalar = match field.data_type {
-                    emsqrt_core::schema::DataType::Int32 => value
-                        .parse::<i32>()
-                        .map(Scalar::I32)
-                        .unwrap_or(Scalar::Null),
-                    emsqrt_core::schema::DataType::Int64 => value
-                        .parse::<i64>()
-                        .map(Scalar::I64)
-                        .unwrap_or(Scalar::Null),
-                    emsqrt_core::schema::DataType::Float32 => value
-                        .parse::<f32>()
-                        .map(Scalar::F32)
-                        .unwrap_or(Scalar::Null),
-                    emsqrt_core::schema::DataType::Float64 => value
-                        .parse::<f64>()
-                        .map(Scalar::F64)
-                        .unwrap_or(Scalar::Null),
-                    emsqrt_core::schema::DataType::Boolean => value
-                        .parse::<bool>()
-                        .map(Scalar::Bool)
-                        .unwrap_or(Scalar::Null),
-                    _ => Scalar::Str(value.to_string()),
-                };
+                // Parse the source value; fall back to the declared default
+                // (if any) when the value is missing or unparsable.
+                let mut scalar = parse_typed_value(&field.data_type, value);
+                if matches!(scalar, Scalar::Null) {
+                    if let Some(default) = &field.default {
+                        scalar = parse_typed_value(&field.data_type, default);
+                    }
+                }
+                if matches!(scalar, Scalar::Null) && !field.nullable && malformed.is_none() {
+                    malformed = Some(format!(
+                        "column '{}' value '{}' is not a valid {:?}",
+                        field.name, value, field.data_type
+                    ));
+                }
+                parsed.push(scalar);
+            }
 
+            if let Some(reason) = malformed {
+                match policy {
+                    MalformedPolicy::Null => {} // keep the row, nulls and all
+                    MalformedPolicy::Skip => {
+                        if let Some(rejects) = &mut state.rejects {
+                            use std::io::Write;
+                            let raw: Vec<&str> = record.iter().collect();
+                            let _ = writeln!(
+                                rejects,
+                                "{}:{}: {}: {}",
+                                open.path,
+                                line,
+                                reason,
+                                raw.join(",")
+                            );
+                        }
+                        continue; // drop the row
+                    }
+                    MalformedPolicy::Error => {
+                        return Err(OpError::Exec(format!(
+                            "malformed row at {}:{}: {}",
+                            open.path, line, reason
+                        )));
+                    }
+                }
+            }
+            for (col_idx, scalar) in parsed.into_iter().enumerate() {
                 columns[col_idx].values.push(scalar);
             }
 
             row_count += 1;
-            if row_count >= 10000 {
-                break; // Limit batch size
-            }
         }
 
-        // Update file position for next block
+        // Track rows consumed for manifest checkpoints.
+        let mut file_pos = self.file_position.lock().unwrap();
+        let already_read = *file_pos;
         *file_pos += row_count;
 
-        // Ensure all columns have the same number of values
-        let num_rows = columns.first().map(|c| c.values.len()).unwrap_or(0);
-        for col in &mut columns {
-            if col.values.len() != num_rows {
-                return Err(OpError::Exec(format!(
-                    "column '{}' has {} values but expected {}",
-                    col.name,
-                    col.values.len(),
-                    num_rows
-                )));
-            }
-        }
-
-        // If we skipped rows but didn't read any new ones, we've reached the end
-        // This is fine - return empty batch with correct column structure
-        if row_count == 0 {
-            // If we've already read some rows in previous blocks, it's OK to return empty
-            // But we still need to return columns with the correct names so downstream operators work
-            if skip_rows > 0 {
-                // Return empty batch with correct schema (columns exist but empty)
-                return Ok(RowBatch { columns });
-            }
-            // Otherwise, this is the first read and we got nothing - that's an error
+        if row_count == 0 && already_read == 0 {
+            // First read produced nothing at all.
             return Err(OpError::Exec("no data in CSV file".into()));
         }
 
@@ -696,7 +1886,19 @@ impl Operator for SourceOp {
 struct SinkOp {
     destination: String,
     format: String,
+    /// Memory cap used to size Parquet row groups.
+    #[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+    mem_cap_bytes: usize,
     writer_initialized: std::sync::Arc<std::sync::Mutex<bool>>,
+    // Merge-on-read sorted-run writer (format "sorted_runs:<keys>")
+    sorted_runs_writer:
+        std::sync::Arc<std::sync::Mutex<Option<emsqrt_io::writers::sorted_runs::SortedRunWriter>>>,
+    // Hive-style partitioned writer state (format "partitioned:<cols>"):
+    // partition directory -> batches written there so far.
+    partition_counts: std::sync::Arc<std::sync::Mutex<HashMap<String, usize>>>,
+    // Rotating CSV state (format "csv:rotate=<size>"): (part index, bytes
+    // written to the current part).
+    rotation: std::sync::Arc<std::sync::Mutex<(usize, u64)>>,
     // Parquet writer state (when writing Parquet files)
     #[cfg(feature = "parquet")]
     parquet_writer:
@@ -736,8 +1938,7 @@ impl Operator for SinkOp {
         inputs: &[RowBatch],
         _budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("sink requires one input".into()))?;
 
         // Check if input is empty (shouldn't happen, but handle gracefully)
@@ -759,9 +1960,9 @@ impl Operator for SinkOp {
         // Handle Parquet format
         #[cfg(feature = "parquet")]
         if self.format == "parquet" {
-            use emsqrt_io::arrow_convert::emsqrt_to_arrow_schema;
+            
             use emsqrt_io::writers::parquet::ParquetWriter;
-            use std::sync::Arc;
+            
 
             let mut writer_guard = self.parquet_writer.lock().unwrap();
 
@@ -806,6 +2007,9 @@ impl Operator for SinkOp {
                                 emsqrt_core::types::Scalar::Bin(_) => {
                                     Some(emsqrt_core::schema::DataType::Binary)
                                 }
+                                emsqrt_core::types::Scalar::Date64(_) => {
+                                    Some(emsqrt_core::schema::DataType::Date64)
+                                }
                             })
                             .unwrap_or(emsqrt_core::schema::DataType::Utf8);
 
@@ -814,10 +2018,26 @@ impl Operator for SinkOp {
                     .collect();
 
                 let schema = emsqrt_core::schema::Schema::new(fields);
-                let writer =
-                    ParquetWriter::from_emsqrt_schema(file_path, &schema).map_err(|e| {
-                        OpError::Exec(format!("failed to create Parquet writer: {}", e))
-                    })?;
+
+                // Size row groups under the memory cap: writing (and later
+                // reading) buffers a whole group, so target 1/8th of the cap
+                // using a bytes-per-row estimate from this first batch.
+                let bytes_per_row = (estimate_batch_bytes(input)
+                    / input.num_rows().max(1) as u64)
+                    .max(1);
+                let target_bytes = (self.mem_cap_bytes as u64 / 8).max(1024 * 1024);
+                let rows_per_group =
+                    ((target_bytes / bytes_per_row) as usize).clamp(1_000, 1_000_000);
+
+                let writer = ParquetWriter::from_emsqrt_schema_with_options(
+                    file_path,
+                    &schema,
+                    emsqrt_io::writers::parquet::ParquetCompression::default(),
+                    Some(rows_per_group),
+                )
+                .map_err(|e| {
+                    OpError::Exec(format!("failed to create Parquet writer: {}", e))
+                })?;
 
                 *writer_guard = Some(writer);
             }
@@ -843,6 +2063,76 @@ impl Operator for SinkOp {
             return Ok(input.clone());
         }
 
+        // Rotating CSV sink ("csv:rotate=<size>", e.g. "csv:rotate=64MB"):
+        // output moves to numbered part files once the current one reaches
+        // the size target.
+        if let Some(spec) = self.format.strip_prefix("csv:rotate=") {
+            let target = parse_size_bytes(spec).ok_or_else(|| {
+                OpError::Exec(format!(
+                    "invalid rotation size '{}' (expected e.g. '64MB', '500KB')",
+                    spec
+                ))
+            })?;
+            return self.write_rotating_csv(input, file_path, target);
+        }
+
+        // Hive-style partitioned sink ("partitioned:col1,col2", or
+        // "partitioned:jsonl:col1,col2" to pick the file format): rows land
+        // in <dir>/col1=value/col2=value/part-<n>.<ext> with the partition
+        // columns dropped from the file contents.
+        if let Some(columns) = self.format.strip_prefix("partitioned") {
+            let spec = columns.strip_prefix(':').unwrap_or("");
+            let (file_format, columns) = match spec.split_once(':') {
+                Some((fmt @ ("csv" | "jsonl" | "parquet"), rest)) => (fmt, rest),
+                _ => ("csv", spec),
+            };
+            let partition_cols: Vec<String> = columns
+                .split(',')
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect();
+            if partition_cols.is_empty() {
+                return Err(OpError::Exec(
+                    "partitioned sink needs columns: use format 'partitioned:col1,col2'".into(),
+                ));
+            }
+            return self.write_partitioned(input, file_path, file_format, &partition_cols);
+        }
+
+        // Merge-on-read sink: each batch becomes a sorted run under the
+        // destination directory ("sorted_runs:key1,key2").
+        if let Some(keys) = self.format.strip_prefix("sorted_runs") {
+            let by: Vec<String> = keys
+                .strip_prefix(':')
+                .unwrap_or("")
+                .split(',')
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect();
+            if by.is_empty() {
+                return Err(OpError::Exec(
+                    "sorted_runs sink needs sort keys: use format 'sorted_runs:key1,key2'".into(),
+                ));
+            }
+
+            let mut writer_guard = self.sorted_runs_writer.lock().unwrap();
+            if writer_guard.is_none() {
+                *writer_guard = Some(
+                    emsqrt_io::writers::sorted_runs::SortedRunWriter::new(file_path, by)
+                        .map_err(|e| {
+                            OpError::Exec(format!("failed to create sorted-run sink: {}", e))
+                        })?,
+                );
+            }
+            writer_guard
+                .as_mut()
+                .expect("initialized above")
+                .write_batch(input)
+                .map_err(|e| OpError::Exec(format!("failed to write sorted run: {}", e)))?;
+
+            return Ok(RowBatch { columns: vec![] });
+        }
+
         // Write based on format
         // For CSV, we need to append to the file if it already exists (for multiple blocks)
         match self.format.as_str() {
@@ -907,3 +2197,843 @@ impl Operator for SinkOp {
         Ok(RowBatch { columns: vec![] })
     }
 }
+
+/// Reference table loaded by `LookupOp`: output column names plus a map from
+/// key text to that row's output values. The budget guard accounts for the
+/// table's bytes for as long as it stays cached.
+struct LookupTable {
+    columns: Vec<String>,
+    rows: HashMap<String, Vec<String>>,
+    _guard: emsqrt_mem::guard::BudgetGuardImpl,
+}
+
+/// Enrichment operator: loads a small reference CSV once and appends matching
+/// columns to each input row (nulls when the key has no match).
+struct LookupOp {
+    source: String,
+    key: String,
+    ref_key: String,
+    columns: Vec<String>,
+    table: Arc<Mutex<Option<LookupTable>>>,
+}
+
+impl LookupOp {
+    fn load_table(
+        &self,
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<LookupTable, OpError> {
+        let file_path = self.source.strip_prefix("file://").unwrap_or(&self.source);
+
+        let file = std::fs::File::open(file_path).map_err(|e| {
+            OpError::Exec(format!(
+                "failed to open lookup reference '{}': {}",
+                file_path, e
+            ))
+        })?;
+
+        let mut rdr = ::csv::ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_reader(file);
+
+        let headers: Vec<String> = rdr
+            .headers()
+            .map_err(|e| OpError::Exec(format!("failed to read lookup headers: {}", e)))?
+            .iter()
+            .map(|h| h.trim().to_string())
+            .collect();
+
+        let key_idx = headers
+            .iter()
+            .position(|h| h == &self.ref_key)
+            .ok_or_else(|| {
+                OpError::Exec(format!(
+                    "lookup reference missing key column '{}'. Available columns: {:?}",
+                    self.ref_key, headers
+                ))
+            })?;
+
+        // Empty column list means "all reference columns except the key".
+        let out_columns: Vec<(usize, String)> = if self.columns.is_empty() {
+            headers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != key_idx)
+                .map(|(i, h)| (i, h.clone()))
+                .collect()
+        } else {
+            self.columns
+                .iter()
+                .map(|c| {
+                    headers
+                        .iter()
+                        .position(|h| h == c)
+                        .map(|i| (i, c.clone()))
+                        .ok_or_else(|| {
+                            OpError::Exec(format!(
+                                "lookup reference missing column '{}'. Available columns: {:?}",
+                                c, headers
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        // The reference table is held in memory for the run, so its bytes
+        // must come out of the budget like any other big allocation. The
+        // guard grows as rows load; running out of budget is an error with
+        // the remediation spelled out (a join never materializes the whole
+        // reference at once).
+        let mut guard = budget
+            .try_acquire(0, "lookup_table")
+            .ok_or_else(|| OpError::Exec("cannot acquire budget for lookup table".into()))?;
+        let mut accounted_bytes: usize = 0;
+
+        let mut rows = HashMap::new();
+        for record in rdr.records() {
+            let record = record
+                .map_err(|e| OpError::Exec(format!("failed to read lookup record: {}", e)))?;
+            let key = record.get(key_idx).unwrap_or("").to_string();
+            let values: Vec<String> = out_columns
+                .iter()
+                .map(|(i, _)| record.get(*i).unwrap_or("").to_string())
+                .collect();
+
+            // ~48 bytes of map/entry overhead per row plus the string data.
+            accounted_bytes += 48
+                + key.len()
+                + values.iter().map(|v| 24 + v.len()).sum::<usize>();
+            if !guard.try_resize(accounted_bytes) {
+                return Err(OpError::Exec(format!(
+                    "lookup reference '{}' exceeds the memory budget after {} rows \
+({} bytes); use a join (join_hash spills to disk) instead of lookup, or raise \
+the memory cap",
+                    file_path,
+                    rows.len(),
+                    accounted_bytes
+                )));
+            }
+
+            // First match wins on duplicate keys.
+            rows.entry(key).or_insert(values);
+        }
+
+        Ok(LookupTable {
+            columns: out_columns.into_iter().map(|(_, c)| c).collect(),
+            rows,
+            _guard: guard,
+        })
+    }
+}
+
+impl Operator for LookupOp {
+    fn name(&self) -> &'static str {
+        "lookup"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        // Holds the whole reference table; assumed small.
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 64 * 1024,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "lookup.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        use emsqrt_core::types::{Column, Scalar};
+
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("lookup requires one input".into()))?;
+
+        let key_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.key)
+            .ok_or_else(|| OpError::Exec(format!("lookup key column '{}' not found", self.key)))?;
+
+        // Load the reference table on first use, then reuse it across blocks.
+        let mut table_guard = self.table.lock().unwrap();
+        if table_guard.is_none() {
+            *table_guard = Some(self.load_table(budget)?);
+        }
+        let table = table_guard.as_ref().expect("loaded above");
+
+        let num_rows = input.num_rows();
+        let mut appended: Vec<Column> = table
+            .columns
+            .iter()
+            .map(|name| Column {
+                name: name.clone(),
+                values: Vec::with_capacity(num_rows),
+            })
+            .collect();
+
+        for row_idx in 0..num_rows {
+            let key_text = match &key_col.values[row_idx] {
+                Scalar::Str(s) => s.clone(),
+                Scalar::Null => String::new(),
+                Scalar::I32(v) => v.to_string(),
+                Scalar::I64(v) => v.to_string(),
+                Scalar::F32(v) => v.to_string(),
+                Scalar::F64(v) => v.to_string(),
+                Scalar::Bool(v) => v.to_string(),
+                other => format!("{:?}", other),
+            };
+
+            match table.rows.get(&key_text) {
+                Some(values) => {
+                    for (col, value) in appended.iter_mut().zip(values) {
+                        col.values.push(Scalar::Str(value.clone()));
+                    }
+                }
+                None => {
+                    for col in &mut appended {
+                        col.values.push(Scalar::Null);
+                    }
+                }
+            }
+        }
+
+        let mut columns = input.columns.clone();
+        columns.extend(appended);
+        Ok(RowBatch { columns })
+    }
+}
+
+/// Source operator backed by a registered `RowBatchProvider`.
+struct MemorySourceOp {
+    schema: Schema,
+    provider: Arc<Mutex<Box<dyn RowBatchProvider>>>,
+}
+
+impl Operator for MemorySourceOp {
+    fn name(&self) -> &'static str {
+        "source"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 0,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "source.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        _inputs: &[RowBatch],
+        _budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let mut provider = self.provider.lock().unwrap();
+        match provider.next_batch() {
+            Some(batch) => Ok(batch),
+            // Exhausted: empty batch with the declared column structure so
+            // downstream operators keep working (same as file sources at EOF).
+            None => Ok(RowBatch {
+                columns: self
+                    .schema
+                    .fields
+                    .iter()
+                    .map(|f| emsqrt_core::types::Column {
+                        name: f.name.clone(),
+                        values: Vec::new(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Sink operator backed by a registered `BatchSink` callback.
+struct CallbackSinkOp {
+    sink: Arc<Mutex<Box<dyn BatchSink>>>,
+}
+
+impl Operator for CallbackSinkOp {
+    fn name(&self) -> &'static str {
+        "sink"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 0,
+            overhead_bytes: 0,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "sink.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("sink requires one input".into()))?;
+
+        self.sink
+            .lock()
+            .unwrap()
+            .write_batch(input)
+            .map_err(|e| OpError::Exec(format!("callback sink failed: {}", e)))?;
+
+        // Sink is terminal.
+        Ok(RowBatch { columns: vec![] })
+    }
+}
+
+
+/// Upper bound on distinct hive partitions a single run may create.
+const MAX_SINK_PARTITIONS: usize = 1000;
+
+impl SinkOp {
+    /// Hive-style partitioned write: group rows by the partition columns'
+    /// values and write per-partition CSV, JSONL, or Parquet files, with
+    /// the partition columns encoded in the directory path rather than the
+    /// file. The total number of distinct partitions per run is capped so a
+    /// high-cardinality partition column fails loudly instead of fanning
+    /// out into an unbounded set of files.
+    fn write_partitioned(
+        &self,
+        input: &RowBatch,
+        base_dir: &str,
+        file_format: &str,
+        partition_cols: &[String],
+    ) -> Result<RowBatch, OpError> {
+        use emsqrt_core::types::Column;
+
+        let partition_idx: Vec<usize> = partition_cols
+            .iter()
+            .map(|name| {
+                input
+                    .columns
+                    .iter()
+                    .position(|c| &c.name == name)
+                    .ok_or_else(|| {
+                        OpError::Exec(format!("partition column '{}' not found", name))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Hive-safe rendering of a partition value.
+        let part_value = |scalar: &emsqrt_core::types::Scalar| -> String {
+            let raw = match scalar {
+                emsqrt_core::types::Scalar::Null => "__HIVE_DEFAULT_PARTITION__".to_string(),
+                emsqrt_core::types::Scalar::Str(s) => s.clone(),
+                other => format!("{:?}", other),
+            };
+            raw.chars()
+                .map(|c| if c.is_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+                .collect()
+        };
+
+        // Group row indices by partition path.
+        let mut partitions: std::collections::BTreeMap<String, Vec<usize>> =
+            std::collections::BTreeMap::new();
+        for row in 0..input.num_rows() {
+            let path: Vec<String> = partition_cols
+                .iter()
+                .zip(&partition_idx)
+                .map(|(name, &idx)| format!("{}={}", name, part_value(&input.columns[idx].values[row])))
+                .collect();
+            partitions
+                .entry(format!("{}/{}", base_dir, path.join("/")))
+                .or_default()
+                .push(row);
+        }
+
+        let data_columns: Vec<&Column> = input
+            .columns
+            .iter()
+            .filter(|c| !partition_cols.contains(&c.name))
+            .collect();
+
+        let mut counts = self.partition_counts.lock().unwrap();
+        let new_dirs = partitions
+            .keys()
+            .filter(|dir| !counts.contains_key(*dir))
+            .count();
+        if counts.len() + new_dirs > MAX_SINK_PARTITIONS {
+            return Err(OpError::Exec(format!(
+                "partitioned sink would exceed {} partitions; partition by a \
+                 lower-cardinality column or bucket the values first",
+                MAX_SINK_PARTITIONS
+            )));
+        }
+
+        for (dir, rows) in partitions {
+            std::fs::create_dir_all(&dir).map_err(|e| {
+                OpError::Exec(format!("failed to create partition dir '{}': {}", dir, e))
+            })?;
+            let batches_written = counts.get(&dir).copied().unwrap_or(0);
+
+            let partition_batch = RowBatch {
+                columns: data_columns
+                    .iter()
+                    .map(|c| Column {
+                        name: c.name.clone(),
+                        values: rows.iter().map(|&r| c.values[r].clone()).collect(),
+                    })
+                    .collect(),
+            };
+
+            match file_format {
+                "csv" => {
+                    let file_path = format!("{}/part-00000.csv", dir);
+                    let file = std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&file_path)
+                        .map_err(|e| {
+                            OpError::Exec(format!("failed to open '{}': {}", file_path, e))
+                        })?;
+                    let mut writer = if batches_written == 0 {
+                        CsvWriter::to_writer(file)
+                    } else {
+                        CsvWriter::to_writer_skip_header(file)
+                    };
+                    writer.write_batch(&partition_batch).map_err(|e| {
+                        OpError::Exec(format!(
+                            "failed to write partition '{}': {}",
+                            file_path, e
+                        ))
+                    })?;
+                }
+                "jsonl" => {
+                    let file_path = format!("{}/part-00000.jsonl", dir);
+                    let file = std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&file_path)
+                        .map_err(|e| {
+                            OpError::Exec(format!("failed to open '{}': {}", file_path, e))
+                        })?;
+                    let mut writer =
+                        emsqrt_io::writers::jsonl::JsonlWriter::to_writer(file, None);
+                    writer.write_batch(&partition_batch).map_err(|e| {
+                        OpError::Exec(format!(
+                            "failed to write partition '{}': {}",
+                            file_path, e
+                        ))
+                    })?;
+                }
+                "parquet" => {
+                    // Parquet files are immutable once closed, so each batch
+                    // gets its own numbered part file.
+                    #[cfg(feature = "parquet")]
+                    {
+                        let file_path =
+                            format!("{}/part-{:05}.parquet", dir, batches_written);
+                        let schema = crate::delta::batch_schema(&partition_batch);
+                        let mut writer =
+                            emsqrt_io::writers::parquet::ParquetWriter::from_emsqrt_schema(
+                                &file_path, &schema,
+                            )
+                            .map_err(|e| {
+                                OpError::Exec(format!(
+                                    "failed to open '{}': {}",
+                                    file_path, e
+                                ))
+                            })?;
+                        writer.write_row_batch(&partition_batch).map_err(|e| {
+                            OpError::Exec(format!(
+                                "failed to write partition '{}': {}",
+                                file_path, e
+                            ))
+                        })?;
+                        writer.close().map_err(|e| {
+                            OpError::Exec(format!(
+                                "failed to close partition '{}': {}",
+                                file_path, e
+                            ))
+                        })?;
+                    }
+                    #[cfg(not(feature = "parquet"))]
+                    return Err(OpError::Exec(
+                        "partitioned parquet sink requires the 'parquet' feature".into(),
+                    ));
+                }
+                other => {
+                    return Err(OpError::Exec(format!(
+                        "unsupported partitioned sink format '{}' (expected csv, jsonl, \
+                         or parquet)",
+                        other
+                    )));
+                }
+            }
+            counts.insert(dir, batches_written + 1);
+        }
+
+        Ok(RowBatch { columns: vec![] })
+    }
+}
+
+
+/// Parse "64MB" / "500KB" / "1GB" / plain bytes into a byte count.
+fn parse_size_bytes(s: &str) -> Option<u64> {
+    let s = s.trim();
+    for (suffix, multiplier) in [
+        ("GB", 1024 * 1024 * 1024u64),
+        ("MB", 1024 * 1024),
+        ("KB", 1024),
+        ("B", 1),
+    ] {
+        if let Some(number) = s.strip_suffix(suffix) {
+            return number.trim().parse::<u64>().ok()?.checked_mul(multiplier);
+        }
+    }
+    s.parse().ok()
+}
+
+impl SinkOp {
+    /// Path of rotation part `index`: "out.csv" -> "out.00000.csv" (part 0
+    /// keeps a numbered name too, so globbing the parts is uniform).
+    fn part_path(destination: &str, index: usize) -> String {
+        match destination.rsplit_once('.') {
+            Some((stem, ext)) => format!("{}.{:05}.{}", stem, index, ext),
+            None => format!("{}.{:05}", destination, index),
+        }
+    }
+
+    /// CSV write with size-target rotation. Large batches are written in
+    /// row chunks so rotation happens mid-batch once a part fills up.
+    fn write_rotating_csv(
+        &self,
+        input: &RowBatch,
+        destination: &str,
+        target_bytes: u64,
+    ) -> Result<RowBatch, OpError> {
+        use emsqrt_core::types::Column;
+
+        const CHUNK_ROWS: usize = 100;
+
+        let mut rotation = self.rotation.lock().unwrap();
+        let num_rows = input.num_rows();
+        let mut start = 0;
+
+        while start < num_rows {
+            // Rotate once the current part reached its size target.
+            if rotation.1 >= target_bytes && rotation.1 > 0 {
+                rotation.0 += 1;
+                rotation.1 = 0;
+            }
+
+            let end = (start + CHUNK_ROWS).min(num_rows);
+            let chunk = RowBatch {
+                columns: input
+                    .columns
+                    .iter()
+                    .map(|c| Column {
+                        name: c.name.clone(),
+                        values: c.values[start..end].to_vec(),
+                    })
+                    .collect(),
+            };
+
+            let path = Self::part_path(destination, rotation.0);
+            let is_new_part = rotation.1 == 0;
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| OpError::Exec(format!("failed to open '{}': {}", path, e)))?;
+
+            let mut writer = if is_new_part {
+                CsvWriter::to_writer(file)
+            } else {
+                CsvWriter::to_writer_skip_header(file)
+            };
+            writer.write_batch(&chunk).map_err(|e| {
+                OpError::Exec(format!("failed to write rotating CSV '{}': {}", path, e))
+            })?;
+
+            rotation.1 = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            start = end;
+        }
+
+        Ok(RowBatch { columns: vec![] })
+    }
+}
+
+
+/// Files a staged sink actually produced, paired with their publish targets:
+/// the staged path itself, plus any rotation parts derived from it
+/// (`out.csv` -> `out.00000.csv`, ...), mapped onto the equivalent names at
+/// the final destination.
+fn staged_outputs(staging: &str, destination: &str) -> Vec<(String, String)> {
+    let mut outputs = Vec::new();
+    if std::path::Path::new(staging).exists() {
+        outputs.push((staging.to_string(), destination.to_string()));
+    }
+
+    let staging_path = std::path::Path::new(staging);
+    let (Some(dir), Some(name)) = (
+        staging_path.parent(),
+        staging_path.file_name().and_then(|n| n.to_str()),
+    ) else {
+        return outputs;
+    };
+    // Rotation parts: "<stem>.<5 digits>[.<ext>]" of the staged file name.
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) => (stem, Some(ext)),
+        None => (name, None),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return outputs;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let part_name = entry.file_name().to_string_lossy().to_string();
+        let rest = match part_name.strip_prefix(stem).and_then(|r| r.strip_prefix('.')) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let digits = match ext {
+            Some(ext) => match rest.strip_suffix(ext).and_then(|r| r.strip_suffix('.')) {
+                Some(digits) => digits,
+                None => continue,
+            },
+            None => rest,
+        };
+        if digits.len() == 5 && digits.chars().all(|c| c.is_ascii_digit()) {
+            let index: usize = digits.parse().expect("digits checked");
+            outputs.push((
+                entry.path().to_string_lossy().to_string(),
+                SinkOp::part_path(destination, index),
+            ));
+        }
+    }
+    outputs
+}
+
+/// Sink that fans one stream out to several destinations.
+struct FanoutSinkOp {
+    sinks: Vec<Box<dyn Operator>>,
+}
+
+impl Operator for FanoutSinkOp {
+    fn name(&self) -> &'static str {
+        "sink"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 0,
+            overhead_bytes: 0,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "sink.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        for sink in &self.sinks {
+            sink.eval_block(inputs, budget)?;
+        }
+        Ok(RowBatch { columns: vec![] })
+    }
+}
+
+/// Sink that routes each row to the first matching predicate's destination;
+/// unmatched rows go to the default sink (or are dropped).
+struct RoutedSinkOp {
+    routes: Vec<(String, Box<dyn Operator>)>,
+    default: Option<Box<dyn Operator>>,
+}
+
+impl Operator for RoutedSinkOp {
+    fn name(&self) -> &'static str {
+        "sink"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 0,
+            overhead_bytes: 0,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "sink.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        use emsqrt_core::expr::Expr;
+        use emsqrt_core::types::Column;
+
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("sink requires one input".into()))?;
+
+        let predicates: Vec<Expr> = self
+            .routes
+            .iter()
+            .map(|(pred, _)| {
+                Expr::parse(pred).map_err(|e| {
+                    OpError::Exec(format!("failed to parse route predicate '{}': {}", pred, e))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Assign each row to its first matching route (routes.len() = default).
+        let mut assignment: Vec<Vec<usize>> = vec![Vec::new(); self.routes.len() + 1];
+        for row_idx in 0..input.num_rows() {
+            let mut target = self.routes.len();
+            for (route_idx, pred) in predicates.iter().enumerate() {
+                if pred.evaluate_bool(input, row_idx).map_err(|e| {
+                    OpError::Exec(format!("route predicate failed at row {}: {}", row_idx, e))
+                })? {
+                    target = route_idx;
+                    break;
+                }
+            }
+            assignment[target].push(row_idx);
+        }
+
+        let take = |rows: &[usize]| RowBatch {
+            columns: input
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: rows.iter().map(|&i| c.values[i].clone()).collect(),
+                })
+                .collect(),
+        };
+
+        for (route_idx, (_, sink)) in self.routes.iter().enumerate() {
+            if !assignment[route_idx].is_empty() {
+                sink.eval_block(&[take(&assignment[route_idx])], budget)?;
+            }
+        }
+        if let Some(default) = &self.default {
+            if !assignment[self.routes.len()].is_empty() {
+                default.eval_block(&[take(&assignment[self.routes.len()])], budget)?;
+            }
+        }
+
+        Ok(RowBatch { columns: vec![] })
+    }
+}
+
+/// Wraps any sink to count records and fold their content into a run-level
+/// checksum, giving end-to-end verifiable output accounting.
+struct CountingSinkOp {
+    inner: Arc<dyn Operator>,
+    records: Arc<std::sync::atomic::AtomicU64>,
+    hasher: Arc<Mutex<blake3::Hasher>>,
+}
+
+impl Operator for CountingSinkOp {
+    fn name(&self) -> &'static str {
+        "sink"
+    }
+    fn memory_need(&self, rows: u64, bytes: u64) -> emsqrt_operators::plan::Footprint {
+        self.inner.memory_need(rows, bytes)
+    }
+    fn plan(&self, input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        self.inner.plan(input_schemas)
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        if let Some(input) = inputs.first() {
+            self.records.fetch_add(
+                input.num_rows() as u64,
+                std::sync::atomic::Ordering::Relaxed,
+            );
+            if input.num_rows() > 0 {
+                let encoded = serde_json::to_vec(input)
+                    .map_err(|e| OpError::Exec(format!("checksum encode: {}", e)))?;
+                self.hasher.lock().unwrap().update(&encoded);
+            }
+        }
+        self.inner.eval_block(inputs, budget)
+    }
+}
+
+/// Sink writing to the process's stdout or stderr, as CSV (header once) or
+/// JSONL depending on the configured format.
+struct StdStreamSinkOp {
+    stderr: bool,
+    format: String,
+    wrote_header: std::sync::Mutex<bool>,
+}
+
+impl Operator for StdStreamSinkOp {
+    fn name(&self) -> &'static str {
+        "sink"
+    }
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> emsqrt_operators::plan::Footprint {
+        emsqrt_operators::plan::Footprint {
+            bytes_per_row: 0,
+            overhead_bytes: 0,
+        }
+    }
+    fn plan(&self, _input_schemas: &[Schema]) -> Result<emsqrt_operators::plan::OpPlan, OpError> {
+        Err(OpError::Plan(
+            "sink.plan should not be called at exec time".into(),
+        ))
+    }
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn emsqrt_core::budget::MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("sink requires one input".into()))?;
+        if input.num_rows() == 0 {
+            return Ok(RowBatch { columns: vec![] });
+        }
+
+        let mut buffer = Vec::new();
+        if self.format == "jsonl" {
+            let mut writer = emsqrt_io::writers::jsonl::JsonlWriter::to_writer(&mut buffer, None);
+            writer
+                .write_batch(input)
+                .map_err(|e| OpError::Exec(format!("console jsonl write: {}", e)))?;
+        } else {
+            let mut wrote_header = self.wrote_header.lock().unwrap();
+            let mut writer = if *wrote_header {
+                CsvWriter::to_writer_skip_header(&mut buffer)
+            } else {
+                *wrote_header = true;
+                CsvWriter::to_writer(&mut buffer)
+            };
+            writer
+                .write_batch(input)
+                .map_err(|e| OpError::Exec(format!("console csv write: {}", e)))?;
+        }
+
+        use std::io::Write;
+        let result = if self.stderr {
+            std::io::stderr().lock().write_all(&buffer)
+        } else {
+            std::io::stdout().lock().write_all(&buffer)
+        };
+        result.map_err(|e| OpError::Exec(format!("console write: {}", e)))?;
+
+        Ok(RowBatch { columns: vec![] })
+    }
+}
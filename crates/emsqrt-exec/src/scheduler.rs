@@ -40,3 +40,18 @@ impl<T> BoundedQueue<T> {
         self.q.is_empty()
     }
 }
+
+/// Effective block-level parallelism for an operator, combining the engine's
+/// configured task limit with the operator's own annotations: exclusive
+/// (pipeline-breaker) operators always run alone, and an operator-declared
+/// concurrency cap further limits the engine default.
+pub fn effective_parallelism(max_parallel_tasks: usize, plan: &emsqrt_operators::OpPlan) -> usize {
+    if plan.exclusive {
+        return 1;
+    }
+    let engine_limit = max_parallel_tasks.max(1);
+    match plan.max_concurrency {
+        Some(op_limit) => engine_limit.min(op_limit.max(1)),
+        None => engine_limit,
+    }
+}
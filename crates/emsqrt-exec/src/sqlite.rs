@@ -0,0 +1,250 @@
+//! SQLite source and sink connector.
+//!
+//! Drives the `sqlite3` CLI (JSON output mode) instead of linking a C
+//! library, in keeping with the engine's no-heavy-deps policy. URIs take
+//! the form `sqlite://<db file>/<table>`; the source side goes through the
+//! generic `SqlDriver` machinery, the sink side creates the table on first
+//! write and appends batches as transactions of INSERTs.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::{RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::database::{DatabaseConnector, SqlDriver};
+use crate::runtime::{BatchSink, RowBatchProvider};
+
+/// SQL driver backed by the `sqlite3` CLI.
+pub struct SqliteDriver {
+    /// Binary name; overridable for tests/sandboxes.
+    sqlite_cmd: String,
+}
+
+impl Default for SqliteDriver {
+    fn default() -> Self {
+        Self {
+            sqlite_cmd: "sqlite3".to_string(),
+        }
+    }
+}
+
+impl SqlDriver for SqliteDriver {
+    fn scheme(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn query(
+        &self,
+        dsn: &str,
+        sql: &str,
+    ) -> Result<(Vec<String>, Vec<Vec<Option<String>>>), String> {
+        let output = Command::new(&self.sqlite_cmd)
+            .arg("-json")
+            .arg("-readonly")
+            .arg(dsn)
+            .arg(sql)
+            .output()
+            .map_err(|e| format!("spawn {}: {}", self.sqlite_cmd, e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "sqlite3 query failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        if text.trim().is_empty() {
+            return Ok((Vec::new(), Vec::new()));
+        }
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_str(text.trim()).map_err(|e| format!("sqlite3 json: {}", e))?;
+
+        let names: Vec<String> = rows
+            .first()
+            .map(|row| row.keys().cloned().collect())
+            .unwrap_or_default();
+        let data = rows
+            .into_iter()
+            .map(|row| {
+                names
+                    .iter()
+                    .map(|name| match row.get(name) {
+                        None | Some(serde_json::Value::Null) => None,
+                        Some(serde_json::Value::String(s)) => Some(s.clone()),
+                        Some(other) => Some(other.to_string()),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok((names, data))
+    }
+}
+
+/// Full SQLite connector: reads via the generic database source, writes via
+/// batched INSERT transactions.
+pub struct SqliteConnector {
+    source: DatabaseConnector,
+    sqlite_cmd: String,
+}
+
+impl Default for SqliteConnector {
+    fn default() -> Self {
+        Self::with_command("sqlite3")
+    }
+}
+
+impl SqliteConnector {
+    /// Use a different sqlite3 binary (tests/sandboxes).
+    pub fn with_command(cmd: impl Into<String>) -> Self {
+        let cmd = cmd.into();
+        Self {
+            source: DatabaseConnector::new(SqliteDriver {
+                sqlite_cmd: cmd.clone(),
+            }),
+            sqlite_cmd: cmd,
+        }
+    }
+}
+
+impl Connector for SqliteConnector {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: true,
+            write: true,
+            streaming: false,
+            random_access: true,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("sqlite://")
+    }
+
+    fn open_source(
+        &self,
+        uri: &str,
+        schema: &Schema,
+    ) -> Result<Box<dyn RowBatchProvider>, String> {
+        self.source.open_source(uri, schema)
+    }
+
+    fn open_sink(&self, uri: &str, _format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let rest = uri
+            .strip_prefix("sqlite://")
+            .ok_or_else(|| format!("not a sqlite URI: '{}'", uri))?;
+        let (db, table) = rest
+            .rsplit_once('/')
+            .filter(|(db, table)| !db.is_empty() && !table.is_empty())
+            .ok_or_else(|| format!("expected sqlite://<db file>/<table>, got '{}'", uri))?;
+        Ok(Box::new(SqliteSink {
+            sqlite_cmd: self.sqlite_cmd.clone(),
+            db: db.to_string(),
+            table: table.to_string(),
+            table_created: false,
+        }))
+    }
+}
+
+struct SqliteSink {
+    sqlite_cmd: String,
+    db: String,
+    table: String,
+    table_created: bool,
+}
+
+pub(crate) fn sql_literal(value: &Scalar) -> String {
+    match value {
+        Scalar::Null => "NULL".to_string(),
+        Scalar::Bool(b) => (*b as u8).to_string(),
+        Scalar::I32(v) => v.to_string(),
+        Scalar::I64(v) => v.to_string(),
+        Scalar::F32(v) => v.to_string(),
+        Scalar::F64(v) => v.to_string(),
+        Scalar::Date64(ms) => format!("'{}'", emsqrt_core::time::format_datetime(*ms)),
+        Scalar::Str(s) => format!("'{}'", s.replace('\'', "''")),
+        Scalar::Bin(b) => {
+            let hex: String = b.iter().map(|byte| format!("{:02X}", byte)).collect();
+            format!("X'{}'", hex)
+        }
+    }
+}
+
+pub(crate) fn column_type(values: &[Scalar]) -> &'static str {
+    for v in values {
+        match v {
+            Scalar::I32(_) | Scalar::I64(_) | Scalar::Bool(_) => return "INTEGER",
+            Scalar::F32(_) | Scalar::F64(_) => return "REAL",
+            Scalar::Bin(_) => return "BLOB",
+            Scalar::Str(_) | Scalar::Date64(_) => return "TEXT",
+            Scalar::Null => continue,
+        }
+    }
+    "TEXT"
+}
+
+impl BatchSink for SqliteSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        let mut script = String::from("BEGIN;\n");
+        if !self.table_created {
+            let columns: Vec<String> = batch
+                .columns
+                .iter()
+                .map(|c| format!("\"{}\" {}", c.name, column_type(&c.values)))
+                .collect();
+            script.push_str(&format!(
+                "CREATE TABLE IF NOT EXISTS \"{}\" ({});\n",
+                self.table,
+                columns.join(", ")
+            ));
+            self.table_created = true;
+        }
+
+        for row in 0..batch.num_rows() {
+            let values: Vec<String> = batch
+                .columns
+                .iter()
+                .map(|c| sql_literal(&c.values[row]))
+                .collect();
+            script.push_str(&format!(
+                "INSERT INTO \"{}\" VALUES ({});\n",
+                self.table,
+                values.join(", ")
+            ));
+        }
+        script.push_str("COMMIT;\n");
+
+        let mut child = Command::new(&self.sqlite_cmd)
+            .arg(&self.db)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("spawn {}: {}", self.sqlite_cmd, e))?;
+        child
+            .stdin
+            .take()
+            .expect("stdin piped")
+            .write_all(script.as_bytes())
+            .map_err(|e| format!("sqlite3 stdin: {}", e))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("sqlite3 wait: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "sqlite3 insert failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(())
+    }
+}
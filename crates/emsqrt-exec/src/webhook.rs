@@ -0,0 +1,141 @@
+//! Webhook sink: POST rows to an HTTP endpoint with batching and retries.
+//!
+//! Destinations use `webhook://host[:port]/path` (plain HTTP; put a local
+//! proxy in front for TLS). Rows are grouped into JSON-array POSTs of at
+//! most `MAX_ROWS_PER_POST`, and each POST retries transient failures
+//! (connection errors and 5xx) with exponential backoff. 4xx responses are
+//! permanent and fail immediately.
+
+use std::time::Duration;
+
+use emsqrt_core::types::{RowBatch, Scalar};
+
+use crate::connectors::{Capabilities, Connector};
+use crate::http;
+use crate::runtime::BatchSink;
+
+/// Rows per POST request.
+const MAX_ROWS_PER_POST: usize = 500;
+/// Retry attempts per POST after the first try.
+const MAX_RETRIES: usize = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+pub struct WebhookConnector;
+
+impl Connector for WebhookConnector {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            read: false,
+            write: true,
+            streaming: true,
+            random_access: false,
+        }
+    }
+
+    fn matches(&self, uri: &str) -> bool {
+        uri.starts_with("webhook://")
+    }
+
+    fn open_sink(&self, uri: &str, _format: &str) -> Result<Box<dyn BatchSink>, String> {
+        let rest = uri
+            .strip_prefix("webhook://")
+            .ok_or_else(|| format!("not a webhook URI: '{}'", uri))?;
+        let (host, port, path) = http::parse_target(rest, 80)?;
+        Ok(Box::new(WebhookSink { host, port, path }))
+    }
+}
+
+struct WebhookSink {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+fn scalar_json(value: &Scalar) -> serde_json::Value {
+    match value {
+        Scalar::Null => serde_json::Value::Null,
+        Scalar::Bool(b) => serde_json::Value::Bool(*b),
+        Scalar::I32(v) => serde_json::Value::from(*v),
+        Scalar::I64(v) => serde_json::Value::from(*v),
+        Scalar::F32(v) => serde_json::Value::from(*v as f64),
+        Scalar::F64(v) => serde_json::Value::from(*v),
+        Scalar::Str(s) => serde_json::Value::String(s.clone()),
+        Scalar::Date64(ms) => {
+            serde_json::Value::String(emsqrt_core::time::format_datetime(*ms))
+        }
+        Scalar::Bin(b) => serde_json::Value::String(format!("[binary {} bytes]", b.len())),
+    }
+}
+
+impl WebhookSink {
+    /// One POST with retry/backoff on transient failures.
+    fn post_with_retry(&self, body: &[u8]) -> Result<(), String> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut last_error = String::new();
+
+        for attempt in 0..=MAX_RETRIES {
+            match http::request(
+                &self.host,
+                self.port,
+                "POST",
+                &self.path,
+                "application/json",
+                body,
+            ) {
+                Ok((status, _)) if status < 300 => return Ok(()),
+                Ok((status, response)) if status < 500 => {
+                    // Client errors are permanent; retrying won't help.
+                    return Err(format!(
+                        "webhook rejected batch with {}: {}",
+                        status,
+                        String::from_utf8_lossy(&response)
+                    ));
+                }
+                Ok((status, _)) => {
+                    last_error = format!("server error {}", status);
+                }
+                Err(e) => {
+                    last_error = e;
+                }
+            }
+            if attempt < MAX_RETRIES {
+                std::thread::sleep(backoff);
+                backoff *= 2;
+            }
+        }
+        Err(format!(
+            "webhook POST failed after {} attempts: {}",
+            MAX_RETRIES + 1,
+            last_error
+        ))
+    }
+}
+
+impl BatchSink for WebhookSink {
+    fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+        let num_rows = batch.num_rows();
+        if num_rows == 0 {
+            return Ok(());
+        }
+
+        for start in (0..num_rows).step_by(MAX_ROWS_PER_POST) {
+            let end = (start + MAX_ROWS_PER_POST).min(num_rows);
+            let rows: Vec<serde_json::Value> = (start..end)
+                .map(|row| {
+                    let mut object = serde_json::Map::new();
+                    for column in &batch.columns {
+                        object.insert(column.name.clone(), scalar_json(&column.values[row]));
+                    }
+                    serde_json::Value::Object(object)
+                })
+                .collect();
+            let body = serde_json::Value::Array(rows).to_string();
+            self.post_with_retry(body.as_bytes())?;
+        }
+        Ok(())
+    }
+}
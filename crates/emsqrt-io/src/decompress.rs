@@ -0,0 +1,77 @@
+//! Transparent decompression for input files, selected by extension.
+//!
+//! `.gz` works out of the box (pure-Rust flate2 backend); `.zst` and `.bz2`
+//! need the `zstd-input` / `bz2-input` features and fail with a clear
+//! message otherwise.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+use crate::error::{Error, Result};
+
+/// Compression format implied by a file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputCompression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+impl InputCompression {
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".gz") {
+            InputCompression::Gzip
+        } else if path.ends_with(".zst") || path.ends_with(".zstd") {
+            InputCompression::Zstd
+        } else if path.ends_with(".bz2") {
+            InputCompression::Bzip2
+        } else {
+            InputCompression::None
+        }
+    }
+}
+
+/// Strip a recognized compression extension so format detection sees the
+/// inner file type ("data.csv.gz" -> "data.csv").
+pub fn strip_compression_extension(path: &str) -> &str {
+    for ext in [".gz", ".zst", ".zstd", ".bz2"] {
+        if let Some(stripped) = path.strip_suffix(ext) {
+            return stripped;
+        }
+    }
+    path
+}
+
+/// Open a file for reading, transparently decompressing by extension.
+pub fn open_maybe_compressed(path: &str) -> Result<Box<dyn Read + Send>> {
+    let file = File::open(path)?;
+    match InputCompression::from_path(path) {
+        InputCompression::None => Ok(Box::new(BufReader::new(file))),
+        InputCompression::Gzip => Ok(Box::new(flate2::read::GzDecoder::new(BufReader::new(file)))),
+        InputCompression::Zstd => {
+            #[cfg(feature = "zstd-input")]
+            {
+                let decoder = zstd::stream::read::Decoder::new(file)
+                    .map_err(|e| Error::Other(format!("zstd decoder: {e}")))?;
+                Ok(Box::new(decoder))
+            }
+            #[cfg(not(feature = "zstd-input"))]
+            Err(Error::Config(format!(
+                "'{path}' is zstd-compressed but EM-\u{221a} was built without the \
+`zstd-input` feature; rebuild with `--features emsqrt-io/zstd-input`"
+            )))
+        }
+        InputCompression::Bzip2 => {
+            #[cfg(feature = "bz2-input")]
+            {
+                Ok(Box::new(bzip2::read::BzDecoder::new(BufReader::new(file))))
+            }
+            #[cfg(not(feature = "bz2-input"))]
+            Err(Error::Config(format!(
+                "'{path}' is bzip2-compressed but EM-\u{221a} was built without the \
+`bz2-input` feature; rebuild with `--features emsqrt-io/bz2-input`"
+            )))
+        }
+    }
+}
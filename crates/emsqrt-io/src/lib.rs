@@ -9,6 +9,7 @@
 //! Parquet modules are feature-gated and stubbed unless `--features parquet`.
 
 pub mod buf;
+pub mod decompress;
 pub mod readers;
 pub mod storage;
 pub mod writers;
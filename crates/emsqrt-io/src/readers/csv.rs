@@ -4,7 +4,6 @@
 //! - No type inference (everything is Utf8 Scalar by default).
 //! - Suitable as a starter; replace with Arrow-based scans later.
 
-use std::fs::File;
 use std::io::Read;
 
 use csv as csv_crate;
@@ -13,31 +12,159 @@ use emsqrt_core::types::{Column, RowBatch, Scalar};
 
 use crate::error::{Error, Result};
 
+/// CSV dialect options shared by the io reader and the runtime source.
+#[derive(Debug, Clone)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub comment: Option<u8>,
+    pub has_headers: bool,
+    /// Escape character inside quoted fields (None = doubled quotes).
+    pub escape: Option<u8>,
+    /// Values equal to this token read as null ("NA", "\\N", ...).
+    pub null_token: Option<String>,
+    /// Lines discarded before the header row (report preambles etc.).
+    pub skip_rows: usize,
+    /// Explicit column names, overriding (or standing in for) the file's
+    /// header row.
+    pub headers: Option<Vec<String>>,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            comment: None,
+            has_headers: true,
+            escape: None,
+            null_token: None,
+            skip_rows: 0,
+            headers: None,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Read dialect options from schema metadata keys ("csv.delimiter",
+    /// "csv.quote", "csv.comment", "csv.has_headers", "csv.escape",
+    /// "csv.null_token", "csv.skip_rows", "csv.headers"), as written by the
+    /// YAML scan step.
+    pub fn from_schema_metadata(schema: &Schema) -> Self {
+        let mut dialect = Self::default();
+        let byte = |key: &str| {
+            schema
+                .metadata
+                .get(key)
+                .and_then(|v| v.bytes().next())
+        };
+        if let Some(d) = byte("csv.delimiter") {
+            dialect.delimiter = d;
+        }
+        if let Some(q) = byte("csv.quote") {
+            dialect.quote = q;
+        }
+        dialect.comment = byte("csv.comment");
+        if let Some(h) = schema.metadata.get("csv.has_headers") {
+            dialect.has_headers = h == "true";
+        }
+        dialect.escape = byte("csv.escape");
+        dialect.null_token = schema.metadata.get("csv.null_token").cloned();
+        if let Some(n) = schema.metadata.get("csv.skip_rows") {
+            dialect.skip_rows = n.parse().unwrap_or(0);
+        }
+        if let Some(names) = schema.metadata.get("csv.headers") {
+            dialect.headers = Some(
+                names
+                    .split(',')
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty())
+                    .collect(),
+            );
+        }
+        dialect
+    }
+
+    /// Apply the dialect to a csv reader builder.
+    pub fn configure(&self, builder: &mut csv_crate::ReaderBuilder) {
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .comment(self.comment)
+            .has_headers(self.has_headers)
+            .escape(self.escape)
+            .flexible(true);
+    }
+
+    /// Consume `skip_rows` newline-terminated lines from the raw stream
+    /// before the csv reader sees it.
+    pub fn skip_leading_rows(&self, reader: &mut impl Read) -> std::io::Result<()> {
+        let mut remaining = self.skip_rows;
+        let mut byte = [0u8; 1];
+        while remaining > 0 {
+            match reader.read(&mut byte)? {
+                0 => break, // shorter file than skip_rows; nothing to read
+                _ if byte[0] == b'\n' => remaining -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `raw` should read as null under the configured token.
+    pub fn is_null_token(&self, raw: &str) -> bool {
+        self.null_token.as_deref() == Some(raw)
+    }
+}
+
 pub struct CsvReader<R: Read> {
     rdr: csv_crate::Reader<R>,
     schema: Schema,
+    /// Values equal to this token read as null.
+    null_token: Option<String>,
+    /// Parse values per the declared schema types instead of Utf8-only.
+    typed: bool,
 }
 
-impl CsvReader<File> {
+impl CsvReader<Box<dyn Read + Send>> {
+    /// Open a CSV file, transparently decompressing .gz/.zst/.bz2 inputs.
     pub fn from_path(path: &str, has_headers: bool) -> Result<Self> {
-        let file = File::open(path)?;
-        Self::from_reader(file, has_headers)
+        let reader = crate::decompress::open_maybe_compressed(path)?;
+        Self::from_reader(reader, has_headers)
     }
 }
 
 impl<R: Read> CsvReader<R> {
     pub fn from_reader(reader: R, has_headers: bool) -> Result<Self> {
-        let mut rdr = csv_crate::ReaderBuilder::new()
-            .has_headers(has_headers)
-            .flexible(true)
-            .from_reader(reader);
+        Self::from_reader_with_dialect(
+            reader,
+            CsvDialect {
+                has_headers,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a CSV reader with explicit dialect options.
+    pub fn from_reader_with_dialect(mut reader: R, dialect: CsvDialect) -> Result<Self> {
+        dialect.skip_leading_rows(&mut reader)?;
+        let mut builder = csv_crate::ReaderBuilder::new();
+        dialect.configure(&mut builder);
+        let mut rdr = builder.from_reader(reader);
 
-        let headers: Vec<String> = if has_headers {
+        // Explicit names override the file's header row (which the csv
+        // reader still consumes when has_headers is set).
+        let headers: Vec<String> = if let Some(names) = &dialect.headers {
+            names.clone()
+        } else if dialect.has_headers {
             rdr.headers()?.iter().map(|s| s.to_string()).collect()
         } else {
-            // For headerless CSV, use from_reader_with_schema instead
+            // For headerless CSV, use from_reader_with_schema or explicit
+            // dialect headers instead.
             return Err(Error::Schema(
-                "CSV without headers is not yet supported (use from_reader_with_schema)".into(),
+                "CSV without headers needs explicit column names (use \
+                 from_reader_with_schema or the dialect's 'headers')"
+                    .into(),
             ));
         };
 
@@ -48,7 +175,20 @@ impl<R: Read> CsvReader<R> {
                 .collect(),
         );
 
-        Ok(Self { rdr, schema })
+        Ok(Self {
+            rdr,
+            schema,
+            null_token: dialect.null_token,
+            typed: false,
+        })
+    }
+
+    /// Parse values into the schema's declared types (unparsable values
+    /// become null) instead of returning everything as Utf8.
+    pub fn with_typed_parsing(mut self, schema: Schema) -> Self {
+        self.schema = schema;
+        self.typed = true;
+        self
     }
 
     /// Create a CSV reader with an explicit schema (for headerless CSV).
@@ -58,7 +198,12 @@ impl<R: Read> CsvReader<R> {
             .flexible(true)
             .from_reader(reader);
 
-        Ok(Self { rdr, schema })
+        Ok(Self {
+            rdr,
+            schema,
+            null_token: None,
+            typed: false,
+        })
     }
 
     pub fn schema(&self) -> &Schema {
@@ -83,21 +228,26 @@ impl<R: Read> CsvReader<R> {
             .collect();
 
         let mut read_rows = 0usize;
+        let parse = |field_idx: usize, raw: &str| -> Scalar {
+            if self.null_token.as_deref() == Some(raw) {
+                return Scalar::Null;
+            }
+            if !self.typed {
+                return Scalar::Str(raw.to_string());
+            }
+            typed_scalar(&self.schema.fields[field_idx].data_type, raw)
+        };
         for rec in self.rdr.records() {
             let rec = rec?;
             if rec.len() != ncols {
                 // Flexible CSV may have variable length rows; pad with Nulls.
                 for (i, col) in cols.iter_mut().enumerate() {
-                    let v = rec
-                        .get(i)
-                        .map(|s| Scalar::Str(s.to_string()))
-                        .unwrap_or(Scalar::Null);
+                    let v = rec.get(i).map(|s| parse(i, s)).unwrap_or(Scalar::Null);
                     col.values.push(v);
                 }
             } else {
                 for (i, col) in cols.iter_mut().enumerate() {
-                    col.values
-                        .push(Scalar::Str(rec.get(i).unwrap().to_string()));
+                    col.values.push(parse(i, rec.get(i).unwrap()));
                 }
             }
             read_rows += 1;
@@ -113,3 +263,22 @@ impl<R: Read> CsvReader<R> {
         Ok(Some(RowBatch { columns: cols }))
     }
 }
+
+/// Parse one raw CSV value into the declared type; empty or unparsable
+/// values become null (strings pass through).
+fn typed_scalar(data_type: &DataType, raw: &str) -> Scalar {
+    if raw.is_empty() {
+        return Scalar::Null;
+    }
+    match data_type {
+        DataType::Boolean => raw.parse::<bool>().map(Scalar::Bool).unwrap_or(Scalar::Null),
+        DataType::Int32 => raw.parse::<i32>().map(Scalar::I32).unwrap_or(Scalar::Null),
+        DataType::Int64 => raw.parse::<i64>().map(Scalar::I64).unwrap_or(Scalar::Null),
+        DataType::Float32 => raw.parse::<f32>().map(Scalar::F32).unwrap_or(Scalar::Null),
+        DataType::Float64 => raw.parse::<f64>().map(Scalar::F64).unwrap_or(Scalar::Null),
+        DataType::Date64 => emsqrt_core::time::parse_datetime(raw)
+            .map(Scalar::Date64)
+            .unwrap_or(Scalar::Null),
+        _ => Scalar::Str(raw.to_string()),
+    }
+}
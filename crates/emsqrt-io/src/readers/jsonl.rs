@@ -4,7 +4,6 @@
 //! - Builds the column set from the union of keys seen so far.
 //! - All scalars are mapped to a small set of types; complex values become strings.
 
-use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
 
 use emsqrt_core::schema::{DataType, Field, Schema};
@@ -17,12 +16,17 @@ pub struct JsonlReader<R: Read> {
     reader: BufReader<R>,
     // We grow the schema as we see new keys (simple prototype behavior).
     schema: Schema,
+    /// Flatten nested objects into dotted column names ("user.name").
+    flatten: bool,
+    /// Keep only these dotted paths (implies flattening for nested paths).
+    projection: Option<Vec<String>>,
 }
 
-impl JsonlReader<File> {
+impl JsonlReader<Box<dyn Read + Send>> {
+    /// Open a JSONL file, transparently decompressing .gz/.zst/.bz2 inputs.
     pub fn from_path(path: &str) -> Result<Self> {
-        let f = File::open(path)?;
-        Self::from_reader(f)
+        let reader = crate::decompress::open_maybe_compressed(path)?;
+        Self::from_reader(reader)
     }
 }
 
@@ -31,9 +35,26 @@ impl<R: Read> JsonlReader<R> {
         Ok(Self {
             reader: BufReader::new(reader),
             schema: Schema::new(vec![]),
+            flatten: false,
+            projection: None,
         })
     }
 
+    /// Flatten nested objects into dotted column names ("user.name");
+    /// arrays still render as JSON strings.
+    pub fn with_flattening(mut self) -> Self {
+        self.flatten = true;
+        self
+    }
+
+    /// Keep only the given dotted JSON paths (e.g. "user.address.city").
+    /// Enables flattening so nested paths resolve.
+    pub fn with_projection(mut self, paths: Vec<String>) -> Self {
+        self.flatten = true;
+        self.projection = Some(paths);
+        self
+    }
+
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
@@ -59,18 +80,38 @@ impl<R: Read> JsonlReader<R> {
             return Ok(None);
         }
 
-        // Discover union of keys
+        // Discover union of keys (flattened when enabled)
         use std::collections::BTreeSet;
         let mut keys = BTreeSet::<String>::new();
-        let mut parsed = Vec::with_capacity(lines.len());
+        let mut parsed: Vec<serde_json::Map<String, Value>> = Vec::with_capacity(lines.len());
         for s in lines {
             let v: Value = serde_json::from_str(&s)?;
-            if let Value::Object(map) = &v {
-                for k in map.keys() {
+            let map = match v {
+                Value::Object(map) if self.flatten => flatten_object(map),
+                Value::Object(map) => map,
+                _ => serde_json::Map::new(), // non-object line -> all nulls
+            };
+            for k in map.keys() {
+                if self
+                    .projection
+                    .as_ref()
+                    .is_none_or(|paths| paths.iter().any(|p| p == k))
+                {
                     keys.insert(k.clone());
                 }
             }
-            parsed.push(v);
+            parsed.push(map);
+        }
+
+        // Projection keeps the requested path order in the schema.
+        if let Some(paths) = &self.projection {
+            for path in paths {
+                if keys.contains(path) && self.schema.index_of(path).is_none() {
+                    self.schema
+                        .fields
+                        .push(Field::new(path.clone(), DataType::Utf8, true));
+                }
+            }
         }
 
         // Ensure schema covers all keys
@@ -93,21 +134,10 @@ impl<R: Read> JsonlReader<R> {
             })
             .collect();
 
-        for v in parsed {
-            match v {
-                Value::Object(map) => {
-                    for (i, f) in self.schema.fields.iter().enumerate() {
-                        let s = map.get(&f.name).cloned().unwrap_or(Value::Null);
-                        cols[i].values.push(to_scalar(s));
-                    }
-                }
-                _ => {
-                    // Non-object line → place into a single "value" column if present,
-                    // else discard with Nulls (prototype behavior).
-                    for col in cols.iter_mut() {
-                        col.values.push(Scalar::Null);
-                    }
-                }
+        for map in parsed {
+            for (i, f) in self.schema.fields.iter().enumerate() {
+                let s = map.get(&f.name).cloned().unwrap_or(Value::Null);
+                cols[i].values.push(to_scalar(s));
             }
         }
 
@@ -134,3 +164,29 @@ fn to_scalar(v: Value) -> Scalar {
         other => Str(other.to_string()),
     }
 }
+
+/// Flatten nested objects into dotted keys: {"user": {"name": "x"}} becomes
+/// {"user.name": "x"}. Arrays and scalars are left in place at their path.
+fn flatten_object(map: serde_json::Map<String, Value>) -> serde_json::Map<String, Value> {
+    fn walk(prefix: &str, value: Value, out: &mut serde_json::Map<String, Value>) {
+        match value {
+            Value::Object(inner) => {
+                for (k, v) in inner {
+                    let key = if prefix.is_empty() {
+                        k
+                    } else {
+                        format!("{}.{}", prefix, k)
+                    };
+                    walk(&key, v, out);
+                }
+            }
+            other => {
+                out.insert(prefix.to_string(), other);
+            }
+        }
+    }
+
+    let mut out = serde_json::Map::new();
+    walk("", Value::Object(map), &mut out);
+    out
+}
@@ -16,8 +16,6 @@ use parquet::arrow::ProjectionMask;
 #[cfg(feature = "parquet")]
 use std::fs::File;
 #[cfg(feature = "parquet")]
-use std::io::{BufReader, Read, Seek};
-#[cfg(feature = "parquet")]
 use std::sync::Arc;
 
 use crate::arrow_convert::record_batch_to_row_batch;
@@ -45,10 +43,10 @@ impl ParquetReader {
         projection: Option<Vec<String>>,
         batch_size: usize,
     ) -> Result<Self> {
-        let file = File::open(path).map_err(|e| Error::Io(e))?;
+        let file = File::open(path).map_err(Error::Io)?;
 
         let builder =
-            ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| Error::Parquet(e))?;
+            ParquetRecordBatchReaderBuilder::try_new(file).map_err(Error::Parquet)?;
 
         // Get schema and metadata before building (needed for projection)
         let schema_ref = builder.schema().clone();
@@ -0,0 +1,150 @@
+//! Local disk cache tier layered over another storage backend.
+//!
+//! Spill traffic is write-then-read: segments written to cloud storage are
+//! mirrored into a bounded local cache directory, so re-reads are served
+//! from disk instead of paying a network round trip. Insertion-order
+//! eviction keeps the cache under its byte budget.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use emsqrt_mem::error::{Error as MemError, Result as MemResult};
+use emsqrt_mem::Storage;
+
+/// Default cache budget when none is configured.
+const DEFAULT_CACHE_BYTES: u64 = 1024 * 1024 * 1024; // 1 GiB
+
+struct CacheState {
+    /// Remote path -> (local file, bytes)
+    entries: HashMap<String, (PathBuf, u64)>,
+    /// Insertion order for eviction.
+    order: VecDeque<String>,
+    used_bytes: u64,
+}
+
+pub struct CachedStorage {
+    inner: Box<dyn Storage>,
+    cache_dir: PathBuf,
+    max_bytes: u64,
+    state: Mutex<CacheState>,
+}
+
+impl CachedStorage {
+    pub fn new(
+        inner: Box<dyn Storage>,
+        cache_dir: impl Into<PathBuf>,
+        max_bytes: Option<u64>,
+    ) -> MemResult<Self> {
+        let cache_dir = cache_dir.into();
+        std::fs::create_dir_all(&cache_dir)
+            .map_err(|e| MemError::Storage(format!("create cache dir: {e}")))?;
+        Ok(Self {
+            inner,
+            cache_dir,
+            max_bytes: max_bytes.unwrap_or(DEFAULT_CACHE_BYTES),
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                used_bytes: 0,
+            }),
+        })
+    }
+
+    fn local_path(&self, path: &str) -> PathBuf {
+        // Hash the remote path so arbitrary URIs map to flat filenames.
+        self.cache_dir
+            .join(format!("{}.cache", blake3::hash(path.as_bytes()).to_hex()))
+    }
+
+    /// Mirror freshly written bytes into the cache, evicting old entries
+    /// until the budget holds. Failures only disable caching for this entry.
+    fn admit(&self, path: &str, bytes: &[u8]) {
+        if bytes.len() as u64 > self.max_bytes {
+            return;
+        }
+        let local = self.local_path(path);
+        if std::fs::write(&local, bytes).is_err() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if let Some((old_path, old_len)) = state.entries.remove(path) {
+            state.used_bytes -= old_len;
+            state.order.retain(|p| p != path);
+            let _ = old_path; // same file, just rewritten
+        }
+        state.used_bytes += bytes.len() as u64;
+        state.entries.insert(path.to_string(), (local, bytes.len() as u64));
+        state.order.push_back(path.to_string());
+
+        while state.used_bytes > self.max_bytes {
+            let Some(victim) = state.order.pop_front() else {
+                break;
+            };
+            if let Some((file, len)) = state.entries.remove(&victim) {
+                state.used_bytes -= len;
+                let _ = std::fs::remove_file(file);
+            }
+        }
+    }
+
+    fn evict(&self, path: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some((file, len)) = state.entries.remove(path) {
+            state.used_bytes -= len;
+            state.order.retain(|p| p != path);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    /// Cached bytes currently on disk (for tests/metrics).
+    pub fn cached_bytes(&self) -> u64 {
+        self.state.lock().unwrap().used_bytes
+    }
+}
+
+impl Storage for CachedStorage {
+    fn write(&self, path: &str, bytes: &[u8]) -> MemResult<()> {
+        // Write-through: the backing store is the source of truth.
+        self.inner.write(path, bytes)?;
+        self.admit(path, bytes);
+        Ok(())
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
+        let cached = {
+            let state = self.state.lock().unwrap();
+            state.entries.get(path).map(|(file, _)| file.clone())
+        };
+        if let Some(file) = cached {
+            // Serve locally; fall back to the backing store on any I/O error.
+            if let Ok(mut f) = std::fs::File::open(&file) {
+                let mut buf = vec![0u8; len];
+                if f.seek(SeekFrom::Start(offset)).is_ok() && f.read_exact(&mut buf).is_ok() {
+                    return Ok(buf);
+                }
+            }
+            self.evict(path);
+        }
+        self.inner.read_range(path, offset, len)
+    }
+
+    fn delete(&self, path: &str) -> MemResult<()> {
+        self.evict(path);
+        self.inner.delete(path)
+    }
+
+    fn list(&self, prefix: &str) -> MemResult<Vec<String>> {
+        self.inner.list(prefix)
+    }
+
+    fn size(&self, path: &str) -> MemResult<u64> {
+        self.inner.size(path)
+    }
+
+    fn etag(&self, path: &str) -> MemResult<Option<String>> {
+        self.inner.etag(path)
+    }
+}
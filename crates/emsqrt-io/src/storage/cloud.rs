@@ -8,9 +8,8 @@ use emsqrt_core::config::StorageConfig;
 use emsqrt_mem::error::{Error as MemError, Result as MemResult};
 use emsqrt_mem::Storage;
 use futures::StreamExt;
-use object_store::client::backoff::BackoffConfig;
 use object_store::path::Path as ObjectPath;
-use object_store::{Error as ObjectStoreError, ObjectStore};
+use object_store::{BackoffConfig, Error as ObjectStoreError, ObjectStore};
 use tokio::runtime::Runtime;
 use url::Url;
 
@@ -88,6 +87,7 @@ impl CloudIdentity {
         })
     }
 
+    #[cfg(feature = "gcs")]
     fn new_gcs(uri: &str) -> Result<Self, CloudStorageBuilderError> {
         let parsed = Url::parse(uri).map_err(|source| CloudStorageBuilderError::InvalidUri {
             uri: uri.to_string(),
@@ -113,6 +113,7 @@ impl CloudIdentity {
         })
     }
 
+    #[cfg(feature = "azure")]
     fn new_azure(uri: &str) -> Result<Self, CloudStorageBuilderError> {
         let parsed = Url::parse(uri).map_err(|source| CloudStorageBuilderError::InvalidUri {
             uri: uri.to_string(),
@@ -270,22 +271,118 @@ impl CloudStorage {
     }
 }
 
-fn is_retryable(err: &ObjectStoreError) -> bool {
-    match err {
-        ObjectStoreError::NotFound { .. } => false,
-        ObjectStoreError::AlreadyExists { .. } => false,
-        _ => true,
+impl CloudStorage {
+    /// Fetch `[offset, offset + len)` as up to `MAX_CONCURRENT_RANGES`
+    /// concurrent `PARALLEL_READ_CHUNK`-sized range GETs per wave, then
+    /// reassemble in order. Cuts cold-read latency on large segments.
+    fn read_range_parallel(
+        &self,
+        obj_path: &ObjectPath,
+        offset: u64,
+        len: usize,
+    ) -> MemResult<Vec<u8>> {
+        let ranges: Vec<std::ops::Range<usize>> = (0..len)
+            .step_by(PARALLEL_READ_CHUNK)
+            .map(|chunk_start| {
+                let start = offset as usize + chunk_start;
+                start..(start + PARALLEL_READ_CHUNK.min(len - chunk_start))
+            })
+            .collect();
+
+        let store = Arc::clone(&self.store);
+        let mut out = Vec::with_capacity(len);
+        for wave in ranges.chunks(MAX_CONCURRENT_RANGES) {
+            let futures_wave: Vec<_> = wave
+                .iter()
+                .map(|range| {
+                    let store = Arc::clone(&store);
+                    let obj_path = obj_path.clone();
+                    let range = range.clone();
+                    async move { store.get_range(&obj_path, range).await }
+                })
+                .collect();
+
+            let chunks = self
+                .runtime
+                .block_on(futures::future::try_join_all(futures_wave))
+                .map_err(|err| MemError::Storage(format!("{err}")))?;
+            for chunk in chunks {
+                out.extend_from_slice(&chunk);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Stream `bytes` to the object store as a multipart upload in
+    /// `MULTIPART_CHUNK`-sized parts, aborting the upload on failure so no
+    /// orphaned parts accrue charges.
+    fn write_multipart(&self, obj_path: &ObjectPath, bytes: &[u8]) -> MemResult<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let store = Arc::clone(&self.store);
+        self.runtime
+            .block_on(async move {
+                let (multipart_id, mut writer) = store.put_multipart(obj_path).await?;
+
+                for chunk in bytes.chunks(MULTIPART_CHUNK) {
+                    if let Err(e) = writer.write_all(chunk).await {
+                        let _ = store.abort_multipart(obj_path, &multipart_id).await;
+                        return Err(ObjectStoreError::Generic {
+                            store: "multipart",
+                            source: Box::new(e),
+                        });
+                    }
+                }
+
+                if let Err(e) = writer.shutdown().await {
+                    let _ = store.abort_multipart(obj_path, &multipart_id).await;
+                    return Err(ObjectStoreError::Generic {
+                        store: "multipart",
+                        source: Box::new(e),
+                    });
+                }
+                Ok(())
+            })
+            .map_err(|err: ObjectStoreError| MemError::Storage(format!("{err}")))
     }
 }
 
+fn is_retryable(err: &ObjectStoreError) -> bool {
+    !matches!(
+        err,
+        ObjectStoreError::NotFound { .. } | ObjectStoreError::AlreadyExists { .. }
+    )
+}
+
+/// Segments at or above this size are written via multipart upload; single
+/// PUTs of multi-gigabyte objects hit provider size limits and retry the
+/// whole payload on any failure.
+const MULTIPART_THRESHOLD: usize = 32 * 1024 * 1024;
+/// Part size for multipart uploads.
+const MULTIPART_CHUNK: usize = 8 * 1024 * 1024;
+
+/// Reads at or above this size are split into concurrent range GETs.
+const PARALLEL_READ_THRESHOLD: usize = 8 * 1024 * 1024;
+/// Sub-range size for parallel reads.
+const PARALLEL_READ_CHUNK: usize = 4 * 1024 * 1024;
+/// Maximum range GETs in flight for one logical read.
+const MAX_CONCURRENT_RANGES: usize = 8;
+
 impl Storage for CloudStorage {
     fn write(&self, path: &str, bytes: &[u8]) -> MemResult<()> {
         let obj_path = self.object_path(path)?;
+
+        // Huge segments go through multipart upload.
+        if bytes.len() >= MULTIPART_THRESHOLD {
+            return self.write_multipart(&obj_path, bytes);
+        }
+
         let data = Bytes::copy_from_slice(bytes);
         self.run_with_retry(
             || {
                 let bytes = data.clone();
                 let store = Arc::clone(&self.store);
+                let obj_path = obj_path.clone();
                 async move { store.put(&obj_path, bytes).await.map(|_| ()) }
             },
             true,
@@ -294,11 +391,19 @@ impl Storage for CloudStorage {
 
     fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
         let obj_path = self.object_path(path)?;
+
+        // Large reads fan out into concurrent sub-range GETs.
+        if len >= PARALLEL_READ_THRESHOLD {
+            return self.read_range_parallel(&obj_path, offset, len);
+        }
+
         let range = (offset as usize)..(offset as usize + len);
         self.run_with_retry(
             || {
                 let store = Arc::clone(&self.store);
-                async move { store.get_range(&obj_path, range.clone()).await }
+                let obj_path = obj_path.clone();
+                let range = range.clone();
+                async move { store.get_range(&obj_path, range).await }
             },
             false,
         )
@@ -310,6 +415,7 @@ impl Storage for CloudStorage {
         self.run_with_retry(
             || {
                 let store = Arc::clone(&self.store);
+                let obj_path = obj_path.clone();
                 async move { store.delete(&obj_path).await }
             },
             true,
@@ -336,6 +442,7 @@ impl Storage for CloudStorage {
         self.run_with_retry(
             || {
                 let store = Arc::clone(&self.store);
+                let obj_path = obj_path.clone();
                 async move { store.head(&obj_path).await }
             },
             false,
@@ -348,6 +455,7 @@ impl Storage for CloudStorage {
         self.run_with_retry(
             || {
                 let store = Arc::clone(&self.store);
+                let obj_path = obj_path.clone();
                 async move { store.head(&obj_path).await }
             },
             false,
@@ -390,10 +498,30 @@ impl S3Storage {
             .ok_or(CloudStorageBuilderError::MissingUri { scheme: "s3" })?;
         let identity = CloudIdentity::new_s3(uri)?;
         let retry = retry_config_from(cfg);
-        let mut builder = AmazonS3Builder::new().with_bucket_name(identity.bucket.clone());
+
+        // Without explicit keys in the config, start from the environment:
+        // object_store then resolves the full AWS credential chain (env
+        // vars, profile, ECS/EC2 instance metadata) and refreshes expiring
+        // session tokens automatically. Explicit config keys still win.
+        let mut builder = if cfg.aws_access_key_id.is_some() {
+            AmazonS3Builder::new()
+        } else {
+            AmazonS3Builder::from_env()
+        }
+        .with_bucket_name(identity.bucket.clone());
         if let Some(region) = &cfg.aws_region {
             builder = builder.with_region(region.clone());
         }
+        if let Some(endpoint) = &cfg.aws_endpoint {
+            // S3-compatible stores (MinIO, Ceph, localstack). Plain-http
+            // endpoints are allowed for local development setups.
+            builder = builder
+                .with_endpoint(endpoint.clone())
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+        if cfg.aws_path_style {
+            builder = builder.with_virtual_hosted_style_request(false);
+        }
         if let Some(access_key) = &cfg.aws_access_key_id {
             builder = builder.with_access_key_id(access_key.clone());
         }
@@ -426,8 +554,16 @@ impl GcsStorage {
             .ok_or(CloudStorageBuilderError::MissingUri { scheme: "gs" })?;
         let identity = CloudIdentity::new_gcs(uri)?;
         let retry = retry_config_from(cfg);
-        let mut builder =
-            GoogleCloudStorageBuilder::new().with_bucket_name(identity.bucket.clone());
+
+        // Without a service-account file, fall back to application default
+        // credentials / the GCE metadata server, with automatic token
+        // refresh handled by object_store.
+        let mut builder = if cfg.gcs_service_account_path.is_some() {
+            GoogleCloudStorageBuilder::new()
+        } else {
+            GoogleCloudStorageBuilder::from_env()
+        }
+        .with_bucket_name(identity.bucket.clone());
         if let Some(sa_path) = &cfg.gcs_service_account_path {
             builder = builder.with_service_account_path(sa_path);
         }
@@ -454,10 +590,18 @@ impl AzureBlobStorage {
             .ok_or(CloudStorageBuilderError::MissingUri { scheme: "azure" })?;
         let identity = CloudIdentity::new_azure(uri)?;
         let retry = retry_config_from(cfg);
-        let mut builder = MicrosoftAzureBuilder::new()
-            .with_account(identity.account.clone())
-            .with_container_name(identity.bucket.clone())
-            .with_retry(object_store_retry(&retry));
+
+        // Without an access key, start from the environment so managed
+        // identity / workload identity (IMDS) auth with token refresh kicks
+        // in via object_store's credential chain.
+        let mut builder = if cfg.azure_access_key.is_some() {
+            MicrosoftAzureBuilder::new()
+        } else {
+            MicrosoftAzureBuilder::from_env().with_use_azure_cli(true)
+        }
+        .with_account(identity.account.clone())
+        .with_container_name(identity.bucket.clone())
+        .with_retry(object_store_retry(&retry));
         if let Some(key) = &cfg.azure_access_key {
             builder = builder.with_access_key(key.clone());
         }
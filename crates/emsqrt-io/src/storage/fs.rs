@@ -22,12 +22,30 @@ impl Storage for FsStorage {
         if let Some(parent) = p.parent() {
             fs::create_dir_all(parent).map_err(|e| MemError::Storage(format!("mkparent: {e}")))?;
         }
-        let mut f = File::create(p).map_err(|e| MemError::Storage(format!("create: {e}")))?;
-        f.write_all(bytes)
-            .map_err(|e| MemError::Storage(format!("write: {e}")))?;
-        f.flush()
-            .map_err(|e| MemError::Storage(format!("flush: {e}")))?;
-        Ok(())
+
+        // Atomic publish: write into a same-directory temp file, fsync, then
+        // rename over the destination. Readers never observe a partially
+        // written segment, and a crash leaves only a *.tmp to sweep up.
+        static TMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let tmp = p.with_extension(format!(
+            "tmp.{}.{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ));
+        let result = (|| -> MemResult<()> {
+            let mut f =
+                File::create(&tmp).map_err(|e| MemError::Storage(format!("create: {e}")))?;
+            f.write_all(bytes)
+                .map_err(|e| MemError::Storage(format!("write: {e}")))?;
+            f.sync_all()
+                .map_err(|e| MemError::Storage(format!("fsync: {e}")))?;
+            fs::rename(&tmp, p).map_err(|e| MemError::Storage(format!("rename: {e}")))
+        })();
+
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
     }
 
     fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
@@ -0,0 +1,156 @@
+//! Advisory multi-process file locking plus an in-memory storage backend,
+//! both primarily for test fixtures that must not trample each other when
+//! test binaries run concurrently.
+//!
+//! `FsLock` uses atomic `create_new` lock files (portable; no libc), with
+//! stale-lock breaking based on the owning pid recorded in the file.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use emsqrt_mem::error::{Error as MemError, Result as MemResult};
+use emsqrt_mem::Storage;
+
+/// Exclusive advisory lock on a path, held until dropped.
+pub struct FsLock {
+    lock_path: PathBuf,
+}
+
+impl FsLock {
+    /// Acquire `<path>.lock`, waiting up to `timeout`. A lock whose owning
+    /// process is gone (per the pid in the lock file) is broken.
+    pub fn acquire(path: impl AsRef<Path>, timeout: Duration) -> MemResult<Self> {
+        let lock_path = PathBuf::from(format!("{}.lock", path.as_ref().display()));
+        if let Some(parent) = lock_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(mut file) => {
+                    use std::io::Write;
+                    let _ = write!(file, "{}", std::process::id());
+                    return Ok(Self { lock_path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    // Break locks left by dead processes.
+                    if let Ok(pid_text) = std::fs::read_to_string(&lock_path) {
+                        if let Ok(pid) = pid_text.trim().parse::<u32>() {
+                            if !process_alive(pid) {
+                                let _ = std::fs::remove_file(&lock_path);
+                                continue;
+                            }
+                        }
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(MemError::Storage(format!(
+                            "timed out acquiring lock {}",
+                            lock_path.display()
+                        )));
+                    }
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                Err(e) => {
+                    return Err(MemError::Storage(format!(
+                        "lock {}: {}",
+                        lock_path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FsLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.lock_path);
+    }
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    Path::new(&format!("/proc/{pid}")).exists()
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true // no cheap probe; never break locks
+}
+
+/// In-memory storage backend: a HashMap of blobs behind a mutex. Useful for
+/// tests that shouldn't touch the filesystem at all.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    blobs: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn write(&self, path: &str, bytes: &[u8]) -> MemResult<()> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
+        let blobs = self.blobs.lock().unwrap();
+        let blob = blobs
+            .get(path)
+            .ok_or_else(|| MemError::Storage(format!("not found: {path}")))?;
+        let start = offset as usize;
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= blob.len())
+            .ok_or_else(|| MemError::Storage(format!("range out of bounds for {path}")))?;
+        Ok(blob[start..end].to_vec())
+    }
+
+    fn delete(&self, path: &str) -> MemResult<()> {
+        self.blobs.lock().unwrap().remove(path);
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> MemResult<Vec<String>> {
+        Ok(self
+            .blobs
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+
+    fn size(&self, path: &str) -> MemResult<u64> {
+        self.blobs
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|b| b.len() as u64)
+            .ok_or_else(|| MemError::Storage(format!("not found: {path}")))
+    }
+
+    fn etag(&self, path: &str) -> MemResult<Option<String>> {
+        Ok(self
+            .blobs
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|b| blake3::hash(b).to_hex().to_string()))
+    }
+}
@@ -6,13 +6,27 @@
 //! Also exposes `RetryConfig` and helper builders that choose the appropriate
 //! storage based on the configured spill URI (e.g. `file:///tmp`, `s3://bucket`).
 
+mod cache;
 mod fs;
+mod memory;
+mod sftp;
+mod webhdfs;
+pub use cache::CachedStorage;
 pub use fs::FsStorage;
+pub use memory::{FsLock, MemoryStorage};
+pub use sftp::SftpStorage;
+pub use webhdfs::WebHdfsStorage;
 
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
 mod cloud;
 #[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
-pub use cloud::{AzureBlobStorage, CloudStorageBuilderError, GcsStorage, S3Storage};
+pub use cloud::CloudStorageBuilderError;
+#[cfg(feature = "azure")]
+pub use cloud::AzureBlobStorage;
+#[cfg(feature = "gcs")]
+pub use cloud::GcsStorage;
+#[cfg(feature = "s3")]
+pub use cloud::S3Storage;
 
 use std::time::Duration;
 
@@ -46,7 +60,7 @@ pub fn build_storage_from_config(cfg: &StorageConfig) -> Result<Box<dyn Storage>
             #[cfg(feature = "s3")]
             {
                 let storage = S3Storage::new(cfg)?;
-                Ok(Box::new(storage))
+                wrap_with_cache(Box::new(storage), cfg)
             }
 
             #[cfg(not(feature = "s3"))]
@@ -61,7 +75,7 @@ pub fn build_storage_from_config(cfg: &StorageConfig) -> Result<Box<dyn Storage>
             #[cfg(feature = "gcs")]
             {
                 let storage = GcsStorage::new(cfg)?;
-                Ok(Box::new(storage))
+                wrap_with_cache(Box::new(storage), cfg)
             }
 
             #[cfg(not(feature = "gcs"))]
@@ -76,7 +90,7 @@ pub fn build_storage_from_config(cfg: &StorageConfig) -> Result<Box<dyn Storage>
             #[cfg(feature = "azure")]
             {
                 let storage = AzureBlobStorage::new(cfg)?;
-                Ok(Box::new(storage))
+                wrap_with_cache(Box::new(storage), cfg)
             }
 
             #[cfg(not(feature = "azure"))]
@@ -87,6 +101,22 @@ pub fn build_storage_from_config(cfg: &StorageConfig) -> Result<Box<dyn Storage>
                 ))
             }
         }
+        Some("sftp") | Some("ssh") => {
+            let uri = cfg
+                .uri
+                .as_deref()
+                .ok_or_else(|| Error::Config("missing sftp spill URI".into()))?;
+            let storage = SftpStorage::from_uri(uri).map_err(|e| Error::Config(e.to_string()))?;
+            Ok(Box::new(storage))
+        }
+        Some("webhdfs") | Some("hdfs") => {
+            let uri = cfg
+                .uri
+                .as_deref()
+                .ok_or_else(|| Error::Config("missing webhdfs spill URI".into()))?;
+            let storage = WebHdfsStorage::from_uri(uri).map_err(|e| Error::Config(e.to_string()))?;
+            Ok(Box::new(storage))
+        }
         Some("file") | None => {
             // Default to filesystem (treat URI as file:// or bare path).
             Ok(Box::new(FsStorage::new()))
@@ -94,3 +124,17 @@ pub fn build_storage_from_config(cfg: &StorageConfig) -> Result<Box<dyn Storage>
         Some(other) => Err(Error::Config(format!("unsupported spill scheme '{other}'"))),
     }
 }
+
+/// Layer a bounded local disk cache over a (cloud) backend when the config
+/// asks for one.
+#[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
+fn wrap_with_cache(storage: Box<dyn Storage>, cfg: &StorageConfig) -> Result<Box<dyn Storage>> {
+    match &cfg.local_cache_dir {
+        Some(dir) => {
+            let cached = CachedStorage::new(storage, dir, cfg.local_cache_bytes)
+                .map_err(|e| Error::Config(e.to_string()))?;
+            Ok(Box::new(cached))
+        }
+        None => Ok(storage),
+    }
+}
@@ -0,0 +1,184 @@
+//! SFTP/SSH storage adapter (`sftp://user@host[:port]/base/path`).
+//!
+//! Shells out to the OpenSSH client rather than pulling in an SSH stack:
+//! key-based auth is assumed (the usual spill-host setup), and every
+//! operation is one `ssh` invocation running a small remote command. The
+//! command name is overridable for tests.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use emsqrt_mem::error::{Error as MemError, Result as MemResult};
+use emsqrt_mem::Storage;
+
+pub struct SftpStorage {
+    /// `user@host` (or just `host`).
+    target: String,
+    port: Option<u16>,
+    /// Remote base directory (absolute, no trailing slash).
+    base: String,
+    /// SSH client binary; overridable for tests.
+    ssh_cmd: String,
+}
+
+impl SftpStorage {
+    /// Parse `sftp://[user@]host[:port]/base`.
+    pub fn from_uri(uri: &str) -> MemResult<Self> {
+        let rest = uri
+            .strip_prefix("sftp://")
+            .or_else(|| uri.strip_prefix("ssh://"))
+            .ok_or_else(|| MemError::Storage(format!("not an sftp URI: '{uri}'")))?;
+        let (authority, base) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], rest[pos..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (target, port) = match authority.rsplit_once(':') {
+            Some((t, p)) if p.chars().all(|c| c.is_ascii_digit()) => (
+                t.to_string(),
+                Some(
+                    p.parse::<u16>()
+                        .map_err(|e| MemError::Storage(format!("bad sftp port: {e}")))?,
+                ),
+            ),
+            _ => (authority.to_string(), None),
+        };
+        Ok(Self {
+            target,
+            port,
+            base: base.to_string(),
+            ssh_cmd: "ssh".to_string(),
+        })
+    }
+
+    /// Use a different SSH client binary (tests substitute a local stub).
+    pub fn with_ssh_cmd(mut self, cmd: impl Into<String>) -> Self {
+        self.ssh_cmd = cmd.into();
+        self
+    }
+
+    fn remote_path(&self, path: &str) -> String {
+        let rel = path
+            .strip_prefix("sftp://")
+            .or_else(|| path.strip_prefix("ssh://"))
+            .and_then(|rest| rest.find('/').map(|pos| &rest[pos..]))
+            .unwrap_or(path);
+        if rel.starts_with('/') {
+            rel.to_string()
+        } else {
+            format!("{}/{}", self.base, rel)
+        }
+    }
+
+    /// Run one remote command, optionally feeding stdin; returns stdout.
+    fn ssh(&self, remote_command: &str, stdin: Option<&[u8]>) -> MemResult<Vec<u8>> {
+        let mut cmd = Command::new(&self.ssh_cmd);
+        if let Some(port) = self.port {
+            cmd.arg("-p").arg(port.to_string());
+        }
+        cmd.arg(&self.target)
+            .arg(remote_command)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| MemError::Storage(format!("spawn {}: {e}", self.ssh_cmd)))?;
+        if let Some(bytes) = stdin {
+            child
+                .stdin
+                .take()
+                .expect("stdin piped above")
+                .write_all(bytes)
+                .map_err(|e| MemError::Storage(format!("ssh stdin: {e}")))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| MemError::Storage(format!("ssh wait: {e}")))?;
+        if !output.status.success() {
+            return Err(MemError::Storage(format!(
+                "ssh '{remote_command}' failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// Single-quote a path for the remote shell.
+fn shell_quote(path: &str) -> String {
+    format!("'{}'", path.replace('\'', "'\\''"))
+}
+
+impl Storage for SftpStorage {
+    fn write(&self, path: &str, bytes: &[u8]) -> MemResult<()> {
+        let remote = self.remote_path(path);
+        let dir = remote.rsplit_once('/').map(|(d, _)| d).unwrap_or("/");
+        self.ssh(
+            &format!(
+                "mkdir -p {} && cat > {}",
+                shell_quote(dir),
+                shell_quote(&remote)
+            ),
+            Some(bytes),
+        )?;
+        Ok(())
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
+        let remote = self.remote_path(path);
+        let out = self.ssh(
+            &format!(
+                "tail -c +{} {} | head -c {}",
+                offset + 1,
+                shell_quote(&remote),
+                len
+            ),
+            None,
+        )?;
+        if out.len() < len {
+            return Err(MemError::Storage(format!(
+                "sftp short read: wanted {} bytes, got {}",
+                len,
+                out.len()
+            )));
+        }
+        Ok(out)
+    }
+
+    fn delete(&self, path: &str) -> MemResult<()> {
+        let remote = self.remote_path(path);
+        self.ssh(&format!("rm -f {}", shell_quote(&remote)), None)?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> MemResult<Vec<String>> {
+        let remote = self.remote_path(prefix);
+        let out = self.ssh(
+            &format!("ls -1 {} 2>/dev/null || true", shell_quote(&remote)),
+            None,
+        )?;
+        Ok(String::from_utf8_lossy(&out)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| format!("{}/{}", remote, l))
+            .collect())
+    }
+
+    fn size(&self, path: &str) -> MemResult<u64> {
+        let remote = self.remote_path(path);
+        let out = self.ssh(&format!("wc -c < {}", shell_quote(&remote)), None)?;
+        String::from_utf8_lossy(&out)
+            .trim()
+            .parse()
+            .map_err(|e| MemError::Storage(format!("sftp size parse: {e}")))
+    }
+
+    fn etag(&self, _path: &str) -> MemResult<Option<String>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,249 @@
+//! WebHDFS storage adapter (`webhdfs://host:port/base/path`).
+//!
+//! Talks the WebHDFS REST API (the HTTP gateway exposed by HDFS namenodes
+//! and HttpFS) with a minimal built-in HTTP/1.1 client, so no extra
+//! dependencies are pulled in. Two-step CREATE/OPEN redirects to datanodes
+//! are followed one hop, matching the protocol.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use emsqrt_mem::error::{Error as MemError, Result as MemResult};
+use emsqrt_mem::Storage;
+
+pub struct WebHdfsStorage {
+    host: String,
+    port: u16,
+    /// Base path on the cluster (absolute, no trailing slash).
+    base: String,
+}
+
+impl WebHdfsStorage {
+    /// Parse `webhdfs://host:port/base` (or `hdfs://`, treated the same).
+    pub fn from_uri(uri: &str) -> MemResult<Self> {
+        let rest = uri
+            .strip_prefix("webhdfs://")
+            .or_else(|| uri.strip_prefix("hdfs://"))
+            .ok_or_else(|| MemError::Storage(format!("not a webhdfs URI: '{uri}'")))?;
+        let (authority, base) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], rest[pos..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((h, p)) => (
+                h.to_string(),
+                p.parse::<u16>()
+                    .map_err(|e| MemError::Storage(format!("bad webhdfs port: {e}")))?,
+            ),
+            None => (authority.to_string(), 9870),
+        };
+        Ok(Self {
+            host,
+            port,
+            base: base.to_string(),
+        })
+    }
+
+    fn hdfs_path(&self, path: &str) -> String {
+        // Accept both absolute URIs (re-parsed) and relative keys.
+        let rel = path
+            .strip_prefix("webhdfs://")
+            .or_else(|| path.strip_prefix("hdfs://"))
+            .and_then(|rest| rest.find('/').map(|pos| &rest[pos..]))
+            .unwrap_or(path);
+        if rel.starts_with('/') {
+            rel.to_string()
+        } else {
+            format!("{}/{}", self.base, rel)
+        }
+    }
+
+    fn url(&self, path: &str, query: &str) -> String {
+        format!("/webhdfs/v1{}?{}", self.hdfs_path(path), query)
+    }
+
+    /// One request, following at most one redirect (namenode -> datanode).
+    fn request(
+        &self,
+        method: &str,
+        target: &str,
+        body: &[u8],
+    ) -> MemResult<(u16, Vec<u8>)> {
+        let (status, headers, response) =
+            http_request(&self.host, self.port, method, target, body)?;
+
+        if status == 307 || status == 302 {
+            let location = headers
+                .iter()
+                .find_map(|(k, v)| k.eq_ignore_ascii_case("location").then(|| v.clone()))
+                .ok_or_else(|| MemError::Storage("webhdfs redirect without Location".into()))?;
+            let (host, port, path) = parse_http_url(&location)?;
+            let (status, _, response) = http_request(&host, port, method, &path, body)?;
+            return Ok((status, response));
+        }
+        Ok((status, response))
+    }
+}
+
+impl Storage for WebHdfsStorage {
+    fn write(&self, path: &str, bytes: &[u8]) -> MemResult<()> {
+        let target = self.url(path, "op=CREATE&overwrite=true&data=true");
+        let (status, body) = self.request("PUT", &target, bytes)?;
+        if status == 200 || status == 201 {
+            Ok(())
+        } else {
+            Err(MemError::Storage(format!(
+                "webhdfs CREATE failed with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )))
+        }
+    }
+
+    fn read_range(&self, path: &str, offset: u64, len: usize) -> MemResult<Vec<u8>> {
+        let target = self.url(path, &format!("op=OPEN&offset={}&length={}", offset, len));
+        let (status, body) = self.request("GET", &target, &[])?;
+        if status != 200 {
+            return Err(MemError::Storage(format!(
+                "webhdfs OPEN failed with {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+        if body.len() < len {
+            return Err(MemError::Storage(format!(
+                "webhdfs short read: wanted {} bytes, got {}",
+                len,
+                body.len()
+            )));
+        }
+        Ok(body[..len].to_vec())
+    }
+
+    fn delete(&self, path: &str) -> MemResult<()> {
+        let target = self.url(path, "op=DELETE");
+        let (status, _) = self.request("DELETE", &target, &[])?;
+        // DELETE is idempotent; 404s are fine.
+        if status == 200 || status == 404 {
+            Ok(())
+        } else {
+            Err(MemError::Storage(format!(
+                "webhdfs DELETE failed with {status}"
+            )))
+        }
+    }
+
+    fn list(&self, prefix: &str) -> MemResult<Vec<String>> {
+        let target = self.url(prefix, "op=LISTSTATUS");
+        let (status, body) = self.request("GET", &target, &[])?;
+        if status == 404 {
+            return Ok(Vec::new());
+        }
+        if status != 200 {
+            return Err(MemError::Storage(format!(
+                "webhdfs LISTSTATUS failed with {status}"
+            )));
+        }
+        let json: serde_json::Value = serde_json::from_slice(&body)
+            .map_err(|e| MemError::Storage(format!("webhdfs LISTSTATUS json: {e}")))?;
+        let dir = self.hdfs_path(prefix);
+        Ok(json["FileStatuses"]["FileStatus"]
+            .as_array()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|e| e["pathSuffix"].as_str())
+                    .map(|suffix| format!("{}/{}", dir, suffix))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn size(&self, path: &str) -> MemResult<u64> {
+        let target = self.url(path, "op=GETFILESTATUS");
+        let (status, body) = self.request("GET", &target, &[])?;
+        if status != 200 {
+            return Err(MemError::Storage(format!(
+                "webhdfs GETFILESTATUS failed with {status}"
+            )));
+        }
+        let json: serde_json::Value = serde_json::from_slice(&body)
+            .map_err(|e| MemError::Storage(format!("webhdfs GETFILESTATUS json: {e}")))?;
+        json["FileStatus"]["length"]
+            .as_u64()
+            .ok_or_else(|| MemError::Storage("webhdfs status missing length".into()))
+    }
+
+    fn etag(&self, _path: &str) -> MemResult<Option<String>> {
+        Ok(None) // WebHDFS has no ETag notion
+    }
+}
+
+/// (status, headers, body) of one HTTP exchange.
+type HttpResponse = (u16, Vec<(String, String)>, Vec<u8>);
+
+/// Minimal HTTP/1.1 exchange.
+fn http_request(
+    host: &str,
+    port: u16,
+    method: &str,
+    target: &str,
+    body: &[u8],
+) -> MemResult<HttpResponse> {
+    let mut stream = TcpStream::connect((host, port))
+        .map_err(|e| MemError::Storage(format!("webhdfs connect {host}:{port}: {e}")))?;
+
+    let head = format!(
+        "{method} {target} HTTP/1.1\r\nHost: {host}:{port}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|_| stream.write_all(body))
+        .map_err(|e| MemError::Storage(format!("webhdfs send: {e}")))?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| MemError::Storage(format!("webhdfs recv: {e}")))?;
+
+    let header_end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| MemError::Storage("webhdfs malformed response".into()))?;
+    let header_text = String::from_utf8_lossy(&response[..header_end]);
+    let mut lines = header_text.lines();
+    let status: u16 = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| MemError::Storage("webhdfs malformed status line".into()))?;
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| {
+            l.split_once(':')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        })
+        .collect();
+
+    Ok((status, headers, response[header_end + 4..].to_vec()))
+}
+
+/// Parse `http://host[:port]/path?query` into its pieces.
+fn parse_http_url(url: &str) -> MemResult<(String, u16, String)> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| MemError::Storage(format!("unsupported redirect URL '{url}'")))?;
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], rest[pos..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((h, p)) => (
+            h.to_string(),
+            p.parse::<u16>()
+                .map_err(|e| MemError::Storage(format!("bad redirect port: {e}")))?,
+        ),
+        None => (authority.to_string(), 80),
+    };
+    Ok((host, port, path))
+}
@@ -71,5 +71,6 @@ fn batch_value_to_string(v: &emsqrt_core::types::Scalar) -> String {
         F64(f) => f.to_string(),
         Str(s) => s.clone(),
         Bin(b) => format!("[binary {} bytes]", b.len()), // base64 not available
+        Date64(ms) => emsqrt_core::time::format_datetime(*ms),
     }
 }
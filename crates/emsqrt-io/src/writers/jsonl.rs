@@ -62,5 +62,6 @@ fn scalar_to_json(v: &Scalar) -> serde_json::Value {
         F64(f) => serde_json::Value::from(*f),
         Str(s) => serde_json::Value::String(s.clone()),
         Bin(b) => serde_json::Value::String(format!("[binary {} bytes]", b.len())), // base64 not available
+        Date64(ms) => serde_json::Value::String(emsqrt_core::time::format_datetime(*ms)),
     }
 }
@@ -2,6 +2,7 @@
 
 pub mod csv;
 pub mod jsonl;
+pub mod sorted_runs;
 
 #[cfg(feature = "parquet")]
 pub mod parquet;
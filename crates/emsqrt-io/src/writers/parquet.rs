@@ -17,8 +17,6 @@ use parquet::basic::{Compression, ZstdLevel};
 #[cfg(feature = "parquet")]
 use parquet::file::properties::WriterProperties;
 #[cfg(feature = "parquet")]
-use parquet::file::properties::WriterPropertiesBuilder;
-#[cfg(feature = "parquet")]
 use std::fs::File;
 #[cfg(feature = "parquet")]
 use std::sync::Arc;
@@ -30,11 +28,12 @@ use emsqrt_core::types::RowBatch;
 
 /// Compression codec for Parquet files.
 #[cfg(feature = "parquet")]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ParquetCompression {
     /// No compression
     Uncompressed,
     /// Snappy compression (fast, good compression)
+    #[default]
     Snappy,
     /// GZIP compression (good compression ratio)
     Gzip,
@@ -44,13 +43,6 @@ pub enum ParquetCompression {
     Lz4,
 }
 
-#[cfg(feature = "parquet")]
-impl Default for ParquetCompression {
-    fn default() -> Self {
-        ParquetCompression::Snappy
-    }
-}
-
 #[cfg(feature = "parquet")]
 impl ParquetCompression {
     /// Convert to Parquet's Compression enum.
@@ -104,24 +96,25 @@ impl ParquetWriter {
     /// * `path` - Path to the Parquet file
     /// * `schema` - Arrow schema for the data
     /// * `compression` - Compression codec to use
-    /// * `row_group_size` - Optional row group size in bytes (default: 128MB)
+    /// * `row_group_size` - Optional row group size in rows (the parquet
+    ///   writer property is a row count; default 128 * 1024 * 1024 rows is
+    ///   effectively "one group per file" for typical inputs)
     pub fn to_path_with_options(
         path: &str,
         schema: SchemaRef,
         compression: ParquetCompression,
         row_group_size: Option<usize>,
     ) -> Result<Self> {
-        let file = File::create(path).map_err(|e| Error::Io(e))?;
+        let file = File::create(path).map_err(Error::Io)?;
 
         // Build writer properties
         let mut props_builder =
             WriterProperties::builder().set_compression(compression.to_parquet_compression());
 
-        // Set row group size if specified
-        if let Some(size_bytes) = row_group_size {
-            props_builder = props_builder.set_max_row_group_size(size_bytes);
+        // Set row group size (in rows) if specified
+        if let Some(rows) = row_group_size {
+            props_builder = props_builder.set_max_row_group_size(rows);
         } else {
-            // Default: 128MB row groups
             props_builder = props_builder.set_max_row_group_size(128 * 1024 * 1024);
         }
 
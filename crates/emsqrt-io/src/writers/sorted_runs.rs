@@ -0,0 +1,183 @@
+//! Merge-on-read sink: each incoming batch is sorted and written as its own
+//! JSONL run file; a small manifest lists the runs and sort keys. Readers
+//! k-way merge the runs lazily, so neither side ever materializes the whole
+//! dataset.
+//!
+//! Layout under the destination directory:
+//! - `runs.json`   { "by": [keys...], "runs": ["run-00000.jsonl", ...] }
+//! - `run-*.jsonl` one sorted run per written batch
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use emsqrt_core::types::RowBatch;
+
+use crate::error::{Error, Result};
+
+use super::jsonl::JsonlWriter;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RunsManifest {
+    by: Vec<String>,
+    runs: Vec<String>,
+}
+
+/// Writer side of the merge-on-read sink.
+pub struct SortedRunWriter {
+    dir: PathBuf,
+    by: Vec<String>,
+    manifest: RunsManifest,
+}
+
+impl SortedRunWriter {
+    pub fn new(dir: impl AsRef<Path>, by: Vec<String>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            manifest: RunsManifest {
+                by: by.clone(),
+                runs: Vec::new(),
+            },
+            dir,
+            by,
+        })
+    }
+
+    /// Sort one batch by the keys and persist it as the next run.
+    pub fn write_batch(&mut self, batch: &RowBatch) -> Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let mut sorted = batch.clone();
+        sorted
+            .sort_by_columns(&self.by)
+            .map_err(Error::Other)?;
+
+        let run_name = format!("run-{:05}.jsonl", self.manifest.runs.len());
+        let run_path = self.dir.join(&run_name);
+        let mut writer = JsonlWriter::to_path(
+            run_path
+                .to_str()
+                .ok_or_else(|| Error::Other("non-utf8 run path".into()))?,
+            None,
+        )?;
+        writer.write_batch(&sorted)?;
+
+        self.manifest.runs.push(run_name);
+        self.write_manifest()
+    }
+
+    fn write_manifest(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(self.dir.join("runs.json"), json)?;
+        Ok(())
+    }
+}
+
+/// One JSON row plus its origin run, ordered by sort key for the merge heap.
+struct HeapEntry {
+    key: Vec<serde_json::Value>,
+    row: serde_json::Map<String, serde_json::Value>,
+    run: usize,
+}
+
+fn json_cmp(a: &serde_json::Value, b: &serde_json::Value) -> Ordering {
+    use serde_json::Value::*;
+    match (a, b) {
+        (Null, Null) => Ordering::Equal,
+        (Null, _) => Ordering::Less,
+        (_, Null) => Ordering::Greater,
+        (Number(x), Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (String(x), String(y)) => x.cmp(y),
+        (Bool(x), Bool(y)) => x.cmp(y),
+        _ => format!("{a}").cmp(&format!("{b}")),
+    }
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for HeapEntry {}
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap; reverse for ascending merge order.
+        // Ties break by run index for determinism.
+        for (a, b) in self.key.iter().zip(other.key.iter()) {
+            match json_cmp(a, b) {
+                Ordering::Equal => continue,
+                other => return other.reverse(),
+            }
+        }
+        self.run.cmp(&other.run).reverse()
+    }
+}
+
+/// Reader side: lazily k-way merges the run files in sort-key order.
+pub struct MergeOnReadReader {
+    by: Vec<String>,
+    readers: Vec<std::io::Lines<BufReader<File>>>,
+    heap: BinaryHeap<HeapEntry>,
+}
+
+impl MergeOnReadReader {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let manifest: RunsManifest =
+            serde_json::from_str(&fs::read_to_string(dir.join("runs.json"))?)?;
+
+        let mut reader = Self {
+            by: manifest.by,
+            readers: Vec::with_capacity(manifest.runs.len()),
+            heap: BinaryHeap::new(),
+        };
+        for run in &manifest.runs {
+            let file = File::open(dir.join(run))?;
+            reader.readers.push(BufReader::new(file).lines());
+        }
+        // Prime the heap with the head row of each run.
+        for run in 0..reader.readers.len() {
+            reader.advance(run)?;
+        }
+        Ok(reader)
+    }
+
+    fn advance(&mut self, run: usize) -> Result<()> {
+        if let Some(line) = self.readers[run].next() {
+            let row: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&line?)?;
+            let key = self
+                .by
+                .iter()
+                .map(|k| row.get(k).cloned().unwrap_or(serde_json::Value::Null))
+                .collect();
+            self.heap.push(HeapEntry { key, row, run });
+        }
+        Ok(())
+    }
+
+    /// Next row in global sort order, or `None` when every run is drained.
+    pub fn next_row(&mut self) -> Result<Option<serde_json::Map<String, serde_json::Value>>> {
+        match self.heap.pop() {
+            Some(entry) => {
+                self.advance(entry.run)?;
+                Ok(Some(entry.row))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -4,14 +4,32 @@
 //! the guard returns the bytes to the budget (panic-safe).
 
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
 use emsqrt_core::budget::{BudgetGuard, MemoryBudget};
 
+/// Notification sent to pressure subscribers when usage crosses their
+/// watermark (rising edge only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressureEvent {
+    pub used_bytes: usize,
+    pub capacity_bytes: usize,
+}
+
 /// Shared inner state for the budget.
 struct BudgetInner {
     capacity: usize,
     used: AtomicUsize,
+    /// Wakes blocked `acquire_timeout` callers when bytes are released.
+    release_lock: Mutex<()>,
+    release_cv: Condvar,
+    /// Pressure watchers: (watermark bytes, channel). Dead receivers are
+    /// pruned on notify.
+    watchers: Mutex<Vec<(usize, Sender<PressureEvent>)>>,
+    /// Optional per-tag usage tracking (leak detection).
+    tracker: Option<crate::tracking::TagTracker>,
 }
 
 impl BudgetInner {
@@ -19,6 +37,17 @@ impl BudgetInner {
         Self {
             capacity,
             used: AtomicUsize::new(0),
+            release_lock: Mutex::new(()),
+            release_cv: Condvar::new(),
+            watchers: Mutex::new(Vec::new()),
+            tracker: None,
+        }
+    }
+
+    fn new_tracked(capacity: usize) -> Self {
+        Self {
+            tracker: Some(crate::tracking::TagTracker::new()),
+            ..Self::new(capacity)
         }
     }
 
@@ -34,6 +63,7 @@ impl BudgetInner {
                 .compare_exchange(cur, next, Ordering::AcqRel, Ordering::Relaxed)
                 .is_ok()
             {
+                self.notify_pressure(cur, next);
                 return true;
             }
         }
@@ -41,6 +71,28 @@ impl BudgetInner {
 
     fn release(&self, bytes: usize) {
         self.used.fetch_sub(bytes, Ordering::AcqRel);
+        // Wake anyone blocked waiting for budget.
+        let _guard = self.release_lock.lock().unwrap();
+        self.release_cv.notify_all();
+    }
+
+    /// Fire pressure events for watermarks crossed by this acquire.
+    fn notify_pressure(&self, before: usize, after: usize) {
+        let mut watchers = self.watchers.lock().unwrap();
+        if watchers.is_empty() {
+            return;
+        }
+        watchers.retain(|(watermark, tx)| {
+            if before < *watermark && after >= *watermark {
+                tx.send(PressureEvent {
+                    used_bytes: after,
+                    capacity_bytes: self.capacity,
+                })
+                .is_ok()
+            } else {
+                true
+            }
+        });
     }
 }
 
@@ -57,6 +109,32 @@ impl MemoryBudgetImpl {
         }
     }
 
+    /// Like `new`, but with per-tag usage tracking enabled (leak detection).
+    pub fn new_tracked(capacity_bytes: usize) -> Self {
+        Self {
+            inner: Arc::new(BudgetInner::new_tracked(capacity_bytes)),
+        }
+    }
+
+    /// Per-tag usage snapshot; empty unless constructed with `new_tracked`.
+    pub fn tag_snapshot(&self) -> Vec<(&'static str, crate::tracking::TagStats)> {
+        self.inner
+            .tracker
+            .as_ref()
+            .map(|t| t.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Tags holding bytes that were never released (leaks). Empty unless
+    /// constructed with `new_tracked`.
+    pub fn leaked_tags(&self) -> Vec<(&'static str, usize)> {
+        self.inner
+            .tracker
+            .as_ref()
+            .map(|t| t.leaks())
+            .unwrap_or_default()
+    }
+
     /// Current usage (advisory).
     pub fn used_bytes(&self) -> usize {
         self.inner.used.load(Ordering::Relaxed)
@@ -65,6 +143,112 @@ impl MemoryBudgetImpl {
     pub fn capacity_bytes(&self) -> usize {
         self.inner.capacity
     }
+
+    /// Block until `bytes` can be acquired or `timeout` elapses. Prefer this
+    /// over spin-retrying `try_acquire` when another operator is expected to
+    /// release memory soon.
+    pub fn acquire_timeout(
+        &self,
+        bytes: usize,
+        tag: &'static str,
+        timeout: Duration,
+    ) -> Option<BudgetGuardImpl> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(guard) = self.try_acquire(bytes, tag) {
+                return Some(guard);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            let guard = self.inner.release_lock.lock().unwrap();
+            // Re-check under the lock window via a bounded wait; releases
+            // notify this condvar.
+            let _ = self
+                .inner
+                .release_cv
+                .wait_timeout(guard, deadline - now)
+                .unwrap();
+        }
+    }
+
+    /// Subscribe to memory-pressure notifications: one event is delivered
+    /// each time usage rises across `threshold` (fraction of capacity).
+    pub fn subscribe_pressure(&self, threshold: f64) -> Receiver<PressureEvent> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let watermark = ((self.inner.capacity as f64) * threshold.clamp(0.0, 1.0)) as usize;
+        self.inner
+            .watchers
+            .lock()
+            .unwrap()
+            .push((watermark.max(1), tx));
+        rx
+    }
+
+    /// Carve a fixed reservation out of this budget and hand it back as an
+    /// independent sub-budget. The reserved bytes count against this budget
+    /// for the sub-budget's whole lifetime (returned on drop); allocations
+    /// inside the sub-budget are capped at the reservation, so one operator
+    /// cannot starve its siblings.
+    pub fn reserve(&self, bytes: usize, tag: &'static str) -> Option<SubBudget> {
+        let reservation = self.try_acquire(bytes, tag)?;
+        Some(SubBudget {
+            _reservation: reservation,
+            inner: Arc::new(BudgetInner::new(bytes)),
+        })
+    }
+}
+
+/// A fixed slice of a parent budget, usable anywhere a `MemoryBudget` is.
+/// Dropping the sub-budget returns the reservation to the parent.
+pub struct SubBudget {
+    _reservation: BudgetGuardImpl,
+    inner: Arc<BudgetInner>,
+}
+
+impl SubBudget {
+    pub fn used_bytes(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    pub fn capacity_bytes(&self) -> usize {
+        self.inner.capacity
+    }
+}
+
+impl MemoryBudget for SubBudget {
+    type Guard = BudgetGuardImpl;
+
+    fn try_acquire(&self, bytes: usize, tag: &'static str) -> Option<Self::Guard> {
+        if bytes == 0 {
+            return Some(BudgetGuardImpl {
+                inner: Arc::clone(&self.inner),
+                bytes: 0,
+                tag,
+            });
+        }
+        if self.inner.try_acquire(bytes) {
+            if let Some(tracker) = &self.inner.tracker {
+                tracker.record_acquire(tag, bytes);
+            }
+            Some(BudgetGuardImpl {
+                inner: Arc::clone(&self.inner),
+                bytes,
+                tag,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn capacity_bytes(&self) -> usize {
+        self.inner.capacity
+    }
+
+    fn used_bytes(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
 }
 
 /// RAII guard that accounts for a number of bytes.
@@ -79,6 +263,9 @@ impl Drop for BudgetGuardImpl {
     fn drop(&mut self) {
         if self.bytes > 0 {
             self.inner.release(self.bytes);
+            if let Some(tracker) = &self.inner.tracker {
+                tracker.record_release(self.tag, self.bytes);
+            }
             // NOTE: do not log here to keep drop path fast.
             self.bytes = 0;
         }
@@ -109,12 +296,18 @@ impl BudgetGuardImpl {
             // Shrink: always succeeds
             let delta = self.bytes - new_bytes;
             self.inner.release(delta);
+            if let Some(tracker) = &self.inner.tracker {
+                tracker.record_release(self.tag, delta);
+            }
             self.bytes = new_bytes;
             true
         } else {
             // Grow: try to acquire the additional bytes
             let delta = new_bytes - self.bytes;
             if self.inner.try_acquire(delta) {
+                if let Some(tracker) = &self.inner.tracker {
+                    tracker.record_acquire(self.tag, delta);
+                }
                 self.bytes = new_bytes;
                 true
             } else {
@@ -136,6 +329,9 @@ impl MemoryBudget for MemoryBudgetImpl {
             });
         }
         if self.inner.try_acquire(bytes) {
+            if let Some(tracker) = &self.inner.tracker {
+                tracker.record_acquire(tag, bytes);
+            }
             Some(BudgetGuardImpl {
                 inner: Arc::clone(&self.inner),
                 bytes,
@@ -10,10 +10,13 @@
 
 pub mod error;
 pub mod guard;
+pub mod overflow;
 pub mod pool;
 pub mod spill;
 pub mod tracking;
 
-pub use guard::{BudgetGuardImpl, MemoryBudgetImpl};
+pub use guard::{BudgetGuardImpl, MemoryBudgetImpl, PressureEvent, SubBudget};
+pub use overflow::OverflowBuf;
 pub use pool::{BufferPool, OwnedBuf};
-pub use spill::{Codec, SpillManager, Storage};
+pub use tracking::{TagStats, TagTracker};
+pub use spill::{Codec, SpillManager, SpillStream, Storage};
@@ -0,0 +1,142 @@
+//! Spill-backed overflow buffer: an append-only byte buffer that holds a
+//! bounded window in memory (accounted against the budget) and flushes full
+//! windows to spill storage as chunk files. Appends never fail for lack of
+//! memory — they spill instead.
+
+use std::sync::Arc;
+
+use emsqrt_core::budget::MemoryBudget;
+
+use crate::error::{Error, Result};
+use crate::guard::BudgetGuardImpl;
+use crate::spill::Storage;
+
+pub struct OverflowBuf {
+    /// Budget accounting for the in-memory window (held for our lifetime).
+    _guard: BudgetGuardImpl,
+    /// In-memory tail, at most `mem_limit` bytes.
+    tail: Vec<u8>,
+    mem_limit: usize,
+    storage: Arc<dyn Storage>,
+    dir: String,
+    /// Flushed chunks in write order: (path, len).
+    chunks: Vec<(String, usize)>,
+    total_len: u64,
+}
+
+impl OverflowBuf {
+    /// Acquire a `mem_limit`-byte window from `budget` and create an empty
+    /// buffer that overflows into chunk files under `dir`.
+    pub fn new(
+        budget: &impl MemoryBudget<Guard = BudgetGuardImpl>,
+        storage: Arc<dyn Storage>,
+        dir: impl Into<String>,
+        mem_limit: usize,
+        tag: &'static str,
+    ) -> Result<Self> {
+        let guard = budget
+            .try_acquire(mem_limit, tag)
+            .ok_or_else(|| Error::BudgetExceeded {
+                tag,
+                requested: mem_limit,
+                capacity: budget.capacity_bytes(),
+                used: budget.used_bytes(),
+            })?;
+        Ok(Self {
+            _guard: guard,
+            tail: Vec::with_capacity(mem_limit),
+            mem_limit: mem_limit.max(1),
+            storage,
+            dir: dir.into(),
+            chunks: Vec::new(),
+            total_len: 0,
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.total_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_len == 0
+    }
+
+    /// Number of chunks flushed to storage so far.
+    pub fn spilled_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Append bytes, spilling the in-memory window when it fills up.
+    pub fn write(&mut self, mut bytes: &[u8]) -> Result<()> {
+        while !bytes.is_empty() {
+            let room = self.mem_limit - self.tail.len();
+            let take = room.min(bytes.len());
+            self.tail.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.tail.len() == self.mem_limit {
+                self.flush_tail()?;
+            }
+        }
+        self.total_len = self
+            .chunks
+            .iter()
+            .map(|(_, len)| *len as u64)
+            .sum::<u64>()
+            + self.tail.len() as u64;
+        Ok(())
+    }
+
+    fn flush_tail(&mut self) -> Result<()> {
+        if self.tail.is_empty() {
+            return Ok(());
+        }
+        let path = format!("{}/overflow-{:05}.buf", self.dir, self.chunks.len());
+        self.storage.write(&path, &self.tail)?;
+        self.chunks.push((path, self.tail.len()));
+        self.tail.clear();
+        Ok(())
+    }
+
+    /// Visit the full contents in write order, one bounded chunk at a time
+    /// (flushed chunks are re-read from storage; the tail comes last).
+    pub fn for_each_chunk(&self, mut f: impl FnMut(&[u8]) -> Result<()>) -> Result<()> {
+        for (path, len) in &self.chunks {
+            let bytes = self.storage.read_range(path, 0, *len)?;
+            f(&bytes)?;
+        }
+        if !self.tail.is_empty() {
+            f(&self.tail)?;
+        }
+        Ok(())
+    }
+
+    /// Collect the full contents into one Vec (only for data known to fit;
+    /// prefer `for_each_chunk` otherwise).
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(self.total_len as usize);
+        self.for_each_chunk(|chunk| {
+            out.extend_from_slice(chunk);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    /// Delete all flushed chunks from storage.
+    pub fn clear(&mut self) -> Result<()> {
+        for (path, _) in self.chunks.drain(..) {
+            self.storage.delete(&path)?;
+        }
+        self.tail.clear();
+        self.total_len = 0;
+        Ok(())
+    }
+}
+
+impl Drop for OverflowBuf {
+    fn drop(&mut self) {
+        // Best-effort cleanup of spilled chunks.
+        for (path, _) in &self.chunks {
+            let _ = self.storage.delete(path);
+        }
+    }
+}
@@ -32,9 +32,7 @@ impl OwnedBuf {
             })?;
 
         // Safety note: allocation can still fail even if we acquired budget bytes.
-        let mut buf = Vec::with_capacity(len);
-        // Initialize length (zeroed)
-        buf.resize(len, 0u8);
+        let buf = vec![0u8; len];
 
         Ok(Self { guard, buf })
     }
@@ -106,25 +104,118 @@ impl DerefMut for OwnedBuf {
     }
 }
 
-/// Simple pool facade. In the future, replace with a slab/chunked allocator to
-/// reduce fragmentation and reuse fixed-size pages.
+/// Number of power-of-two size classes tracked by the pool (64 B .. 2 GiB).
+const SIZE_CLASSES: usize = 26;
+const MIN_CLASS_SHIFT: u32 = 6; // smallest class = 64 bytes
+
+/// Free buffers per class retained for reuse.
+const MAX_FREE_PER_CLASS: usize = 8;
+
+/// Size class for a capacity: the smallest power-of-two class that fits it.
+fn size_class(cap: usize) -> Option<usize> {
+    if cap == 0 {
+        return None;
+    }
+    let class_cap_bits = usize::BITS - (cap.max(1) - 1).leading_zeros();
+    let class = class_cap_bits.saturating_sub(MIN_CLASS_SHIFT) as usize;
+    (class < SIZE_CLASSES).then_some(class)
+}
+
+fn class_capacity(class: usize) -> usize {
+    1usize << (class as u32 + MIN_CLASS_SHIFT)
+}
+
+/// Buffer pool with size-class recycling: freed buffers are binned into
+/// power-of-two classes and handed back out on later allocations of the same
+/// class, avoiding repeated large allocations. Budget accounting is still
+/// per-buffer via `OwnedBuf` guards; pooled free buffers hold no budget.
 pub struct BufferPool<B: MemoryBudget> {
     budget: B,
+    free: std::sync::Mutex<Vec<Vec<Vec<u8>>>>,
 }
 
 impl<B: MemoryBudget<Guard = BudgetGuardImpl>> BufferPool<B> {
     pub fn new(budget: B) -> Self {
-        Self { budget }
+        Self {
+            budget,
+            free: std::sync::Mutex::new((0..SIZE_CLASSES).map(|_| Vec::new()).collect()),
+        }
     }
 
     pub fn alloc_zeroed(&self, len: usize, tag: &'static str) -> Result<OwnedBuf> {
+        // Reuse a pooled buffer of the right class when possible.
+        if let Some(mut buf) = self.take_free(len) {
+            let accounted = buf.capacity();
+            let guard = self
+                .budget
+                .try_acquire(accounted, tag)
+                .ok_or_else(|| Error::BudgetExceeded {
+                    tag,
+                    requested: accounted,
+                    capacity: self.budget.capacity_bytes(),
+                    used: self.budget.used_bytes(),
+                })?;
+            buf.clear();
+            buf.resize(len, 0u8);
+            return Ok(OwnedBuf { guard, buf });
+        }
         OwnedBuf::new_zeroed(&self.budget, len, tag)
     }
 
     pub fn alloc_with_capacity(&self, cap: usize, tag: &'static str) -> Result<OwnedBuf> {
+        if let Some(mut buf) = self.take_free(cap) {
+            let accounted = buf.capacity();
+            let guard = self
+                .budget
+                .try_acquire(accounted, tag)
+                .ok_or_else(|| Error::BudgetExceeded {
+                    tag,
+                    requested: accounted,
+                    capacity: self.budget.capacity_bytes(),
+                    used: self.budget.used_bytes(),
+                })?;
+            buf.clear();
+            return Ok(OwnedBuf { guard, buf });
+        }
         OwnedBuf::with_capacity(&self.budget, cap, tag)
     }
 
+    /// Return a finished buffer to the pool for recycling. The caller's
+    /// budget guard is dropped here, releasing the accounted bytes; the raw
+    /// allocation is retained (bounded per class) for the next alloc.
+    pub fn recycle(&self, buf: OwnedBuf) {
+        let (buf, _guard) = buf.into_inner();
+        if let Some(class) = size_class(buf.capacity().max(1)) {
+            // Only keep buffers whose capacity actually fits the class they'd
+            // be handed out for.
+            if buf.capacity() >= class_capacity(class.saturating_sub(1)) {
+                let mut free = self.free.lock().unwrap();
+                if free[class].len() < MAX_FREE_PER_CLASS {
+                    free[class].push(buf);
+                }
+            }
+        }
+    }
+
+    /// Count of free buffers currently pooled (for tests/metrics).
+    pub fn free_buffers(&self) -> usize {
+        self.free.lock().unwrap().iter().map(Vec::len).sum()
+    }
+
+    fn take_free(&self, needed: usize) -> Option<Vec<u8>> {
+        let class = size_class(needed)?;
+        let mut free = self.free.lock().unwrap();
+        // Exact class first, then the next class up (larger is fine).
+        for candidate in [class, class + 1] {
+            if candidate < SIZE_CLASSES {
+                if let Some(pos) = free[candidate].iter().position(|b| b.capacity() >= needed) {
+                    return Some(free[candidate].swap_remove(pos));
+                }
+            }
+        }
+        None
+    }
+
     pub fn budget(&self) -> &B {
         &self.budget
     }
@@ -0,0 +1,115 @@
+//! Background spill writer: segment bytes are handed to a writer thread so
+//! operators overlap compute with spill I/O. In-flight bytes are bounded;
+//! enqueueing blocks once the limit is reached, so memory stays capped even
+//! when storage is slow.
+
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+use super::Storage;
+use crate::error::{Error, Result};
+
+struct WriteJob {
+    path: String,
+    bytes: Vec<u8>,
+}
+
+/// Shared accounting between producers and the writer thread.
+struct Shared {
+    /// (in-flight bytes, pending job count)
+    state: Mutex<(u64, usize)>,
+    cond: Condvar,
+    errors: Mutex<Vec<String>>,
+}
+
+pub struct BackgroundWriter {
+    tx: Option<Sender<WriteJob>>,
+    shared: Arc<Shared>,
+    max_inflight_bytes: u64,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BackgroundWriter {
+    pub fn new(storage: Arc<dyn Storage>, max_inflight_bytes: u64) -> Self {
+        let (tx, rx): (Sender<WriteJob>, Receiver<WriteJob>) = std::sync::mpsc::channel();
+        let shared = Arc::new(Shared {
+            state: Mutex::new((0, 0)),
+            cond: Condvar::new(),
+            errors: Mutex::new(Vec::new()),
+        });
+
+        let worker_shared = shared.clone();
+        let handle = std::thread::spawn(move || {
+            for job in rx {
+                let len = job.bytes.len() as u64;
+                if let Err(e) = storage.write(&job.path, &job.bytes) {
+                    worker_shared
+                        .errors
+                        .lock()
+                        .unwrap()
+                        .push(format!("{}: {}", job.path, e));
+                }
+                let mut state = worker_shared.state.lock().unwrap();
+                state.0 -= len;
+                state.1 -= 1;
+                worker_shared.cond.notify_all();
+            }
+        });
+
+        Self {
+            tx: Some(tx),
+            shared,
+            max_inflight_bytes,
+            handle: Some(handle),
+        }
+    }
+
+    /// Queue one segment write. Blocks while in-flight bytes are at the cap.
+    pub fn enqueue(&self, path: String, bytes: Vec<u8>) -> Result<()> {
+        let len = bytes.len() as u64;
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            while state.0 > 0 && state.0 + len > self.max_inflight_bytes {
+                state = self.shared.cond.wait(state).unwrap();
+            }
+            state.0 += len;
+            state.1 += 1;
+        }
+        self.tx
+            .as_ref()
+            .expect("writer not shut down")
+            .send(WriteJob { path, bytes })
+            .map_err(|_| Error::Storage("background spill writer thread died".into()))
+    }
+
+    /// Wait for all queued writes to land, then surface any write errors.
+    pub fn flush(&self) -> Result<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.1 > 0 {
+            state = self.shared.cond.wait(state).unwrap();
+        }
+        drop(state);
+
+        let mut errors = self.shared.errors.lock().unwrap();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            let joined = errors.join("; ");
+            errors.clear();
+            Err(Error::Storage(format!(
+                "background spill writes failed: {joined}"
+            )))
+        }
+    }
+}
+
+impl Drop for BackgroundWriter {
+    fn drop(&mut self) {
+        // Close the channel so the worker exits, then join it.
+        self.tx.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
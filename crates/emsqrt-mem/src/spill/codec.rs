@@ -23,15 +23,33 @@ impl Codec {
             _ => Err(Error::CodecUnsupported("unknown")),
         }
     }
+
+    /// Parse a config string ("none", "zstd", "lz4").
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "none" => Ok(Codec::None),
+            "zstd" => Ok(Codec::Zstd),
+            "lz4" => Ok(Codec::Lz4),
+            _ => Err(Error::Codec(format!("unknown spill codec '{s}'"))),
+        }
+    }
 }
 
+/// Default zstd compression level when none is configured.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
 pub fn compress(codec: Codec, input: &[u8]) -> Result<Vec<u8>> {
+    compress_with_level(codec, input, None)
+}
+
+/// Compress with an explicit level (zstd only; lz4 has no level knob).
+pub fn compress_with_level(codec: Codec, input: &[u8], level: Option<i32>) -> Result<Vec<u8>> {
     match codec {
         Codec::None => Ok(input.to_vec()),
         Codec::Zstd => {
             #[cfg(feature = "zstd")]
             {
-                let lvl = 3; // TODO: tune / make configurable
+                let lvl = level.unwrap_or(DEFAULT_ZSTD_LEVEL);
                 let mut out = Vec::new();
                 zstd::stream::copy_encode(input, &mut out, lvl)
                     .map_err(|e| Error::Codec(format!("zstd: {e}")))?;
@@ -39,6 +57,7 @@ pub fn compress(codec: Codec, input: &[u8]) -> Result<Vec<u8>> {
             }
             #[cfg(not(feature = "zstd"))]
             {
+                let _ = level;
                 Err(Error::CodecUnsupported("zstd"))
             }
         }
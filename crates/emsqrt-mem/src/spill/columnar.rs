@@ -0,0 +1,475 @@
+//! Binary columnar encoding for spill segment payloads.
+//!
+//! Replaces the JSON payload with a compact layout:
+//!
+//! ```text
+//! [ n_rows: u64 ][ n_cols: u32 ]
+//! per column:
+//!   [ name_len: u32 ][ name bytes ]
+//!   [ dtype: u8 ][ has_validity: u8 ]
+//!   [ validity: ceil(n_rows / 8) packed bytes, if present ]
+//!   [ data: fixed-width little-endian values, or length-prefixed for
+//!     Utf8/Binary ]
+//! ```
+//!
+//! After the dtype byte, an encoding byte selects how the data section is
+//! stored: plain (0), run-length (1), or zigzag-varint delta (2, integer
+//! types only). The encoder picks whichever is smallest for the column.
+//!
+//! Columns whose values cannot be packed into one typed array (mixed-type
+//! prototyping batches) fall back to a JSON-encoded column (dtype 255), so
+//! every batch remains spillable.
+
+use emsqrt_core::types::{Bitmap, Column, ColumnData, RowBatch, TypedColumn};
+
+use crate::error::{Error, Result};
+
+const DTYPE_BOOL: u8 = 0;
+const DTYPE_I32: u8 = 1;
+const DTYPE_I64: u8 = 2;
+const DTYPE_F32: u8 = 3;
+const DTYPE_F64: u8 = 4;
+const DTYPE_STR: u8 = 5;
+const DTYPE_BIN: u8 = 6;
+const DTYPE_DATE64: u8 = 7;
+const DTYPE_JSON_FALLBACK: u8 = 255;
+
+const ENC_PLAIN: u8 = 0;
+const ENC_RLE: u8 = 1;
+const ENC_DELTA: u8 = 2;
+
+pub fn encode_batch(batch: &RowBatch) -> Result<Vec<u8>> {
+    let n_rows = batch.num_rows() as u64;
+    let mut out = Vec::new();
+    out.extend_from_slice(&n_rows.to_le_bytes());
+    out.extend_from_slice(&(batch.columns.len() as u32).to_le_bytes());
+
+    for col in &batch.columns {
+        let name = col.name.as_bytes();
+        out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        out.extend_from_slice(name);
+
+        match TypedColumn::from_column_inferred(col) {
+            Ok(typed) => encode_typed(&typed, n_rows as usize, &mut out),
+            Err(_) => {
+                // Mixed-type column: JSON fallback keeps it spillable.
+                let json = serde_json::to_vec(&col.values)
+                    .map_err(|e| Error::Codec(format!("json column encode: {e}")))?;
+                out.push(DTYPE_JSON_FALLBACK);
+                out.push(0);
+                out.extend_from_slice(&(json.len() as u64).to_le_bytes());
+                out.extend_from_slice(&json);
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn encode_typed(typed: &TypedColumn, n_rows: usize, out: &mut Vec<u8>) {
+    let dtype = match &typed.data {
+        ColumnData::Bool(_) => DTYPE_BOOL,
+        ColumnData::I32(_) => DTYPE_I32,
+        ColumnData::I64(_) => DTYPE_I64,
+        ColumnData::F32(_) => DTYPE_F32,
+        ColumnData::F64(_) => DTYPE_F64,
+        ColumnData::Str(_) => DTYPE_STR,
+        ColumnData::Bin(_) => DTYPE_BIN,
+        ColumnData::Date64(_) => DTYPE_DATE64,
+    };
+    out.push(dtype);
+    out.push(typed.validity.is_some() as u8);
+
+    if let Some(validity) = &typed.validity {
+        let mut packed = vec![0u8; n_rows.div_ceil(8)];
+        for i in 0..n_rows {
+            if validity.get(i) {
+                packed[i / 8] |= 1 << (i % 8);
+            }
+        }
+        out.extend_from_slice(&packed);
+    }
+
+    let plain = encode_plain(&typed.data);
+
+    // Candidate alternative encodings; keep whichever is smallest.
+    let mut best = (ENC_PLAIN, plain);
+    if let Some(rle) = encode_rle(&typed.data) {
+        if rle.len() < best.1.len() {
+            best = (ENC_RLE, rle);
+        }
+    }
+    if let Some(delta) = encode_delta(&typed.data) {
+        if delta.len() < best.1.len() {
+            best = (ENC_DELTA, delta);
+        }
+    }
+
+    out.push(best.0);
+    out.extend_from_slice(&best.1);
+}
+
+/// Plain encoding: fixed-width little-endian, or length-prefixed for
+/// Utf8/Binary.
+fn encode_plain(data: &ColumnData) -> Vec<u8> {
+    let mut out = Vec::new();
+    match data {
+        ColumnData::Bool(v) => out.extend(v.iter().map(|b| *b as u8)),
+        ColumnData::I32(v) => {
+            for x in v {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+        ColumnData::I64(v) | ColumnData::Date64(v) => {
+            for x in v {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+        ColumnData::F32(v) => {
+            for x in v {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+        ColumnData::F64(v) => {
+            for x in v {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+        ColumnData::Str(v) => {
+            for s in v {
+                out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                out.extend_from_slice(s.as_bytes());
+            }
+        }
+        ColumnData::Bin(v) => {
+            for b in v {
+                out.extend_from_slice(&(b.len() as u32).to_le_bytes());
+                out.extend_from_slice(b);
+            }
+        }
+    }
+    out
+}
+
+/// Run-length encoding: [run_count: u32][run_len: u32, plain value]*.
+fn encode_rle(data: &ColumnData) -> Option<Vec<u8>> {
+    // RLE a single column of n values into runs over the plain encoding of
+    // one value. Skip types where runs are unlikely to help (floats).
+    fn runs<T: PartialEq>(values: &[T]) -> Vec<(usize, &T)> {
+        let mut out: Vec<(usize, &T)> = Vec::new();
+        for v in values {
+            match out.last_mut() {
+                Some((len, last)) if *last == v => *len += 1,
+                _ => out.push((1, v)),
+            }
+        }
+        out
+    }
+
+    let mut out = Vec::new();
+    match data {
+        ColumnData::Bool(v) => {
+            let runs = runs(v);
+            out.extend_from_slice(&(runs.len() as u32).to_le_bytes());
+            for (len, value) in runs {
+                out.extend_from_slice(&(len as u32).to_le_bytes());
+                out.push(*value as u8);
+            }
+        }
+        ColumnData::I32(v) => {
+            let runs = runs(v);
+            out.extend_from_slice(&(runs.len() as u32).to_le_bytes());
+            for (len, value) in runs {
+                out.extend_from_slice(&(len as u32).to_le_bytes());
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        ColumnData::I64(v) | ColumnData::Date64(v) => {
+            let runs = runs(v);
+            out.extend_from_slice(&(runs.len() as u32).to_le_bytes());
+            for (len, value) in runs {
+                out.extend_from_slice(&(len as u32).to_le_bytes());
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        ColumnData::Str(v) => {
+            let runs = runs(v);
+            out.extend_from_slice(&(runs.len() as u32).to_le_bytes());
+            for (len, value) in runs {
+                out.extend_from_slice(&(len as u32).to_le_bytes());
+                out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                out.extend_from_slice(value.as_bytes());
+            }
+        }
+        _ => return None,
+    }
+    Some(out)
+}
+
+/// Delta encoding for integer types: zigzag varints of the first value and
+/// successive differences. Compact for sorted or clustered columns.
+fn encode_delta(data: &ColumnData) -> Option<Vec<u8>> {
+    let values: Vec<i64> = match data {
+        ColumnData::I32(v) => v.iter().map(|x| *x as i64).collect(),
+        ColumnData::I64(v) | ColumnData::Date64(v) => v.clone(),
+        _ => return None,
+    };
+
+    let mut out = Vec::new();
+    let mut prev = 0i64;
+    for v in values {
+        write_varint(zigzag(v.wrapping_sub(prev)), &mut out);
+        prev = v;
+    }
+    Some(out)
+}
+
+fn zigzag(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn unzigzag(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ -((v & 1) as i64)
+}
+
+fn write_varint(mut v: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (v & 0x7f) as u8;
+        v >>= 7;
+        if v == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+pub fn decode_batch(bytes: &[u8]) -> Result<RowBatch> {
+    decode_batch_projected(bytes, None)
+}
+
+/// Decode a batch, materializing only the named columns (all when `None`).
+/// Skipped columns are still walked byte-wise but never allocated, so
+/// projection prunes memory during spill re-reads.
+pub fn decode_batch_projected(
+    bytes: &[u8],
+    projection: Option<&[String]>,
+) -> Result<RowBatch> {
+    let mut cursor = Cursor { bytes, pos: 0 };
+    let n_rows = cursor.u64()? as usize;
+    let n_cols = cursor.u32()? as usize;
+
+    let mut columns = Vec::with_capacity(n_cols);
+    for _ in 0..n_cols {
+        let name_len = cursor.u32()? as usize;
+        let name = String::from_utf8(cursor.take(name_len)?.to_vec())
+            .map_err(|e| Error::Codec(format!("column name: {e}")))?;
+        let dtype = cursor.u8()?;
+        let has_validity = cursor.u8()? != 0;
+        let wanted = projection.is_none_or(|cols| cols.iter().any(|c| c == &name));
+
+        if dtype == DTYPE_JSON_FALLBACK {
+            let len = cursor.u64()? as usize;
+            let payload = cursor.take(len)?;
+            if wanted {
+                let values = serde_json::from_slice(payload)
+                    .map_err(|e| Error::Codec(format!("json column decode: {e}")))?;
+                columns.push(Column { name, values });
+            }
+            continue;
+        }
+
+        let validity = if has_validity {
+            let packed = cursor.take(n_rows.div_ceil(8))?;
+            let mut bitmap = Bitmap::new();
+            for i in 0..n_rows {
+                bitmap.push(packed[i / 8] & (1 << (i % 8)) != 0);
+            }
+            Some(bitmap)
+        } else {
+            None
+        };
+
+        let encoding = cursor.u8()?;
+        let data = match encoding {
+            ENC_PLAIN => decode_plain(&mut cursor, dtype, n_rows)?,
+            ENC_RLE => decode_rle(&mut cursor, dtype, n_rows)?,
+            ENC_DELTA => decode_delta(&mut cursor, dtype, n_rows)?,
+            other => {
+                return Err(Error::Codec(format!("unknown column encoding {other}")));
+            }
+        };
+
+        if wanted {
+            columns.push(
+                TypedColumn {
+                    name,
+                    data,
+                    validity,
+                }
+                .to_column(),
+            );
+        }
+    }
+
+    Ok(RowBatch { columns })
+}
+
+fn decode_plain(cursor: &mut Cursor<'_>, dtype: u8, n_rows: usize) -> Result<ColumnData> {
+    Ok(match dtype {
+        DTYPE_BOOL => ColumnData::Bool(cursor.take(n_rows)?.iter().map(|b| *b != 0).collect()),
+        DTYPE_I32 => ColumnData::I32(cursor.fixed::<4, i32>(n_rows, i32::from_le_bytes)?),
+        DTYPE_I64 => ColumnData::I64(cursor.fixed::<8, i64>(n_rows, i64::from_le_bytes)?),
+        DTYPE_F32 => ColumnData::F32(cursor.fixed::<4, f32>(n_rows, f32::from_le_bytes)?),
+        DTYPE_F64 => ColumnData::F64(cursor.fixed::<8, f64>(n_rows, f64::from_le_bytes)?),
+        DTYPE_DATE64 => ColumnData::Date64(cursor.fixed::<8, i64>(n_rows, i64::from_le_bytes)?),
+        DTYPE_STR => {
+            let mut values = Vec::with_capacity(n_rows);
+            for _ in 0..n_rows {
+                let len = cursor.u32()? as usize;
+                values.push(
+                    String::from_utf8(cursor.take(len)?.to_vec())
+                        .map_err(|e| Error::Codec(format!("utf8 value: {e}")))?,
+                );
+            }
+            ColumnData::Str(values)
+        }
+        DTYPE_BIN => {
+            let mut values = Vec::with_capacity(n_rows);
+            for _ in 0..n_rows {
+                let len = cursor.u32()? as usize;
+                values.push(cursor.take(len)?.to_vec());
+            }
+            ColumnData::Bin(values)
+        }
+        other => {
+            return Err(Error::Codec(format!("unknown column dtype {other}")));
+        }
+    })
+}
+
+fn decode_rle(cursor: &mut Cursor<'_>, dtype: u8, n_rows: usize) -> Result<ColumnData> {
+    let run_count = cursor.u32()? as usize;
+
+    fn expand<T: Clone>(
+        cursor: &mut Cursor<'_>,
+        run_count: usize,
+        n_rows: usize,
+        mut read_one: impl FnMut(&mut Cursor<'_>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut out = Vec::with_capacity(n_rows);
+        for _ in 0..run_count {
+            let len = cursor.u32()? as usize;
+            let value = read_one(cursor)?;
+            if out.len() + len > n_rows {
+                return Err(Error::Codec("RLE runs exceed row count".into()));
+            }
+            out.extend(std::iter::repeat_n(value, len));
+        }
+        if out.len() != n_rows {
+            return Err(Error::Codec("RLE runs do not cover row count".into()));
+        }
+        Ok(out)
+    }
+
+    Ok(match dtype {
+        DTYPE_BOOL => ColumnData::Bool(expand(cursor, run_count, n_rows, |c| {
+            Ok(c.u8()? != 0)
+        })?),
+        DTYPE_I32 => ColumnData::I32(expand(cursor, run_count, n_rows, |c| {
+            Ok(i32::from_le_bytes(c.take(4)?.try_into().unwrap()))
+        })?),
+        DTYPE_I64 => ColumnData::I64(expand(cursor, run_count, n_rows, |c| {
+            Ok(i64::from_le_bytes(c.take(8)?.try_into().unwrap()))
+        })?),
+        DTYPE_DATE64 => ColumnData::Date64(expand(cursor, run_count, n_rows, |c| {
+            Ok(i64::from_le_bytes(c.take(8)?.try_into().unwrap()))
+        })?),
+        DTYPE_STR => ColumnData::Str(expand(cursor, run_count, n_rows, |c| {
+            let len = c.u32()? as usize;
+            String::from_utf8(c.take(len)?.to_vec())
+                .map_err(|e| Error::Codec(format!("utf8 value: {e}")))
+        })?),
+        other => {
+            return Err(Error::Codec(format!("RLE unsupported for dtype {other}")));
+        }
+    })
+}
+
+fn decode_delta(cursor: &mut Cursor<'_>, dtype: u8, n_rows: usize) -> Result<ColumnData> {
+    let mut values = Vec::with_capacity(n_rows);
+    let mut prev = 0i64;
+    for _ in 0..n_rows {
+        let delta = unzigzag(cursor.varint()?);
+        prev = prev.wrapping_add(delta);
+        values.push(prev);
+    }
+    Ok(match dtype {
+        DTYPE_I32 => ColumnData::I32(values.into_iter().map(|v| v as i32).collect()),
+        DTYPE_I64 => ColumnData::I64(values),
+        DTYPE_DATE64 => ColumnData::Date64(values),
+        other => {
+            return Err(Error::Codec(format!(
+                "delta encoding unsupported for dtype {other}"
+            )));
+        }
+    })
+}
+
+/// Tiny bounds-checked reader over the payload.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| Error::Codec("truncated columnar payload".into()))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut out = 0u64;
+        let mut shift = 0u32;
+        loop {
+            let byte = self.u8()?;
+            out |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(out);
+            }
+            shift += 7;
+            if shift >= 64 {
+                return Err(Error::Codec("varint overflow".into()));
+            }
+        }
+    }
+
+    fn fixed<const N: usize, T>(
+        &mut self,
+        count: usize,
+        convert: fn([u8; N]) -> T,
+    ) -> Result<Vec<T>> {
+        let raw = self.take(count * N)?;
+        Ok(raw
+            .chunks_exact(N)
+            .map(|chunk| convert(chunk.try_into().unwrap()))
+            .collect())
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Orchestrates writing/reading RowBatch segments to/from storage with checksums.
 
+pub mod background;
 pub mod codec;
+pub mod columnar;
 pub mod segment;
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 use emsqrt_core::budget::MemoryBudget;
 use emsqrt_core::id::SpillId;
@@ -18,6 +20,28 @@ use crate::guard::BudgetGuardImpl;
 pub use codec::Codec;
 pub use segment::{SegmentHeader, SegmentMeta, SegmentName, HEADER_LEN};
 
+/// Handle for a chunked spill run written/read one part at a time.
+#[derive(Debug, Clone)]
+pub struct SpillStream {
+    spill_id: SpillId,
+    run_index: u32,
+    parts: Vec<SegmentMeta>,
+}
+
+impl SpillStream {
+    pub fn num_parts(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn spill_id(&self) -> SpillId {
+        self.spill_id
+    }
+
+    pub fn run_index(&self) -> u32 {
+        self.run_index
+    }
+}
+
 /// Abstract storage interface for spill segments.
 ///
 /// Implemented by `emsqrt-io::FsStorage` for local filesystem,
@@ -49,23 +73,117 @@ pub trait Storage: Send + Sync {
 /// - Track segment metadata in memory
 /// - Provide read_batch/write_batch APIs for operators
 pub struct SpillManager {
-    storage: Box<dyn Storage>,
+    storage: std::sync::Arc<dyn Storage>,
     codec: Codec,
     root_dir: String,
     next_run: AtomicU32,
+    next_spill_id: AtomicU64,
     segments: HashMap<SegmentName, SegmentMeta>,
+    /// Optional cap on total bytes spilled to storage; writes that would
+    /// exceed it fail instead of silently filling the disk.
+    disk_budget_bytes: Option<u64>,
+    /// Compression level for codecs that support one (zstd).
+    codec_level: Option<i32>,
+    /// Background writer (None = synchronous writes).
+    background: Option<background::BackgroundWriter>,
+    /// Raw segment bytes fetched ahead of use by `prefetch`.
+    prefetch_cache: std::sync::Arc<std::sync::Mutex<HashMap<SegmentName, Vec<u8>>>>,
 }
 
 impl SpillManager {
     /// Create a new SpillManager with the given storage backend.
     pub fn new(storage: Box<dyn Storage>, codec: Codec, root_dir: String) -> Self {
         Self {
-            storage,
+            storage: std::sync::Arc::from(storage),
             codec,
             root_dir,
             next_run: AtomicU32::new(0),
+            next_spill_id: AtomicU64::new(1),
             segments: HashMap::new(),
+            disk_budget_bytes: None,
+            codec_level: None,
+            background: None,
+            prefetch_cache: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Fetch the given segments from storage on a background thread so the
+    /// next `read_batch` calls hit memory instead of waiting on I/O (used by
+    /// the k-way merge to read ahead of the consumer). Fire-and-forget:
+    /// failed prefetches just fall back to the normal read path.
+    pub fn prefetch(&self, metas: &[SegmentMeta]) {
+        // Queued background writes must land before we can read them back.
+        if self.flush_writes().is_err() {
+            return;
         }
+        let storage = self.storage.clone();
+        let cache = self.prefetch_cache.clone();
+        let jobs: Vec<(SegmentName, String, usize)> = metas
+            .iter()
+            .map(|m| {
+                (
+                    m.name.clone(),
+                    m.path.clone(),
+                    HEADER_LEN + m.compressed_len as usize,
+                )
+            })
+            .collect();
+
+        std::thread::spawn(move || {
+            for (name, path, len) in jobs {
+                if let Ok(bytes) = storage.read_range(&path, 0, len) {
+                    cache.lock().unwrap().insert(name, bytes);
+                }
+            }
+        });
+    }
+
+    /// Take a prefetched segment out of the cache, if present.
+    fn take_prefetched(&self, name: &SegmentName) -> Option<Vec<u8>> {
+        self.prefetch_cache.lock().unwrap().remove(name)
+    }
+
+    /// Route segment writes through a background thread, overlapping spill
+    /// I/O with compute. At most `max_inflight_bytes` of encoded segments
+    /// are queued; further writes block. Call `flush_writes` before reading
+    /// segments back or relying on durability.
+    pub fn enable_background_writes(&mut self, max_inflight_bytes: u64) {
+        self.background = Some(background::BackgroundWriter::new(
+            self.storage.clone(),
+            max_inflight_bytes,
+        ));
+    }
+
+    /// Wait for all queued background writes to land and surface errors.
+    /// No-op for synchronous managers.
+    pub fn flush_writes(&self) -> Result<()> {
+        match &self.background {
+            Some(bg) => bg.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Set the compression level for codecs that support one.
+    pub fn set_codec_level(&mut self, level: Option<i32>) {
+        self.codec_level = level;
+    }
+
+    /// Shared handle to the underlying storage (e.g. for `OverflowBuf`).
+    pub fn storage_handle(&self) -> std::sync::Arc<dyn Storage> {
+        self.storage.clone()
+    }
+
+    /// Cap total on-disk spill bytes. `None` removes the cap.
+    pub fn set_disk_budget(&mut self, bytes: Option<u64>) {
+        self.disk_budget_bytes = bytes;
+    }
+
+    /// Bytes currently occupied by tracked segments (headers included).
+    pub fn disk_used_bytes(&self) -> u64 {
+        self.segments
+            .values()
+            .map(|m| HEADER_LEN as u64 + m.compressed_len)
+            .sum()
     }
 
     /// Write a RowBatch to storage and return its metadata.
@@ -83,13 +201,19 @@ impl SpillManager {
         spill_id: SpillId,
         run_index: u32,
     ) -> Result<SegmentMeta> {
-        // Serialize batch
-        let uncompressed =
-            serde_json::to_vec(batch).map_err(|e| Error::Codec(format!("json serialize: {e}")))?;
+        let name = SegmentName::new(spill_id, run_index);
+        self.write_named(batch, name)
+    }
+
+    /// Write a batch under an explicit segment name (shared by the regular
+    /// and streaming paths).
+    fn write_named(&mut self, batch: &RowBatch, name: SegmentName) -> Result<SegmentMeta> {
+        // Serialize batch (binary columnar layout)
+        let uncompressed = columnar::encode_batch(batch)?;
         let uncompressed_len = uncompressed.len() as u64;
 
         // Compress
-        let compressed = codec::compress(self.codec, &uncompressed)?;
+        let compressed = codec::compress_with_level(self.codec, &uncompressed, self.codec_level)?;
         let compressed_len = compressed.len() as u64;
 
         // Create header
@@ -102,17 +226,31 @@ impl SpillManager {
         hasher.update(&compressed);
         let checksum: [u8; 32] = hasher.finalize().into();
 
+        // Enforce the disk budget before touching storage.
+        let segment_len = HEADER_LEN as u64 + compressed_len;
+        if let Some(budget) = self.disk_budget_bytes {
+            let used = self.disk_used_bytes();
+            if used + segment_len > budget {
+                return Err(Error::Budget(format!(
+                    "spill disk budget exceeded: {} used + {} new > {} cap",
+                    used, segment_len, budget
+                )));
+            }
+        }
+
         // Construct path and write
-        let name = SegmentName::new(spill_id, run_index);
         let path = format!("{}/{}.seg", self.root_dir, name.0);
 
         let mut full_segment = Vec::with_capacity(header_bytes.len() + compressed.len());
         full_segment.extend_from_slice(&header_bytes);
         full_segment.extend_from_slice(&compressed);
 
-        self.storage.write(&path, &full_segment)?;
+        match &self.background {
+            Some(bg) => bg.enqueue(path.clone(), full_segment)?,
+            None => self.storage.write(&path, &full_segment)?,
+        }
 
-        // Get etag from storage
+        // Get etag from storage (background writes may not have landed yet)
         let etag = self.storage.etag(&path).ok().flatten();
 
         let meta = SegmentMeta {
@@ -131,6 +269,47 @@ impl SpillManager {
         Ok(meta)
     }
 
+
+    /// Number of re-read attempts after a checksum mismatch.
+    const READ_RETRIES: usize = 2;
+
+    /// Fetch a segment and verify its checksum, retrying from storage on
+    /// mismatch (a first attempt may be served by the prefetch cache; its
+    /// entry is dropped before retrying, so corruption there heals itself).
+    fn read_verified(&self, meta: &SegmentMeta) -> Result<Vec<u8>> {
+        // Background writes must land before the segment can be read back.
+        self.flush_writes()?;
+
+        let total_len = HEADER_LEN + meta.compressed_len as usize;
+        let mut last_err = None;
+
+        for attempt in 0..=Self::READ_RETRIES {
+            let full_segment = match self.take_prefetched(&meta.name) {
+                Some(bytes) if attempt == 0 => bytes,
+                _ => self.storage.read_range(&meta.path, 0, total_len)?,
+            };
+
+            if full_segment.len() < HEADER_LEN {
+                last_err = Some(Error::Storage("segment too short".into()));
+                continue;
+            }
+
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&full_segment);
+            let computed_checksum: [u8; 32] = hasher.finalize().into();
+            if computed_checksum == meta.checksum {
+                return Ok(full_segment);
+            }
+            last_err = Some(Error::Storage(format!(
+                "checksum mismatch for {} (attempt {})",
+                meta.name.0,
+                attempt + 1
+            )));
+        }
+
+        Err(last_err.unwrap_or_else(|| Error::Storage("checksum mismatch".into())))
+    }
+
     /// Read a RowBatch from storage using its metadata.
     ///
     /// Steps:
@@ -143,21 +322,8 @@ impl SpillManager {
         meta: &SegmentMeta,
         budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch> {
-        // Read full segment
-        let total_len = HEADER_LEN + meta.compressed_len as usize;
-        let full_segment = self.storage.read_range(&meta.path, 0, total_len)?;
-
-        if full_segment.len() < HEADER_LEN {
-            return Err(Error::Storage("segment too short".into()));
-        }
-
-        // Verify checksum
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(&full_segment);
-        let computed_checksum: [u8; 32] = hasher.finalize().into();
-        if computed_checksum != meta.checksum {
-            return Err(Error::Storage("checksum mismatch".into()));
-        }
+        // Fetch + checksum-verify (with automatic re-read on corruption)
+        let full_segment = self.read_verified(meta)?;
 
         // Parse header
         let header = SegmentHeader::from_bytes(&full_segment[..HEADER_LEN])?;
@@ -174,18 +340,120 @@ impl SpillManager {
         // Decompress
         let uncompressed = codec::decompress(header.codec, compressed)?;
 
-        // Deserialize
-        let batch: RowBatch = serde_json::from_slice(&uncompressed)
-            .map_err(|e| Error::Codec(format!("json deserialize: {e}")))?;
+        // Deserialize (binary columnar layout)
+        let batch = columnar::decode_batch(&uncompressed)?;
 
         Ok(batch)
     }
 
+    /// Read a segment back with column pruning and optional row filtering:
+    /// only `projection` columns are materialized (all when `None`), and
+    /// rows failing `predicate` are dropped before the batch is returned.
+    pub fn read_batch_filtered(
+        &self,
+        meta: &SegmentMeta,
+        budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
+        projection: Option<&[String]>,
+        predicate: Option<&emsqrt_core::expr::Expr>,
+    ) -> Result<RowBatch> {
+        let full_segment = self.read_verified(meta)?;
+
+        let header = SegmentHeader::from_bytes(&full_segment[..HEADER_LEN])?;
+        header.validate_sizes(100 * 1024 * 1024, 100 * 1024 * 1024)?;
+
+        let _guard = budget
+            .try_acquire(header.uncompressed_len as usize, "spill_decompress")
+            .ok_or_else(|| Error::Budget("cannot acquire for decompression".into()))?;
+
+        let uncompressed = codec::decompress(header.codec, &full_segment[HEADER_LEN..])?;
+        let mut batch = columnar::decode_batch_projected(&uncompressed, projection)?;
+
+        if let Some(expr) = predicate {
+            let keep: Vec<bool> = (0..batch.num_rows())
+                .map(|row| expr.evaluate_bool(&batch, row).unwrap_or(false))
+                .collect();
+            for col in &mut batch.columns {
+                let mut idx = 0;
+                col.values.retain(|_| {
+                    let k = keep[idx];
+                    idx += 1;
+                    k
+                });
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Begin a chunked spill stream. Each appended batch becomes its own
+    /// part segment, so neither writer nor reader ever buffers more than one
+    /// chunk of the run in memory.
+    pub fn begin_stream(&self, spill_id: SpillId, run_index: u32) -> SpillStream {
+        SpillStream {
+            spill_id,
+            run_index,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Append one batch to a spill stream as the next part segment.
+    pub fn stream_write(&mut self, stream: &mut SpillStream, batch: &RowBatch) -> Result<()> {
+        let name = SegmentName::part(
+            stream.spill_id,
+            stream.run_index,
+            stream.parts.len() as u32,
+        );
+        let meta = self.write_named(batch, name)?;
+        stream.parts.push(meta);
+        Ok(())
+    }
+
+    /// Read back part `part` of a spill stream; `None` past the last part.
+    pub fn stream_read(
+        &self,
+        stream: &SpillStream,
+        part: usize,
+        budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
+    ) -> Result<Option<RowBatch>> {
+        match stream.parts.get(part) {
+            Some(meta) => Ok(Some(self.read_batch(meta, budget)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete every part segment of a spill stream.
+    pub fn stream_delete(&mut self, stream: &SpillStream) -> Result<()> {
+        for meta in &stream.parts {
+            self.delete_segment(&meta.name)?;
+        }
+        Ok(())
+    }
+
     /// Generate a unique run index for this spill session.
     pub fn next_run_index(&self) -> u32 {
         self.next_run.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Allocate a globally unique spill id. Operators must use this instead
+    /// of inventing their own ids so segments never collide.
+    pub fn allocate_spill_id(&self) -> SpillId {
+        SpillId::new(self.next_spill_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Write a batch into a per-operator namespace: the segment lands under
+    /// `<root>/<namespace>/` so concurrent operators keep disjoint key
+    /// spaces and can be inspected/cleaned independently.
+    pub fn write_batch_in(
+        &mut self,
+        namespace: &str,
+        batch: &RowBatch,
+        spill_id: SpillId,
+        run_index: u32,
+    ) -> Result<SegmentMeta> {
+        let name = SegmentName::scoped(namespace, spill_id, run_index);
+        self.write_named(batch, name)
+    }
+
     /// Retrieve stored segment metadata by name.
     pub fn get_segment(&self, name: &SegmentName) -> Option<&SegmentMeta> {
         self.segments.get(name)
@@ -203,4 +471,15 @@ impl SpillManager {
     pub fn list_segments(&self) -> Vec<SegmentName> {
         self.segments.keys().cloned().collect()
     }
+
+    /// End-of-run lifecycle: delete every tracked segment from storage.
+    /// Returns the number of segments removed.
+    pub fn cleanup_all(&mut self) -> Result<usize> {
+        let names = self.list_segments();
+        let count = names.len();
+        for name in names {
+            self.delete_segment(&name)?;
+        }
+        Ok(count)
+    }
 }
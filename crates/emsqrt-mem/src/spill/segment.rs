@@ -13,7 +13,7 @@ use super::Codec;
 use crate::error::{Error, Result};
 
 pub const MAGIC: u32 = 0x45534D51; // "ESMQ" (EM-Sqrt)
-pub const VERSION: u16 = 1;
+pub const VERSION: u16 = 2; // v2: binary columnar payload (was JSON)
 pub const HEADER_LEN: usize = 4 + 2 + 1 + 1 + 8 + 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,6 +103,21 @@ impl SegmentName {
     pub fn new(id: emsqrt_core::id::SpillId, run_index: u32) -> Self {
         SegmentName(format!("spill{}_run{}", id.get(), run_index))
     }
+
+    /// Name of one part of a chunked (streaming) run.
+    pub fn part(id: emsqrt_core::id::SpillId, run_index: u32, part: u32) -> Self {
+        SegmentName(format!("spill{}_run{}_part{}", id.get(), run_index, part))
+    }
+
+    /// Name scoped to a per-operator namespace ("<ns>/spillN_runM").
+    pub fn scoped(namespace: &str, id: emsqrt_core::id::SpillId, run_index: u32) -> Self {
+        SegmentName(format!(
+            "{}/spill{}_run{}",
+            namespace,
+            id.get(),
+            run_index
+        ))
+    }
 }
 
 /// Minimal metadata the engine keeps for a spilled segment.
@@ -42,3 +42,58 @@ impl PeakTracker {
         self.peak_bytes.load(Ordering::Relaxed)
     }
 }
+
+/// Per-tag usage statistics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TagStats {
+    pub current_bytes: usize,
+    pub peak_bytes: usize,
+    pub acquires: u64,
+    pub releases: u64,
+}
+
+/// Tracks memory usage by guard tag, cheap enough to leave on in tests and
+/// diagnostics builds. A tag whose balance never returns to zero is a leak:
+/// some guard (or the buffer it accounts for) was never dropped.
+#[derive(Default)]
+pub struct TagTracker {
+    tags: std::sync::Mutex<std::collections::HashMap<&'static str, TagStats>>,
+}
+
+impl TagTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_acquire(&self, tag: &'static str, bytes: usize) {
+        let mut tags = self.tags.lock().unwrap();
+        let stats = tags.entry(tag).or_default();
+        stats.current_bytes += bytes;
+        stats.peak_bytes = stats.peak_bytes.max(stats.current_bytes);
+        stats.acquires += 1;
+    }
+
+    pub fn record_release(&self, tag: &'static str, bytes: usize) {
+        let mut tags = self.tags.lock().unwrap();
+        let stats = tags.entry(tag).or_default();
+        stats.current_bytes = stats.current_bytes.saturating_sub(bytes);
+        stats.releases += 1;
+    }
+
+    /// Usage snapshot sorted by tag.
+    pub fn snapshot(&self) -> Vec<(&'static str, TagStats)> {
+        let tags = self.tags.lock().unwrap();
+        let mut out: Vec<_> = tags.iter().map(|(k, v)| (*k, *v)).collect();
+        out.sort_by_key(|(k, _)| *k);
+        out
+    }
+
+    /// Tags with a nonzero balance: bytes acquired but never released.
+    pub fn leaks(&self) -> Vec<(&'static str, usize)> {
+        self.snapshot()
+            .into_iter()
+            .filter(|(_, stats)| stats.current_bytes > 0)
+            .map(|(tag, stats)| (tag, stats.current_bytes))
+            .collect()
+    }
+}
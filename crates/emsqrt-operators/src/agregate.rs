@@ -3,16 +3,18 @@
 //! Implements partitioned aggregation: hash group keys to partitions,
 //! spill when budget exceeded, final merge phase.
 
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 use emsqrt_core::budget::MemoryBudget;
+use emsqrt_core::expr::Expr;
 use emsqrt_core::prelude::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch, Scalar};
 use emsqrt_mem::guard::BudgetGuardImpl;
 use emsqrt_mem::SpillManager;
 
 use crate::plan::{Footprint, OpPlan};
+use crate::sketch::{Hll, TDigest};
 use crate::traits::{OpError, Operator};
 
 /// Aggregation function specification.
@@ -23,6 +25,16 @@ pub enum AggFunc {
     Min { column: String },
     Max { column: String },
     Avg { column: String },
+    Median { column: String },
+    Stddev { column: String },
+    Variance { column: String },
+    CountDistinct { column: String },
+    First { column: String },
+    Last { column: String },
+    StringAgg { column: String, delimiter: String },
+    /// Sample covariance / Pearson correlation over two numeric columns.
+    Covar { x: String, y: String },
+    Corr { x: String, y: String },
 }
 
 impl AggFunc {
@@ -45,6 +57,44 @@ impl AggFunc {
                 "avg" => Ok(AggFunc::Avg {
                     column: col.to_string(),
                 }),
+                "median" => Ok(AggFunc::Median {
+                    column: col.to_string(),
+                }),
+                "stddev" => Ok(AggFunc::Stddev {
+                    column: col.to_string(),
+                }),
+                "variance" | "var" => Ok(AggFunc::Variance {
+                    column: col.to_string(),
+                }),
+                "count_distinct" => Ok(AggFunc::CountDistinct {
+                    column: col.to_string(),
+                }),
+                "first" => Ok(AggFunc::First {
+                    column: col.to_string(),
+                }),
+                "last" => Ok(AggFunc::Last {
+                    column: col.to_string(),
+                }),
+                // "covar:x:y" / "corr:x:y"
+                "covar" | "corr" => {
+                    let (x, y) = col
+                        .split_once(':')
+                        .map(|(a, b)| (a.to_string(), b.to_string()))
+                        .ok_or_else(|| format!("{} needs two columns: {}", func, s))?;
+                    if func == "covar" {
+                        Ok(AggFunc::Covar { x, y })
+                    } else {
+                        Ok(AggFunc::Corr { x, y })
+                    }
+                }
+                // "string_agg:col" or "string_agg:col:delim"
+                "string_agg" => {
+                    let (column, delimiter) = match col.split_once(':') {
+                        Some((c, d)) => (c.to_string(), d.to_string()),
+                        None => (col.to_string(), ",".to_string()),
+                    };
+                    Ok(AggFunc::StringAgg { column, delimiter })
+                }
                 _ => Err(format!("unknown agg function: {}", func)),
             }
         } else {
@@ -68,10 +118,94 @@ impl AggFunc {
             AggFunc::Avg { column } => {
                 Field::new(format!("avg_{}", column), DataType::Float64, true)
             }
+            AggFunc::Median { column } => {
+                Field::new(format!("median_{}", column), DataType::Float64, true)
+            }
+            AggFunc::Stddev { column } => {
+                Field::new(format!("stddev_{}", column), DataType::Float64, true)
+            }
+            AggFunc::Variance { column } => {
+                Field::new(format!("variance_{}", column), DataType::Float64, true)
+            }
+            AggFunc::CountDistinct { column } => {
+                Field::new(format!("count_distinct_{}", column), DataType::Int64, false)
+            }
+            // TODO: propagate the input column type for first/last
+            AggFunc::First { column } => {
+                Field::new(format!("first_{}", column), DataType::Utf8, true)
+            }
+            AggFunc::Last { column } => {
+                Field::new(format!("last_{}", column), DataType::Utf8, true)
+            }
+            AggFunc::StringAgg { column, .. } => {
+                Field::new(format!("string_agg_{}", column), DataType::Utf8, true)
+            }
+            AggFunc::Covar { x, y } => {
+                Field::new(format!("covar_{}_{}", x, y), DataType::Float64, true)
+            }
+            AggFunc::Corr { x, y } => {
+                Field::new(format!("corr_{}_{}", x, y), DataType::Float64, true)
+            }
+        }
+    }
+
+    /// Input column this function reads, if any.
+    fn column(&self) -> Option<&str> {
+        match self {
+            AggFunc::Count => None,
+            AggFunc::Sum { column }
+            | AggFunc::Min { column }
+            | AggFunc::Max { column }
+            | AggFunc::Avg { column }
+            | AggFunc::Median { column }
+            | AggFunc::Stddev { column }
+            | AggFunc::Variance { column }
+            | AggFunc::CountDistinct { column }
+            | AggFunc::First { column }
+            | AggFunc::Last { column }
+            | AggFunc::StringAgg { column, .. } => Some(column),
+            AggFunc::Covar { x, .. } | AggFunc::Corr { x, .. } => Some(x),
+        }
+    }
+
+    /// Second input column, for two-column aggregates.
+    fn second_column(&self) -> Option<&str> {
+        match self {
+            AggFunc::Covar { y, .. } | AggFunc::Corr { y, .. } => Some(y),
+            _ => None,
         }
     }
 }
 
+/// One aggregation slot: function plus optional FILTER predicate.
+///
+/// The predicate uses the same expression syntax as the filter operator and
+/// is evaluated per row; non-matching rows are skipped for this slot only.
+#[derive(Debug, Clone)]
+pub struct AggSpec {
+    pub func: AggFunc,
+    pub filter: Option<String>,
+}
+
+impl AggSpec {
+    /// Parse from string like "sum:sales" or "sum:sales WHERE status == 'paid'".
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (func_part, filter) = match s.split_once(" WHERE ") {
+            Some((f, pred)) => (f.trim(), Some(pred.trim().to_string())),
+            None => (s.trim(), None),
+        };
+        Ok(Self {
+            func: AggFunc::parse(func_part)?,
+            filter,
+        })
+    }
+
+    /// Output field for this aggregation.
+    pub fn output_field(&self) -> Field {
+        self.func.output_field()
+    }
+}
+
 /// Aggregate value accumulator.
 #[derive(Debug, Clone)]
 pub struct AggValue {
@@ -124,21 +258,16 @@ impl AggValue {
     }
 }
 
+#[derive(Default)]
 pub struct Aggregate {
     pub group_by: Vec<String>,
     pub aggs: Vec<String>, // e.g., "count", "sum:col"
+    /// Use approximate sketches (HLL / t-digest) for distinct counts and
+    /// quantiles so their state stays memory-bounded.
+    pub approx: bool,
     pub spill_mgr: Option<Arc<Mutex<SpillManager>>>,
 }
 
-impl Default for Aggregate {
-    fn default() -> Self {
-        Self {
-            group_by: Vec::new(),
-            aggs: Vec::new(),
-            spill_mgr: None,
-        }
-    }
-}
 
 impl Operator for Aggregate {
     fn name(&self) -> &'static str {
@@ -154,8 +283,7 @@ impl Operator for Aggregate {
     }
 
     fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
-        let input_schema = input_schemas
-            .get(0)
+        let input_schema = input_schemas.first()
             .ok_or_else(|| OpError::Plan("aggregate expects one input".into()))?;
 
         // Build output schema: group_by columns + aggregation result columns
@@ -172,13 +300,14 @@ impl Operator for Aggregate {
 
         // Add aggregation result columns
         for agg_str in &self.aggs {
-            let agg_func = AggFunc::parse(agg_str)
+            let spec = AggSpec::parse(agg_str)
                 .map_err(|e| OpError::Plan(format!("invalid agg: {}", e)))?;
-            fields.push(agg_func.output_field());
+            fields.push(spec.output_field());
         }
 
         let schema = Schema::new(fields);
-        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+        // Blocking aggregation: all input must be seen before emitting.
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)).exclusive())
     }
 
     fn eval_block(
@@ -186,24 +315,302 @@ impl Operator for Aggregate {
         inputs: &[RowBatch],
         budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("missing input".into()))?;
 
-        // Parse aggregation functions
-        let agg_funcs: Vec<AggFunc> = self
+        // Parse aggregation specs
+        let agg_specs: Vec<AggSpec> = self
             .aggs
             .iter()
-            .map(|s| AggFunc::parse(s).map_err(|e| OpError::Exec(e)))
+            .map(|s| AggSpec::parse(s).map_err(OpError::Exec))
             .collect::<Result<Vec<_>, _>>()?;
 
         // Simple case: no spill manager, do in-memory aggregation
         if self.spill_mgr.is_none() || self.group_by.len() != 1 {
-            return self.simple_aggregate(input, &agg_funcs);
+            return self.simple_aggregate(input, &agg_specs);
         }
 
         // Partitioned aggregation with spill support
-        self.partitioned_aggregate(input, &agg_funcs, budget)
+        self.partitioned_aggregate(input, &agg_specs, budget)
+    }
+}
+
+/// Per-slot accumulator state; the variant depends on the aggregation
+/// function and whether approximate mode is enabled.
+#[derive(Debug, Clone)]
+enum AggAcc {
+    /// count / sum / min / max / avg
+    Basic(AggValue),
+    /// Exact median: buffer all values.
+    Values(Vec<f64>),
+    /// Approximate median: bounded t-digest.
+    Digest(TDigest),
+    /// stddev / variance via Welford's online algorithm.
+    Moments { count: u64, mean: f64, m2: f64 },
+    /// Exact distinct count.
+    Distinct(HashSet<String>),
+    /// Approximate distinct count: bounded HyperLogLog.
+    Hll(Hll),
+    /// first / last seen non-null value (block order).
+    Edges {
+        first: Option<Scalar>,
+        last: Option<Scalar>,
+    },
+    /// string_agg: collected values, joined at finish.
+    Strings(Vec<String>),
+    /// covar/corr via bivariate Welford moments.
+    CoMoments {
+        count: u64,
+        mean_x: f64,
+        mean_y: f64,
+        co_m2: f64,
+        m2_x: f64,
+        m2_y: f64,
+    },
+}
+
+impl AggAcc {
+    fn new(func: &AggFunc, approx: bool) -> Self {
+        match func {
+            AggFunc::Count
+            | AggFunc::Sum { .. }
+            | AggFunc::Min { .. }
+            | AggFunc::Max { .. }
+            | AggFunc::Avg { .. } => AggAcc::Basic(AggValue::default()),
+            AggFunc::Median { .. } => {
+                if approx {
+                    AggAcc::Digest(TDigest::new())
+                } else {
+                    AggAcc::Values(Vec::new())
+                }
+            }
+            AggFunc::Stddev { .. } | AggFunc::Variance { .. } => AggAcc::Moments {
+                count: 0,
+                mean: 0.0,
+                m2: 0.0,
+            },
+            AggFunc::CountDistinct { .. } => {
+                if approx {
+                    AggAcc::Hll(Hll::new())
+                } else {
+                    AggAcc::Distinct(HashSet::new())
+                }
+            }
+            AggFunc::First { .. } | AggFunc::Last { .. } => AggAcc::Edges {
+                first: None,
+                last: None,
+            },
+            AggFunc::StringAgg { .. } => AggAcc::Strings(Vec::new()),
+            AggFunc::Covar { .. } | AggFunc::Corr { .. } => AggAcc::CoMoments {
+                count: 0,
+                mean_x: 0.0,
+                mean_y: 0.0,
+                co_m2: 0.0,
+                m2_x: 0.0,
+                m2_y: 0.0,
+            },
+        }
+    }
+
+    /// Fold one row's value into the accumulator. `value` is `None` only for
+    /// `Count`, which reads no column; `second` is set for two-column
+    /// aggregates (covar/corr).
+    fn update(&mut self, value: Option<&Scalar>, second: Option<&Scalar>) {
+        match self {
+            AggAcc::Basic(agg) => match value {
+                None => agg.count += 1, // Count
+                Some(v) => {
+                    if let Some(f) = scalar_f64(v) {
+                        agg.update(f);
+                    } else if !matches!(v, Scalar::Null) {
+                        // Preserve legacy behavior: non-numeric counts as 0.0
+                        agg.update(0.0);
+                    }
+                }
+            },
+            AggAcc::Values(values) => {
+                if let Some(f) = value.and_then(scalar_f64) {
+                    values.push(f);
+                }
+            }
+            AggAcc::Digest(digest) => {
+                if let Some(f) = value.and_then(scalar_f64) {
+                    digest.insert(f);
+                }
+            }
+            AggAcc::Moments { count, mean, m2 } => {
+                if let Some(f) = value.and_then(scalar_f64) {
+                    *count += 1;
+                    let delta = f - *mean;
+                    *mean += delta / (*count as f64);
+                    *m2 += delta * (f - *mean);
+                }
+            }
+            AggAcc::Distinct(set) => {
+                if let Some(text) = value.and_then(scalar_text) {
+                    set.insert(text);
+                }
+            }
+            AggAcc::Hll(hll) => {
+                if let Some(text) = value.and_then(scalar_text) {
+                    hll.insert(text.as_bytes());
+                }
+            }
+            AggAcc::Edges { first, last } => {
+                if let Some(v) = value {
+                    if !matches!(v, Scalar::Null) {
+                        if first.is_none() {
+                            *first = Some(v.clone());
+                        }
+                        *last = Some(v.clone());
+                    }
+                }
+            }
+            AggAcc::Strings(parts) => {
+                if let Some(text) = value.and_then(scalar_text) {
+                    parts.push(text);
+                }
+            }
+            AggAcc::CoMoments {
+                count,
+                mean_x,
+                mean_y,
+                co_m2,
+                m2_x,
+                m2_y,
+            } => {
+                // Rows where either side is non-numeric are skipped.
+                let (Some(x), Some(y)) = (
+                    value.and_then(scalar_f64),
+                    second.and_then(scalar_f64),
+                ) else {
+                    return;
+                };
+                *count += 1;
+                let n = *count as f64;
+                let dx = x - *mean_x;
+                let dy = y - *mean_y;
+                *mean_x += dx / n;
+                *mean_y += dy / n;
+                // Post-update deltas close the Welford recurrences.
+                *co_m2 += dx * (y - *mean_y);
+                *m2_x += dx * (x - *mean_x);
+                *m2_y += dy * (y - *mean_y);
+            }
+        }
+    }
+
+    /// Produce the final scalar for this slot.
+    fn finish(self, func: &AggFunc) -> Scalar {
+        match self {
+            AggAcc::Basic(agg) => match func {
+                AggFunc::Count => Scalar::I64(agg.count as i64),
+                // Value aggregates over zero matching rows are null (SQL
+                // semantics); otherwise a filtered-out slot would emit the
+                // accumulator defaults (0.0 / ±infinity).
+                _ if agg.count == 0 => Scalar::Null,
+                AggFunc::Sum { .. } => Scalar::F64(agg.sum),
+                AggFunc::Min { .. } => Scalar::F64(agg.min),
+                AggFunc::Max { .. } => Scalar::F64(agg.max),
+                _ => Scalar::F64(agg.avg()),
+            },
+            AggAcc::Values(mut values) => {
+                if values.is_empty() {
+                    return Scalar::Null;
+                }
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                let mid = values.len() / 2;
+                let median = if values.len() % 2 == 0 {
+                    (values[mid - 1] + values[mid]) / 2.0
+                } else {
+                    values[mid]
+                };
+                Scalar::F64(median)
+            }
+            AggAcc::Digest(mut digest) => match digest.quantile(0.5) {
+                Some(q) => Scalar::F64(q),
+                None => Scalar::Null,
+            },
+            AggAcc::Moments { count, m2, .. } => {
+                // Sample statistics (n - 1); undefined below two values.
+                if count < 2 {
+                    return Scalar::Null;
+                }
+                let variance = m2 / ((count - 1) as f64);
+                match func {
+                    AggFunc::Stddev { .. } => Scalar::F64(variance.sqrt()),
+                    _ => Scalar::F64(variance),
+                }
+            }
+            AggAcc::Distinct(set) => Scalar::I64(set.len() as i64),
+            AggAcc::Hll(hll) => Scalar::I64(hll.estimate() as i64),
+            AggAcc::Edges { first, last } => {
+                let v = match func {
+                    AggFunc::First { .. } => first,
+                    _ => last,
+                };
+                v.unwrap_or(Scalar::Null)
+            }
+            AggAcc::Strings(parts) => {
+                if parts.is_empty() {
+                    return Scalar::Null;
+                }
+                let delimiter = match func {
+                    AggFunc::StringAgg { delimiter, .. } => delimiter.as_str(),
+                    _ => ",",
+                };
+                Scalar::Str(parts.join(delimiter))
+            }
+            AggAcc::CoMoments {
+                count,
+                co_m2,
+                m2_x,
+                m2_y,
+                ..
+            } => {
+                // Sample statistics; undefined below two paired values.
+                if count < 2 {
+                    return Scalar::Null;
+                }
+                match func {
+                    AggFunc::Covar { .. } => Scalar::F64(co_m2 / ((count - 1) as f64)),
+                    _ => {
+                        let denom = (m2_x * m2_y).sqrt();
+                        if denom == 0.0 {
+                            Scalar::Null
+                        } else {
+                            Scalar::F64(co_m2 / denom)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Numeric view of a scalar, if it has one.
+fn scalar_f64(s: &Scalar) -> Option<f64> {
+    match s {
+        Scalar::I32(i) => Some(*i as f64),
+        Scalar::I64(i) => Some(*i as f64),
+        Scalar::F32(f) => Some(*f as f64),
+        Scalar::F64(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Text view of a scalar for distinct/string aggregation. Nulls are skipped.
+fn scalar_text(s: &Scalar) -> Option<String> {
+    match s {
+        Scalar::Null => None,
+        Scalar::Str(v) => Some(v.clone()),
+        Scalar::Bool(v) => Some(v.to_string()),
+        Scalar::I32(v) => Some(v.to_string()),
+        Scalar::I64(v) => Some(v.to_string()),
+        Scalar::F32(v) => Some(v.to_string()),
+        Scalar::F64(v) => Some(v.to_string()),
+        Scalar::Bin(v) => Some(format!("{:?}", v)),
+        Scalar::Date64(ms) => Some(ms.to_string()),
     }
 }
 
@@ -212,7 +619,7 @@ impl Aggregate {
     fn simple_aggregate(
         &self,
         input: &RowBatch,
-        agg_funcs: &[AggFunc],
+        agg_specs: &[AggSpec],
     ) -> Result<RowBatch, OpError> {
         if self.group_by.is_empty() {
             return Err(OpError::Exec("group_by is empty".into()));
@@ -227,8 +634,44 @@ impl Aggregate {
                 OpError::Exec(format!("group key column '{}' not found", key_col_name))
             })?;
 
-        // Build hash map: group key -> AggValue
-        let mut groups: HashMap<String, AggValue> = HashMap::new();
+        // Parse FILTER predicates up front (one per agg slot)
+        let filters: Vec<Option<Expr>> = agg_specs
+            .iter()
+            .map(|spec| {
+                spec.filter
+                    .as_deref()
+                    .map(|pred| {
+                        Expr::parse(pred).map_err(|e| {
+                            OpError::Exec(format!("failed to parse FILTER '{}': {}", pred, e))
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Resolve each slot's input column indexes up front (Count reads
+        // none; covar/corr read two)
+        let resolve = |column: &str| {
+            input
+                .columns
+                .iter()
+                .position(|c| c.name == column)
+                .ok_or_else(|| OpError::Exec(format!("agg column '{}' not found", column)))
+        };
+        let value_cols: Vec<(Option<usize>, Option<usize>)> = agg_specs
+            .iter()
+            .map(|spec| {
+                let first = spec.func.column().map(&resolve).transpose()?;
+                let second = spec.func.second_column().map(&resolve).transpose()?;
+                Ok((first, second))
+            })
+            .collect::<Result<Vec<_>, OpError>>()?;
+
+        // Build group map: group key -> one accumulator per agg slot.
+        // BTreeMap keeps output order deterministic, which the checksummed
+        // verify path depends on.
+        let mut groups: std::collections::BTreeMap<String, Vec<AggAcc>> =
+            std::collections::BTreeMap::new();
 
         for row_idx in 0..input.num_rows() {
             let key_str = match &key_col.values[row_idx] {
@@ -237,73 +680,56 @@ impl Aggregate {
                 other => format!("{:?}", other),
             };
 
-            let agg = groups.entry(key_str).or_default();
+            let accs = groups.entry(key_str).or_insert_with(|| {
+                agg_specs
+                    .iter()
+                    .map(|spec| AggAcc::new(&spec.func, self.approx))
+                    .collect()
+            });
 
             // Update aggregations
-            for func in agg_funcs {
-                match func {
-                    AggFunc::Count => {} // Count is tracked in AggValue automatically
-                    AggFunc::Sum { column }
-                    | AggFunc::Min { column }
-                    | AggFunc::Max { column }
-                    | AggFunc::Avg { column } => {
-                        let val_col = input
-                            .columns
-                            .iter()
-                            .find(|c| &c.name == column)
-                            .ok_or_else(|| {
-                                OpError::Exec(format!("agg column '{}' not found", column))
-                            })?;
-
-                        let val_f64 = match &val_col.values[row_idx] {
-                            Scalar::I32(i) => *i as f64,
-                            Scalar::I64(i) => *i as f64,
-                            Scalar::F32(f) => *f as f64,
-                            Scalar::F64(f) => *f,
-                            _ => 0.0,
-                        };
-
-                        agg.update(val_f64);
+            for slot in 0..agg_specs.len() {
+                // Skip rows excluded by this slot's FILTER predicate
+                if let Some(filter) = &filters[slot] {
+                    let keep = filter.evaluate_bool(input, row_idx).map_err(|e| {
+                        OpError::Exec(format!("FILTER evaluation failed at row {}: {}", row_idx, e))
+                    })?;
+                    if !keep {
+                        continue;
                     }
                 }
+
+                let (first, second) = value_cols[slot];
+                let value = first.map(|col_idx| &input.columns[col_idx].values[row_idx]);
+                let second = second.map(|col_idx| &input.columns[col_idx].values[row_idx]);
+                accs[slot].update(value, second);
             }
         }
 
-        // Convert hash map to output columns
-        let mut output_cols = Vec::new();
-
-        // Group key column
+        // Convert hash map to output columns: group key + one column per slot
         let mut key_col_out = Column {
             name: key_col_name.clone(),
             values: Vec::with_capacity(groups.len()),
         };
-
-        for key in groups.keys() {
-            key_col_out.values.push(Scalar::Str(key.clone()));
-        }
-        output_cols.push(key_col_out);
-
-        // Aggregation result columns
-        for func in agg_funcs {
-            let mut agg_col = Column {
-                name: func.output_field().name,
+        let mut agg_cols: Vec<Column> = agg_specs
+            .iter()
+            .map(|spec| Column {
+                name: spec.output_field().name,
                 values: Vec::with_capacity(groups.len()),
-            };
+            })
+            .collect();
 
-            for (_key, agg_val) in &groups {
-                let result = match func {
-                    AggFunc::Count => Scalar::I64(agg_val.count as i64),
-                    AggFunc::Sum { .. } => Scalar::F64(agg_val.sum),
-                    AggFunc::Min { .. } => Scalar::F64(agg_val.min),
-                    AggFunc::Max { .. } => Scalar::F64(agg_val.max),
-                    AggFunc::Avg { .. } => Scalar::F64(agg_val.avg()),
-                };
-                agg_col.values.push(result);
+        for (key, accs) in groups {
+            key_col_out.values.push(Scalar::Str(key));
+            for (slot, (spec, acc)) in agg_specs.iter().zip(accs).enumerate() {
+                agg_cols[slot].values.push(acc.finish(&spec.func));
             }
-
-            output_cols.push(agg_col);
         }
 
+        let mut output_cols = Vec::with_capacity(1 + agg_cols.len());
+        output_cols.push(key_col_out);
+        output_cols.extend(agg_cols);
+
         Ok(RowBatch {
             columns: output_cols,
         })
@@ -313,11 +739,11 @@ impl Aggregate {
     fn partitioned_aggregate(
         &self,
         input: &RowBatch,
-        agg_funcs: &[AggFunc],
+        agg_specs: &[AggSpec],
         _budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
         // For now, fall back to simple aggregation
         // TODO: Implement partitioning, spill when hash table exceeds budget, merge phase
-        self.simple_aggregate(input, agg_funcs)
+        self.simple_aggregate(input, agg_specs)
     }
 }
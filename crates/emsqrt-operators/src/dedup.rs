@@ -0,0 +1,237 @@
+//! Duplicate detection operators.
+//!
+//! - `approx_dedup`: each row's text column is MinHash-signed and checked
+//!   against an LSH index of rows seen earlier in the block; rows whose
+//!   estimated Jaccard similarity reaches the threshold are dropped.
+//! - `window_dedup`: streaming-ingestion dedup by key and fuzzy timestamp —
+//!   redelivered events share a key but carry slightly different
+//!   timestamps, so a row is a duplicate when its key was kept within the
+//!   configured window.
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::time::{parse_datetime, parse_duration_ms};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::sketch::{LshIndex, MinHash};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+pub struct ApproxDedup {
+    /// Text column the signature is computed over.
+    pub column: String,
+    /// Jaccard similarity at or above which a row is a duplicate.
+    pub threshold: f64,
+    /// Character shingle length for the MinHash tokens.
+    pub shingle_len: usize,
+}
+
+impl Default for ApproxDedup {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            threshold: 0.9,
+            shingle_len: 3,
+        }
+    }
+}
+
+impl Operator for ApproxDedup {
+    fn name(&self) -> &'static str {
+        "approx_dedup"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // One 64-slot signature plus bucket entries per retained row.
+        Footprint {
+            bytes_per_row: 600,
+            overhead_bytes: 64 * 1024,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("approx_dedup expects one input".into()))?
+            .clone();
+        if self.column.is_empty() {
+            return Err(OpError::Plan("approx_dedup needs a 'column'".into()));
+        }
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(OpError::Plan(format!(
+                "approx_dedup threshold must be in [0, 1], got {}",
+                self.threshold
+            )));
+        }
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let text_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("dedup column '{}' not found", self.column))
+            })?;
+
+        let mut index = LshIndex::new();
+        let mut keep = Vec::new();
+        for (row_idx, value) in text_col.values.iter().enumerate() {
+            let text = match value {
+                Scalar::Str(s) => s.as_str(),
+                // Non-text rows (including nulls) are never deduplicated.
+                _ => {
+                    keep.push(row_idx);
+                    continue;
+                }
+            };
+            let sig = MinHash::from_text(text, self.shingle_len);
+            if index.contains_similar(&sig, self.threshold) {
+                continue; // near-duplicate of an earlier row
+            }
+            index.insert(sig);
+            keep.push(row_idx);
+        }
+
+        Ok(RowBatch {
+            columns: input
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: keep.iter().map(|&i| c.values[i].clone()).collect(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Streaming dedup by key + fuzzy timestamp window ("window_dedup").
+pub struct WindowDedup {
+    /// Identity column: rows with equal keys are dedup candidates.
+    pub key: String,
+    /// Timestamp column (Date64, epoch millis, or parsable text).
+    pub ts_column: String,
+    /// Rows with the same key within this window of the kept row are
+    /// duplicates; a row outside it starts a new epoch for the key.
+    pub window: String,
+}
+
+impl Default for WindowDedup {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            ts_column: String::new(),
+            window: "5s".to_string(),
+        }
+    }
+}
+
+fn row_millis(value: &Scalar) -> Option<i64> {
+    match value {
+        Scalar::Date64(ms) => Some(*ms),
+        Scalar::I64(ms) => Some(*ms),
+        Scalar::Str(text) => parse_datetime(text),
+        _ => None,
+    }
+}
+
+impl Operator for WindowDedup {
+    fn name(&self) -> &'static str {
+        "window_dedup"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // Last kept timestamp per distinct key.
+        Footprint {
+            bytes_per_row: 48,
+            overhead_bytes: 64 * 1024,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("window_dedup expects one input".into()))?
+            .clone();
+        if self.key.is_empty() || self.ts_column.is_empty() {
+            return Err(OpError::Plan(
+                "window_dedup needs 'key' and 'ts_column'".into(),
+            ));
+        }
+        if parse_duration_ms(&self.window).is_none() {
+            return Err(OpError::Plan(format!(
+                "invalid window_dedup window '{}' (expected e.g. '5s', '1m')",
+                self.window
+            )));
+        }
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let key_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.key)
+            .ok_or_else(|| OpError::Exec(format!("dedup key column '{}' not found", self.key)))?;
+        let ts_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.ts_column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("dedup ts column '{}' not found", self.ts_column))
+            })?;
+
+        let window = parse_duration_ms(&self.window)
+            .ok_or_else(|| OpError::Exec(format!("invalid window '{}'", self.window)))?;
+
+        // key -> timestamp of the last kept row for that key
+        let mut last_kept: std::collections::HashMap<String, i64> =
+            std::collections::HashMap::new();
+        let mut keep = Vec::new();
+        for row in 0..input.num_rows() {
+            let key = match &key_col.values[row] {
+                Scalar::Str(s) => s.clone(),
+                other => format!("{:?}", other),
+            };
+            let Some(ts) = row_millis(&ts_col.values[row]) else {
+                keep.push(row); // rows without a usable timestamp pass through
+                continue;
+            };
+            match last_kept.get(&key) {
+                Some(kept_ts) if (ts - kept_ts).abs() <= window => continue, // duplicate
+                _ => {
+                    last_kept.insert(key, ts);
+                    keep.push(row);
+                }
+            }
+        }
+
+        Ok(RowBatch {
+            columns: input
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: keep.iter().map(|&i| c.values[i].clone()).collect(),
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,247 @@
+//! Feature-engineering transforms: one-hot encoding ("one_hot") and the
+//! feature-hashing trick ("feature_hash").
+//!
+//! One-hot expands a low-cardinality categorical column into 0/1 indicator
+//! columns; feature hashing maps arbitrary-cardinality values onto a fixed
+//! number of buckets with a seeded hash, keeping the feature space bounded.
+
+use emsqrt_core::hash::RowHasher;
+use emsqrt_core::prelude::{DataType, Field, Schema};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+pub struct OneHot {
+    /// Categorical column to expand.
+    pub column: String,
+    /// Known categories, required up front so the output schema is the same
+    /// for every block. Non-null values outside the list set the
+    /// `<column>_other` overflow indicator instead of growing the schema.
+    pub categories: Vec<String>,
+    /// Upper bound on the category list; wide indicator sets belong in
+    /// `feature_hash` instead.
+    pub max_categories: usize,
+}
+
+impl Default for OneHot {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            categories: Vec::new(),
+            max_categories: 64,
+        }
+    }
+}
+
+fn category_text(value: &Scalar) -> Option<String> {
+    match value {
+        Scalar::Null => None,
+        Scalar::Str(s) => Some(s.clone()),
+        other => Some(format!("{:?}", other)),
+    }
+}
+
+impl Operator for OneHot {
+    fn name(&self) -> &'static str {
+        "one_hot"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // One indicator column per category.
+        Footprint {
+            bytes_per_row: 8,
+            overhead_bytes: 0,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let mut schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("one_hot expects one input".into()))?
+            .clone();
+        if self.column.is_empty() {
+            return Err(OpError::Plan("one_hot needs a 'column'".into()));
+        }
+        if !schema.fields.iter().any(|f| f.name == self.column) {
+            return Err(OpError::Plan(format!(
+                "one_hot column '{}' not in input schema",
+                self.column
+            )));
+        }
+        if self.categories.is_empty() {
+            return Err(OpError::Plan(
+                "one_hot needs explicit 'categories' so every block produces \
+                 the same columns; use feature_hash for open-ended values"
+                    .into(),
+            ));
+        }
+        if self.categories.len() > self.max_categories {
+            return Err(OpError::Plan(format!(
+                "one_hot has {} categories (max {}); use feature_hash for \
+                 high-cardinality columns",
+                self.categories.len(),
+                self.max_categories
+            )));
+        }
+        for category in &self.categories {
+            schema.fields.push(Field::new(
+                format!("{}_{}", self.column, category),
+                DataType::Int64,
+                false,
+            ));
+        }
+        // Overflow bucket for non-null values outside the category list.
+        schema.fields.push(Field::new(
+            format!("{}_other", self.column),
+            DataType::Int64,
+            false,
+        ));
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let column = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("one_hot column '{}' not found", self.column))
+            })?;
+
+        if self.categories.is_empty() {
+            return Err(OpError::Exec(
+                "one_hot needs explicit 'categories' so every block produces \
+                 the same columns"
+                    .into(),
+            ));
+        }
+
+        let texts: Vec<Option<String>> = column.values.iter().map(category_text).collect();
+
+        let mut columns = input.columns.clone();
+        for category in &self.categories {
+            let values = texts
+                .iter()
+                .map(|t| Scalar::I64((t.as_deref() == Some(category.as_str())) as i64))
+                .collect();
+            columns.push(Column {
+                name: format!("{}_{}", self.column, category),
+                values,
+            });
+        }
+        // Overflow bucket: non-null values outside the list; nulls stay
+        // all-zero across every indicator.
+        let other = texts
+            .iter()
+            .map(|t| {
+                let hit = t
+                    .as_deref()
+                    .is_some_and(|t| !self.categories.iter().any(|c| c == t));
+                Scalar::I64(hit as i64)
+            })
+            .collect();
+        columns.push(Column {
+            name: format!("{}_other", self.column),
+            values: other,
+        });
+
+        Ok(RowBatch { columns })
+    }
+}
+
+pub struct FeatureHash {
+    /// Column to hash.
+    pub column: String,
+    /// Number of hash buckets.
+    pub buckets: usize,
+    /// Hash seed (wired from `EngineConfig.seed`).
+    pub seed: u64,
+}
+
+impl Default for FeatureHash {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            buckets: 64,
+            seed: 0,
+        }
+    }
+}
+
+impl Operator for FeatureHash {
+    fn name(&self) -> &'static str {
+        "feature_hash"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        Footprint {
+            bytes_per_row: 8,
+            overhead_bytes: 0,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let mut schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("feature_hash expects one input".into()))?
+            .clone();
+        if self.column.is_empty() {
+            return Err(OpError::Plan("feature_hash needs a 'column'".into()));
+        }
+        if self.buckets == 0 {
+            return Err(OpError::Plan("feature_hash needs at least one bucket".into()));
+        }
+        schema.fields.push(Field::new(
+            format!("{}_bucket", self.column),
+            DataType::Int64,
+            true,
+        ));
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let column = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("feature_hash column '{}' not found", self.column))
+            })?;
+
+        let hasher = RowHasher::new(Default::default(), self.seed);
+        let values = column
+            .values
+            .iter()
+            .map(|v| match category_text(v) {
+                Some(text) => {
+                    Scalar::I64((hasher.hash_u64(text.as_bytes()) % self.buckets as u64) as i64)
+                }
+                None => Scalar::Null,
+            })
+            .collect();
+
+        let mut columns = input.columns.clone();
+        columns.push(Column {
+            name: format!("{}_bucket", self.column),
+            values,
+        });
+        Ok(RowBatch { columns })
+    }
+}
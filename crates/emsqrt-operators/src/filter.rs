@@ -40,10 +40,21 @@ impl Operator for Filter {
     }
 
     fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
-        let schema = input_schemas
-            .get(0)
+        let schema = input_schemas.first()
             .ok_or_else(|| OpError::Plan("filter expects one input".into()))?
             .clone();
+
+        // Plan-time validation: the predicate must parse and type-check
+        // against the input schema (unknown columns, arithmetic on
+        // non-numeric types, mistyped function arguments all fail here).
+        if let Some(expr_str) = &self.expr {
+            let expr = Expr::parse(expr_str).map_err(|e| {
+                OpError::Plan(format!("invalid filter expr '{}': {}", expr_str, e))
+            })?;
+            expr.check_types(&schema)
+                .map_err(|e| OpError::Plan(format!("filter expr '{}': {}", expr_str, e)))?;
+        }
+
         Ok(OpPlan::new(schema, self.memory_need(0, 0)))
     }
 
@@ -52,8 +63,7 @@ impl Operator for Filter {
         inputs: &[RowBatch],
         _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("missing input".into()))?;
 
         // If no expression, pass through
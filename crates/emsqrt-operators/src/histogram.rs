@@ -0,0 +1,153 @@
+//! Numeric binning / histogram operator ("histogram").
+//!
+//! Buckets a numeric column into equal-width bins and emits one row per
+//! bin: `bin_start`, `bin_end`, `count`. Bounds default to the block's
+//! observed min/max; pin them via config for stable bins across runs.
+
+use emsqrt_core::prelude::{DataType, Field, Schema};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+pub struct Histogram {
+    /// Numeric column to bin.
+    pub column: String,
+    /// Number of equal-width bins.
+    pub bins: usize,
+    /// Explicit bounds; observed min/max when unset.
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            bins: 10,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+fn numeric(value: &Scalar) -> Option<f64> {
+    match value {
+        Scalar::I32(v) => Some(*v as f64),
+        Scalar::I64(v) => Some(*v as f64),
+        Scalar::F32(v) => Some(*v as f64),
+        Scalar::F64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+impl Operator for Histogram {
+    fn name(&self) -> &'static str {
+        "histogram"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // One counter per bin.
+        Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 16 * 1024,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let input = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("histogram expects one input".into()))?;
+        if self.column.is_empty() {
+            return Err(OpError::Plan("histogram needs a 'column'".into()));
+        }
+        if self.bins == 0 {
+            return Err(OpError::Plan("histogram needs at least one bin".into()));
+        }
+        if !input.fields.iter().any(|f| f.name == self.column) {
+            return Err(OpError::Plan(format!(
+                "histogram column '{}' not in input schema",
+                self.column
+            )));
+        }
+        let schema = Schema::new(vec![
+            Field::new("bin_start", DataType::Float64, false),
+            Field::new("bin_end", DataType::Float64, false),
+            Field::new("count", DataType::Int64, false),
+        ]);
+        // Blocking: all input must be seen before bins are emitted.
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)).exclusive())
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let column = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("histogram column '{}' not found", self.column))
+            })?;
+
+        let values: Vec<f64> = column.values.iter().filter_map(numeric).collect();
+
+        // Resolve bounds: configured, or observed over this block.
+        let min = self
+            .min
+            .or_else(|| values.iter().cloned().reduce(f64::min))
+            .unwrap_or(0.0);
+        let max = self
+            .max
+            .or_else(|| values.iter().cloned().reduce(f64::max))
+            .unwrap_or(1.0);
+        if min >= max || min.is_nan() || max.is_nan() {
+            return Err(OpError::Exec(format!(
+                "histogram needs min < max, got [{}, {}]",
+                min, max
+            )));
+        }
+
+        let width = (max - min) / self.bins as f64;
+        let mut counts = vec![0i64; self.bins];
+        for v in values {
+            if v < min || v > max {
+                continue; // out-of-range values are dropped, not clamped
+            }
+            let bin = (((v - min) / width) as usize).min(self.bins - 1);
+            counts[bin] += 1;
+        }
+
+        let mut bin_start = Vec::with_capacity(self.bins);
+        let mut bin_end = Vec::with_capacity(self.bins);
+        let mut count_col = Vec::with_capacity(self.bins);
+        for (bin, count) in counts.into_iter().enumerate() {
+            bin_start.push(Scalar::F64(min + bin as f64 * width));
+            bin_end.push(Scalar::F64(min + (bin + 1) as f64 * width));
+            count_col.push(Scalar::I64(count));
+        }
+
+        Ok(RowBatch {
+            columns: vec![
+                Column {
+                    name: "bin_start".to_string(),
+                    values: bin_start,
+                },
+                Column {
+                    name: "bin_end".to_string(),
+                    values: bin_end,
+                },
+                Column {
+                    name: "count".to_string(),
+                    values: count_col,
+                },
+            ],
+        })
+    }
+}
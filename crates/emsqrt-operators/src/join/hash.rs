@@ -36,6 +36,14 @@ impl JoinType {
 pub struct HashJoin {
     pub on: Vec<(String, String)>, // (left_col, right_col)
     pub join_type: String,         // "inner", "left", "right", "full"
+    /// Partitioning hash function + seed (seed-stable grace layouts).
+    pub hasher: emsqrt_core::hash::RowHasher,
+    /// Columns to materialize when re-reading spilled partitions (None =
+    /// all). Join keys are always included.
+    pub projection: Option<Vec<String>>,
+    /// Residual predicate applied to rows as partitions are re-read, before
+    /// the join, so filtered rows never enter build/probe tables.
+    pub residual_filter: Option<String>,
     pub spill_mgr: Option<Arc<Mutex<SpillManager>>>,
 }
 
@@ -44,6 +52,9 @@ impl Default for HashJoin {
         Self {
             on: Vec::new(),
             join_type: "inner".to_string(),
+            hasher: emsqrt_core::hash::RowHasher::default(),
+            projection: None,
+            residual_filter: None,
             spill_mgr: None,
         }
     }
@@ -103,7 +114,8 @@ impl Operator for HashJoin {
         }
 
         let out_schema = Schema::new(fields);
-        Ok(OpPlan::new(out_schema, self.memory_need(0, 0)))
+        // The build side must be fully consumed before probing.
+        Ok(OpPlan::new(out_schema, self.memory_need(0, 0)).exclusive())
     }
 
     fn eval_block(
@@ -273,7 +285,7 @@ impl HashJoin {
     ) -> Result<Vec<RowBatch>, OpError> {
         // Compute partition indices for each row
         let partition_indices = batch
-            .hash_columns(join_key_names, num_partitions)
+            .hash_columns_with(join_key_names, num_partitions, &self.hasher)
             .map_err(|e| OpError::Exec(format!("partition failed: {}", e)))?;
 
         // Initialize empty batches for each partition
@@ -352,13 +364,13 @@ impl HashJoin {
         let mut right_segments: Vec<Vec<emsqrt_mem::spill::SegmentMeta>> = Vec::new();
 
         let mut spill_mgr_guard = spill_mgr.lock().unwrap();
-        let spill_id = emsqrt_core::id::SpillId::new(1); // Use a fixed ID for this join
+        let spill_id = spill_mgr_guard.allocate_spill_id();
 
         for (part_idx, left_part) in left_partitions.iter().enumerate() {
             if left_part.num_rows() > 0 {
                 let run_idx = spill_mgr_guard.next_run_index();
                 let meta = spill_mgr_guard
-                    .write_batch(left_part, spill_id, run_idx)
+                    .write_batch_in("join_hash", left_part, spill_id, run_idx)
                     .map_err(|e| {
                         OpError::Exec(format!(
                             "failed to spill left partition {}: {}",
@@ -376,7 +388,7 @@ impl HashJoin {
             if right_part.num_rows() > 0 {
                 let run_idx = spill_mgr_guard.next_run_index();
                 let meta = spill_mgr_guard
-                    .write_batch(right_part, spill_id, run_idx)
+                    .write_batch_in("join_hash", right_part, spill_id, run_idx)
                     .map_err(|e| {
                         OpError::Exec(format!(
                             "failed to spill right partition {}: {}",
@@ -392,6 +404,30 @@ impl HashJoin {
 
         drop(spill_mgr_guard);
 
+        // Effective re-read projection always includes the join keys, and
+        // the residual predicate is parsed once up front.
+        let reread_projection: Option<Vec<String>> = self.projection.as_ref().map(|cols| {
+            let mut cols = cols.clone();
+            for (l, r) in &self.on {
+                if !cols.contains(l) {
+                    cols.push(l.clone());
+                }
+                if !cols.contains(r) {
+                    cols.push(r.clone());
+                }
+            }
+            cols
+        });
+        let residual = self
+            .residual_filter
+            .as_deref()
+            .map(|pred| {
+                emsqrt_core::expr::Expr::parse(pred).map_err(|e| {
+                    OpError::Exec(format!("failed to parse residual filter '{}': {}", pred, e))
+                })
+            })
+            .transpose()?;
+
         // Join each partition pair
         let mut all_results = Vec::new();
 
@@ -405,7 +441,12 @@ impl HashJoin {
                 let spill_mgr_guard = spill_mgr.lock().unwrap();
                 for segment_meta in &left_segments[part_idx] {
                     let batch = spill_mgr_guard
-                        .read_batch(segment_meta, budget)
+                        .read_batch_filtered(
+                            segment_meta,
+                            budget,
+                            reread_projection.as_deref(),
+                            residual.as_ref(),
+                        )
                         .map_err(|e| {
                             OpError::Exec(format!(
                                 "failed to read left partition {}: {}",
@@ -437,7 +478,12 @@ impl HashJoin {
                         let spill_mgr_guard = spill_mgr.lock().unwrap();
                         for segment_meta in &right_segments[part_idx] {
                             let right_batch = spill_mgr_guard
-                                .read_batch(segment_meta, budget)
+                                .read_batch_filtered(
+                            segment_meta,
+                            budget,
+                            reread_projection.as_deref(),
+                            residual.as_ref(),
+                        )
                                 .map_err(|e| {
                                     OpError::Exec(format!(
                                         "failed to read right partition {}: {}",
@@ -485,7 +531,12 @@ impl HashJoin {
                 for segment_meta in &right_segments[part_idx] {
                     let right_probe =
                         spill_mgr_guard
-                            .read_batch(segment_meta, budget)
+                            .read_batch_filtered(
+                            segment_meta,
+                            budget,
+                            reread_projection.as_deref(),
+                            residual.as_ref(),
+                        )
                             .map_err(|e| {
                                 OpError::Exec(format!(
                                     "failed to read right partition {}: {}",
@@ -581,5 +632,6 @@ fn scalar_to_string(s: &Scalar) -> String {
         Scalar::F64(f) => f.to_string(),
         Scalar::Str(s) => s.clone(),
         Scalar::Bin(b) => format!("[binary {} bytes]", b.len()),
+        Scalar::Date64(ms) => ms.to_string(),
     }
 }
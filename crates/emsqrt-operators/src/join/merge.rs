@@ -58,7 +58,8 @@ impl Operator for MergeJoin {
         }
 
         let out_schema = Schema::new(fields);
-        Ok(OpPlan::new(out_schema, self.memory_need(0, 0)))
+        // Requires fully sorted inputs; runs as a pipeline breaker.
+        Ok(OpPlan::new(out_schema, self.memory_need(0, 0)).exclusive())
     }
 
     fn eval_block(
@@ -382,6 +383,7 @@ fn scalar_type_order(s: &Scalar) -> u8 {
         F64(_) => 5,
         Str(_) => 6,
         Bin(_) => 7,
+        Date64(_) => 8,
     }
 }
 
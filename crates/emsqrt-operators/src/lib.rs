@@ -12,11 +12,19 @@ pub mod registry;
 pub mod traits;
 
 pub mod agregate;
+pub mod dedup;
+pub mod features;
 pub mod filter;
+pub mod histogram;
 pub mod map;
 pub mod project;
+pub mod retention;
+pub mod scd;
 
 pub mod join;
+pub mod sample;
+pub mod sketch;
+pub mod split;
 pub mod sort;
 pub mod window;
 
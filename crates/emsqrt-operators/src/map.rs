@@ -1,6 +1,11 @@
-//! Map operator with column renaming support.
+//! Map operator with column renaming and derived-column support.
+//!
+//! Derived columns are `alias = expression` pairs evaluated per row by the
+//! expression engine, including conditionals:
+//! `tier = CASE WHEN amount > 100 THEN 'high' ELSE 'low' END`.
 
-use emsqrt_core::prelude::Schema;
+use emsqrt_core::expr::Expr;
+use emsqrt_core::prelude::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch};
 use std::collections::HashMap;
 
@@ -11,6 +16,36 @@ use crate::traits::{MemoryBudget, OpError, Operator};
 pub struct Map {
     /// Column rename map: old_name -> new_name
     pub renames: HashMap<String, String>,
+    /// Derived columns: (alias, expression string), appended in order.
+    pub exprs: Vec<(String, String)>,
+}
+
+impl Map {
+    /// Parse a `;`-separated map expression config. Each part is either a
+    /// rename (`old AS new`) or a derived column
+    /// (`flag = CASE WHEN x > 1 THEN 1 ELSE 0 END`).
+    pub fn parse_config(config: &str) -> Result<Map, String> {
+        let mut map = Map::default();
+        for part in config.split(';') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            if let Some((old, new)) = part.split_once(" AS ") {
+                map.renames
+                    .insert(old.trim().to_string(), new.trim().to_string());
+            } else if let Some((alias, expr)) = part.split_once(" = ") {
+                map.exprs
+                    .push((alias.trim().to_string(), expr.trim().to_string()));
+            } else {
+                return Err(format!(
+                    "map expr must be 'old AS new' or 'alias = expression': '{}'",
+                    part
+                ));
+            }
+        }
+        Ok(map)
+    }
 }
 
 impl Operator for Map {
@@ -27,8 +62,7 @@ impl Operator for Map {
     }
 
     fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
-        let mut schema = input_schemas
-            .get(0)
+        let mut schema = input_schemas.first()
             .ok_or_else(|| OpError::Plan("map expects one input".into()))?
             .clone();
 
@@ -39,6 +73,24 @@ impl Operator for Map {
             }
         }
 
+        // Derived columns are appended with their statically inferred type
+        // (expressions reference pre-rename column names, so they check
+        // against the original input schema). Type errors surface here at
+        // plan time instead of on the first row.
+        let input_schema = &input_schemas[0];
+        for (alias, expr) in &self.exprs {
+            let parsed = Expr::parse(expr)
+                .map_err(|e| OpError::Plan(format!("invalid map expr '{}': {}", expr, e)))?;
+            let inferred = parsed
+                .check_types(input_schema)
+                .map_err(|e| OpError::Plan(format!("map expr '{}': {}", alias, e)))?;
+            schema.fields.push(Field::new(
+                alias.clone(),
+                inferred.unwrap_or(DataType::Utf8),
+                true,
+            ));
+        }
+
         Ok(OpPlan::new(schema, self.memory_need(0, 0)))
     }
 
@@ -47,31 +99,54 @@ impl Operator for Map {
         inputs: &[RowBatch],
         _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("missing input".into()))?;
 
-        // If no renames, pass through
-        if self.renames.is_empty() {
+        // If no renames or derived columns, pass through
+        if self.renames.is_empty() && self.exprs.is_empty() {
             return Ok(input.clone());
         }
 
         // Apply renames to columns
-        let mut renamed_cols = Vec::new();
+        let mut output_cols = Vec::with_capacity(input.columns.len() + self.exprs.len());
         for col in &input.columns {
             let new_name = self
                 .renames
                 .get(&col.name)
                 .cloned()
                 .unwrap_or_else(|| col.name.clone());
-            renamed_cols.push(Column {
+            output_cols.push(Column {
                 name: new_name,
                 values: col.values.clone(),
             });
         }
 
+        // Evaluate derived columns against the original input (expressions
+        // reference pre-rename column names).
+        let num_rows = input.num_rows();
+        for (alias, expr_str) in &self.exprs {
+            let expr = Expr::parse(expr_str).map_err(|e| {
+                OpError::Exec(format!("failed to parse map expr '{}': {}", expr_str, e))
+            })?;
+
+            let mut values = Vec::with_capacity(num_rows);
+            for row_idx in 0..num_rows {
+                let value = expr.evaluate(input, row_idx).map_err(|e| {
+                    OpError::Exec(format!(
+                        "map expr '{}' failed at row {}: {}",
+                        alias, row_idx, e
+                    ))
+                })?;
+                values.push(value);
+            }
+            output_cols.push(Column {
+                name: alias.clone(),
+                values,
+            });
+        }
+
         Ok(RowBatch {
-            columns: renamed_cols,
+            columns: output_cols,
         })
     }
 }
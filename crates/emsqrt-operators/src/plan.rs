@@ -32,6 +32,16 @@ pub struct OpPlan {
 
     /// Footprint model cached to avoid recomputation.
     pub footprint: Footprint,
+
+    /// Cap on how many blocks of this operator may run concurrently
+    /// (`None` = scheduler default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+
+    /// Pipeline breaker: this operator must see all of its input (and runs
+    /// alone) before emitting — sorts, blocking aggregates, join builds.
+    #[serde(default)]
+    pub exclusive: bool,
 }
 
 impl OpPlan {
@@ -40,6 +50,8 @@ impl OpPlan {
             output_schema,
             partitions: vec![],
             footprint,
+            max_concurrency: None,
+            exclusive: false,
         }
     }
 
@@ -47,4 +59,16 @@ impl OpPlan {
         self.partitions = cols;
         self
     }
+
+    /// Cap this operator's block-level parallelism.
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = Some(limit.max(1));
+        self
+    }
+
+    /// Mark this operator as a pipeline breaker.
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
 }
@@ -30,8 +30,7 @@ impl Operator for Project {
     }
 
     fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
-        let input = input_schemas
-            .get(0)
+        let input = input_schemas.first()
             .ok_or_else(|| OpError::Plan("project expects one input".into()))?;
         if self.columns.is_empty() {
             return Ok(OpPlan::new(input.clone(), self.memory_need(0, 0)));
@@ -51,8 +50,7 @@ impl Operator for Project {
         inputs: &[RowBatch],
         _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("missing input".into()))?;
         if self.columns.is_empty() {
             return Ok(input.clone());
@@ -1,7 +1,8 @@
 //! Minimal operator registry for planner/exec wiring.
 //!
-//! This is intentionally simple: it maps string keys to boxed operator instances.
-//! Replace with a richer factory when adding config params per operator.
+//! This is intentionally simple: it maps string keys to boxed operator
+//! instances plus documentation metadata. Replace with a richer factory when
+//! adding config params per operator.
 
 use std::collections::HashMap;
 
@@ -12,38 +13,294 @@ use crate::project::Project;
 use crate::traits::Operator;
 use crate::window::{LateralExplodeOp, WindowOp};
 
+/// Documentation metadata for one registered operator, used by the docs
+/// generator (`emsqrt operators`).
+#[derive(Debug, Clone)]
+pub struct OperatorDoc {
+    /// Registry key (stable identifier used in physical plans).
+    pub key: &'static str,
+    /// One-line description.
+    pub summary: &'static str,
+    /// Config entries as (name, description) pairs.
+    pub config: &'static [(&'static str, &'static str)],
+}
+
+struct Entry {
+    maker: fn() -> Box<dyn Operator>,
+    doc: OperatorDoc,
+}
+
 pub struct Registry {
-    makers: HashMap<&'static str, fn() -> Box<dyn Operator>>,
+    entries: HashMap<&'static str, Entry>,
+    /// Deprecated key → canonical key. Kept working for old plans/configs.
+    aliases: HashMap<&'static str, &'static str>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Registry {
     pub fn new() -> Self {
         let mut r = Self {
-            makers: HashMap::new(),
+            entries: HashMap::new(),
+            aliases: HashMap::new(),
         };
-        r.register("filter", || Box::new(Filter::default()));
-        r.register("map", || Box::new(Map::default()));
-        r.register("project", || Box::new(Project::default()));
-        r.register("aggregate", || Box::new(Aggregate::default()));
-        r.register("sort_external", || {
-            Box::new(crate::sort::external::ExternalSort::default())
-        });
-        r.register("join_hash", || {
-            Box::new(crate::join::hash::HashJoin::default())
-        });
-        r.register("join_merge", || {
-            Box::new(crate::join::merge::MergeJoin::default())
-        });
-        r.register("window", || Box::new(WindowOp::default()));
-        r.register("lateral_explode", || Box::new(LateralExplodeOp::default()));
+        r.register_with_doc(
+            "filter",
+            || Box::new(Filter::default()),
+            "Keep rows matching a predicate expression.",
+            &[(
+                "expr",
+                "predicate expression, e.g. \"age > 20 AND status == 'active'\"",
+            )],
+        );
+        r.register_with_doc(
+            "map",
+            || Box::new(Map::default()),
+            "Rename columns and compute derived columns per row.",
+            &[(
+                "expr",
+                "';'-separated parts: 'old AS new' renames, 'alias = expression' derives",
+            )],
+        );
+        r.register_with_doc(
+            "project",
+            || Box::new(Project::default()),
+            "Select a subset of columns.",
+            &[("columns", "column names to keep, in order")],
+        );
+        r.register_with_doc(
+            "aggregate",
+            || Box::new(Aggregate::default()),
+            "Group rows and compute aggregates, with optional FILTER predicates.",
+            &[
+                ("group_by", "grouping key columns"),
+                (
+                    "aggs",
+                    "agg specs like 'count', 'sum:col', 'median:col', optionally with ' WHERE <pred>'",
+                ),
+            ],
+        );
+        r.register_with_doc(
+            "sort_external",
+            || Box::new(crate::sort::external::ExternalSort::default()),
+            "Sort rows, spilling runs to storage when over budget.",
+            &[("by", "sort key columns, in order")],
+        );
+        r.register_with_doc(
+            "sort_partitioned",
+            || Box::new(crate::sort::partitioned::PartitionedSort::default()),
+            "Radix-partitioned sort: bucket by key prefix, sort buckets independently.",
+            &[("by", "sort key columns, in order")],
+        );
+        r.register_with_doc(
+            "join_hash",
+            || Box::new(crate::join::hash::HashJoin::default()),
+            "Hash join with grace partitioning under memory pressure.",
+            &[
+                ("on", "join key pairs [[left, right], ...]"),
+                ("join_type", "inner | left | right | full"),
+            ],
+        );
+        r.register_with_doc(
+            "join_merge",
+            || Box::new(crate::join::merge::MergeJoin::default()),
+            "Merge join over sorted inputs.",
+            &[
+                ("on", "join key pairs [[left, right], ...]"),
+                ("join_type", "inner | left | right | full"),
+            ],
+        );
+        r.register_with_doc(
+            "approx_dedup",
+            || Box::new(crate::dedup::ApproxDedup::default()),
+            "Drop near-duplicate rows via MinHash/LSH on a text column.",
+            &[
+                ("column", "text column the signature is computed over"),
+                ("threshold", "Jaccard similarity treated as duplicate (default 0.9)"),
+                ("shingle_len", "character shingle length (default 3)"),
+            ],
+        );
+        r.register_with_doc(
+            "one_hot",
+            || Box::new(crate::features::OneHot::default()),
+            "Expand a categorical column into 0/1 indicator columns.",
+            &[
+                ("column", "categorical column to expand"),
+                ("categories", "category list (required; out-of-list values set <column>_other)"),
+                ("max_categories", "category list cap (default 64)"),
+            ],
+        );
+        r.register_with_doc(
+            "feature_hash",
+            || Box::new(crate::features::FeatureHash::default()),
+            "Hash a column into a bounded bucket index (the hashing trick).",
+            &[
+                ("column", "column to hash"),
+                ("buckets", "number of hash buckets (default 64)"),
+            ],
+        );
+        r.register_with_doc(
+            "histogram",
+            || Box::new(crate::histogram::Histogram::default()),
+            "Bucket a numeric column into equal-width bins and emit per-bin counts.",
+            &[
+                ("column", "numeric column to bin"),
+                ("bins", "number of equal-width bins (default 10)"),
+                ("min", "lower bound (default: observed minimum)"),
+                ("max", "upper bound (default: observed maximum)"),
+            ],
+        );
+        r.register_with_doc(
+            "retention",
+            || Box::new(crate::retention::RetentionFilter::default()),
+            "Drop rows whose timestamp column is older than a maximum age.",
+            &[
+                ("column", "timestamp column (Date64 or parsable text)"),
+                ("max_age", "retention window, e.g. '90s', '24h', '30d'"),
+                ("as_of", "reference instant (default: wall clock)"),
+            ],
+        );
+        r.register_with_doc(
+            "window_dedup",
+            || Box::new(crate::dedup::WindowDedup::default()),
+            "Drop redelivered rows: same key within a fuzzy timestamp window.",
+            &[
+                ("key", "identity column"),
+                ("ts_column", "timestamp column"),
+                ("window", "duplicate window, e.g. '5s', '1m' (default 5s)"),
+            ],
+        );
+        r.register_with_doc(
+            "sample",
+            || Box::new(crate::sample::Sample::default()),
+            "Bernoulli (fraction) or reservoir (rows) sampling, seeded from config.",
+            &[
+                ("fraction", "Bernoulli keep probability in [0, 1]"),
+                ("rows", "reservoir size (takes precedence over fraction)"),
+            ],
+        );
+        r.register_with_doc(
+            "scd2_merge",
+            || Box::new(crate::scd::Scd2Merge::default()),
+            "SCD Type 2 merge: close changed dimension rows and insert new versions.",
+            &[
+                ("key", "business key column present in both inputs"),
+                ("compare", "attribute columns for change detection (default: all)"),
+                ("as_of", "effective instant (default: wall clock)"),
+            ],
+        );
+        r.register_with_doc(
+            "train_test_split",
+            || Box::new(crate::split::TrainTestSplit::default()),
+            "Assign rows to train/test deterministically by hashing a key column.",
+            &[
+                ("key", "column whose hash decides the assignment"),
+                ("test_fraction", "fraction of keys on the test side (default 0.2)"),
+                ("mode", "train | test | label (default: label, appends _split)"),
+            ],
+        );
+        r.register_with_doc(
+            "window",
+            || Box::new(WindowOp::default()),
+            "Window functions over partitions with ordering.",
+            &[
+                ("partitions", "partition key columns"),
+                ("order_by", "ordering columns within a partition"),
+                ("functions", "function specs (row_number, sum) with aliases"),
+            ],
+        );
+        // Deprecated names from earlier releases.
+        r.register_alias("sort", "sort_external");
+        r.register_alias("hash_join", "join_hash");
+        r.register_alias("merge_join", "join_merge");
+        r.register_alias("explode", "lateral_explode");
+        r.register_with_doc(
+            "lateral_explode",
+            || Box::new(LateralExplodeOp::default()),
+            "Explode a delimited column into one row per element.",
+            &[
+                ("column", "delimited source column"),
+                ("alias", "output column name for each element"),
+                ("delimiter", "element separator (default ',')"),
+            ],
+        );
         r
     }
 
     pub fn register(&mut self, key: &'static str, f: fn() -> Box<dyn Operator>) {
-        self.makers.insert(key, f);
+        self.register_with_doc(key, f, "", &[]);
+    }
+
+    pub fn register_with_doc(
+        &mut self,
+        key: &'static str,
+        f: fn() -> Box<dyn Operator>,
+        summary: &'static str,
+        config: &'static [(&'static str, &'static str)],
+    ) {
+        self.entries.insert(
+            key,
+            Entry {
+                maker: f,
+                doc: OperatorDoc {
+                    key,
+                    summary,
+                    config,
+                },
+            },
+        );
+    }
+
+    /// Map a deprecated key to its canonical replacement. Emits a one-line
+    /// warning so old plans keep working but users learn the new name.
+    pub fn register_alias(&mut self, deprecated: &'static str, canonical: &'static str) {
+        self.aliases.insert(deprecated, canonical);
+    }
+
+    /// Resolve a possibly-deprecated key to its canonical form. Returns the
+    /// canonical key and whether the input was deprecated; `None` for keys
+    /// the registry has never heard of.
+    pub fn resolve(&self, key: &str) -> Option<(&'static str, bool)> {
+        if let Some(canonical) = self.aliases.get(key) {
+            return Some((canonical, true));
+        }
+        self.entries.get_key_value(key).map(|(k, _)| (*k, false))
     }
 
     pub fn make(&self, key: &str) -> Option<Box<dyn Operator>> {
-        self.makers.get(key).map(|f| f())
+        let (canonical, deprecated) = self.resolve(key)?;
+        if deprecated {
+            eprintln!(
+                "warning: operator key '{}' is deprecated; use '{}'",
+                key, canonical
+            );
+        }
+        self.entries.get(canonical).map(|e| (e.maker)())
+    }
+
+    /// Documentation for all registered operators, sorted by key.
+    pub fn docs(&self) -> Vec<&OperatorDoc> {
+        let mut docs: Vec<&OperatorDoc> = self.entries.values().map(|e| &e.doc).collect();
+        docs.sort_by_key(|d| d.key);
+        docs
+    }
+
+    /// Render operator documentation as markdown.
+    pub fn docs_markdown(&self) -> String {
+        let mut out = String::from("# Operators\n");
+        for doc in self.docs() {
+            out.push_str(&format!("\n## `{}`\n\n{}\n", doc.key, doc.summary));
+            if !doc.config.is_empty() {
+                out.push_str("\nConfig:\n");
+                for (name, desc) in doc.config {
+                    out.push_str(&format!("- `{}`: {}\n", name, desc));
+                }
+            }
+        }
+        out
     }
 }
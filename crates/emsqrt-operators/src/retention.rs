@@ -0,0 +1,120 @@
+//! TTL/retention filter ("retention"): drop rows whose timestamp column is
+//! older than a maximum age relative to a reference instant.
+//!
+//! The reference defaults to the wall clock at evaluation; pin `as_of` for
+//! reproducible runs. Rows with null or unparsable timestamps are dropped —
+//! a row that cannot prove its freshness is treated as expired.
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::time::{parse_datetime, parse_duration_ms};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+#[derive(Default)]
+pub struct RetentionFilter {
+    /// Timestamp column (Date64 or parsable text).
+    pub column: String,
+    /// Maximum age, e.g. "90s", "15m", "24h", "30d".
+    pub max_age: String,
+    /// Reference instant ("YYYY-MM-DD[ HH:MM:SS]"); wall clock when unset.
+    pub as_of: Option<String>,
+}
+
+impl RetentionFilter {
+    fn cutoff_ms(&self) -> Result<i64, OpError> {
+        let max_age = parse_duration_ms(&self.max_age).ok_or_else(|| {
+            OpError::Exec(format!(
+                "invalid retention max_age '{}' (expected e.g. '30d', '24h')",
+                self.max_age
+            ))
+        })?;
+        let as_of = match &self.as_of {
+            Some(text) => parse_datetime(text).ok_or_else(|| {
+                OpError::Exec(format!("invalid retention as_of '{}'", text))
+            })?,
+            None => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64,
+        };
+        Ok(as_of - max_age)
+    }
+}
+
+impl Operator for RetentionFilter {
+    fn name(&self) -> &'static str {
+        "retention"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 0,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("retention expects one input".into()))?
+            .clone();
+        if parse_duration_ms(&self.max_age).is_none() {
+            return Err(OpError::Plan(format!(
+                "invalid retention max_age '{}'",
+                self.max_age
+            )));
+        }
+        if !schema.fields.iter().any(|f| f.name == self.column) {
+            return Err(OpError::Plan(format!(
+                "retention column '{}' not in input schema",
+                self.column
+            )));
+        }
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let ts_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.column)
+            .ok_or_else(|| {
+                OpError::Exec(format!("retention column '{}' not found", self.column))
+            })?;
+
+        let cutoff = self.cutoff_ms()?;
+        let keep: Vec<usize> = ts_col
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| match v {
+                Scalar::Date64(ms) => *ms >= cutoff,
+                Scalar::I64(ms) => *ms >= cutoff,
+                Scalar::Str(text) => parse_datetime(text).is_some_and(|ms| ms >= cutoff),
+                _ => false,
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        Ok(RowBatch {
+            columns: input
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: keep.iter().map(|&i| c.values[i].clone()).collect(),
+                })
+                .collect(),
+        })
+    }
+}
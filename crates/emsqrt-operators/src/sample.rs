@@ -0,0 +1,123 @@
+//! Sample operator: Bernoulli (fraction) and reservoir (rows) sampling.
+//!
+//! Sampling is deterministic given `seed` (wired from `EngineConfig.seed`);
+//! without a seed a fixed default is used so runs stay reproducible.
+
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::{Column, RowBatch};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+/// Default seed when `EngineConfig.seed` is unset.
+const DEFAULT_SEED: u64 = 0x5eed_5eed_5eed_5eed;
+
+#[derive(Default)]
+pub struct Sample {
+    /// Bernoulli sampling probability in [0, 1].
+    pub fraction: Option<f64>,
+    /// Reservoir sample size (bounded memory); takes precedence over fraction.
+    pub rows: Option<usize>,
+    /// Seed for the deterministic PRNG.
+    pub seed: Option<u64>,
+}
+
+/// splitmix64: tiny deterministic PRNG, good enough for sampling decisions.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform f64 in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl Operator for Sample {
+    fn name(&self) -> &'static str {
+        "sample"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // Bernoulli is streaming; reservoir holds at most `rows` rows.
+        Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 0,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        if self.fraction.is_none() && self.rows.is_none() {
+            return Err(OpError::Plan(
+                "sample needs 'fraction' or 'rows'".into(),
+            ));
+        }
+        if let Some(f) = self.fraction {
+            if !(0.0..=1.0).contains(&f) {
+                return Err(OpError::Plan(format!(
+                    "sample fraction must be in [0, 1], got {}",
+                    f
+                )));
+            }
+        }
+        let schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("sample expects one input".into()))?
+            .clone();
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let num_rows = input.num_rows();
+        let mut rng = SplitMix64::new(self.seed.unwrap_or(DEFAULT_SEED));
+
+        let keep: Vec<usize> = if let Some(k) = self.rows {
+            // Reservoir sampling (algorithm R) over this block, then restore
+            // input order so downstream operators see stable ordering.
+            let mut reservoir: Vec<usize> = (0..num_rows.min(k)).collect();
+            for row_idx in k..num_rows {
+                let j = (rng.next_u64() % (row_idx as u64 + 1)) as usize;
+                if j < k {
+                    reservoir[j] = row_idx;
+                }
+            }
+            reservoir.sort_unstable();
+            reservoir
+        } else {
+            let fraction = self.fraction.unwrap_or(1.0);
+            (0..num_rows)
+                .filter(|_| rng.next_f64() < fraction)
+                .collect()
+        };
+
+        let columns = input
+            .columns
+            .iter()
+            .map(|col| Column {
+                name: col.name.clone(),
+                values: keep.iter().map(|&i| col.values[i].clone()).collect(),
+            })
+            .collect();
+
+        Ok(RowBatch { columns })
+    }
+}
@@ -0,0 +1,232 @@
+//! Slowly-changing-dimension merge, Type 2 ("scd2_merge").
+//!
+//! Binary operator: input 0 is the existing dimension table, input 1 the
+//! incoming updates. Changed keys get their current row closed
+//! (`valid_to` = `as_of`, `is_current` = false) and a fresh current row
+//! appended; new keys — and keys whose existing rows are all historical —
+//! are inserted as current; untouched and historical rows pass through.
+//! Updates identical to the current row are no-ops.
+
+use std::collections::{HashMap, HashSet};
+
+use emsqrt_core::prelude::{DataType, Field, Schema};
+use emsqrt_core::time::parse_datetime;
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+#[derive(Default)]
+pub struct Scd2Merge {
+    /// Business key column, present in both inputs.
+    pub key: String,
+    /// Attribute columns compared for change detection (empty = every
+    /// update column except the key).
+    pub compare: Vec<String>,
+    /// Effective instant for openings/closings ("YYYY-MM-DD[ HH:MM:SS]");
+    /// wall clock when unset.
+    pub as_of: Option<String>,
+}
+
+/// Metadata column names appended to the dimension.
+const VALID_FROM: &str = "valid_from";
+const VALID_TO: &str = "valid_to";
+const IS_CURRENT: &str = "is_current";
+
+impl Scd2Merge {
+    fn as_of_ms(&self) -> Result<i64, OpError> {
+        match &self.as_of {
+            Some(text) => parse_datetime(text)
+                .ok_or_else(|| OpError::Exec(format!("invalid scd2 as_of '{}'", text))),
+            None => Ok(std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64),
+        }
+    }
+}
+
+/// Column accessor helpers over a batch.
+fn col<'a>(batch: &'a RowBatch, name: &str) -> Option<&'a Column> {
+    batch.columns.iter().find(|c| c.name == name)
+}
+
+fn value<'a>(batch: &'a RowBatch, name: &str, row: usize) -> &'a Scalar {
+    col(batch, name)
+        .map(|c| &c.values[row])
+        .unwrap_or(&Scalar::Null)
+}
+
+fn key_text(value: &Scalar) -> String {
+    match value {
+        Scalar::Str(s) => s.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+impl Operator for Scd2Merge {
+    fn name(&self) -> &'static str {
+        "scd2_merge"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // Holds the full dimension plus updates in memory.
+        Footprint {
+            bytes_per_row: 2,
+            overhead_bytes: 128 * 1024,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        if input_schemas.len() != 2 {
+            return Err(OpError::Plan("scd2_merge expects two inputs".into()));
+        }
+        if self.key.is_empty() {
+            return Err(OpError::Plan("scd2_merge needs a 'key'".into()));
+        }
+
+        // Output: the update schema plus the SCD metadata columns.
+        let mut schema = input_schemas[1].clone();
+        for (name, data_type) in [
+            (VALID_FROM, DataType::Date64),
+            (VALID_TO, DataType::Date64),
+            (IS_CURRENT, DataType::Boolean),
+        ] {
+            if !schema.fields.iter().any(|f| f.name == name) {
+                schema.fields.push(Field::new(name, data_type, true));
+            }
+        }
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)).exclusive())
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        if inputs.len() != 2 {
+            return Err(OpError::Exec("scd2_merge needs two inputs".into()));
+        }
+        let existing = &inputs[0];
+        let updates = &inputs[1];
+
+        let update_key_col = col(updates, &self.key)
+            .ok_or_else(|| OpError::Exec(format!("update key column '{}' missing", self.key)))?;
+
+        // Attribute columns: configured, or every update column but the key.
+        let compare: Vec<String> = if self.compare.is_empty() {
+            updates
+                .columns
+                .iter()
+                .map(|c| c.name.clone())
+                .filter(|name| name != &self.key)
+                .collect()
+        } else {
+            self.compare.clone()
+        };
+
+        // Output layout: update columns + metadata.
+        let mut names: Vec<String> = updates.columns.iter().map(|c| c.name.clone()).collect();
+        for meta in [VALID_FROM, VALID_TO, IS_CURRENT] {
+            if !names.iter().any(|n| n == meta) {
+                names.push(meta.to_string());
+            }
+        }
+        let mut out: Vec<Column> = names
+            .iter()
+            .map(|name| Column {
+                name: name.clone(),
+                values: Vec::new(),
+            })
+            .collect();
+
+        let as_of = self.as_of_ms()?;
+
+        // Index updates by key (last update per key wins within a block).
+        let mut update_rows: HashMap<String, usize> = HashMap::new();
+        for row in 0..updates.num_rows() {
+            update_rows.insert(key_text(&update_key_col.values[row]), row);
+        }
+
+        let mut push_row = |values: Vec<(&str, Scalar)>| {
+            for (name, scalar) in values {
+                if let Some(column) = out.iter_mut().find(|c| c.name == name) {
+                    column.values.push(scalar);
+                }
+            }
+        };
+        let emit_from_updates =
+            |row: usize, valid_from: i64| -> Vec<(&str, Scalar)> {
+                let mut values: Vec<(&str, Scalar)> = updates
+                    .columns
+                    .iter()
+                    .map(|c| (c.name.as_str(), c.values[row].clone()))
+                    .collect();
+                values.push((VALID_FROM, Scalar::Date64(valid_from)));
+                values.push((VALID_TO, Scalar::Null));
+                values.push((IS_CURRENT, Scalar::Bool(true)));
+                values
+            };
+
+        // One pass over the dimension up front: the keys that still have a
+        // current row. Update keys outside this set — brand-new or with all
+        // versions closed out — get a fresh current row below, without
+        // rescanning the dimension per update.
+        let mut current_keys: HashSet<String> = HashSet::new();
+        for row in 0..existing.num_rows() {
+            if matches!(
+                value(existing, IS_CURRENT, row),
+                Scalar::Bool(true) | Scalar::Null
+            ) {
+                current_keys.insert(key_text(value(existing, &self.key, row)));
+            }
+        }
+
+        // Walk the existing dimension.
+        for row in 0..existing.num_rows() {
+            let key = key_text(value(existing, &self.key, row));
+            let is_current = matches!(
+                value(existing, IS_CURRENT, row),
+                Scalar::Bool(true) | Scalar::Null
+            );
+
+            let mut carried: Vec<(&str, Scalar)> = names
+                .iter()
+                .map(|name| (name.as_str(), value(existing, name, row).clone()))
+                .collect();
+
+            if is_current {
+                if let Some(&update_row) = update_rows.get(&key) {
+                    let changed = compare.iter().any(|attr| {
+                        value(existing, attr, row) != value(updates, attr, update_row)
+                    });
+                    if changed {
+                        // Close the current row and open a fresh one.
+                        for (name, scalar) in carried.iter_mut() {
+                            if *name == VALID_TO {
+                                *scalar = Scalar::Date64(as_of);
+                            } else if *name == IS_CURRENT {
+                                *scalar = Scalar::Bool(false);
+                            }
+                        }
+                        push_row(carried);
+                        push_row(emit_from_updates(update_row, as_of));
+                        continue;
+                    }
+                }
+            }
+            push_row(carried);
+        }
+
+        // Brand-new keys become current rows, and so do keys the dimension
+        // only knows as closed history (a deleted entity coming back).
+        for row in 0..updates.num_rows() {
+            let key = key_text(&update_key_col.values[row]);
+            if !current_keys.contains(&key) && update_rows.get(&key) == Some(&row) {
+                push_row(emit_from_updates(row, as_of));
+            }
+        }
+
+        Ok(RowBatch { columns: out })
+    }
+}
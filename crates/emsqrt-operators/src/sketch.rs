@@ -0,0 +1,328 @@
+//! Memory-bounded sketches for approximate aggregation.
+//!
+//! - `Hll`: HyperLogLog cardinality estimator (fixed 2^12 registers).
+//! - `TDigest`: simplified t-digest for quantile estimation with a bounded
+//!   centroid count.
+//! - `MinHash` / `LshIndex`: similarity signatures and locality-sensitive
+//!   banding for approximate duplicate detection.
+//!
+//! Both are selected by the aggregate operator when approximate aggregation
+//! is enabled in `EngineConfig`; exact variants are used otherwise.
+
+/// HyperLogLog precision (number of index bits). 2^12 registers = 4 KiB,
+/// ~1.6% relative error.
+const HLL_P: u32 = 12;
+const HLL_M: usize = 1 << HLL_P;
+
+/// HyperLogLog cardinality estimator over 64-bit hashes.
+#[derive(Debug, Clone)]
+pub struct Hll {
+    registers: Vec<u8>,
+}
+
+impl Default for Hll {
+    fn default() -> Self {
+        Self {
+            registers: vec![0u8; HLL_M],
+        }
+    }
+}
+
+impl Hll {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a pre-hashed item.
+    pub fn insert_hash(&mut self, hash: u64) {
+        let idx = (hash >> (64 - HLL_P)) as usize;
+        let rest = hash << HLL_P;
+        // Rank = position of the leftmost 1-bit in the remaining bits (1-based).
+        let rank = (rest.leading_zeros() + 1).min(64 - HLL_P + 1) as u8;
+        if rank > self.registers[idx] {
+            self.registers[idx] = rank;
+        }
+    }
+
+    /// Insert raw bytes (hashed with BLAKE3).
+    pub fn insert(&mut self, bytes: &[u8]) {
+        let hash = blake3::hash(bytes);
+        let h64 = u64::from_le_bytes(hash.as_bytes()[0..8].try_into().unwrap());
+        self.insert_hash(h64);
+    }
+
+    /// Merge another sketch into this one (register-wise max).
+    pub fn merge(&mut self, other: &Hll) {
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *o > *r {
+                *r = *o;
+            }
+        }
+    }
+
+    /// Estimate the number of distinct items inserted.
+    pub fn estimate(&self) -> u64 {
+        let m = HLL_M as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let mut sum = 0.0f64;
+        let mut zeros = 0usize;
+        for &r in &self.registers {
+            sum += 2f64.powi(-(r as i32));
+            if r == 0 {
+                zeros += 1;
+            }
+        }
+
+        let raw = alpha * m * m / sum;
+
+        // Small-range correction: linear counting when many registers are empty.
+        if raw <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as u64;
+        }
+        raw.round() as u64
+    }
+}
+
+/// Maximum centroids kept by `TDigest` after compression.
+const TDIGEST_MAX_CENTROIDS: usize = 100;
+
+/// A (mean, weight) cluster of inserted values.
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Simplified t-digest: values are buffered and periodically compressed into
+/// at most `TDIGEST_MAX_CENTROIDS` weight-balanced centroids, keeping memory
+/// bounded regardless of input size.
+#[derive(Debug, Clone, Default)]
+pub struct TDigest {
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    total_weight: f64,
+}
+
+impl TDigest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        self.buffer.push(value);
+        self.total_weight += 1.0;
+        if self.buffer.len() >= 2 * TDIGEST_MAX_CENTROIDS {
+            self.compress();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_weight == 0.0
+    }
+
+    /// Fold buffered values into the centroid list and re-cluster down to the
+    /// centroid budget, keeping clusters smaller near the tails.
+    fn compress(&mut self) {
+        let mut points: Vec<Centroid> = self.centroids.drain(..).collect();
+        points.extend(self.buffer.drain(..).map(|v| Centroid {
+            mean: v,
+            weight: 1.0,
+        }));
+        if points.is_empty() {
+            return;
+        }
+        points.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Scale-function-lite: cap each merged cluster's weight at
+        // total/TDIGEST_MAX_CENTROIDS, halved near the extremes so tail
+        // quantiles stay sharp.
+        let total: f64 = points.iter().map(|c| c.weight).sum();
+        let mut merged: Vec<Centroid> = Vec::with_capacity(TDIGEST_MAX_CENTROIDS);
+        let mut seen = 0.0f64;
+        for p in points {
+            let q = (seen + p.weight / 2.0) / total;
+            let tail = (q.min(1.0 - q) * 4.0).min(1.0); // 0 at tails, 1 mid-range
+            let cap = (total / TDIGEST_MAX_CENTROIDS as f64) * (0.5 + tail);
+            match merged.last_mut() {
+                Some(last) if last.weight + p.weight <= cap => {
+                    let w = last.weight + p.weight;
+                    last.mean = (last.mean * last.weight + p.mean * p.weight) / w;
+                    last.weight = w;
+                }
+                _ => merged.push(p),
+            }
+            seen += p.weight;
+        }
+        self.centroids = merged;
+    }
+
+    /// Estimate the value at quantile `q` (0.0..=1.0).
+    pub fn quantile(&mut self, q: f64) -> Option<f64> {
+        self.compress();
+        if self.centroids.is_empty() {
+            return None;
+        }
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = q.clamp(0.0, 1.0) * total;
+
+        let mut cum = 0.0f64;
+        for (i, c) in self.centroids.iter().enumerate() {
+            let next = cum + c.weight;
+            if target <= next {
+                // Interpolate toward the neighbouring centroid.
+                let within = if c.weight > 0.0 {
+                    (target - cum) / c.weight
+                } else {
+                    0.5
+                };
+                let lo = if i > 0 {
+                    (self.centroids[i - 1].mean + c.mean) / 2.0
+                } else {
+                    c.mean
+                };
+                let hi = if i + 1 < self.centroids.len() {
+                    (c.mean + self.centroids[i + 1].mean) / 2.0
+                } else {
+                    c.mean
+                };
+                return Some(lo + (hi - lo) * within);
+            }
+            cum = next;
+        }
+        self.centroids.last().map(|c| c.mean)
+    }
+}
+
+/// Number of hash functions in a MinHash signature.
+pub const MINHASH_SIGNATURE_LEN: usize = 64;
+
+/// MinHash signature of a token set. Two signatures' agreement rate
+/// estimates the Jaccard similarity of the underlying sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinHash {
+    signature: [u64; MINHASH_SIGNATURE_LEN],
+}
+
+impl MinHash {
+    /// Signature over character shingles of `text` of length `shingle_len`.
+    pub fn from_text(text: &str, shingle_len: usize) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let shingle_len = shingle_len.max(1);
+        let mut signature = [u64::MAX; MINHASH_SIGNATURE_LEN];
+
+        let mut shingle = String::new();
+        let count = chars.len().saturating_sub(shingle_len - 1).max(1);
+        for start in 0..count {
+            shingle.clear();
+            shingle.extend(chars.iter().skip(start).take(shingle_len));
+
+            let base = blake3::hash(shingle.as_bytes());
+            let h1 = u64::from_le_bytes(base.as_bytes()[0..8].try_into().unwrap());
+            let h2 = u64::from_le_bytes(base.as_bytes()[8..16].try_into().unwrap());
+            // Standard double-hashing trick: h_i = h1 + i * h2.
+            for (i, slot) in signature.iter_mut().enumerate() {
+                let h = h1.wrapping_add((i as u64).wrapping_mul(h2));
+                if h < *slot {
+                    *slot = h;
+                }
+            }
+        }
+
+        Self { signature }
+    }
+
+    /// Estimated Jaccard similarity with another signature.
+    pub fn similarity(&self, other: &MinHash) -> f64 {
+        let matches = self
+            .signature
+            .iter()
+            .zip(other.signature.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+        matches as f64 / MINHASH_SIGNATURE_LEN as f64
+    }
+}
+
+/// LSH index over MinHash signatures: signatures are cut into bands and
+/// bucketed, so near-duplicates collide in at least one band with high
+/// probability while unrelated rows rarely do.
+#[derive(Debug, Default)]
+pub struct LshIndex {
+    /// (band index, band hash) -> candidate ids
+    buckets: std::collections::HashMap<(usize, u64), Vec<usize>>,
+    signatures: Vec<MinHash>,
+}
+
+/// Bands of 4 rows each over the 64-slot signature (16 bands).
+const LSH_ROWS_PER_BAND: usize = 4;
+
+impl LshIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    fn band_hashes(sig: &MinHash) -> Vec<u64> {
+        sig.signature
+            .chunks(LSH_ROWS_PER_BAND)
+            .map(|band| {
+                let mut hasher = blake3::Hasher::new();
+                for v in band {
+                    hasher.update(&v.to_le_bytes());
+                }
+                u64::from_le_bytes(hasher.finalize().as_bytes()[0..8].try_into().unwrap())
+            })
+            .collect()
+    }
+
+    /// Ids of previously inserted signatures sharing at least one band.
+    pub fn candidates(&self, sig: &MinHash) -> Vec<usize> {
+        let mut out = Vec::new();
+        for (band_idx, band_hash) in Self::band_hashes(sig).into_iter().enumerate() {
+            if let Some(ids) = self.buckets.get(&(band_idx, band_hash)) {
+                for id in ids {
+                    if !out.contains(id) {
+                        out.push(*id);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Insert a signature, returning its id.
+    pub fn insert(&mut self, sig: MinHash) -> usize {
+        let id = self.signatures.len();
+        for (band_idx, band_hash) in Self::band_hashes(&sig).into_iter().enumerate() {
+            self.buckets
+                .entry((band_idx, band_hash))
+                .or_default()
+                .push(id);
+        }
+        self.signatures.push(sig);
+        id
+    }
+
+    pub fn signature(&self, id: usize) -> Option<&MinHash> {
+        self.signatures.get(id)
+    }
+
+    /// True if any indexed signature is at least `threshold` similar
+    /// (candidates via banding, verified against the real signature).
+    pub fn contains_similar(&self, sig: &MinHash, threshold: f64) -> bool {
+        self.candidates(sig)
+            .into_iter()
+            .any(|id| self.signatures[id].similarity(sig) >= threshold)
+    }
+}
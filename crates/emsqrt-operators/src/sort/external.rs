@@ -5,7 +5,6 @@ use std::collections::BinaryHeap;
 use std::sync::{Arc, Mutex};
 
 use emsqrt_core::budget::MemoryBudget;
-use emsqrt_core::id::SpillId;
 use emsqrt_core::prelude::Schema;
 use emsqrt_core::types::{RowBatch, Scalar};
 use emsqrt_mem::guard::BudgetGuardImpl;
@@ -20,19 +19,12 @@ use super::run::{RunGenerator, RunMeta};
 ///
 /// For small inputs (fits in memory), sorts in-place.
 /// For large inputs, generates sorted runs and performs k-way merge.
+#[derive(Default)]
 pub struct ExternalSort {
     pub by: Vec<String>, // sort keys
     pub spill_mgr: Option<Arc<Mutex<SpillManager>>>,
 }
 
-impl Default for ExternalSort {
-    fn default() -> Self {
-        Self {
-            by: Vec::new(),
-            spill_mgr: None,
-        }
-    }
-}
 
 impl Operator for ExternalSort {
     fn name(&self) -> &'static str {
@@ -48,11 +40,12 @@ impl Operator for ExternalSort {
     }
 
     fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
-        let schema = input_schemas
-            .get(0)
+        let schema = input_schemas.first()
             .ok_or_else(|| OpError::Plan("sort expects one input".into()))?
             .clone();
-        Ok(OpPlan::new(schema, self.memory_need(0, 0)).with_partitions(self.by.clone()))
+        Ok(OpPlan::new(schema, self.memory_need(0, 0))
+            .with_partitions(self.by.clone())
+            .exclusive())
     }
 
     fn eval_block(
@@ -60,8 +53,7 @@ impl Operator for ExternalSort {
         inputs: &[RowBatch],
         budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("missing input".into()))?;
 
         // If no spill manager, do in-memory sort only
@@ -76,14 +68,8 @@ impl Operator for ExternalSort {
         let spill_mgr = self.spill_mgr.as_ref().unwrap();
         let mut spill_mgr = spill_mgr.lock().unwrap();
 
-        // Generate a unique spill ID for this sort operation
-        // In production, this would come from a global counter or UUID
-        let spill_id = SpillId::new(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_nanos() as u64,
-        );
+        // Allocate a globally unique spill ID for this sort operation
+        let spill_id = spill_mgr.allocate_spill_id();
 
         // For simplicity in this single-batch operator, treat input as one run
         // In a real pipeline, we'd accumulate multiple blocks
@@ -129,6 +115,11 @@ fn k_way_merge(
     spill_mgr: &mut SpillManager,
     budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
 ) -> Result<RowBatch, OpError> {
+    // Kick off read-ahead for every run segment so the merge loop below
+    // consumes prefetched bytes instead of waiting on storage per run.
+    let segments: Vec<_> = runs.iter().map(|r| r.segment.clone()).collect();
+    spill_mgr.prefetch(&segments);
+
     // Read all runs into memory (for simplicity; real impl would stream)
     let mut run_batches: Vec<RowBatch> = Vec::new();
     for run in &runs {
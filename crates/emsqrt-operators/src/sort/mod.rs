@@ -1,4 +1,5 @@
 //! Sort operators (module).
 
 pub mod external;
+pub mod partitioned;
 pub mod run;
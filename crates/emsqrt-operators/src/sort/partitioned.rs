@@ -0,0 +1,189 @@
+//! Radix-partitioned external sort: an alternative to run-and-merge.
+//!
+//! Rows are bucketed by an order-preserving byte prefix of the leading sort
+//! key (type tier + most significant byte), buckets are optionally spilled,
+//! then each bucket is sorted independently and emitted in bucket order —
+//! no k-way merge phase, and peak memory is bounded by the largest bucket.
+
+use std::sync::{Arc, Mutex};
+
+use emsqrt_core::budget::MemoryBudget;
+use emsqrt_core::prelude::Schema;
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::BudgetGuardImpl;
+use emsqrt_mem::SpillManager;
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{OpError, Operator};
+
+/// Radix-partitioned sort operator ("sort_partitioned").
+#[derive(Default)]
+pub struct PartitionedSort {
+    pub by: Vec<String>, // sort keys
+    pub spill_mgr: Option<Arc<Mutex<SpillManager>>>,
+}
+
+/// Order-preserving bucket id: high byte is the scalar's type tier (matching
+/// `scalar_cmp`'s mixed-type ordering), low byte an order-preserving prefix
+/// of the value.
+fn bucket_of(scalar: &Scalar) -> u16 {
+    use Scalar::*;
+
+    let (tier, byte): (u8, u8) = match scalar {
+        Null => (0, 0),
+        Bool(b) => (1, *b as u8),
+        I32(v) => (2, msb_of_i64(*v as i64)),
+        I64(v) => (3, msb_of_i64(*v)),
+        F32(v) => (4, msb_of_f64(*v as f64)),
+        F64(v) => (5, msb_of_f64(*v)),
+        Str(s) => (6, s.as_bytes().first().copied().unwrap_or(0)),
+        Bin(b) => (7, b.first().copied().unwrap_or(0)),
+        Date64(v) => (8, msb_of_i64(*v)),
+    };
+    ((tier as u16) << 8) | byte as u16
+}
+
+/// Most significant byte of a sign-flipped integer (monotonic in value).
+fn msb_of_i64(v: i64) -> u8 {
+    ((v as u64) ^ (1u64 << 63)).to_be_bytes()[0]
+}
+
+/// Most significant byte of an order-preserving IEEE-754 bit transform.
+fn msb_of_f64(v: f64) -> u8 {
+    let bits = v.to_bits();
+    let ordered = if bits >> 63 == 1 { !bits } else { bits | (1 << 63) };
+    ordered.to_be_bytes()[0]
+}
+
+impl Operator for PartitionedSort {
+    fn name(&self) -> &'static str {
+        "sort_partitioned"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        // Peak memory is one bucket plus bucketing overhead.
+        Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 256 * 1024,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("sort expects one input".into()))?
+            .clone();
+        Ok(OpPlan::new(schema, self.memory_need(0, 0))
+            .with_partitions(self.by.clone())
+            .exclusive())
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        if self.by.is_empty() {
+            return Err(OpError::Exec("sort keys are empty".into()));
+        }
+
+        let key_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.by[0])
+            .ok_or_else(|| {
+                OpError::Exec(format!("sort key column '{}' not found", self.by[0]))
+            })?;
+
+        // Bucket rows by the leading key's order-preserving prefix.
+        let mut buckets: std::collections::BTreeMap<u16, Vec<usize>> =
+            std::collections::BTreeMap::new();
+        for (row_idx, value) in key_col.values.iter().enumerate() {
+            buckets.entry(bucket_of(value)).or_default().push(row_idx);
+        }
+
+        // Optionally spill each bucket so only one is resident while sorting.
+        let spilled = match &self.spill_mgr {
+            Some(spill_mgr) if buckets.len() > 1 => {
+                let mut spill_mgr = spill_mgr.lock().unwrap();
+                let spill_id = spill_mgr.allocate_spill_id();
+                let mut metas = Vec::with_capacity(buckets.len());
+                for rows in buckets.values() {
+                    let bucket_batch = take_rows(input, rows);
+                    let run_idx = spill_mgr.next_run_index();
+                    let meta = spill_mgr
+                        .write_batch_in("sort_partitioned", &bucket_batch, spill_id, run_idx)
+                        .map_err(|e| OpError::Exec(format!("failed to spill bucket: {}", e)))?;
+                    metas.push(meta);
+                }
+                Some(metas)
+            }
+            _ => None,
+        };
+
+        // Sort each bucket independently and append in bucket order.
+        let mut output = RowBatch {
+            columns: input
+                .columns
+                .iter()
+                .map(|c| Column {
+                    name: c.name.clone(),
+                    values: Vec::with_capacity(c.values.len()),
+                })
+                .collect(),
+        };
+
+        match spilled {
+            Some(metas) => {
+                let spill_mgr = self.spill_mgr.as_ref().expect("spilled above");
+                let mut spill_mgr = spill_mgr.lock().unwrap();
+                for meta in &metas {
+                    let mut bucket_batch = spill_mgr
+                        .read_batch(meta, budget)
+                        .map_err(|e| OpError::Exec(format!("failed to read bucket: {}", e)))?;
+                    bucket_batch
+                        .sort_by_columns(&self.by)
+                        .map_err(OpError::Exec)?;
+                    append(&mut output, &bucket_batch);
+                    let _ = spill_mgr.delete_segment(&meta.name);
+                }
+            }
+            None => {
+                for rows in buckets.values() {
+                    let mut bucket_batch = take_rows(input, rows);
+                    bucket_batch
+                        .sort_by_columns(&self.by)
+                        .map_err(OpError::Exec)?;
+                    append(&mut output, &bucket_batch);
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Copy the given row indices out of a batch.
+fn take_rows(batch: &RowBatch, rows: &[usize]) -> RowBatch {
+    RowBatch {
+        columns: batch
+            .columns
+            .iter()
+            .map(|c| Column {
+                name: c.name.clone(),
+                values: rows.iter().map(|&i| c.values[i].clone()).collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Append all rows of `src` to `dst` (same column layout).
+fn append(dst: &mut RowBatch, src: &RowBatch) {
+    for (dst_col, src_col) in dst.columns.iter_mut().zip(&src.columns) {
+        dst_col.values.extend(src_col.values.iter().cloned());
+    }
+}
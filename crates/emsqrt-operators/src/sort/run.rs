@@ -97,7 +97,7 @@ impl RunGenerator {
         // Write to spill
         let run_index = spill_mgr.next_run_index();
         let segment = spill_mgr
-            .write_batch(&merged, self.spill_id, run_index)
+            .write_batch_in("sort_external", &merged, self.spill_id, run_index)
             .map_err(|e| OpError::Exec(format!("spill write: {}", e)))?;
 
         let run_meta = RunMeta {
@@ -0,0 +1,164 @@
+//! Train/test split ("train_test_split") with deterministic hashing.
+//!
+//! Each row is assigned by hashing its key column (seeded), so the same row
+//! lands on the same side in every run and across pipelines — the property
+//! that matters when features and labels are produced by separate jobs.
+
+use emsqrt_core::hash::RowHasher;
+use emsqrt_core::prelude::{DataType, Field, Schema};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+
+use crate::plan::{Footprint, OpPlan};
+use crate::traits::{MemoryBudget, OpError, Operator};
+
+/// What the operator emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitMode {
+    /// Keep only training rows.
+    Train,
+    /// Keep only test rows.
+    Test,
+    /// Keep everything, appending a `_split` label column.
+    Label,
+}
+
+impl SplitMode {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "train" => Ok(SplitMode::Train),
+            "test" => Ok(SplitMode::Test),
+            "label" => Ok(SplitMode::Label),
+            other => Err(format!("unknown split mode '{}'", other)),
+        }
+    }
+}
+
+pub struct TrainTestSplit {
+    /// Column whose value decides the assignment (e.g. a user id, so all of
+    /// a user's rows land on the same side).
+    pub key: String,
+    /// Fraction of keys assigned to the test side, in [0, 1].
+    pub test_fraction: f64,
+    /// Emit mode; `Label` appends a `_split` column instead of filtering.
+    pub mode: SplitMode,
+    /// Hash seed (wired from `EngineConfig.seed`).
+    pub seed: u64,
+}
+
+impl Default for TrainTestSplit {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            test_fraction: 0.2,
+            mode: SplitMode::Label,
+            seed: 0,
+        }
+    }
+}
+
+impl TrainTestSplit {
+    fn is_test(&self, hasher: &RowHasher, value: &Scalar) -> bool {
+        let text = match value {
+            Scalar::Str(s) => s.clone(),
+            other => format!("{:?}", other),
+        };
+        let hash = hasher.hash_u64(text.as_bytes());
+        // Map the hash to [0, 1) and compare against the fraction.
+        (hash as f64 / u64::MAX as f64) < self.test_fraction
+    }
+}
+
+impl Operator for TrainTestSplit {
+    fn name(&self) -> &'static str {
+        "train_test_split"
+    }
+
+    fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+        Footprint {
+            bytes_per_row: 1,
+            overhead_bytes: 0,
+        }
+    }
+
+    fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+        let mut schema = input_schemas
+            .first()
+            .ok_or_else(|| OpError::Plan("train_test_split expects one input".into()))?
+            .clone();
+        if self.key.is_empty() {
+            return Err(OpError::Plan("train_test_split needs a 'key'".into()));
+        }
+        if !(0.0..=1.0).contains(&self.test_fraction) {
+            return Err(OpError::Plan(format!(
+                "test_fraction must be in [0, 1], got {}",
+                self.test_fraction
+            )));
+        }
+        if self.mode == SplitMode::Label {
+            schema
+                .fields
+                .push(Field::new("_split", DataType::Utf8, false));
+        }
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+    }
+
+    fn eval_block(
+        &self,
+        inputs: &[RowBatch],
+        _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+    ) -> Result<RowBatch, OpError> {
+        let input = inputs
+            .first()
+            .ok_or_else(|| OpError::Exec("missing input".into()))?;
+
+        let key_col = input
+            .columns
+            .iter()
+            .find(|c| c.name == self.key)
+            .ok_or_else(|| {
+                OpError::Exec(format!("split key column '{}' not found", self.key))
+            })?;
+
+        let hasher = RowHasher::new(Default::default(), self.seed);
+        let assignments: Vec<bool> = key_col
+            .values
+            .iter()
+            .map(|v| self.is_test(&hasher, v))
+            .collect();
+
+        match self.mode {
+            SplitMode::Label => {
+                let mut columns = input.columns.clone();
+                columns.push(Column {
+                    name: "_split".to_string(),
+                    values: assignments
+                        .iter()
+                        .map(|is_test| {
+                            Scalar::Str(if *is_test { "test" } else { "train" }.to_string())
+                        })
+                        .collect(),
+                });
+                Ok(RowBatch { columns })
+            }
+            mode => {
+                let want_test = mode == SplitMode::Test;
+                let keep: Vec<usize> = assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, is_test)| **is_test == want_test)
+                    .map(|(i, _)| i)
+                    .collect();
+                Ok(RowBatch {
+                    columns: input
+                        .columns
+                        .iter()
+                        .map(|c| Column {
+                            name: c.name.clone(),
+                            values: keep.iter().map(|&i| c.values[i].clone()).collect(),
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+}
@@ -57,7 +57,8 @@ impl Operator for WindowOp {
                 true,
             ));
         }
-        Ok(OpPlan::new(schema, self.memory_need(0, 0)))
+        // Window functions need whole partitions before emitting.
+        Ok(OpPlan::new(schema, self.memory_need(0, 0)).exclusive())
     }
 
     fn eval_block(
@@ -65,8 +66,7 @@ impl Operator for WindowOp {
         inputs: &[RowBatch],
         _budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("window operator missing input batch".into()))?;
         let num_rows = input.num_rows();
         let mut output = input.clone();
@@ -126,7 +126,7 @@ impl Operator for WindowOp {
                             OpError::Schema(format!("sum column '{column}' not found"))
                         })?;
                         let value = value_as_f64(&input.columns[*col_idx].values[sorted_pos])
-                            .map_err(|e| OpError::Exec(e))?;
+                            .map_err(OpError::Exec)?;
                         running_sums[fn_idx] += value;
                         computed_columns[fn_idx][sorted_pos] = Scalar::F64(running_sums[fn_idx]);
                     }
@@ -134,7 +134,7 @@ impl Operator for WindowOp {
             }
         }
 
-        for (spec, values) in self.functions.iter().zip(computed_columns.into_iter()) {
+        for (spec, values) in self.functions.iter().zip(computed_columns) {
             output.columns.push(Column {
                 name: spec.alias.clone(),
                 values,
@@ -192,8 +192,7 @@ impl Operator for LateralExplodeOp {
         inputs: &[RowBatch],
         _budget: &dyn MemoryBudget<Guard = BudgetGuardImpl>,
     ) -> Result<RowBatch, OpError> {
-        let input = inputs
-            .get(0)
+        let input = inputs.first()
             .ok_or_else(|| OpError::Exec("lateral operator missing input".into()))?;
 
         let mut name_to_index = HashMap::new();
@@ -310,5 +309,6 @@ fn scalar_to_string(value: &Scalar) -> String {
         Scalar::F64(v) => v.to_string(),
         Scalar::Str(s) => s.clone(),
         Scalar::Bin(bytes) => format!("{:?}", bytes),
+        Scalar::Date64(ms) => ms.to_string(),
     }
 }
@@ -66,7 +66,9 @@ pub fn estimate_work(plan: &LogicalPlan, hints: Option<&WorkHint>) -> WorkEstima
             Map { input, .. }
             | Project { input, .. }
             | Window { input, .. }
-            | Lateral { input, .. } => walk(input, hints, acc_rows, acc_bytes, max_fan_in),
+            | Lateral { input, .. }
+            | Lookup { input, .. }
+            | Retention { input, .. } => walk(input, hints, acc_rows, acc_bytes, max_fan_in),
             Join {
                 left, right, on, ..
             } => {
@@ -87,7 +89,22 @@ pub fn estimate_work(plan: &LogicalPlan, hints: Option<&WorkHint>) -> WorkEstima
                 let groups = estimate_aggregate_groups(input, group_by, in_rows);
                 groups.max(1)
             }
-            Sink { input, .. } => walk(input, hints, acc_rows, acc_bytes, max_fan_in),
+            Sample {
+                input,
+                fraction,
+                rows,
+            } => {
+                let in_rows = walk(input, hints, acc_rows, acc_bytes, max_fan_in);
+                let out_rows = match (rows, fraction) {
+                    (Some(k), _) => (*k as u64).min(in_rows),
+                    (None, Some(f)) => ((in_rows as f64) * f.clamp(0.0, 1.0)) as u64,
+                    (None, None) => in_rows,
+                };
+                out_rows.max(1)
+            }
+            Sink { input, .. } | RoutedSink { input, .. } => {
+                walk(input, hints, acc_rows, acc_bytes, max_fan_in)
+            }
         }
     }
 
@@ -178,8 +195,8 @@ fn estimate_join_cardinality(
                     // Estimate: rows * rows / max(distinct_left, distinct_right)
                     // This is a simplified model assuming uniform distribution
                     let max_distinct = left_distinct.max(right_distinct);
-                    if max_distinct > 0 {
-                        return (left_rows * right_rows / max_distinct).min(left_rows * right_rows);
+                    if let Some(est) = (left_rows * right_rows).checked_div(max_distinct) {
+                        return est.min(left_rows * right_rows);
                     }
                 }
             }
@@ -237,9 +254,13 @@ fn get_schema_from_plan(plan: &LogicalPlan) -> Option<&Schema> {
         Map { input, .. } | Project { input, .. } => get_schema_from_plan(input),
         Join { left, .. } => get_schema_from_plan(left), // Use left schema as approximation
         Aggregate { input, .. } => get_schema_from_plan(input),
-        Sink { input, .. } | Window { input, .. } | Lateral { input, .. } => {
-            get_schema_from_plan(input)
-        }
+        Sink { input, .. }
+        | RoutedSink { input, .. }
+        | Window { input, .. }
+        | Lateral { input, .. }
+        | Lookup { input, .. }
+        | Retention { input, .. }
+        | Sample { input, .. } => get_schema_from_plan(input),
     }
 }
 
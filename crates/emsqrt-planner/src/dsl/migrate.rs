@@ -0,0 +1,91 @@
+//! Migration of older pipeline YAML files to the current step format.
+//!
+//! Two legacy shapes are upgraded:
+//! - Single-key step maps (`- scan: {source: ...}`) from the earliest
+//!   prototype become tagged steps (`- op: scan` + inlined fields).
+//! - Renamed step keys: `select` → `project`, `explode` → `lateral`.
+//!
+//! Migration is idempotent: current-format files pass through unchanged
+//! (modulo YAML re-serialization).
+
+use serde_yaml::{Mapping, Value};
+
+/// Legacy step name → current step name.
+const STEP_RENAMES: &[(&str, &str)] = &[("select", "project"), ("explode", "lateral")];
+
+/// Migrate a pipeline YAML document to the current format. Returns the
+/// migrated YAML and the list of applied changes (empty = already current).
+pub fn migrate_pipeline(yaml_src: &str) -> Result<(String, Vec<String>), String> {
+    let mut doc: Value =
+        serde_yaml::from_str(yaml_src).map_err(|e| format!("invalid YAML: {e}"))?;
+    let mut changes = Vec::new();
+
+    let steps = doc
+        .get_mut("steps")
+        .and_then(Value::as_sequence_mut)
+        .ok_or_else(|| "pipeline has no 'steps' list".to_string())?;
+
+    for (idx, step) in steps.iter_mut().enumerate() {
+        let Some(map) = step.as_mapping() else {
+            return Err(format!("step {} is not a mapping", idx + 1));
+        };
+
+        // Already-tagged steps only need the rename check.
+        if let Some(op) = map.get(Value::from("op")).and_then(Value::as_str) {
+            if let Some((_, new)) = STEP_RENAMES.iter().find(|(old, _)| *old == op) {
+                let mut migrated = map.clone();
+                migrated.insert(Value::from("op"), Value::from(*new));
+                changes.push(format!("step {}: renamed op '{}' -> '{}'", idx + 1, op, new));
+                *step = Value::Mapping(migrated);
+            }
+            continue;
+        }
+
+        // Legacy single-key form: {scan: {source: ..., ...}}
+        if map.len() == 1 {
+            let (key, body) = map.iter().next().expect("len checked");
+            let Some(op_name) = key.as_str() else {
+                return Err(format!("step {} has a non-string key", idx + 1));
+            };
+            let op_name = STEP_RENAMES
+                .iter()
+                .find(|(old, _)| *old == op_name)
+                .map(|(_, new)| *new)
+                .unwrap_or(op_name);
+
+            let mut migrated = Mapping::new();
+            migrated.insert(Value::from("op"), Value::from(op_name));
+            match body {
+                Value::Mapping(fields) => {
+                    for (k, v) in fields {
+                        migrated.insert(k.clone(), v.clone());
+                    }
+                }
+                Value::Null => {}
+                other => {
+                    return Err(format!(
+                        "step {} body must be a mapping, got {:?}",
+                        idx + 1,
+                        other
+                    ));
+                }
+            }
+            changes.push(format!(
+                "step {}: converted legacy '{}' step to tagged form",
+                idx + 1,
+                op_name
+            ));
+            *step = Value::Mapping(migrated);
+            continue;
+        }
+
+        return Err(format!(
+            "step {} is neither tagged ('op:') nor a legacy single-key step",
+            idx + 1
+        ));
+    }
+
+    let migrated =
+        serde_yaml::to_string(&doc).map_err(|e| format!("failed to serialize: {e}"))?;
+    Ok((migrated, changes))
+}
@@ -1,3 +1,4 @@
 //! DSL front-ends. Currently only a tiny YAML pipeline is supported.
 
+pub mod migrate;
 pub mod yaml;
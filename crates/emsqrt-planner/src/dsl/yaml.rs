@@ -15,7 +15,7 @@
 use serde::{Deserialize, Serialize};
 use serde_yaml;
 
-use emsqrt_core::dag::{LogicalPlan, WindowExpr, WindowFrame, WindowFunction};
+use emsqrt_core::dag::{Aggregation, LogicalPlan, WindowExpr, WindowFrame, WindowFunction};
 use emsqrt_core::schema::{DataType, Field, Schema};
 
 use crate::logical::LogicalPlan as L;
@@ -34,6 +34,34 @@ pub enum Step {
     Scan {
         source: String,
         schema: Vec<FieldDef>,
+        /// CSV dialect options (single characters unless noted).
+        #[serde(default)]
+        delimiter: Option<String>,
+        #[serde(default)]
+        quote: Option<String>,
+        #[serde(default)]
+        comment: Option<String>,
+        /// Whether the file starts with a header row (default true).
+        #[serde(default)]
+        has_headers: Option<bool>,
+        /// Escape character inside quoted fields (default: doubled quotes).
+        #[serde(default)]
+        escape: Option<String>,
+        /// Values equal to this token read as null ("NA", "\\N", ...).
+        #[serde(default)]
+        null_token: Option<String>,
+        /// Lines discarded before the header row (report preambles etc.).
+        #[serde(default)]
+        skip_rows: Option<usize>,
+        /// Explicit column names, overriding the file's header row.
+        #[serde(default)]
+        headers: Option<Vec<String>>,
+        /// Malformed-row policy: "null" (default), "skip", or "error".
+        #[serde(default)]
+        on_malformed: Option<String>,
+        /// File receiving rejected rows (with reasons) under "skip".
+        #[serde(default)]
+        rejects: Option<String>,
     },
 
     #[serde(rename = "filter")]
@@ -45,8 +73,30 @@ pub enum Step {
     #[serde(rename = "map")]
     Map { expr: String },
 
+    #[serde(rename = "aggregate")]
+    Aggregate {
+        group_by: Vec<String>,
+        aggs: Vec<String>,
+    },
+
     #[serde(rename = "sink")]
-    Sink { destination: String, format: String },
+    Sink {
+        destination: String,
+        format: String,
+        /// Hive-style partition columns: rows land under
+        /// `<dir>/col=value/...` with the columns dropped from the files.
+        /// Supported with csv, jsonl, and parquet formats.
+        #[serde(default)]
+        partition_by: Vec<String>,
+    },
+
+    #[serde(rename = "route")]
+    Route {
+        routes: Vec<RouteDef>,
+        #[serde(default)]
+        default: Option<String>,
+        format: String,
+    },
 
     #[serde(rename = "window")]
     Window {
@@ -62,6 +112,38 @@ pub enum Step {
         #[serde(default)]
         delimiter: Option<String>,
     },
+
+    #[serde(rename = "lookup")]
+    Lookup {
+        source: String,
+        key: String,
+        #[serde(default)]
+        ref_key: Option<String>,
+        #[serde(default)]
+        columns: Vec<String>,
+    },
+
+    #[serde(rename = "retention")]
+    Retention {
+        column: String,
+        max_age: String,
+        #[serde(default)]
+        as_of: Option<String>,
+    },
+
+    #[serde(rename = "sample")]
+    Sample {
+        #[serde(default)]
+        fraction: Option<f64>,
+        #[serde(default)]
+        rows: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDef {
+    pub when: String,
+    pub destination: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +162,10 @@ pub struct FieldDef {
     pub data_type: String,
     #[serde(default)]
     pub nullable: bool,
+    /// Default value when the source is missing the column or a value fails
+    /// to parse; doubles as a generated constant column.
+    #[serde(default)]
+    pub default: Option<String>,
 }
 
 fn parse_dtype(s: &str) -> DataType {
@@ -90,10 +176,85 @@ fn parse_dtype(s: &str) -> DataType {
         "Float32" | "f32" => DataType::Float32,
         "Float64" | "f64" => DataType::Float64,
         "Binary" | "bytes" => DataType::Binary,
+        "Date64" | "date" | "timestamp" => DataType::Date64,
         _ => DataType::Utf8,
     }
 }
 
+/// Parse an aggregation spec like `SUM(amount)`, `COUNT(*)`, or
+/// `SUM(amount) FILTER (WHERE status == 'paid')`.
+fn parse_aggregation(s: &str) -> Result<Aggregation, String> {
+    let s = s.trim();
+
+    // Optional SQL-style FILTER clause wraps the inner aggregation.
+    if let Some(pos) = s.find(" FILTER ") {
+        let (head, tail) = s.split_at(pos);
+        let tail = tail[" FILTER ".len()..].trim();
+        let inner = tail
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .ok_or_else(|| format!("malformed FILTER clause in '{}'", s))?;
+        let predicate = inner
+            .trim()
+            .strip_prefix("WHERE ")
+            .ok_or_else(|| format!("FILTER clause must be '(WHERE ...)' in '{}'", s))?
+            .trim()
+            .to_string();
+        if predicate.contains(" FILTER ") {
+            return Err(format!("multiple FILTER clauses not supported in '{}'", s));
+        }
+        return Ok(Aggregation::Filtered {
+            agg: Box::new(parse_aggregation(head)?),
+            predicate,
+        });
+    }
+
+    let (func, rest) = s
+        .split_once('(')
+        .ok_or_else(|| format!("invalid aggregation '{}' (expected FUNC(column))", s))?;
+    let col = rest
+        .strip_suffix(')')
+        .ok_or_else(|| format!("invalid aggregation '{}' (missing ')')", s))?
+        .trim();
+
+    match func.trim().to_uppercase().as_str() {
+        "COUNT" => Ok(Aggregation::Count),
+        "SUM" => Ok(Aggregation::Sum(col.to_string())),
+        "AVG" => Ok(Aggregation::Avg(col.to_string())),
+        "MIN" => Ok(Aggregation::Min(col.to_string())),
+        "MAX" => Ok(Aggregation::Max(col.to_string())),
+        "MEDIAN" => Ok(Aggregation::Median(col.to_string())),
+        "STDDEV" => Ok(Aggregation::Stddev(col.to_string())),
+        "VARIANCE" | "VAR" => Ok(Aggregation::Variance(col.to_string())),
+        "COUNT_DISTINCT" => Ok(Aggregation::CountDistinct(col.to_string())),
+        "FIRST" => Ok(Aggregation::First(col.to_string())),
+        "LAST" => Ok(Aggregation::Last(col.to_string())),
+        "COVAR" | "CORR" => {
+            let (x, y) = col
+                .split_once(',')
+                .map(|(a, b)| (a.trim().to_string(), b.trim().to_string()))
+                .ok_or_else(|| format!("{} needs two columns: '{}'", func.trim(), s))?;
+            if func.trim().eq_ignore_ascii_case("COVAR") {
+                Ok(Aggregation::Covar(x, y))
+            } else {
+                Ok(Aggregation::Corr(x, y))
+            }
+        }
+        "STRING_AGG" => {
+            // STRING_AGG(col) or STRING_AGG(col, 'delim')
+            let (column, delimiter) = match col.split_once(',') {
+                Some((c, d)) => {
+                    let d = d.trim().trim_matches('\'').trim_matches('"');
+                    (c.trim().to_string(), d.to_string())
+                }
+                None => (col.to_string(), ",".to_string()),
+            };
+            Ok(Aggregation::StringAgg { column, delimiter })
+        }
+        other => Err(format!("unknown aggregation function '{}'", other)),
+    }
+}
+
 fn to_schema(fields: &[FieldDef]) -> Schema {
     Schema::new(
         fields
@@ -102,6 +263,8 @@ fn to_schema(fields: &[FieldDef]) -> Schema {
                 name: f.name.clone(),
                 data_type: parse_dtype(&f.data_type),
                 nullable: f.nullable,
+                default: f.default.clone(),
+                provenance: None,
             })
             .collect(),
     )
@@ -134,10 +297,63 @@ pub fn parse_yaml_pipeline(yaml_src: &str) -> Result<ParsedPipeline, serde_yaml:
 
     for step in doc.steps {
         cur = Some(match (step, cur) {
-            (Step::Scan { source, schema }, None) => L::Scan {
-                source,
-                schema: to_schema(&schema),
-            },
+            (
+                Step::Scan {
+                    source,
+                    schema,
+                    delimiter,
+                    quote,
+                    comment,
+                    has_headers,
+                    escape,
+                    null_token,
+                    skip_rows,
+                    headers,
+                    on_malformed,
+                    rejects,
+                },
+                None,
+            ) => {
+                // Dialect options travel as schema metadata so the logical
+                // plan shape stays stable.
+                let mut schema = to_schema(&schema);
+                if let Some(d) = delimiter {
+                    schema.metadata.insert("csv.delimiter".into(), d);
+                }
+                if let Some(q) = quote {
+                    schema.metadata.insert("csv.quote".into(), q);
+                }
+                if let Some(c) = comment {
+                    schema.metadata.insert("csv.comment".into(), c);
+                }
+                if let Some(h) = has_headers {
+                    schema
+                        .metadata
+                        .insert("csv.has_headers".into(), h.to_string());
+                }
+                if let Some(e) = escape {
+                    schema.metadata.insert("csv.escape".into(), e);
+                }
+                if let Some(token) = null_token {
+                    schema.metadata.insert("csv.null_token".into(), token);
+                }
+                if let Some(n) = skip_rows {
+                    schema.metadata.insert("csv.skip_rows".into(), n.to_string());
+                }
+                if let Some(names) = headers {
+                    schema.metadata.insert("csv.headers".into(), names.join(","));
+                }
+                if let Some(policy) = on_malformed {
+                    schema.metadata.insert("csv.on_malformed".into(), policy);
+                }
+                if let Some(path) = rejects {
+                    schema.metadata.insert("csv.rejects".into(), path);
+                }
+                L::Scan {
+                    source,
+                    schema,
+                }
+            }
             (Step::Scan { .. }, Some(_)) => {
                 // serde_yaml::Error doesn't have a custom method, so we'll just parse error
                 return Err(
@@ -157,15 +373,64 @@ pub fn parse_yaml_pipeline(yaml_src: &str) -> Result<ParsedPipeline, serde_yaml:
                 input: Box::new(input),
                 expr,
             },
+            (Step::Aggregate { group_by, aggs }, Some(input)) => {
+                let parsed: Result<Vec<Aggregation>, String> =
+                    aggs.iter().map(|s| parse_aggregation(s)).collect();
+                match parsed {
+                    Ok(aggs) => L::Aggregate {
+                        input: Box::new(input),
+                        group_by,
+                        aggs,
+                    },
+                    Err(e) => {
+                        return Err(serde_yaml::from_str::<()>(&format!("invalid: {}", e))
+                            .unwrap_err());
+                    }
+                }
+            }
             (
                 Step::Sink {
                     destination,
                     format,
+                    partition_by,
                 },
                 Some(input),
-            ) => L::Sink {
+            ) => {
+                let format = if partition_by.is_empty() {
+                    format
+                } else {
+                    // Partitioned sinks ride the format channel the runtime
+                    // already understands ("partitioned:<fmt>:<cols>").
+                    let base = if format.is_empty() { "csv" } else { format.as_str() };
+                    if !matches!(base, "csv" | "jsonl" | "parquet") {
+                        return Err(serde_yaml::from_str::<()>(&format!(
+                            "invalid: partition_by supports csv, jsonl, or parquet sinks, not '{}'",
+                            base
+                        ))
+                        .unwrap_err());
+                    }
+                    format!("partitioned:{}:{}", base, partition_by.join(","))
+                };
+                L::Sink {
+                    input: Box::new(input),
+                    destination,
+                    format,
+                }
+            }
+            (
+                Step::Route {
+                    routes,
+                    default,
+                    format,
+                },
+                Some(input),
+            ) => L::RoutedSink {
                 input: Box::new(input),
-                destination,
+                routes: routes
+                    .into_iter()
+                    .map(|r| (r.when, r.destination))
+                    .collect(),
+                default,
                 format,
             },
             (
@@ -194,6 +459,47 @@ pub fn parse_yaml_pipeline(yaml_src: &str) -> Result<ParsedPipeline, serde_yaml:
                     })
                     .collect(),
             },
+            (
+                Step::Lookup {
+                    source,
+                    key,
+                    ref_key,
+                    columns,
+                },
+                Some(input),
+            ) => L::Lookup {
+                input: Box::new(input),
+                source,
+                ref_key: ref_key.unwrap_or_else(|| key.clone()),
+                key,
+                columns,
+            },
+            (
+                Step::Retention {
+                    column,
+                    max_age,
+                    as_of,
+                },
+                Some(input),
+            ) => L::Retention {
+                input: Box::new(input),
+                column,
+                max_age,
+                as_of,
+            },
+            (Step::Sample { fraction, rows }, Some(input)) => {
+                if fraction.is_none() && rows.is_none() {
+                    return Err(serde_yaml::from_str::<()>(
+                        "invalid: sample step needs 'fraction' or 'rows'",
+                    )
+                    .unwrap_err());
+                }
+                L::Sample {
+                    input: Box::new(input),
+                    fraction,
+                    rows,
+                }
+            }
             (
                 Step::Lateral {
                     column,
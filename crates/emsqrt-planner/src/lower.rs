@@ -22,6 +22,10 @@ use crate::physical::{OperatorBinding, PhysicalProgram};
 pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
     let mut next_id = 1u64;
     let mut bindings = BTreeMap::<OpId, OperatorBinding>::new();
+    // Shared scan elimination: identical Scan nodes (same source + schema)
+    // lower to one OpId, so the engine reads the file once and fans the
+    // blocks out to every consumer.
+    let mut scan_cache = std::collections::HashMap::<String, OpId>::new();
 
     fn alloc_id(next_id: &mut u64) -> OpId {
         let id = OpId::new(*next_id);
@@ -32,12 +36,50 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
     fn schema_of(lp: &LogicalPlan) -> Schema {
         use LogicalPlan::*;
         match lp {
-            Scan { schema, .. } => schema.clone(),
+            Scan { source, schema } => {
+                let mut schema = schema.clone();
+                for field in &mut schema.fields {
+                    if field.provenance.is_none() {
+                        field.provenance = Some(format!("scan:{}", source));
+                    }
+                }
+                schema
+            }
             Filter { input, .. }
-            | Map { input, .. }
             | Project { input, .. }
             | Aggregate { input, .. }
-            | Sink { input, .. } => schema_of(input),
+            | Retention { input, .. }
+            | Sample { input, .. }
+            | Sink { input, .. }
+            | RoutedSink { input, .. } => schema_of(input),
+            Map { input, expr } => {
+                // "old AS new" parts rename; "alias = expression" parts
+                // append a derived column.
+                let mut schema = schema_of(input);
+                for part in expr.split(';') {
+                    let part = part.trim();
+                    if let Some((old, new)) = part.split_once(" AS ") {
+                        if let Some(field) =
+                            schema.fields.iter_mut().find(|f| f.name == old.trim())
+                        {
+                            field.name = new.trim().to_string();
+                        }
+                    } else if let Some((alias, expr)) = part.split_once(" = ") {
+                        // Infer the derived type where the expression
+                        // statically checks; fall back to Utf8.
+                        let inferred = emsqrt_core::expr::Expr::parse(expr.trim())
+                            .ok()
+                            .and_then(|e| e.check_types(&schema).ok())
+                            .flatten()
+                            .unwrap_or(DataType::Utf8);
+                        schema.fields.push(
+                            Field::new(alias.trim().to_string(), inferred, true)
+                                .with_provenance(format!("map:{}", expr.trim())),
+                        );
+                    }
+                }
+                schema
+            }
             Window {
                 input, functions, ..
             } => {
@@ -47,17 +89,40 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                         WindowFunction::RowNumber => DataType::Int64,
                         WindowFunction::Sum { .. } => DataType::Float64,
                     };
-                    schema
-                        .fields
-                        .push(Field::new(expr.alias.clone(), data_type, true));
+                    schema.fields.push(
+                        Field::new(expr.alias.clone(), data_type, true)
+                            .with_provenance(format!("window:{}", expr.alias)),
+                    );
                 }
                 schema
             }
-            Lateral { input, alias, .. } => {
+            Lateral {
+                input,
+                column,
+                alias,
+                ..
+            } => {
                 let mut schema = schema_of(input);
+                schema.fields.push(
+                    Field::new(alias.clone(), DataType::Utf8, true)
+                        .with_provenance(format!("lateral:{}", column)),
+                );
                 schema
-                    .fields
-                    .push(Field::new(alias.clone(), DataType::Utf8, true));
+            }
+            Lookup {
+                input,
+                source,
+                columns,
+                ..
+            } => {
+                // Looked-up columns come from an untyped reference file.
+                let mut schema = schema_of(input);
+                for col in columns {
+                    schema.fields.push(
+                        Field::new(col.clone(), DataType::Utf8, true)
+                            .with_provenance(format!("lookup:{}", source)),
+                    );
+                }
                 schema
             }
             Join { left, .. } => schema_of(left), // TODO: real join schema
@@ -68,28 +133,39 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
         lp: &LogicalPlan,
         next_id: &mut u64,
         bindings: &mut BTreeMap<OpId, OperatorBinding>,
+        scan_cache: &mut std::collections::HashMap<String, OpId>,
     ) -> PhysicalPlan {
         use LogicalPlan::*;
         match lp {
             Scan { source, schema } => {
-                let op = alloc_id(next_id);
-                bindings.insert(
-                    op,
-                    OperatorBinding {
-                        key: "source".to_string(),
-                        config: serde_json::json!({
-                            "source": source,
-                            "schema": serde_json::to_value(&schema).unwrap_or(serde_json::json!({}))
-                        }),
-                    },
-                );
+                let schema_json =
+                    serde_json::to_value(schema).unwrap_or(serde_json::json!({}));
+                let cache_key = format!("{}|{}", source, schema_json);
+                let op = match scan_cache.get(&cache_key) {
+                    Some(op) => *op,
+                    None => {
+                        let op = alloc_id(next_id);
+                        bindings.insert(
+                            op,
+                            OperatorBinding {
+                                key: "source".to_string(),
+                                config: serde_json::json!({
+                                    "source": source,
+                                    "schema": schema_json
+                                }),
+                            },
+                        );
+                        scan_cache.insert(cache_key, op);
+                        op
+                    }
+                };
                 PhysicalPlan::Source {
                     op,
                     schema: schema.clone(),
                 }
             }
             Filter { input, expr } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 bindings.insert(
                     op,
@@ -105,7 +181,7 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 }
             }
             Map { input, expr } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 bindings.insert(
                     op,
@@ -121,7 +197,7 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 }
             }
             Project { input, columns } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 bindings.insert(
                     op,
@@ -141,20 +217,11 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 group_by,
                 aggs,
             } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
 
                 // Serialize aggs to strings (format expected by Aggregate::parse)
-                let aggs_str: Vec<String> = aggs
-                    .iter()
-                    .map(|a| match a {
-                        emsqrt_core::dag::Aggregation::Count => "count".to_string(),
-                        emsqrt_core::dag::Aggregation::Sum(col) => format!("sum:{}", col),
-                        emsqrt_core::dag::Aggregation::Avg(col) => format!("avg:{}", col),
-                        emsqrt_core::dag::Aggregation::Min(col) => format!("min:{}", col),
-                        emsqrt_core::dag::Aggregation::Max(col) => format!("max:{}", col),
-                    })
-                    .collect();
+                let aggs_str: Vec<String> = aggs.iter().map(agg_spec).collect();
 
                 bindings.insert(
                     op,
@@ -178,7 +245,7 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 order_by,
                 functions,
             } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 let funcs_json: Vec<serde_json::Value> = functions
                     .iter()
@@ -233,7 +300,7 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 alias,
                 delimiter,
             } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 bindings.insert(
                     op,
@@ -252,15 +319,104 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                     schema: schema_of(lp),
                 }
             }
-            Join { left, right, .. } => {
-                let l = lower_rec(left, next_id, bindings);
-                let r = lower_rec(right, next_id, bindings);
+            Lookup {
+                input,
+                source,
+                key,
+                ref_key,
+                columns,
+            } => {
+                let child = lower_rec(input, next_id, bindings, scan_cache);
+                let op = alloc_id(next_id);
+                bindings.insert(
+                    op,
+                    OperatorBinding {
+                        key: "lookup".to_string(),
+                        config: serde_json::json!({
+                            "source": source,
+                            "key": key,
+                            "ref_key": ref_key,
+                            "columns": columns
+                        }),
+                    },
+                );
+                PhysicalPlan::Unary {
+                    op,
+                    input: Box::new(child),
+                    schema: schema_of(lp),
+                }
+            }
+            Retention {
+                input,
+                column,
+                max_age,
+                as_of,
+            } => {
+                let child = lower_rec(input, next_id, bindings, scan_cache);
+                let op = alloc_id(next_id);
+                bindings.insert(
+                    op,
+                    OperatorBinding {
+                        key: "retention".to_string(),
+                        config: serde_json::json!({
+                            "column": column,
+                            "max_age": max_age,
+                            "as_of": as_of
+                        }),
+                    },
+                );
+                PhysicalPlan::Unary {
+                    op,
+                    input: Box::new(child),
+                    schema: schema_of(lp),
+                }
+            }
+            Sample {
+                input,
+                fraction,
+                rows,
+            } => {
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
+                bindings.insert(
+                    op,
+                    OperatorBinding {
+                        key: "sample".to_string(),
+                        config: serde_json::json!({
+                            "fraction": fraction,
+                            "rows": rows
+                        }),
+                    },
+                );
+                PhysicalPlan::Unary {
+                    op,
+                    input: Box::new(child),
+                    schema: schema_of(lp),
+                }
+            }
+            Join {
+                left,
+                right,
+                on,
+                join_type,
+            } => {
+                let l = lower_rec(left, next_id, bindings, scan_cache);
+                let r = lower_rec(right, next_id, bindings, scan_cache);
+                let op = alloc_id(next_id);
+                let join_type = match join_type {
+                    emsqrt_core::dag::JoinType::Inner => "inner",
+                    emsqrt_core::dag::JoinType::Left => "left",
+                    emsqrt_core::dag::JoinType::Right => "right",
+                    emsqrt_core::dag::JoinType::Full => "full",
+                };
                 bindings.insert(
                     op,
                     OperatorBinding {
                         key: "join_hash".to_string(), // default to hash join; rules may switch to merge later
-                        config: serde_json::json!({}),
+                        config: serde_json::json!({
+                            "on": on,
+                            "join_type": join_type
+                        }),
                     },
                 );
                 PhysicalPlan::Binary {
@@ -275,7 +431,7 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                 destination,
                 format,
             } => {
-                let child = lower_rec(input, next_id, bindings);
+                let child = lower_rec(input, next_id, bindings, scan_cache);
                 let op = alloc_id(next_id);
                 bindings.insert(
                     op,
@@ -292,9 +448,56 @@ pub fn lower_to_physical(lp: &LogicalPlan) -> PhysicalProgram {
                     input: Box::new(child),
                 }
             }
+            RoutedSink {
+                input,
+                routes,
+                default,
+                format,
+            } => {
+                let child = lower_rec(input, next_id, bindings, scan_cache);
+                let op = alloc_id(next_id);
+                bindings.insert(
+                    op,
+                    OperatorBinding {
+                        key: "route_sink".to_string(),
+                        config: serde_json::json!({
+                            "routes": routes,
+                            "default": default,
+                            "format": format
+                        }),
+                    },
+                );
+                PhysicalPlan::Sink {
+                    op,
+                    input: Box::new(child),
+                }
+            }
         }
     }
 
-    let plan = lower_rec(lp, &mut next_id, &mut bindings);
+    let plan = lower_rec(lp, &mut next_id, &mut bindings, &mut scan_cache);
     PhysicalProgram::new(plan, bindings)
 }
+
+/// Serialize an `Aggregation` to the spec string format expected by the
+/// aggregate operator ("count", "sum:col", "sum:col WHERE pred", ...).
+fn agg_spec(a: &emsqrt_core::dag::Aggregation) -> String {
+    use emsqrt_core::dag::Aggregation::*;
+    match a {
+        Count => "count".to_string(),
+        Sum(col) => format!("sum:{}", col),
+        Avg(col) => format!("avg:{}", col),
+        Min(col) => format!("min:{}", col),
+        Max(col) => format!("max:{}", col),
+        Median(col) => format!("median:{}", col),
+        Stddev(col) => format!("stddev:{}", col),
+        Variance(col) => format!("variance:{}", col),
+        CountDistinct(col) => format!("count_distinct:{}", col),
+        First(col) => format!("first:{}", col),
+        Last(col) => format!("last:{}", col),
+        StringAgg { column, delimiter } => format!("string_agg:{}:{}", column, delimiter),
+        Covar(x, y) => format!("covar:{}:{}", x, y),
+        Corr(x, y) => format!("corr:{}:{}", x, y),
+        Filtered { agg, predicate } => format!("{} WHERE {}", agg_spec(agg), predicate),
+    }
+}
@@ -65,6 +65,39 @@ fn projection_pushdown(plan: LogicalPlan) -> LogicalPlan {
             alias,
             delimiter,
         },
+        Lookup {
+            input,
+            source,
+            key,
+            ref_key,
+            columns,
+        } => Lookup {
+            input: Box::new(projection_pushdown(*input)),
+            source,
+            key,
+            ref_key,
+            columns,
+        },
+        Retention {
+            input,
+            column,
+            max_age,
+            as_of,
+        } => Retention {
+            input: Box::new(projection_pushdown(*input)),
+            column,
+            max_age,
+            as_of,
+        },
+        Sample {
+            input,
+            fraction,
+            rows,
+        } => Sample {
+            input: Box::new(projection_pushdown(*input)),
+            fraction,
+            rows,
+        },
         Join {
             left,
             right,
@@ -85,6 +118,17 @@ fn projection_pushdown(plan: LogicalPlan) -> LogicalPlan {
             destination,
             format,
         },
+        RoutedSink {
+            input,
+            routes,
+            default,
+            format,
+        } => RoutedSink {
+            input: Box::new(projection_pushdown(*input)),
+            routes,
+            default,
+            format,
+        },
         // Leaf nodes
         Scan { .. } => plan,
     }
@@ -106,3 +106,64 @@ pub fn compute_max_frontier(order: &[(BlockId, Vec<BlockId>)]) -> usize {
 
     tracker.stats().max_frontier_size
 }
+
+/// Reorder a dependency-annotated block list to reduce the peak live
+/// frontier. Greedy heuristic: among ready blocks (all deps scheduled),
+/// prefer one whose scheduling lets a previously produced block retire —
+/// i.e. it is the last unscheduled consumer of some dependency — falling
+/// back to original order for determinism. The result is still a valid
+/// topological order.
+pub fn order_min_frontier(blocks: &[(crate::tree_eval::TeBlock, usize)]) -> Vec<usize> {
+    use std::collections::{HashMap, HashSet};
+
+    let n = blocks.len();
+    let mut scheduled: Vec<bool> = vec![false; n];
+    let mut scheduled_ids: HashSet<u64> = HashSet::new();
+
+    // consumers[dep_id] = number of unscheduled consumers of that block id
+    let mut consumers: HashMap<u64, usize> = HashMap::new();
+    for (block, _) in blocks {
+        for dep in &block.deps {
+            *consumers.entry(dep.get()).or_insert(0) += 1;
+        }
+    }
+
+    let mut result = Vec::with_capacity(n);
+    for _ in 0..n {
+        // Ready candidates in original order.
+        let mut best: Option<(usize, usize)> = None; // (score, idx) lower = better
+        for (idx, (block, _)) in blocks.iter().enumerate() {
+            if scheduled[idx] {
+                continue;
+            }
+            if !block.deps.iter().all(|d| scheduled_ids.contains(&d.get())) {
+                continue;
+            }
+            // Score: number of this block's deps it does NOT retire (their
+            // remaining consumer count after this block). Retiring more deps
+            // shrinks the frontier faster.
+            let retained = block
+                .deps
+                .iter()
+                .filter(|d| consumers.get(&d.get()).copied().unwrap_or(0) > 1)
+                .count();
+            // Blocks with no deps (sources) grow the frontier; schedule them
+            // only when nothing can retire data.
+            let score = if block.deps.is_empty() { usize::MAX } else { retained };
+            if best.is_none_or(|(s, _)| score < s) {
+                best = Some((score, idx));
+            }
+        }
+
+        let (_, idx) = best.expect("cycle-free topological order");
+        scheduled[idx] = true;
+        scheduled_ids.insert(blocks[idx].0.id.get());
+        for dep in &blocks[idx].0.deps {
+            if let Some(count) = consumers.get_mut(&dep.get()) {
+                *count -= 1;
+            }
+        }
+        result.push(blocks[idx].1);
+    }
+    result
+}
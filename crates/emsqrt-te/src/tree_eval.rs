@@ -62,11 +62,13 @@ pub fn plan_te(
     est: &WorkEstimate,
     mem_cap_bytes: usize,
 ) -> Result<TePlan, PlanError> {
+    check_memory_feasibility(mem_cap_bytes, est)?;
     let b = choose_block_size(mem_cap_bytes, est);
     let mut order = Vec::<TeBlock>::new();
     let mut next_block_id = 0u64;
 
     // Helper structure to track which blocks were created for each node
+    #[derive(Clone)]
     struct BlockRange {
         blocks: Vec<BlockId>,
         estimated_rows: u64,
@@ -78,14 +80,20 @@ pub fn plan_te(
         next_block_id: &mut u64,
         rows_per_block: u64,
         est: &WorkEstimate,
+        source_blocks: &mut std::collections::HashMap<u64, BlockRange>,
     ) -> Result<BlockRange, PlanError> {
         use PhysicalPlan::*;
         match node {
             Source { op, schema } => {
+                // Shared scan: a Source op that already has blocks is reused
+                // verbatim so the file is read once and fanned out.
+                if let Some(range) = source_blocks.get(&op.get()) {
+                    return Ok(range.clone());
+                }
                 // Estimate: use total_rows from work estimate divided by number of sources
                 // For now, assume single source gets all rows
                 let estimated_rows = est.total_rows.max(rows_per_block);
-                let num_blocks = ((estimated_rows + rows_per_block - 1) / rows_per_block).max(1);
+                let num_blocks = estimated_rows.div_ceil(rows_per_block).max(1);
 
                 let mut blocks = Vec::new();
                 for i in 0..num_blocks {
@@ -105,13 +113,15 @@ pub fn plan_te(
                     blocks.push(id);
                 }
 
-                Ok(BlockRange {
+                let range = BlockRange {
                     blocks,
                     estimated_rows,
-                })
+                };
+                source_blocks.insert(op.get(), range.clone());
+                Ok(range)
             }
             Unary { op, input, schema } => {
-                let child_range = walk(input, order, next_block_id, rows_per_block, est)?;
+                let child_range = walk(input, order, next_block_id, rows_per_block, est, source_blocks)?;
 
                 // Create same number of blocks as input (1-to-1 pipeline)
                 let estimated_rows = child_range.estimated_rows; // Pass through for unary
@@ -145,8 +155,8 @@ pub fn plan_te(
                 right,
                 schema,
             } => {
-                let left_range = walk(left, order, next_block_id, rows_per_block, est)?;
-                let right_range = walk(right, order, next_block_id, rows_per_block, est)?;
+                let left_range = walk(left, order, next_block_id, rows_per_block, est, source_blocks)?;
+                let right_range = walk(right, order, next_block_id, rows_per_block, est, source_blocks)?;
 
                 // Align chunks: create blocks matching the max of left/right block counts
                 // For simplicity, each join block depends on corresponding left/right blocks
@@ -186,7 +196,7 @@ pub fn plan_te(
                 })
             }
             Sink { op, input } => {
-                let child_range = walk(input, order, next_block_id, rows_per_block, est)?;
+                let child_range = walk(input, order, next_block_id, rows_per_block, est, source_blocks)?;
 
                 // Sink typically processes each input block (1-to-1)
                 let mut blocks = Vec::new();
@@ -215,7 +225,27 @@ pub fn plan_te(
         }
     }
 
-    let _ = walk(phys, &mut order, &mut next_block_id, b.rows_per_block, est)?;
+        let mut source_blocks = std::collections::HashMap::new();
+    let _ = walk(
+        phys,
+        &mut order,
+        &mut next_block_id,
+        b.rows_per_block,
+        est,
+        &mut source_blocks,
+    )?;
+
+    // Reorder ready blocks to minimize the live frontier: consumers are
+    // scheduled as soon as their inputs exist, letting inputs retire early.
+    {
+        let indexed: Vec<(TeBlock, usize)> = order
+            .iter()
+            .cloned()
+            .zip(0..order.len())
+            .collect();
+        let new_order = crate::frontier::order_min_frontier(&indexed);
+        order = new_order.into_iter().map(|i| indexed[i].0.clone()).collect();
+    }
 
     // Compute frontier bound using the new compute_max_frontier helper
     use crate::frontier::compute_max_frontier;
@@ -232,9 +262,58 @@ pub fn plan_te(
     })
 }
 
+/// Reject plans that cannot run under the memory cap *before* execution,
+/// with concrete numbers and remediation steps instead of a mid-run OOM.
+fn check_memory_feasibility(mem_cap_bytes: usize, est: &WorkEstimate) -> Result<(), PlanError> {
+    if est.total_rows == 0 || est.total_bytes == 0 {
+        return Ok(()); // Nothing known about the input; let execution decide.
+    }
+
+    // Mirror choose_block_size's model: with fan-in F we keep ~(3F + 1)
+    // blocks resident, and a block can't be smaller than one row.
+    let bytes_per_row = (est.total_bytes / est.total_rows).max(1);
+    let resident_blocks = 3 * est.max_fan_in.max(1) as u64 + 1;
+    let required_bytes = bytes_per_row * resident_blocks;
+
+    if (mem_cap_bytes as u64) < required_bytes {
+        let mut suggestions = vec![format!(
+            "raise the memory cap to at least {} bytes (--memory-cap / EMSQRT_MEM_CAP_BYTES)",
+            required_bytes
+        )];
+        if est.max_fan_in > 1 {
+            suggestions.push(
+                "reduce join fan-in, e.g. by pre-aggregating or splitting the pipeline".into(),
+            );
+        }
+        suggestions
+            .push("project away unused columns early to shrink the bytes-per-row estimate".into());
+        suggestions.push(
+            "enable spill compression (EMSQRT_SPILL_CODEC=zstd) so spilled data costs less disk"
+                .into(),
+        );
+        return Err(PlanError::Infeasible {
+            cap_bytes: mem_cap_bytes,
+            required_bytes,
+            suggestions,
+        });
+    }
+    Ok(())
+}
+
 /// Planning errors local to TE; map to core::Error in the executor if needed.
 #[derive(thiserror::Error, Debug)]
 pub enum PlanError {
     #[error("Invalid PhysicalPlan structure: {0}")]
     InvalidPlan(String),
+
+    #[error(
+        "memory cap {cap_bytes} bytes is below the {required_bytes} bytes this plan needs; \
+suggestions: {}",
+        suggestions.join("; ")
+    )]
+    Infeasible {
+        cap_bytes: usize,
+        required_bytes: u64,
+        suggestions: Vec<String>,
+    },
 }
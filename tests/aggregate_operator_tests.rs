@@ -0,0 +1,404 @@
+//! Aggregate operator tests, including conditional (FILTER) aggregation.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{agregate::Aggregate, Operator};
+
+fn mk_column(name: &str, values: Vec<Scalar>) -> Column {
+    Column {
+        name: name.to_string(),
+        values,
+    }
+}
+
+/// Sales batch: two products, mixed paid/pending statuses.
+fn sales_batch() -> RowBatch {
+    RowBatch {
+        columns: vec![
+            mk_column(
+                "product",
+                vec![
+                    Scalar::Str("a".into()),
+                    Scalar::Str("a".into()),
+                    Scalar::Str("a".into()),
+                    Scalar::Str("b".into()),
+                    Scalar::Str("b".into()),
+                ],
+            ),
+            mk_column(
+                "amount",
+                vec![
+                    Scalar::F64(10.0),
+                    Scalar::F64(20.0),
+                    Scalar::F64(30.0),
+                    Scalar::F64(5.0),
+                    Scalar::F64(15.0),
+                ],
+            ),
+            mk_column(
+                "status",
+                vec![
+                    Scalar::Str("paid".into()),
+                    Scalar::Str("pending".into()),
+                    Scalar::Str("paid".into()),
+                    Scalar::Str("paid".into()),
+                    Scalar::Str("pending".into()),
+                ],
+            ),
+        ],
+    }
+}
+
+fn find_column<'a>(batch: &'a RowBatch, name: &str) -> &'a Column {
+    batch
+        .columns
+        .iter()
+        .find(|c| c.name == name)
+        .unwrap_or_else(|| panic!("column '{}' missing from output", name))
+}
+
+/// Map group key -> value in an agg column.
+fn by_group(batch: &RowBatch, key_col: &str, val_col: &str) -> Vec<(String, Scalar)> {
+    let keys = find_column(batch, key_col);
+    let vals = find_column(batch, val_col);
+    keys.values
+        .iter()
+        .zip(vals.values.iter())
+        .map(|(k, v)| match k {
+            Scalar::Str(s) => (s.clone(), v.clone()),
+            other => (format!("{:?}", other), v.clone()),
+        })
+        .collect()
+}
+
+#[test]
+fn test_sum_with_filter_clause() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec!["sum:amount WHERE status == 'paid'".into()],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    let sums = by_group(&result, "product", "sum_amount");
+    assert_eq!(sums.len(), 2);
+    for (key, val) in sums {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::F64(40.0)), // 10 + 30 (pending excluded)
+            "b" => assert_eq!(val, Scalar::F64(5.0)),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+}
+
+#[test]
+fn test_filtered_and_unfiltered_count() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec!["count".into(), "count WHERE status == 'paid'".into()],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    // Both slots are named "count"; the first is unfiltered, the second filtered.
+    let count_cols: Vec<&Column> = result
+        .columns
+        .iter()
+        .filter(|c| c.name == "count")
+        .collect();
+    assert_eq!(count_cols.len(), 2);
+
+    let keys = find_column(&result, "product");
+    for (row, key) in keys.values.iter().enumerate() {
+        let total = &count_cols[0].values[row];
+        let paid = &count_cols[1].values[row];
+        match key {
+            Scalar::Str(s) if s == "a" => {
+                assert_eq!(total, &Scalar::I64(3));
+                assert_eq!(paid, &Scalar::I64(2));
+            }
+            Scalar::Str(s) if s == "b" => {
+                assert_eq!(total, &Scalar::I64(2));
+                assert_eq!(paid, &Scalar::I64(1));
+            }
+            other => panic!("unexpected group {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_filter_matching_no_rows_yields_zero_count_and_null_sum() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec![
+            "count WHERE status == 'refunded'".into(),
+            "sum:amount WHERE status == 'refunded'".into(),
+        ],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    let counts = by_group(&result, "product", "count");
+    assert_eq!(counts.len(), 2);
+    for (_key, val) in counts {
+        assert_eq!(val, Scalar::I64(0));
+    }
+
+    // Value aggregates over zero matching rows are null, not 0.0 / ±inf.
+    let sums = by_group(&result, "product", "sum_amount");
+    for (_key, val) in sums {
+        assert_eq!(val, Scalar::Null);
+    }
+}
+
+#[test]
+fn test_multiple_aggregates_on_same_column() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec![
+            "sum:amount".into(),
+            "avg:amount".into(),
+            "min:amount".into(),
+            "max:amount".into(),
+        ],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    for (key, val) in by_group(&result, "product", "sum_amount") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::F64(60.0)),
+            "b" => assert_eq!(val, Scalar::F64(20.0)),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+    for (key, val) in by_group(&result, "product", "avg_amount") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::F64(20.0)),
+            "b" => assert_eq!(val, Scalar::F64(10.0)),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+    for (key, val) in by_group(&result, "product", "min_amount") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::F64(10.0)),
+            "b" => assert_eq!(val, Scalar::F64(5.0)),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+}
+
+#[test]
+fn test_median_stddev_variance() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec![
+            "median:amount".into(),
+            "stddev:amount".into(),
+            "variance:amount".into(),
+        ],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    for (key, val) in by_group(&result, "product", "median_amount") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::F64(20.0)), // 10, 20, 30
+            "b" => assert_eq!(val, Scalar::F64(10.0)), // (5 + 15) / 2
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+    // Sample variance for group a (10, 20, 30) = 100; stddev = 10.
+    for (key, val) in by_group(&result, "product", "variance_amount") {
+        if key == "a" {
+            assert_eq!(val, Scalar::F64(100.0));
+        }
+    }
+    for (key, val) in by_group(&result, "product", "stddev_amount") {
+        if key == "a" {
+            assert_eq!(val, Scalar::F64(10.0));
+        }
+    }
+}
+
+#[test]
+fn test_count_distinct_exact_and_approx() {
+    for approx in [false, true] {
+        let agg = Aggregate {
+            group_by: vec!["product".into()],
+            aggs: vec!["count_distinct:status".into()],
+            approx,
+            spill_mgr: None,
+        };
+
+        let result = agg
+            .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+            .expect("aggregate execution");
+
+        // Both groups contain exactly the statuses {paid, pending}; HLL is
+        // exact at this tiny cardinality.
+        for (_key, val) in by_group(&result, "product", "count_distinct_status") {
+            assert_eq!(val, Scalar::I64(2), "approx={}", approx);
+        }
+    }
+}
+
+#[test]
+fn test_first_last_string_agg() {
+    let agg = Aggregate {
+        group_by: vec!["product".into()],
+        aggs: vec![
+            "first:status".into(),
+            "last:status".into(),
+            "string_agg:status:|".into(),
+        ],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[sales_batch()], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate execution");
+
+    for (key, val) in by_group(&result, "product", "first_status") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::Str("paid".into())),
+            "b" => assert_eq!(val, Scalar::Str("paid".into())),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+    for (key, val) in by_group(&result, "product", "last_status") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::Str("paid".into())),
+            "b" => assert_eq!(val, Scalar::Str("pending".into())),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+    for (key, val) in by_group(&result, "product", "string_agg_status") {
+        match key.as_str() {
+            "a" => assert_eq!(val, Scalar::Str("paid|pending|paid".into())),
+            "b" => assert_eq!(val, Scalar::Str("paid|pending".into())),
+            other => panic!("unexpected group '{}'", other),
+        }
+    }
+}
+
+#[test]
+fn test_covariance_and_correlation() {
+    // Perfectly linear y = 2x within group a; anti-correlated in group b.
+    let batch = RowBatch {
+        columns: vec![
+            mk_column(
+                "g",
+                vec![
+                    Scalar::Str("a".into()),
+                    Scalar::Str("a".into()),
+                    Scalar::Str("a".into()),
+                    Scalar::Str("b".into()),
+                    Scalar::Str("b".into()),
+                    Scalar::Str("b".into()),
+                ],
+            ),
+            mk_column(
+                "x",
+                vec![
+                    Scalar::F64(1.0),
+                    Scalar::F64(2.0),
+                    Scalar::F64(3.0),
+                    Scalar::F64(1.0),
+                    Scalar::F64(2.0),
+                    Scalar::F64(3.0),
+                ],
+            ),
+            mk_column(
+                "y",
+                vec![
+                    Scalar::F64(2.0),
+                    Scalar::F64(4.0),
+                    Scalar::F64(6.0),
+                    Scalar::F64(3.0),
+                    Scalar::F64(2.0),
+                    Scalar::F64(1.0),
+                ],
+            ),
+        ],
+    };
+
+    let agg = Aggregate {
+        group_by: vec!["g".into()],
+        aggs: vec!["covar:x:y".into(), "corr:x:y".into()],
+        approx: false,
+        spill_mgr: None,
+    };
+
+    let result = agg
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("aggregate");
+
+    for (key, val) in by_group(&result, "g", "covar_x_y") {
+        let covar = match val {
+            Scalar::F64(v) => v,
+            other => panic!("{:?}", other),
+        };
+        match key.as_str() {
+            "a" => assert!((covar - 2.0).abs() < 1e-9), // covar(x, 2x) = 2*var(x) = 2
+            "b" => assert!((covar + 1.0).abs() < 1e-9),
+            other => panic!("group {}", other),
+        }
+    }
+    for (key, val) in by_group(&result, "g", "corr_x_y") {
+        let corr = match val {
+            Scalar::F64(v) => v,
+            other => panic!("{:?}", other),
+        };
+        match key.as_str() {
+            "a" => assert!((corr - 1.0).abs() < 1e-9),
+            "b" => assert!((corr + 1.0).abs() < 1e-9),
+            other => panic!("group {}", other),
+        }
+    }
+}
+
+#[test]
+fn test_corr_with_too_few_pairs_is_null() {
+    let batch = RowBatch {
+        columns: vec![
+            mk_column("g", vec![Scalar::Str("a".into())]),
+            mk_column("x", vec![Scalar::F64(1.0)]),
+            mk_column("y", vec![Scalar::F64(2.0)]),
+        ],
+    };
+    let agg = Aggregate {
+        group_by: vec!["g".into()],
+        aggs: vec!["corr:x:y".into()],
+        approx: false,
+        spill_mgr: None,
+    };
+    let result = agg
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024))
+        .expect("aggregate");
+    assert_eq!(
+        by_group(&result, "g", "corr_x_y")[0].1,
+        Scalar::Null
+    );
+}
@@ -0,0 +1,136 @@
+//! MinHash/LSH approximate duplicate detection tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::sketch::MinHash;
+use emsqrt_operators::{dedup::ApproxDedup, Operator};
+
+#[test]
+fn test_minhash_similarity_estimates() {
+    let a = MinHash::from_text("the quick brown fox jumps over the lazy dog", 3);
+    let b = MinHash::from_text("the quick brown fox jumps over the lazy dog", 3);
+    let c = MinHash::from_text("the quick brown fox jumps over the lazy cat", 3);
+    let d = MinHash::from_text("completely unrelated text about databases", 3);
+
+    assert_eq!(a.similarity(&b), 1.0);
+    assert!(a.similarity(&c) > 0.6, "near-dup sim {}", a.similarity(&c));
+    assert!(a.similarity(&d) < 0.3, "unrelated sim {}", a.similarity(&d));
+}
+
+#[test]
+fn test_approx_dedup_drops_near_duplicates() {
+    let dedup = ApproxDedup {
+        column: "text".to_string(),
+        threshold: 0.7,
+        shingle_len: 3,
+    };
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "text".to_string(),
+            values: vec![
+                Scalar::Str("customer ordered 3 widgets on 2024-01-05".into()),
+                Scalar::Str("customer ordered 3 widgets on 2024-01-06".into()), // near-dup
+                Scalar::Str("supplier invoice overdue since march".into()),
+                Scalar::Str("customer ordered 3 widgets on 2024-01-05".into()), // exact dup
+                Scalar::Null, // never deduplicated
+            ],
+        }],
+    };
+
+    let result = dedup
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("dedup");
+
+    assert_eq!(result.num_rows(), 3);
+    assert!(matches!(result.columns[0].values[2], Scalar::Null));
+}
+
+#[test]
+fn test_approx_dedup_plan_validation() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    let schema = Schema::new(vec![Field::new("text", DataType::Utf8, false)]);
+
+    assert!(ApproxDedup::default().plan(std::slice::from_ref(&schema)).is_err()); // no column
+    let bad_threshold = ApproxDedup {
+        column: "text".into(),
+        threshold: 1.5,
+        shingle_len: 3,
+    };
+    assert!(bad_threshold.plan(std::slice::from_ref(&schema)).is_err());
+    let ok = ApproxDedup {
+        column: "text".into(),
+        ..Default::default()
+    };
+    assert!(ok.plan(&[schema]).is_ok());
+}
+
+#[test]
+fn test_window_dedup_fuzzy_timestamps() {
+    use emsqrt_operators::dedup::WindowDedup;
+
+    let dedup = WindowDedup {
+        key: "event".to_string(),
+        ts_column: "ts".to_string(),
+        window: "5s".to_string(),
+    };
+
+    let batch = RowBatch {
+        columns: vec![
+            Column {
+                name: "event".to_string(),
+                values: vec![
+                    Scalar::Str("e1".into()),
+                    Scalar::Str("e1".into()), // redelivered 2s later -> dup
+                    Scalar::Str("e2".into()), // different key -> kept
+                    Scalar::Str("e1".into()), // 10s later -> new epoch, kept
+                    Scalar::Str("e1".into()), // no timestamp -> passes through
+                ],
+            },
+            Column {
+                name: "ts".to_string(),
+                values: vec![
+                    Scalar::I64(1_000),
+                    Scalar::I64(3_000),
+                    Scalar::I64(3_500),
+                    Scalar::I64(11_000),
+                    Scalar::Null,
+                ],
+            },
+        ],
+    };
+
+    let result = dedup
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("dedup");
+    assert_eq!(result.num_rows(), 4);
+    assert_eq!(
+        result.columns[1].values,
+        vec![
+            Scalar::I64(1_000),
+            Scalar::I64(3_500),
+            Scalar::I64(11_000),
+            Scalar::Null
+        ]
+    );
+}
+
+#[test]
+fn test_window_dedup_plan_validation() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_operators::dedup::WindowDedup;
+
+    let schema = Schema::new(vec![
+        Field::new("event", DataType::Utf8, false),
+        Field::new("ts", DataType::Date64, false),
+    ]);
+    assert!(WindowDedup::default()
+        .plan(std::slice::from_ref(&schema))
+        .is_err());
+    let bad_window = WindowDedup {
+        key: "event".into(),
+        ts_column: "ts".into(),
+        window: "sometime".into(),
+    };
+    assert!(bad_window.plan(std::slice::from_ref(&schema)).is_err());
+}
@@ -0,0 +1,101 @@
+//! BufferPool size-class recycling tests.
+
+use emsqrt_mem::{BufferPool, MemoryBudgetImpl};
+
+#[test]
+fn test_recycled_buffer_is_reused() {
+    let pool = BufferPool::new(MemoryBudgetImpl::new(10 * 1024 * 1024));
+
+    let buf = pool.alloc_zeroed(4096, "test").expect("alloc");
+    assert_eq!(buf.len(), 4096);
+    pool.recycle(buf);
+    assert_eq!(pool.free_buffers(), 1);
+
+    // Same size class: the pooled buffer is handed back out.
+    let again = pool.alloc_zeroed(4000, "test").expect("realloc");
+    assert_eq!(pool.free_buffers(), 0);
+    assert!(again.len() == 4000);
+    assert!(again.iter().all(|b| *b == 0), "reused buffer must be zeroed");
+}
+
+#[test]
+fn test_budget_released_while_pooled() {
+    let budget_cap = 8192;
+    let pool = BufferPool::new(MemoryBudgetImpl::new(budget_cap));
+
+    let buf = pool.alloc_zeroed(8192, "test").expect("alloc");
+    // Budget is fully used while the buffer is live...
+    assert!(pool.alloc_zeroed(8192, "test").is_err());
+
+    // ...but recycling releases the accounting even though the allocation
+    // stays pooled.
+    pool.recycle(buf);
+    assert_eq!(pool.free_buffers(), 1);
+    let again = pool.alloc_zeroed(8192, "test").expect("realloc after recycle");
+    drop(again);
+}
+
+#[test]
+fn test_free_list_is_bounded() {
+    let pool = BufferPool::new(MemoryBudgetImpl::new(100 * 1024 * 1024));
+
+    let bufs: Vec<_> = (0..20)
+        .map(|_| pool.alloc_zeroed(1024, "test").expect("alloc"))
+        .collect();
+    for buf in bufs {
+        pool.recycle(buf);
+    }
+    // Only MAX_FREE_PER_CLASS buffers are retained per class.
+    assert!(pool.free_buffers() <= 8, "pooled {}", pool.free_buffers());
+}
+
+#[test]
+fn test_overflow_buf_spills_past_memory_window() {
+    use emsqrt_io::storage::FsStorage;
+    use emsqrt_mem::OverflowBuf;
+    use std::sync::Arc;
+
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_overflow_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let budget = MemoryBudgetImpl::new(1024);
+    let storage: Arc<dyn emsqrt_mem::Storage> = Arc::new(FsStorage::new());
+
+    // 256-byte in-memory window; writing 1000 bytes must spill chunks.
+    let mut buf = OverflowBuf::new(&budget, storage, &dir, 256, "overflow_test").unwrap();
+    let payload: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+    buf.write(&payload).unwrap();
+
+    assert_eq!(buf.len(), 1000);
+    assert!(buf.spilled_chunks() >= 3, "chunks: {}", buf.spilled_chunks());
+
+    // Content round-trips in order across chunks and the in-memory tail.
+    assert_eq!(buf.to_vec().unwrap(), payload);
+
+    // Chunked iteration never hands out more than the window at once.
+    buf.for_each_chunk(|chunk| {
+        assert!(chunk.len() <= 256);
+        Ok(())
+    })
+    .unwrap();
+
+    buf.clear().unwrap();
+    assert!(buf.is_empty());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_overflow_buf_respects_budget() {
+    use emsqrt_io::storage::FsStorage;
+    use emsqrt_mem::OverflowBuf;
+    use std::sync::Arc;
+
+    let budget = MemoryBudgetImpl::new(100);
+    let storage: Arc<dyn emsqrt_mem::Storage> = Arc::new(FsStorage::new());
+    // A window larger than the budget is refused up front.
+    assert!(OverflowBuf::new(&budget, storage, "/tmp", 200, "too_big").is_err());
+}
@@ -28,8 +28,6 @@ steps:
 
 #[test]
 fn test_parse_pipeline_with_aggregate() {
-    // Note: Aggregate is not yet supported in YAML DSL parser
-    // This test documents current limitation
     let yaml = r#"
 steps:
   - op: scan
@@ -53,8 +51,61 @@ steps:
 "#;
 
     let result = parse_yaml_pipeline(yaml);
-    // Currently fails because aggregate is not in Step enum
-    // This is expected until aggregate support is added to YAML parser
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_aggregate_with_filter_clause() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/sales.csv"
+    schema:
+      - name: "product"
+        type: "Utf8"
+        nullable: false
+      - name: "amount"
+        type: "Float64"
+        nullable: false
+      - name: "status"
+        type: "Utf8"
+        nullable: false
+  - op: aggregate
+    group_by:
+      - "product"
+    aggs:
+      - "SUM(amount) FILTER (WHERE status == 'paid')"
+      - "COUNT(*)"
+  - op: sink
+    destination: "output/summary.csv"
+    format: "csv"
+"#;
+
+    let result = parse_yaml_pipeline(yaml);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_aggregate_rejects_malformed_filter() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/sales.csv"
+    schema:
+      - name: "product"
+        type: "Utf8"
+        nullable: false
+  - op: aggregate
+    group_by:
+      - "product"
+    aggs:
+      - "SUM(amount) FILTER status == 'paid'"
+  - op: sink
+    destination: "output/summary.csv"
+    format: "csv"
+"#;
+
+    let result = parse_yaml_pipeline(yaml);
     assert!(result.is_err());
 }
 
@@ -157,3 +208,226 @@ steps:
     assert_eq!(parsed.config.spill_dir.as_deref(), Some("/tmp/alt-spill"));
     assert_eq!(parsed.config.spill_aws_region.as_deref(), Some("us-west-2"));
 }
+
+#[test]
+fn test_parse_lookup_pipeline() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/sales.csv"
+    schema:
+      - name: "product"
+        type: "Utf8"
+        nullable: false
+  - op: lookup
+    source: "data/products.csv"
+    key: "product"
+    ref_key: "sku"
+    columns:
+      - "category"
+  - op: sink
+    destination: "output/enriched.csv"
+    format: "csv"
+"#;
+
+    let result = parse_yaml_pipeline(yaml);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_scan_with_column_defaults() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/input.csv"
+    schema:
+      - name: "id"
+        type: "Int64"
+        nullable: false
+      - name: "region"
+        type: "Utf8"
+        nullable: false
+        default: "unknown"
+  - op: sink
+    destination: "output/result.csv"
+    format: "csv"
+"#;
+
+    let parsed = parse_yaml_pipeline(yaml).expect("parsed pipeline");
+    match parsed.plan {
+        emsqrt_planner::logical::LogicalPlan::Sink { input, .. } => match *input {
+            emsqrt_planner::logical::LogicalPlan::Scan { schema, .. } => {
+                assert_eq!(schema.fields[0].default, None);
+                assert_eq!(schema.fields[1].default.as_deref(), Some("unknown"));
+            }
+            other => panic!("expected scan, got {:?}", other),
+        },
+        other => panic!("expected sink, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_route_sink_pipeline() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/sales.csv"
+    schema:
+      - name: "amount"
+        type: "Float64"
+        nullable: false
+  - op: route
+    format: "csv"
+    default: "output/rest.csv"
+    routes:
+      - when: "amount >= 100.0"
+        destination: "output/big.csv"
+      - when: "amount >= 10.0"
+        destination: "output/medium.csv"
+"#;
+
+    let result = parse_yaml_pipeline(yaml);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_retention_pipeline() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/events.csv"
+    schema:
+      - name: "ts"
+        type: "Date64"
+        nullable: true
+  - op: retention
+    column: "ts"
+    max_age: "30d"
+    as_of: "2024-03-15"
+  - op: sink
+    destination: "output/fresh.csv"
+    format: "csv"
+"#;
+    assert!(parse_yaml_pipeline(yaml).is_ok());
+}
+
+#[test]
+fn test_parse_scan_with_csv_dialect_options() {
+    let yaml = r##"
+steps:
+  - op: scan
+    source: "data/input.csv"
+    delimiter: ";"
+    quote: "'"
+    comment: "#"
+    has_headers: false
+    schema:
+      - name: "id"
+        type: "Int64"
+        nullable: false
+  - op: sink
+    destination: "out.csv"
+    format: "csv"
+"##;
+    let parsed = parse_yaml_pipeline(yaml).expect("parsed");
+    match parsed.plan {
+        emsqrt_planner::logical::LogicalPlan::Sink { input, .. } => match *input {
+            emsqrt_planner::logical::LogicalPlan::Scan { schema, .. } => {
+                assert_eq!(schema.metadata.get("csv.delimiter").map(String::as_str), Some(";"));
+                assert_eq!(
+                    schema.metadata.get("csv.has_headers").map(String::as_str),
+                    Some("false")
+                );
+            }
+            other => panic!("expected scan, got {:?}", other),
+        },
+        other => panic!("expected sink, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_sink_with_partition_by() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/events.csv"
+    schema:
+      - name: "region"
+        type: "Utf8"
+        nullable: false
+      - name: "amount"
+        type: "Float64"
+        nullable: false
+  - op: sink
+    destination: "output/events"
+    format: "jsonl"
+    partition_by: ["region"]
+"#;
+    let parsed = parse_yaml_pipeline(yaml).expect("parsed");
+    match parsed.plan {
+        emsqrt_planner::logical::LogicalPlan::Sink { format, .. } => {
+            assert_eq!(format, "partitioned:jsonl:region");
+        }
+        other => panic!("expected sink, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_sink_partition_by_rejects_unsupported_format() {
+    let yaml = r#"
+steps:
+  - op: scan
+    source: "data/events.csv"
+    schema:
+      - name: "region"
+        type: "Utf8"
+        nullable: false
+  - op: sink
+    destination: "out"
+    format: "sorted_runs:region"
+    partition_by: ["region"]
+"#;
+    assert!(parse_yaml_pipeline(yaml).is_err());
+}
+
+#[test]
+fn test_parse_scan_with_extended_csv_dialect_options() {
+    let yaml = r##"
+steps:
+  - op: scan
+    source: "data/report.csv"
+    escape: "\\"
+    null_token: "NA"
+    skip_rows: 2
+    headers: ["id", "label"]
+    schema:
+      - name: "id"
+        type: "Int64"
+        nullable: false
+  - op: sink
+    destination: "out.csv"
+    format: "csv"
+"##;
+    let parsed = parse_yaml_pipeline(yaml).expect("parsed");
+    match parsed.plan {
+        emsqrt_planner::logical::LogicalPlan::Sink { input, .. } => match *input {
+            emsqrt_planner::logical::LogicalPlan::Scan { schema, .. } => {
+                assert_eq!(schema.metadata.get("csv.escape").map(String::as_str), Some("\\"));
+                assert_eq!(
+                    schema.metadata.get("csv.null_token").map(String::as_str),
+                    Some("NA")
+                );
+                assert_eq!(
+                    schema.metadata.get("csv.skip_rows").map(String::as_str),
+                    Some("2")
+                );
+                assert_eq!(
+                    schema.metadata.get("csv.headers").map(String::as_str),
+                    Some("id,label")
+                );
+            }
+            other => panic!("expected scan, got {:?}", other),
+        },
+        other => panic!("expected sink, got {:?}", other),
+    }
+}
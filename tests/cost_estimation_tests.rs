@@ -173,3 +173,57 @@ fn test_cost_estimation_with_hints() {
     assert_eq!(work.total_rows, 5000);
     assert_eq!(work.total_bytes, 100000);
 }
+
+#[test]
+fn test_min_frontier_block_ordering() {
+    use emsqrt_core::dag::LogicalPlan as L;
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_planner::{estimate_work, lower_to_physical, WorkHint};
+    use emsqrt_te::plan_te;
+    use std::collections::HashSet;
+
+    let scan = L::Scan {
+        source: "data/big.csv".to_string(),
+        schema: Schema::new(vec![Field::new("x", DataType::Int64, false)]),
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "x > 0".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: "out.csv".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys = lower_to_physical(&sink);
+    let hints = WorkHint {
+        source_rows: vec![("data/big.csv".to_string(), 100_000)],
+        source_bytes: vec![("data/big.csv".to_string(), 8_000_000)],
+    };
+    let work = estimate_work(&sink, Some(&hints));
+
+    // Small-ish cap so the pipeline splits into many blocks per operator.
+    let te = plan_te(&phys.plan, &work, 1_000_000).expect("plan");
+    assert!(te.order.len() > 6, "expected multiple blocks per op");
+
+    // Order must stay topological: every dep appears before its consumer.
+    let mut seen = HashSet::new();
+    for block in &te.order {
+        for dep in &block.deps {
+            assert!(seen.contains(&dep.get()), "dep scheduled after consumer");
+        }
+        seen.insert(block.id.get());
+    }
+
+    // Frontier stays bounded by the pipeline depth, not the block count:
+    // consumers run right after their producers instead of per-operator
+    // sweeps that keep every source block live.
+    let frontier = te.max_frontier_hint.expect("hint");
+    assert!(
+        frontier <= 4,
+        "frontier {} should not scale with block count {}",
+        frontier,
+        te.order.len()
+    );
+}
@@ -45,8 +45,10 @@ fn e2e_smoke() {
     let work = estimate_work(&lp, None);
     let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
 
-    let mut config = EngineConfig::default();
-    config.spill_dir = format!("{}/spill", temp_dir);
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
     let mut eng = Engine::new(config).expect("engine init");
     let manifest = eng.run(&phys_prog, &te).unwrap();
     assert!(manifest.started_ms <= manifest.finished_ms);
@@ -54,3 +56,802 @@ fn e2e_smoke() {
     // Cleanup
     let _ = fs::remove_dir_all(temp_dir);
 }
+
+#[test]
+fn test_metadata_virtual_columns() {
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_virtual_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).expect("create temp dir");
+
+    let input_file = format!("{}/input.csv", temp_dir);
+    let output_file = format!("{}/output.csv", temp_dir);
+    fs::write(&input_file, "id\n10\n20\n").expect("write input");
+
+    let scan = L::Scan {
+        source: input_file.clone(),
+        schema: Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("_file", DataType::Utf8, false),
+            Field::new("_line", DataType::Int64, false),
+            Field::new("_byte_offset", DataType::Int64, false),
+        ]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: output_file.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    eng.run(&phys_prog, &te).expect("run");
+
+    let output = fs::read_to_string(&output_file).expect("read output");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "id,_file,_line,_byte_offset");
+    // Header is line 1, first data row is line 2 at byte offset 3 ("id\n")
+    assert_eq!(lines[1], format!("10,{},2,3", input_file));
+    assert_eq!(lines[2], format!("20,{},3,6", input_file));
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_manifest_records_source_checkpoints() {
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_checkpoint_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).expect("create temp dir");
+
+    let input_file = format!("{}/input.csv", temp_dir);
+    let output_file = format!("{}/output.csv", temp_dir);
+    fs::write(&input_file, "id\n1\n2\n3\n").expect("write input");
+
+    let scan = L::Scan {
+        source: input_file.clone(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: output_file,
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    let manifest = eng.run(&phys_prog, &te).expect("run");
+
+    assert_eq!(manifest.source_checkpoints.len(), 1);
+    assert_eq!(manifest.source_checkpoints[0].source, input_file);
+    assert_eq!(manifest.source_checkpoints[0].rows_read, 3);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_registered_in_memory_source() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::MemorySource;
+
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_memsrc_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).expect("create temp dir");
+    let output_file = format!("{}/output.csv", temp_dir);
+
+    let scan = L::Scan {
+        source: "mem://events".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "id > 1".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: output_file.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    eng.register_source(
+        "events",
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(1), Scalar::I64(2), Scalar::I64(3)],
+            }],
+        }]),
+    );
+    eng.run(&phys_prog, &te).expect("run");
+
+    let output = fs::read_to_string(&output_file).expect("read output");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines, vec!["id", "2", "3"]);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_unregistered_memory_source_is_error() {
+    let scan = L::Scan {
+        source: "mem://missing".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "/tmp/never-written.csv".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    assert!(eng.run(&phys_prog, &te).is_err());
+}
+
+#[test]
+fn test_registered_callback_sink() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::{CallbackSink, MemorySource};
+    use std::sync::{Arc, Mutex};
+
+    let scan = L::Scan {
+        source: "mem://events".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "callback://collect".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    eng.register_source(
+        "events",
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(10), Scalar::I64(20)],
+            }],
+        }]),
+    );
+
+    let collected: Arc<Mutex<Vec<RowBatch>>> = Arc::new(Mutex::new(Vec::new()));
+    let collected_in_sink = collected.clone();
+    eng.register_sink(
+        "collect",
+        CallbackSink(move |batch: &RowBatch| {
+            collected_in_sink.lock().unwrap().push(batch.clone());
+            Ok(())
+        }),
+    );
+
+    eng.run(&phys_prog, &te).expect("run");
+
+    let batches = collected.lock().unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].num_rows(), 2);
+    assert_eq!(batches[0].columns[0].values[1], Scalar::I64(20));
+}
+
+#[test]
+fn test_channel_sink_streams_batches_with_backpressure() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::MemorySource;
+
+    let scan = L::Scan {
+        source: "mem://stream".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "callback://out".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    eng.register_source(
+        "stream",
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(1)],
+            }],
+        }]),
+    );
+    // Capacity 0 = rendezvous channel: the engine blocks in the sink until
+    // the consumer receives, proving the send is backpressured.
+    let rx = eng.register_channel_sink("out", 0);
+
+    let engine_thread = std::thread::spawn(move || eng.run(&phys_prog, &te));
+
+    let batch = rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .expect("batch from channel sink");
+    assert_eq!(batch.num_rows(), 1);
+
+    engine_thread
+        .join()
+        .expect("engine thread")
+        .expect("engine run");
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_stream_sink() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::MemorySource;
+    use futures::StreamExt;
+
+    let scan = L::Scan {
+        source: "mem://stream".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "callback://out".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    eng.register_source(
+        "stream",
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(7)],
+            }],
+        }]),
+    );
+    let mut stream = eng.register_stream_sink("out", 1);
+
+    let engine_thread = std::thread::spawn(move || eng.run(&phys_prog, &te));
+
+    let batch = futures::executor::block_on(stream.next()).expect("batch from stream");
+    assert_eq!(batch.columns[0].values, vec![Scalar::I64(7)]);
+
+    // Drain so the engine isn't blocked on a full buffer, then join.
+    futures::executor::block_on(async { while stream.next().await.is_some() {} });
+    engine_thread
+        .join()
+        .expect("engine thread")
+        .expect("engine run");
+}
+
+#[test]
+fn test_speculative_execution_produces_same_output() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::MemorySource;
+    use std::sync::{Arc, Mutex};
+
+    let scan = L::Scan {
+        source: "mem://spec".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "id > 5".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: "callback://spec_out".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    // Aggressive threshold: every block looks like a straggler, so the
+    // speculative path (including backup attempts) runs for each block.
+    let config = EngineConfig {
+        speculative_straggler_ms: Some(0),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    eng.register_source(
+        "spec",
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: (0..10).map(Scalar::I64).collect(),
+            }],
+        }]),
+    );
+
+    let collected: Arc<Mutex<Vec<RowBatch>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink_collected = collected.clone();
+    eng.register_sink(
+        "spec_out",
+        emsqrt_exec::CallbackSink(move |batch: &RowBatch| {
+            sink_collected.lock().unwrap().push(batch.clone());
+            Ok(())
+        }),
+    );
+
+    eng.run(&phys_prog, &te).expect("run");
+
+    let batches = collected.lock().unwrap();
+    // Exactly one batch lands in the sink (the winner), with ids 6..=9.
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].num_rows(), 4);
+}
+
+#[test]
+fn test_shared_scan_elimination_in_self_join() {
+    use emsqrt_core::dag::JoinType;
+
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_sharedscan_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).expect("create temp dir");
+    let input_file = format!("{}/input.csv", temp_dir);
+    let output_file = format!("{}/output.csv", temp_dir);
+    fs::write(&input_file, "id\n1\n2\n").expect("write input");
+
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+    let scan = |_: ()| L::Scan {
+        source: input_file.clone(),
+        schema: schema.clone(),
+    };
+    let join = L::Join {
+        left: Box::new(scan(())),
+        right: Box::new(scan(())),
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: JoinType::Inner,
+    };
+    let sink = L::Sink {
+        input: Box::new(join),
+        destination: output_file.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+
+    // Both scans collapse onto one source binding.
+    let source_bindings = phys_prog
+        .bindings
+        .values()
+        .filter(|b| b.key == "source")
+        .count();
+    assert_eq!(source_bindings, 1);
+
+    // And execution still produces the self-join output.
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    eng.run(&phys_prog, &te).expect("run");
+
+    let output = fs::read_to_string(&output_file).expect("read output");
+    assert_eq!(output.lines().count(), 3); // header + 2 matched rows
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_custom_connector_source_and_sink() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::{BatchSink, Capabilities, Connector, RowBatchProvider};
+    use std::sync::{Arc, Mutex};
+
+    // Toy connector for "toy://" URIs: the source emits one fixed batch,
+    // the sink collects into shared state.
+    struct ToySource(Option<RowBatch>);
+    impl RowBatchProvider for ToySource {
+        fn next_batch(&mut self) -> Option<RowBatch> {
+            self.0.take()
+        }
+    }
+    struct ToySink(Arc<Mutex<u64>>);
+    impl BatchSink for ToySink {
+        fn write_batch(&mut self, batch: &RowBatch) -> Result<(), String> {
+            *self.0.lock().unwrap() += batch.num_rows() as u64;
+            Ok(())
+        }
+    }
+    struct ToyConnector {
+        written: Arc<Mutex<u64>>,
+    }
+    impl Connector for ToyConnector {
+        fn name(&self) -> &'static str {
+            "toy"
+        }
+        fn capabilities(&self) -> Capabilities {
+            Capabilities {
+                read: true,
+                write: true,
+                streaming: true,
+                random_access: false,
+            }
+        }
+        fn matches(&self, uri: &str) -> bool {
+            uri.starts_with("toy://")
+        }
+        fn open_source(
+            &self,
+            _uri: &str,
+            _schema: &Schema,
+        ) -> Result<Box<dyn RowBatchProvider>, String> {
+            Ok(Box::new(ToySource(Some(RowBatch {
+                columns: vec![Column {
+                    name: "id".to_string(),
+                    values: vec![Scalar::I64(1), Scalar::I64(2), Scalar::I64(3)],
+                }],
+            }))))
+        }
+        fn open_sink(&self, _uri: &str, _format: &str) -> Result<Box<dyn BatchSink>, String> {
+            Ok(Box::new(ToySink(self.written.clone())))
+        }
+    }
+
+    let scan = L::Scan {
+        source: "toy://numbers".to_string(),
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "toy://collector".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let written = Arc::new(Mutex::new(0u64));
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    eng.register_connector(ToyConnector {
+        written: written.clone(),
+    });
+    let manifest = eng.run(&phys_prog, &te).expect("run");
+
+    assert_eq!(*written.lock().unwrap(), 3);
+    assert_eq!(manifest.records_written, 3);
+}
+
+#[test]
+fn test_atomic_sinks_publish_on_success_and_not_on_failure() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::MemorySource;
+
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_atomic_sink_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = fs::remove_dir_all(&temp_dir);
+    fs::create_dir_all(&temp_dir).unwrap();
+    let output = format!("{}/out.csv", temp_dir);
+
+    let build = |expr: &str| {
+        let scan = L::Scan {
+            source: "mem://rows".to_string(),
+            schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+        };
+        let filter = L::Filter {
+            input: Box::new(scan),
+            expr: expr.to_string(),
+        };
+        L::Sink {
+            input: Box::new(filter),
+            destination: output.clone(),
+            format: "csv".to_string(),
+        }
+    };
+    let source_batch = || {
+        MemorySource::new(vec![RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(1), Scalar::I64(2)],
+            }],
+        }])
+    };
+    let config = || EngineConfig {
+        atomic_sinks: true,
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+
+    // Failing run (bad filter expression): no output file appears.
+    let bad = build("id >>> nonsense");
+    let phys = lower_to_physical(&bad);
+    let te = plan_te(&phys.plan, &estimate_work(&bad, None), 64 * 1024 * 1024).unwrap();
+    let mut eng = Engine::new(config()).unwrap();
+    eng.register_source("rows", source_batch());
+    assert!(eng.run(&phys, &te).is_err());
+    assert!(
+        !std::path::Path::new(&output).exists(),
+        "failed run must not publish output"
+    );
+
+    // Successful run publishes, and the staging file is gone.
+    let good = build("id > 0");
+    let phys = lower_to_physical(&good);
+    let te = plan_te(&phys.plan, &estimate_work(&good, None), 64 * 1024 * 1024).unwrap();
+    let mut eng = Engine::new(config()).unwrap();
+    eng.register_source("rows", source_batch());
+    eng.run(&phys, &te).expect("run");
+
+    let content = fs::read_to_string(&output).expect("published output");
+    assert_eq!(content.lines().count(), 3); // header + 2 rows
+    assert!(!std::path::Path::new(&format!("{}/.emsqrt-staging/out.csv", temp_dir)).exists());
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_engine_warm_start_reuses_operators() {
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_warm_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).unwrap();
+    let input = format!("{}/input.csv", temp_dir);
+    let output = format!("{}/output.csv", temp_dir);
+    fs::write(&input, "id\n1\n2\n3\n").unwrap();
+
+    let scan = L::Scan {
+        source: input,
+        schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "id > 1".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: output.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).unwrap();
+    assert_eq!(eng.warm_operator_count(), 0);
+
+    let first = eng.run(&phys_prog, &te).expect("first run");
+    let warm = eng.warm_operator_count();
+    assert!(warm > 0, "stateless operators should be cached");
+
+    // Second run on the same engine reuses the cache and produces the
+    // same output (sources are rebuilt, so the file is re-read fully).
+    let second = eng.run(&phys_prog, &te).expect("second run");
+    assert_eq!(eng.warm_operator_count(), warm);
+    assert_eq!(first.records_written, second.records_written);
+    assert_eq!(first.outputs_digest, second.outputs_digest);
+
+    let content = fs::read_to_string(&output).unwrap();
+    assert_eq!(content.lines().count(), 3);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_lookup_table_respects_memory_budget() {
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_lookup_budget_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).unwrap();
+    let input = format!("{}/input.csv", temp_dir);
+    let reference = format!("{}/ref.csv", temp_dir);
+    let output = format!("{}/out.csv", temp_dir);
+
+    fs::write(&input, "id\n1\n2\n").unwrap();
+    // ~100KB reference table.
+    let mut ref_csv = String::from("id,payload\n");
+    for i in 0..1000 {
+        ref_csv.push_str(&format!("{},{}\n", i, "x".repeat(100)));
+    }
+    fs::write(&reference, ref_csv).unwrap();
+
+    let build = || {
+        let scan = L::Scan {
+            source: input.clone(),
+            schema: Schema::new(vec![Field::new("id", DataType::Int64, false)]),
+        };
+        L::Sink {
+            input: Box::new(L::Lookup {
+                input: Box::new(scan),
+                source: reference.clone(),
+                key: "id".to_string(),
+                ref_key: "id".to_string(),
+                columns: vec!["payload".to_string()],
+            }),
+            destination: output.clone(),
+            format: "csv".to_string(),
+        }
+    };
+
+    let run_with_cap = |cap: usize| {
+        let sink = build();
+        let phys = lower_to_physical(&sink);
+        let te = plan_te(&phys.plan, &estimate_work(&sink, None), cap).unwrap();
+        let config = EngineConfig {
+            mem_cap_bytes: cap,
+            spill_dir: format!("{}/spill", temp_dir),
+            ..Default::default()
+        };
+        Engine::new(config).unwrap().run(&phys, &te)
+    };
+
+    // A tight cap rejects the table with an actionable error...
+    let err = run_with_cap(16 * 1024).unwrap_err().to_string();
+    assert!(err.contains("memory budget"), "err: {}", err);
+    assert!(err.contains("join"), "err should point at joins: {}", err);
+
+    // ...and a reasonable cap loads it and enriches.
+    run_with_cap(64 * 1024 * 1024).expect("run");
+    let content = fs::read_to_string(&output).unwrap();
+    assert!(content.lines().count() == 3);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn test_speculative_attempts_still_retry_recoverable_errors() {
+    use emsqrt_core::budget::MemoryBudget;
+    use emsqrt_core::types::RowBatch;
+    use emsqrt_operators::plan::{Footprint, OpPlan};
+    use emsqrt_operators::traits::{OpError, Operator};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    // Operator that fails with a recoverable error twice, then succeeds —
+    // the flaky-storage shape both retry and speculation exist for.
+    struct Flaky {
+        attempts: Arc<AtomicU32>,
+    }
+    impl Operator for Flaky {
+        fn name(&self) -> &'static str {
+            "filter"
+        }
+        fn memory_need(&self, _rows: u64, _bytes: u64) -> Footprint {
+            Footprint {
+                bytes_per_row: 1,
+                overhead_bytes: 0,
+            }
+        }
+        fn plan(&self, input_schemas: &[Schema]) -> Result<OpPlan, OpError> {
+            Ok(OpPlan::new(input_schemas[0].clone(), self.memory_need(0, 0)))
+        }
+        fn eval_block(
+            &self,
+            _inputs: &[RowBatch],
+            _budget: &dyn MemoryBudget<Guard = emsqrt_mem::guard::BudgetGuardImpl>,
+        ) -> Result<RowBatch, OpError> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(OpError::Recoverable("transient storage hiccup".into()))
+            } else {
+                Ok(RowBatch { columns: vec![] })
+            }
+        }
+    }
+
+    // Exercise the retry helper through the speculative path by registering
+    // the flaky operator via the registry and running a one-op program.
+    // Simpler: drive a pipeline whose filter is replaced isn't possible, so
+    // assert at the engine level with speculation enabled and a pipeline
+    // that succeeds — combined with the unit above via direct evaluation.
+    let attempts = Arc::new(AtomicU32::new(0));
+    let flaky = Flaky {
+        attempts: attempts.clone(),
+    };
+    // Direct check of the shared behavior: recoverable errors retry until
+    // success under the speculative configuration.
+    let config = EngineConfig {
+        speculative_straggler_ms: Some(10_000), // attempt 0 finishes in time
+        ..Default::default()
+    };
+    let eng = Engine::new(config).unwrap();
+    let result = eng.execute_for_test(Arc::new(flaky), vec![RowBatch { columns: vec![] }]);
+    assert!(result.is_ok(), "retries should absorb recoverable errors");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_csv_dialect_skip_rows_null_token_and_header_override() {
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_dialect_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(&temp_dir).expect("create temp dir");
+
+    let input_file = format!("{}/report.csv", temp_dir);
+    let output_file = format!("{}/output.csv", temp_dir);
+    // Two preamble lines, then a (wrongly named) header row, then data
+    // with "NA" standing in for null.
+    fs::write(
+        &input_file,
+        "generated by reporting tool\n2024-06-01\ncol_a,col_b\n1,x\n2,NA\n",
+    )
+    .expect("write input");
+
+    let mut schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("label", DataType::Utf8, true),
+    ]);
+    schema.metadata.insert("csv.skip_rows".into(), "2".into());
+    schema.metadata.insert("csv.null_token".into(), "NA".into());
+    schema.metadata.insert("csv.headers".into(), "id,label".into());
+
+    let scan = L::Scan {
+        source: input_file.clone(),
+        schema,
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: output_file.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).expect("engine init");
+    eng.run(&phys_prog, &te).expect("run");
+
+    let output = fs::read_to_string(&output_file).expect("read output");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "id,label");
+    assert_eq!(lines[1], "1,x");
+    // The "NA" label came through as null (empty CSV field).
+    assert_eq!(lines[2], "2,");
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
@@ -0,0 +1,189 @@
+//! Elasticsearch bulk sink tests against a fake HTTP endpoint.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_exec::{Connector, ElasticsearchConnector};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// Fake _bulk endpoint replying with the scripted (status, errors-flag)
+/// sequence, recording "<path>|<body>" per request.
+fn spawn_fake_es(
+    replies: Vec<(u16, bool)>,
+) -> (u16, Arc<Mutex<Vec<String>>>, std::thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let bodies: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let server_bodies = bodies.clone();
+    let handle = std::thread::spawn(move || {
+        for (status, errors) in replies {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut chunk).unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    let head = String::from_utf8_lossy(&request[..pos]).to_string();
+                    let content_length: usize = head
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                        .unwrap_or(0);
+                    if request.len() >= pos + 4 + content_length {
+                        let path = head
+                            .lines()
+                            .next()
+                            .and_then(|l| l.split_whitespace().nth(1))
+                            .unwrap_or("")
+                            .to_string();
+                        server_bodies.lock().unwrap().push(format!(
+                            "{}|{}",
+                            path,
+                            String::from_utf8_lossy(&request[pos + 4..])
+                        ));
+                        break;
+                    }
+                }
+            }
+            let body = format!("{{\"errors\": {}, \"items\": []}}", errors);
+            let reason = if status < 300 { "OK" } else { "Nope" };
+            let response = format!(
+                "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                reason,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    (port, bodies, handle)
+}
+
+fn event_batch() -> RowBatch {
+    RowBatch {
+        columns: vec![
+            Column {
+                name: "event_id".to_string(),
+                values: vec![Scalar::Str("e1".into()), Scalar::Str("e2".into())],
+            },
+            Column {
+                name: "value".to_string(),
+                values: vec![Scalar::F64(1.5), Scalar::Null],
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_bulk_sink_sends_ndjson_pairs() {
+    let (port, bodies, server) = spawn_fake_es(vec![(200, false)]);
+
+    let mut sink = ElasticsearchConnector
+        .open_sink(&format!("es://127.0.0.1:{}/events", port), "event_id")
+        .expect("open sink");
+    sink.write_batch(&event_batch()).expect("write");
+    server.join().unwrap();
+
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 1);
+    let (_, body) = bodies[0].split_once('|').unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), 4); // 2 action lines + 2 documents
+
+    let action: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(action["index"]["_id"], "e1");
+    let doc: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+    assert_eq!(doc["value"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_bulk_retries_429_then_succeeds() {
+    let (port, bodies, server) = spawn_fake_es(vec![(429, false), (200, false)]);
+
+    let mut sink = ElasticsearchConnector
+        .open_sink(&format!("es://127.0.0.1:{}/events", port), "csv")
+        .expect("open sink");
+    sink.write_batch(&event_batch()).expect("write with retry");
+    server.join().unwrap();
+    assert_eq!(bodies.lock().unwrap().len(), 2); // throttled attempt + retry
+}
+
+#[test]
+fn test_bulk_item_errors_fail_the_write_without_dead_letter() {
+    let (port, _bodies, server) = spawn_fake_es(vec![(200, true)]);
+
+    let mut sink = ElasticsearchConnector
+        .open_sink(&format!("es://127.0.0.1:{}/events", port), "csv")
+        .expect("open sink");
+    let err = sink.write_batch(&event_batch()).unwrap_err();
+    assert!(err.contains("item errors"), "err: {}", err);
+    server.join().unwrap();
+}
+
+#[test]
+fn test_permanent_failures_route_to_dead_letter() {
+    let (port, _bodies, server) = spawn_fake_es(vec![(200, true)]);
+    let dead_letter = std::env::temp_dir()
+        .join(format!("emsqrt_es_dlq_{}.jsonl", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_file(&dead_letter);
+
+    let mut sink = ElasticsearchConnector
+        .open_sink(
+            &format!("es://127.0.0.1:{}/events?dead_letter={}", port, dead_letter),
+            "csv",
+        )
+        .expect("open sink");
+    // Item errors no longer abort the run; they land in the dead letter.
+    sink.write_batch(&event_batch()).expect("dead-lettered");
+    server.join().unwrap();
+
+    let entry: serde_json::Value = serde_json::from_str(
+        std::fs::read_to_string(&dead_letter).unwrap().trim(),
+    )
+    .unwrap();
+    assert_eq!(entry["index"], "events");
+    assert!(entry["error"].as_str().unwrap().contains("item errors"));
+    assert!(entry["bulk_body"].as_str().unwrap().contains("event_id"));
+
+    let _ = std::fs::remove_file(&dead_letter);
+}
+
+#[test]
+fn test_index_from_column() {
+    // Two rows with different regions -> two bulk requests to two indices.
+    let (port, bodies, server) = spawn_fake_es(vec![(200, false), (200, false)]);
+
+    let mut sink = ElasticsearchConnector
+        .open_sink(&format!("es://127.0.0.1:{}/events-{{region}}", port), "csv")
+        .expect("open sink");
+    sink.write_batch(&RowBatch {
+        columns: vec![
+            Column {
+                name: "region".to_string(),
+                values: vec![Scalar::Str("EU".into()), Scalar::Str("us".into())],
+            },
+            Column {
+                name: "value".to_string(),
+                values: vec![Scalar::I64(1), Scalar::I64(2)],
+            },
+        ],
+    })
+    .expect("write");
+    server.join().unwrap();
+
+    let bodies = bodies.lock().unwrap();
+    let paths: Vec<&str> = bodies
+        .iter()
+        .map(|b| b.split_once('|').unwrap().0)
+        .collect();
+    assert!(paths.contains(&"/events-eu/_bulk"), "paths: {:?}", paths);
+    assert!(paths.contains(&"/events-us/_bulk"), "paths: {:?}", paths);
+}
@@ -1,7 +1,6 @@
 //! Expression evaluation tests
 
 use emsqrt_core::expr::Expr;
-use emsqrt_core::schema::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch, Scalar};
 
 fn create_test_batch() -> RowBatch {
@@ -75,15 +74,15 @@ fn test_evaluate_comparison() {
 
     // Row 0: age=25 > 18 -> true
     let result = expr.evaluate_bool(&batch, 0).unwrap();
-    assert_eq!(result, true);
+    assert!(result);
 
     // Row 1: age=18 > 18 -> false
     let result = expr.evaluate_bool(&batch, 1).unwrap();
-    assert_eq!(result, false);
+    assert!(!result);
 
     // Row 2: age=30 > 18 -> true
     let result = expr.evaluate_bool(&batch, 2).unwrap();
-    assert_eq!(result, true);
+    assert!(result);
 }
 
 #[test]
@@ -93,11 +92,11 @@ fn test_evaluate_equality() {
 
     // Row 0: name="Alice" -> true
     let result = expr.evaluate_bool(&batch, 0).unwrap();
-    assert_eq!(result, true);
+    assert!(result);
 
     // Row 1: name="Bob" -> false
     let result = expr.evaluate_bool(&batch, 1).unwrap();
-    assert_eq!(result, false);
+    assert!(!result);
 }
 
 #[test]
@@ -141,11 +140,11 @@ fn test_evaluate_logical_and() {
 
     // Row 0: age=25 > 20 (true) AND price=10.5 < 15 (true) -> true
     let result = expr.evaluate_bool(&batch, 0).unwrap();
-    assert_eq!(result, true);
+    assert!(result);
 
     // Row 1: age=18 > 20 (false) AND price=20.0 < 15 (false) -> false
     let result = expr.evaluate_bool(&batch, 1).unwrap();
-    assert_eq!(result, false);
+    assert!(!result);
 }
 
 #[test]
@@ -155,11 +154,11 @@ fn test_evaluate_logical_or() {
 
     // Row 0: age=25 < 20 (false) OR price=10.5 > 15 (false) -> false
     let result = expr.evaluate_bool(&batch, 0).unwrap();
-    assert_eq!(result, false);
+    assert!(!result);
 
     // Row 1: age=18 < 20 (true) OR price=20.0 > 15 (true) -> true
     let result = expr.evaluate_bool(&batch, 1).unwrap();
-    assert_eq!(result, true);
+    assert!(result);
 }
 
 #[test]
@@ -187,7 +186,7 @@ fn test_evaluate_null_handling() {
 
     // Row 3 has null age - should evaluate to false
     let result = expr.evaluate_bool(&batch, 3).unwrap();
-    assert_eq!(result, false);
+    assert!(!result);
 }
 
 #[test]
@@ -206,3 +205,386 @@ fn test_evaluate_division_by_zero() {
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("division by zero"));
 }
+
+#[test]
+fn test_evaluate_case_when_branches() {
+    let batch = create_test_batch();
+    let expr = Expr::parse(
+        "CASE WHEN age > 26 THEN 'older' WHEN age > 18 THEN 'adult' ELSE 'other' END",
+    )
+    .unwrap();
+
+    assert_eq!(
+        expr.evaluate(&batch, 0).unwrap(),
+        Scalar::Str("adult".to_string())
+    ); // age 25
+    assert_eq!(
+        expr.evaluate(&batch, 1).unwrap(),
+        Scalar::Str("other".to_string())
+    ); // age 18
+    assert_eq!(
+        expr.evaluate(&batch, 2).unwrap(),
+        Scalar::Str("older".to_string())
+    ); // age 30
+}
+
+#[test]
+fn test_evaluate_case_without_else_yields_null() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("CASE WHEN age > 100 THEN 'ancient' END").unwrap();
+    assert_eq!(expr.evaluate(&batch, 0).unwrap(), Scalar::Null);
+}
+
+#[test]
+fn test_evaluate_coalesce() {
+    let batch = create_test_batch();
+    // age row 3 is null
+    let expr = Expr::parse("COALESCE(age, 0)").unwrap();
+    assert_eq!(expr.evaluate(&batch, 0).unwrap(), Scalar::I32(25));
+    assert_eq!(expr.evaluate(&batch, 3).unwrap(), Scalar::I32(0));
+}
+
+#[test]
+fn test_evaluate_ifnull_and_nullif() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("IFNULL(age, -1)").unwrap();
+    assert_eq!(expr.evaluate(&batch, 3).unwrap(), Scalar::I32(-1));
+
+    // NULLIF(age, 18) nulls out row 1 (age 18), passes others through
+    let expr = Expr::parse("NULLIF(age, 18)").unwrap();
+    assert_eq!(expr.evaluate(&batch, 0).unwrap(), Scalar::I32(25));
+    assert_eq!(expr.evaluate(&batch, 1).unwrap(), Scalar::Null);
+}
+
+#[test]
+fn test_evaluate_coalesce_in_comparison() {
+    let batch = create_test_batch();
+    // Null age treated as 0, so the comparison holds for every row
+    let expr = Expr::parse("COALESCE(age, 0) >= 0").unwrap();
+    for row in 0..4 {
+        assert_eq!(expr.evaluate(&batch, row).unwrap(), Scalar::Bool(true));
+    }
+}
+
+#[test]
+fn test_coalesce_wrong_arity_is_error() {
+    assert!(Expr::parse("NULLIF(age)").is_err());
+    assert!(Expr::parse("COALESCE()").is_err());
+}
+
+#[test]
+fn test_evaluate_split_part() {
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "date".to_string(),
+            values: vec![
+                Scalar::Str("2024-03-15".to_string()),
+                Scalar::Str("2024-07".to_string()),
+                Scalar::Null,
+            ],
+        }],
+    };
+
+    let year = Expr::parse("SPLIT_PART(date, '-', 1)").unwrap();
+    let day = Expr::parse("SPLIT_PART(date, '-', 3)").unwrap();
+
+    assert_eq!(
+        year.evaluate(&batch, 0).unwrap(),
+        Scalar::Str("2024".to_string())
+    );
+    assert_eq!(
+        day.evaluate(&batch, 0).unwrap(),
+        Scalar::Str("15".to_string())
+    );
+    // Out-of-range part and null input are both null
+    assert_eq!(day.evaluate(&batch, 1).unwrap(), Scalar::Null);
+    assert_eq!(year.evaluate(&batch, 2).unwrap(), Scalar::Null);
+}
+
+#[test]
+fn test_split_part_rejects_bad_index() {
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "s".to_string(),
+            values: vec![Scalar::Str("a-b".to_string())],
+        }],
+    };
+    let expr = Expr::parse("SPLIT_PART(s, '-', 0)").unwrap();
+    assert!(expr.evaluate(&batch, 0).is_err());
+}
+
+#[test]
+fn test_evaluate_concat_and_concat_ws() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("CONCAT(name, '-', age)").unwrap();
+    assert_eq!(
+        expr.evaluate(&batch, 0).unwrap(),
+        Scalar::Str("Alice-25".to_string())
+    );
+    // Null age (row 3) is skipped, not rendered as "null"
+    assert_eq!(
+        expr.evaluate(&batch, 3).unwrap(),
+        Scalar::Str("David-".to_string())
+    );
+
+    let expr = Expr::parse("CONCAT_WS('/', name, age)").unwrap();
+    assert_eq!(
+        expr.evaluate(&batch, 0).unwrap(),
+        Scalar::Str("Alice/25".to_string())
+    );
+    // Null argument is dropped entirely (no dangling delimiter)
+    assert_eq!(
+        expr.evaluate(&batch, 3).unwrap(),
+        Scalar::Str("David".to_string())
+    );
+}
+
+#[test]
+fn test_evaluate_format_templating() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("FORMAT('{} is {} years old', name, age)").unwrap();
+    assert_eq!(
+        expr.evaluate(&batch, 1).unwrap(),
+        Scalar::Str("Bob is 18 years old".to_string())
+    );
+}
+
+#[test]
+fn test_format_placeholder_mismatch_is_error() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("FORMAT('{} and {}', name)").unwrap();
+    assert!(expr.evaluate(&batch, 0).is_err());
+}
+
+#[test]
+fn test_evaluate_string_functions() {
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "s".to_string(),
+            values: vec![Scalar::Str("  Hello World  ".to_string()), Scalar::Null],
+        }],
+    };
+
+    let cases: Vec<(&str, Scalar)> = vec![
+        ("UPPER(TRIM(s))", Scalar::Str("HELLO WORLD".into())),
+        ("LOWER(TRIM(s))", Scalar::Str("hello world".into())),
+        ("LENGTH(TRIM(s))", Scalar::I64(11)),
+        ("SUBSTR(TRIM(s), 7)", Scalar::Str("World".into())),
+        ("SUBSTR(TRIM(s), 1, 5)", Scalar::Str("Hello".into())),
+        (
+            "REPLACE(TRIM(s), 'World', 'There')",
+            Scalar::Str("Hello There".into()),
+        ),
+        ("LEFT(TRIM(s), 5)", Scalar::Str("Hello".into())),
+        ("RIGHT(TRIM(s), 5)", Scalar::Str("World".into())),
+        ("REVERSE(LEFT(TRIM(s), 3))", Scalar::Str("leH".into())),
+        ("CONTAINS(s, 'World')", Scalar::Bool(true)),
+        ("STARTS_WITH(TRIM(s), 'Hello')", Scalar::Bool(true)),
+        ("ENDS_WITH(TRIM(s), 'Hello')", Scalar::Bool(false)),
+    ];
+
+    for (expr_str, expected) in cases {
+        let expr = Expr::parse(expr_str).unwrap_or_else(|e| panic!("parse '{}': {}", expr_str, e));
+        assert_eq!(
+            expr.evaluate(&batch, 0).unwrap(),
+            expected,
+            "expr: {}",
+            expr_str
+        );
+        // Null input propagates as null
+        assert_eq!(
+            expr.evaluate(&batch, 1).unwrap(),
+            Scalar::Null,
+            "null propagation for {}",
+            expr_str
+        );
+    }
+}
+
+#[test]
+fn test_string_function_type_errors() {
+    let batch = create_test_batch();
+    // age is an integer column
+    assert!(Expr::parse("UPPER(age)").unwrap().evaluate(&batch, 0).is_err());
+    assert!(Expr::parse("SUBSTR(name, 'x')")
+        .unwrap()
+        .evaluate(&batch, 0)
+        .is_err());
+}
+
+#[test]
+fn test_evaluate_temporal_functions() {
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "ts".to_string(),
+            values: vec![
+                Scalar::Str("2024-03-15 13:45:30".to_string()),
+                Scalar::Date64(0), // 1970-01-01 epoch
+                Scalar::Null,
+            ],
+        }],
+    };
+
+    let to_ts = Expr::parse("TO_TIMESTAMP(ts)").unwrap();
+    let parsed = to_ts.evaluate(&batch, 0).unwrap();
+    assert!(matches!(parsed, Scalar::Date64(_)));
+    assert_eq!(to_ts.evaluate(&batch, 2).unwrap(), Scalar::Null);
+
+    let year = Expr::parse("DATE_PART('year', ts)").unwrap();
+    assert_eq!(year.evaluate(&batch, 0).unwrap(), Scalar::I64(2024));
+    assert_eq!(year.evaluate(&batch, 1).unwrap(), Scalar::I64(1970));
+
+    let hour = Expr::parse("DATE_PART('hour', ts)").unwrap();
+    assert_eq!(hour.evaluate(&batch, 0).unwrap(), Scalar::I64(13));
+
+    // 2024-03-15 is a Friday (dow 5, Sunday = 0)
+    let dow = Expr::parse("DATE_PART('dow', ts)").unwrap();
+    assert_eq!(dow.evaluate(&batch, 0).unwrap(), Scalar::I64(5));
+
+    // Truncation to day, then re-extract the hour
+    let trunc = Expr::parse("DATE_PART('hour', DATE_TRUNC('day', ts))").unwrap();
+    assert_eq!(trunc.evaluate(&batch, 0).unwrap(), Scalar::I64(0));
+}
+
+#[test]
+fn test_temporal_parse_errors() {
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "ts".to_string(),
+            values: vec![Scalar::Str("not a date".to_string())],
+        }],
+    };
+    assert!(Expr::parse("TO_TIMESTAMP(ts)")
+        .unwrap()
+        .evaluate(&batch, 0)
+        .is_err());
+    assert!(Expr::parse("DATE_PART('century', ts)").unwrap().evaluate(
+        &RowBatch {
+            columns: vec![Column {
+                name: "ts".to_string(),
+                values: vec![Scalar::Date64(0)],
+            }],
+        },
+        0
+    )
+    .is_err());
+}
+
+#[test]
+fn test_zorder_key_clusters_dimensions() {
+    let batch = RowBatch {
+        columns: vec![
+            Column {
+                name: "x".to_string(),
+                values: vec![
+                    Scalar::I64(0),
+                    Scalar::I64(0),
+                    Scalar::I64(1 << 62),
+                    Scalar::I64(1 << 62),
+                    Scalar::Null,
+                ],
+            },
+            Column {
+                name: "y".to_string(),
+                values: vec![
+                    Scalar::I64(0),
+                    Scalar::I64(1 << 62),
+                    Scalar::I64(0),
+                    Scalar::I64(1 << 62),
+                    Scalar::I64(5),
+                ],
+            },
+        ],
+    };
+
+    let expr = Expr::parse("ZORDER(x, y)").unwrap();
+    let keys: Vec<Scalar> = (0..5).map(|r| expr.evaluate(&batch, r).unwrap()).collect();
+
+    // Null in any dimension nulls the key.
+    assert_eq!(keys[4], Scalar::Null);
+
+    // All keys distinct, and (0,0) sorts before (max,max).
+    let as_i64 = |s: &Scalar| match s {
+        Scalar::I64(v) => *v,
+        other => panic!("unexpected {:?}", other),
+    };
+    let k00 = as_i64(&keys[0]);
+    let k01 = as_i64(&keys[1]);
+    let k10 = as_i64(&keys[2]);
+    let k11 = as_i64(&keys[3]);
+    assert!(k00 < k01 && k00 < k10 && k11 > k01 && k11 > k10);
+    let distinct: std::collections::HashSet<i64> = [k00, k01, k10, k11].into_iter().collect();
+    assert_eq!(distinct.len(), 4);
+}
+
+#[test]
+fn test_zorder_arity() {
+    assert!(Expr::parse("ZORDER(x)").is_err());
+}
+
+#[test]
+fn test_evaluate_regexp_match() {
+    let batch = create_test_batch();
+    let expr = Expr::parse("REGEXP_MATCH(name, '^[AB]')").unwrap();
+    assert_eq!(expr.evaluate(&batch, 0).unwrap(), Scalar::Bool(true)); // Alice
+    assert_eq!(expr.evaluate(&batch, 2).unwrap(), Scalar::Bool(false)); // Charlie
+
+    // Invalid patterns surface an error; null text is null.
+    let bad = Expr::parse("REGEXP_MATCH(name, '([')").unwrap();
+    assert!(bad.evaluate(&batch, 0).is_err());
+}
+
+#[test]
+fn test_plan_time_type_checking() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+
+    let schema = Schema::new(vec![
+        Field::new("age", DataType::Int32, true),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+
+    // Inference resolves column types through operators and functions.
+    assert_eq!(
+        Expr::parse("age + 1").unwrap().check_types(&schema).unwrap(),
+        Some(DataType::Int32)
+    );
+    assert_eq!(
+        Expr::parse("LENGTH(name)").unwrap().check_types(&schema).unwrap(),
+        Some(DataType::Int64)
+    );
+    assert_eq!(
+        Expr::parse("REGEXP_MATCH(name, 'x')")
+            .unwrap()
+            .check_types(&schema)
+            .unwrap(),
+        Some(DataType::Boolean)
+    );
+
+    // Definite mistakes fail at plan time.
+    assert!(Expr::parse("UPPER(age)").unwrap().check_types(&schema).is_err());
+    assert!(Expr::parse("name * 2").unwrap().check_types(&schema).is_err());
+    assert!(Expr::parse("missing_col > 1")
+        .unwrap()
+        .check_types(&schema)
+        .is_err());
+}
+
+#[test]
+fn test_map_and_filter_plan_reject_type_errors() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_operators::{filter::Filter, map::Map, Operator};
+
+    let schema = Schema::new(vec![Field::new("age", DataType::Int32, true)]);
+
+    let bad_map = Map::parse_config("shout = UPPER(age)").unwrap();
+    assert!(bad_map.plan(std::slice::from_ref(&schema)).is_err());
+
+    let good_map = Map::parse_config("next = age + 1").unwrap();
+    let plan = good_map.plan(std::slice::from_ref(&schema)).unwrap();
+    assert_eq!(plan.output_schema.fields[1].data_type, DataType::Int32);
+
+    let bad_filter = Filter {
+        expr: Some("nonexistent > 3".into()),
+    };
+    assert!(bad_filter.plan(std::slice::from_ref(&schema)).is_err());
+}
@@ -1,7 +1,7 @@
 //! Expression parsing and AST construction tests
 
 use emsqrt_core::expr::{BinOp, Expr, UnaryOp};
-use emsqrt_core::types::{RowBatch, Scalar};
+use emsqrt_core::types::Scalar;
 
 #[test]
 fn test_parse_simple_column() {
@@ -110,3 +110,44 @@ fn test_unaryop_parse() {
     assert_eq!(UnaryOp::parse("IS NOT NULL"), Ok(UnaryOp::IsNotNull));
     assert!(UnaryOp::parse("invalid").is_err());
 }
+
+#[test]
+fn test_parse_case_when() {
+    let expr = Expr::parse("CASE WHEN age > 18 THEN 'adult' ELSE 'minor' END").unwrap();
+    match expr {
+        Expr::Case {
+            branches,
+            else_expr,
+        } => {
+            assert_eq!(branches.len(), 1);
+            assert!(else_expr.is_some());
+        }
+        _ => panic!("Expected Case"),
+    }
+}
+
+#[test]
+fn test_parse_case_multiple_branches_no_else() {
+    let expr =
+        Expr::parse("CASE WHEN age > 65 THEN 'senior' WHEN age > 18 THEN 'adult' END").unwrap();
+    match expr {
+        Expr::Case {
+            branches,
+            else_expr,
+        } => {
+            assert_eq!(branches.len(), 2);
+            assert!(else_expr.is_none());
+        }
+        _ => panic!("Expected Case"),
+    }
+}
+
+#[test]
+fn test_parse_case_missing_end_is_error() {
+    assert!(Expr::parse("CASE WHEN age > 18 THEN 'adult'").is_err());
+}
+
+#[test]
+fn test_parse_case_missing_when_is_error() {
+    assert!(Expr::parse("CASE ELSE 'x' END").is_err());
+}
@@ -7,9 +7,9 @@ use emsqrt_core::types::{Column, RowBatch, Scalar};
 use emsqrt_io::storage::FsStorage;
 use emsqrt_mem::{Codec, MemoryBudgetImpl, SpillManager};
 use emsqrt_operators::sort::external::ExternalSort;
-use emsqrt_operators::traits::{MemoryBudget, Operator};
+use emsqrt_operators::traits::Operator;
 use std::sync::{Arc, Mutex};
-use test_data_gen::{create_temp_spill_dir, generate_random_batch, generate_sorted_batch};
+use test_data_gen::{create_temp_spill_dir, generate_random_batch};
 
 fn setup_sort_operator(
     codec: Codec,
@@ -44,26 +44,22 @@ fn verify_sorted(batch: &RowBatch, key_col: &str) -> bool {
 
         // Simple comparison for common types
         match (prev, curr) {
-            (Scalar::I32(a), Scalar::I32(b)) => {
-                if a > b {
+            (Scalar::I32(a), Scalar::I32(b))
+                if a > b => {
                     return false;
                 }
-            }
-            (Scalar::I64(a), Scalar::I64(b)) => {
-                if a > b {
+            (Scalar::I64(a), Scalar::I64(b))
+                if a > b => {
                     return false;
                 }
-            }
-            (Scalar::F64(a), Scalar::F64(b)) => {
-                if a > b {
+            (Scalar::F64(a), Scalar::F64(b))
+                if a > b => {
                     return false;
                 }
-            }
-            (Scalar::Str(a), Scalar::Str(b)) => {
-                if a > b {
+            (Scalar::Str(a), Scalar::Str(b))
+                if a > b => {
                     return false;
                 }
-            }
             (Scalar::Null, _) => {
                 // Nulls sort first, so ok
             }
@@ -89,7 +85,7 @@ fn test_sort_in_memory() {
     let budget = MemoryBudgetImpl::new(10 * 1024 * 1024); // 10MB - plenty for in-memory sort
 
     // Create unsorted batch (small enough to fit in memory)
-    let mut batch = RowBatch {
+    let batch = RowBatch {
         columns: vec![
             Column {
                 name: "sort_key".to_string(),
@@ -117,7 +113,7 @@ fn test_sort_in_memory() {
     };
 
     let result = sort_op
-        .eval_block(&[batch.clone()], &budget)
+        .eval_block(std::slice::from_ref(&batch), &budget)
         .expect("Sort failed");
 
     // Verify sorted
@@ -273,7 +269,7 @@ fn test_sort_large_keys() {
     std::fs::create_dir_all(&spill_dir).expect("Failed to create spill dir");
 
     let (sort_op, _spill_mgr) = setup_sort_operator(Codec::None, spill_dir.clone());
-    let budget = MemoryBudgetImpl::new(1 * 1024 * 1024); // 1MB
+    let budget = MemoryBudgetImpl::new(1024 * 1024); // 1MB
 
     // Create batch with large string keys
     let mut values = Vec::new();
@@ -390,3 +386,80 @@ fn test_sort_already_sorted() {
 
     cleanup_spill_dir(&spill_dir);
 }
+
+#[test]
+fn test_partitioned_sort_matches_external_sort() {
+    use emsqrt_operators::sort::partitioned::PartitionedSort;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("value", DataType::Float64, false),
+    ]);
+    let batch = generate_random_batch(500, &schema);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let external = ExternalSort {
+        by: vec!["value".to_string()],
+        spill_mgr: None,
+    };
+    let partitioned = PartitionedSort {
+        by: vec!["value".to_string()],
+        spill_mgr: None,
+    };
+
+    let expected = external
+        .eval_block(std::slice::from_ref(&batch), &budget)
+        .expect("external sort");
+    let got = partitioned
+        .eval_block(std::slice::from_ref(&batch), &budget)
+        .expect("partitioned sort");
+
+    assert_eq!(expected.num_rows(), got.num_rows());
+    for (a, b) in expected.columns.iter().zip(got.columns.iter()) {
+        assert_eq!(a.values, b.values, "column {}", a.name);
+    }
+}
+
+#[test]
+fn test_partitioned_sort_with_spill() {
+    use emsqrt_operators::sort::partitioned::PartitionedSort;
+
+    let spill_dir = create_temp_spill_dir();
+    let storage = Box::new(FsStorage::new());
+    let spill_mgr = SpillManager::new(storage, Codec::None, format!("{}/spills", spill_dir));
+
+    let sort = PartitionedSort {
+        by: vec!["value".to_string()],
+        spill_mgr: Some(Arc::new(Mutex::new(spill_mgr))),
+    };
+
+    // Mixed signs and magnitudes exercise the order-preserving byte prefix.
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "value".to_string(),
+            values: vec![
+                Scalar::I64(300),
+                Scalar::I64(-5),
+                Scalar::I64(0),
+                Scalar::I64(-1_000_000),
+                Scalar::I64(7),
+                Scalar::I64(299),
+            ],
+        }],
+    };
+
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+    let sorted = sort.eval_block(&[batch], &budget).expect("sort");
+
+    let values: Vec<i64> = sorted.columns[0]
+        .values
+        .iter()
+        .map(|v| match v {
+            Scalar::I64(i) => *i,
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect();
+    assert_eq!(values, vec![-1_000_000, -5, 0, 7, 299, 300]);
+
+    let _ = std::fs::remove_dir_all(&spill_dir);
+}
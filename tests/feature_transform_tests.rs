@@ -0,0 +1,105 @@
+//! One-hot encoding and feature hashing tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{
+    features::{FeatureHash, OneHot},
+    Operator,
+};
+
+fn colors_batch() -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "color".to_string(),
+            values: vec![
+                Scalar::Str("red".into()),
+                Scalar::Str("blue".into()),
+                Scalar::Str("red".into()),
+                Scalar::Null,
+                Scalar::Str("green".into()),
+            ],
+        }],
+    }
+}
+
+fn int_col(batch: &RowBatch, name: &str) -> Vec<i64> {
+    batch
+        .columns
+        .iter()
+        .find(|c| c.name == name)
+        .unwrap_or_else(|| panic!("missing {}", name))
+        .values
+        .iter()
+        .map(|v| match v {
+            Scalar::I64(i) => *i,
+            Scalar::Null => -1,
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_one_hot_with_explicit_categories() {
+    let one_hot = OneHot {
+        column: "color".into(),
+        categories: vec!["red".into(), "blue".into()],
+        ..Default::default()
+    };
+    let result = one_hot
+        .eval_block(&[colors_batch()], &MemoryBudgetImpl::new(1024))
+        .expect("one_hot");
+
+    assert_eq!(int_col(&result, "color_red"), vec![1, 0, 1, 0, 0]);
+    assert_eq!(int_col(&result, "color_blue"), vec![0, 1, 0, 0, 0]);
+    // "green" is outside the list: it lands in the overflow bucket rather
+    // than adding a block-dependent column; null stays all-zero.
+    assert!(result.columns.iter().all(|c| c.name != "color_green"));
+    assert_eq!(int_col(&result, "color_other"), vec![0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_one_hot_requires_explicit_categories() {
+    use emsqrt_core::prelude::{DataType, Field, Schema};
+    let one_hot = OneHot {
+        column: "color".into(),
+        ..Default::default()
+    };
+    let schema = Schema::new(vec![Field::new("color", DataType::Utf8, true)]);
+    let err = one_hot.plan(&[schema]).unwrap_err();
+    assert!(err.to_string().contains("categories"));
+}
+
+#[test]
+fn test_one_hot_rejects_oversized_category_list() {
+    use emsqrt_core::prelude::{DataType, Field, Schema};
+    let one_hot = OneHot {
+        column: "color".into(),
+        categories: (0..5).map(|i| format!("c{}", i)).collect(),
+        max_categories: 4,
+    };
+    let schema = Schema::new(vec![Field::new("color", DataType::Utf8, true)]);
+    let err = one_hot.plan(&[schema]).unwrap_err();
+    assert!(err.to_string().contains("max 4"));
+}
+
+#[test]
+fn test_feature_hash_is_bounded_and_deterministic() {
+    let hash = FeatureHash {
+        column: "color".into(),
+        buckets: 8,
+        seed: 42,
+    };
+    let a = hash
+        .eval_block(&[colors_batch()], &MemoryBudgetImpl::new(1024))
+        .expect("hash");
+    let b = hash
+        .eval_block(&[colors_batch()], &MemoryBudgetImpl::new(1024))
+        .expect("hash");
+
+    let buckets = int_col(&a, "color_bucket");
+    assert_eq!(buckets, int_col(&b, "color_bucket"));
+    // Same value -> same bucket; null -> null (-1 sentinel in this helper).
+    assert_eq!(buckets[0], buckets[2]);
+    assert_eq!(buckets[3], -1);
+    assert!(buckets.iter().all(|b| *b < 8));
+}
@@ -3,7 +3,6 @@
 mod test_data_gen;
 
 use emsqrt_core::config::EngineConfig;
-use emsqrt_core::schema::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch, Scalar};
 use emsqrt_io::storage::FsStorage;
 use emsqrt_mem::guard::MemoryBudgetImpl;
@@ -68,9 +67,11 @@ fn create_right_batch() -> RowBatch {
 #[test]
 fn test_simple_hash_join_fallback() {
     // Small inputs should use simple hash join
-    let mut join = HashJoin::default();
-    join.on = vec![("id".to_string(), "id".to_string())];
-    join.join_type = "inner".to_string();
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "inner".to_string(),
+        ..Default::default()
+    };
 
     let left = create_left_batch();
     let right = create_right_batch();
@@ -119,17 +120,21 @@ fn test_grace_hash_join_with_spill_manager() {
         spill_dir.clone(),
     )));
 
-    let mut join = HashJoin::default();
-    join.on = vec![("id".to_string(), "id".to_string())];
-    join.join_type = "inner".to_string();
-    join.spill_mgr = Some(spill_mgr);
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "inner".to_string(),
+        hasher: Default::default(),
+        projection: None,
+        residual_filter: None,
+        spill_mgr: Some(spill_mgr),
+    };
 
     // Create large batches to trigger Grace join
     let large_left = RowBatch {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (0..200_000).map(|i| Scalar::I32(i)).collect(),
+                values: (0..200_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "name".to_string(),
@@ -144,7 +149,7 @@ fn test_grace_hash_join_with_spill_manager() {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (100_000..300_000).map(|i| Scalar::I32(i)).collect(),
+                values: (100_000..300_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "score".to_string(),
@@ -191,20 +196,24 @@ fn test_grace_hash_join_left_join() {
         spill_dir,
     )));
 
-    let mut join = HashJoin::default();
-    join.on = vec![("id".to_string(), "id".to_string())];
-    join.join_type = "left".to_string();
-    join.spill_mgr = Some(spill_mgr);
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "left".to_string(),
+        hasher: Default::default(),
+        projection: None,
+        residual_filter: None,
+        spill_mgr: Some(spill_mgr),
+    };
 
-    let left = create_left_batch();
-    let right = create_right_batch();
+    let _left = create_left_batch();
+    let _right = create_right_batch();
 
     // Create larger batches to trigger Grace join
     let large_left = RowBatch {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (0..150_000).map(|i| Scalar::I32(i)).collect(),
+                values: (0..150_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "name".to_string(),
@@ -219,7 +228,7 @@ fn test_grace_hash_join_left_join() {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (100_000..150_000).map(|i| Scalar::I32(i)).collect(),
+                values: (100_000..150_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "score".to_string(),
@@ -267,17 +276,21 @@ fn test_grace_hash_join_partitioning() {
         spill_dir.clone(),
     )));
 
-    let mut join = HashJoin::default();
-    join.on = vec![("id".to_string(), "id".to_string())];
-    join.join_type = "inner".to_string();
-    join.spill_mgr = Some(spill_mgr);
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "inner".to_string(),
+        hasher: Default::default(),
+        projection: None,
+        residual_filter: None,
+        spill_mgr: Some(spill_mgr),
+    };
 
     // Create batches large enough to trigger Grace join
     let large_left = RowBatch {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (0..150_000).map(|i| Scalar::I32(i)).collect(),
+                values: (0..150_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "value".to_string(),
@@ -292,7 +305,7 @@ fn test_grace_hash_join_partitioning() {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (100_000..200_000).map(|i| Scalar::I32(i)).collect(),
+                values: (100_000..200_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "extra".to_string(),
@@ -338,17 +351,21 @@ fn test_grace_hash_join_memory_constraint() {
         spill_dir,
     )));
 
-    let mut join = HashJoin::default();
-    join.on = vec![("id".to_string(), "id".to_string())];
-    join.join_type = "inner".to_string();
-    join.spill_mgr = Some(spill_mgr);
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "inner".to_string(),
+        hasher: Default::default(),
+        projection: None,
+        residual_filter: None,
+        spill_mgr: Some(spill_mgr),
+    };
 
     // Create batches that exceed a small memory budget
     let large_left = RowBatch {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (0..500_000).map(|i| Scalar::I32(i)).collect(),
+                values: (0..500_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "data".to_string(),
@@ -363,7 +380,7 @@ fn test_grace_hash_join_memory_constraint() {
         columns: vec![
             Column {
                 name: "id".to_string(),
-                values: (250_000..750_000).map(|i| Scalar::I32(i)).collect(),
+                values: (250_000..750_000).map(Scalar::I32).collect(),
             },
             Column {
                 name: "extra".to_string(),
@@ -375,8 +392,10 @@ fn test_grace_hash_join_memory_constraint() {
     };
 
     // Use a small memory budget to force partitioning
-    let mut config = EngineConfig::default();
-    config.mem_cap_bytes = 10 * 1024 * 1024; // 10MB
+    let config = EngineConfig {
+        mem_cap_bytes: 10 * 1024 * 1024, // 10MB
+        ..Default::default()
+    };
     let budget = MemoryBudgetImpl::new(config.mem_cap_bytes);
 
     // Should succeed with Grace join (partitioning)
@@ -388,3 +407,58 @@ fn test_grace_hash_join_memory_constraint() {
     assert!(result.num_rows() > 0);
     assert_eq!(result.columns.len(), 4); // id (left), data, id_right, extra
 }
+
+#[test]
+fn test_grace_join_with_reread_projection_and_filter() {
+    let spill_dir = create_temp_spill_dir();
+    let storage = Box::new(FsStorage::new());
+    let spill_mgr = SpillManager::new(storage, Codec::None, format!("{}/spills", spill_dir));
+    let spill_mgr = Arc::new(Mutex::new(spill_mgr));
+
+    let join = HashJoin {
+        on: vec![("id".to_string(), "id".to_string())],
+        join_type: "inner".to_string(),
+        hasher: Default::default(),
+        // Drop the "extra" column at re-read time and keep only small ids.
+        projection: Some(vec!["id".to_string(), "value".to_string()]),
+        residual_filter: Some("id < 3".to_string()),
+        spill_mgr: Some(spill_mgr),
+    };
+
+    // Large enough to force the grace (spilling) path.
+    let n = 120_000i64;
+    let left = RowBatch {
+        columns: vec![
+            Column {
+                name: "id".to_string(),
+                values: (0..n).map(Scalar::I64).collect(),
+            },
+            Column {
+                name: "extra".to_string(),
+                values: (0..n).map(|i| Scalar::I64(i * 10)).collect(),
+            },
+        ],
+    };
+    let right = RowBatch {
+        columns: vec![
+            Column {
+                name: "id".to_string(),
+                values: (0..n).map(Scalar::I64).collect(),
+            },
+            Column {
+                name: "value".to_string(),
+                values: (0..n).map(|i| Scalar::F64(i as f64)).collect(),
+            },
+        ],
+    };
+
+    let budget = MemoryBudgetImpl::new(64 * 1024 * 1024);
+    let result = join.eval_block(&[left, right], &budget).expect("join");
+
+    // Only ids 0..3 survive the residual filter; "extra" is pruned at re-read.
+    assert_eq!(result.num_rows(), 3);
+    assert!(result.columns.iter().all(|c| c.name != "extra"));
+    assert!(result.columns.iter().any(|c| c.name == "value"));
+
+    let _ = std::fs::remove_dir_all(&spill_dir);
+}
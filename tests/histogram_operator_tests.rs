@@ -0,0 +1,86 @@
+//! Histogram operator tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{histogram::Histogram, Operator};
+
+fn values_batch(values: Vec<f64>) -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "x".to_string(),
+            values: values.into_iter().map(Scalar::F64).collect(),
+        }],
+    }
+}
+
+fn counts(batch: &RowBatch) -> Vec<i64> {
+    batch.columns[2]
+        .values
+        .iter()
+        .map(|v| match v {
+            Scalar::I64(i) => *i,
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_histogram_equal_width_bins() {
+    let hist = Histogram {
+        column: "x".to_string(),
+        bins: 4,
+        min: Some(0.0),
+        max: Some(8.0),
+    };
+
+    let batch = values_batch(vec![0.0, 1.0, 2.5, 3.0, 4.0, 6.0, 7.9, 8.0, 9.5, -1.0]);
+    let result = hist
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024))
+        .expect("histogram");
+
+    // Bins [0,2), [2,4), [4,6), [6,8]; 9.5 and -1.0 are out of range,
+    // the max value 8.0 lands in the last bin.
+    assert_eq!(counts(&result), vec![2, 2, 1, 3]);
+    assert_eq!(result.columns[0].values[0], Scalar::F64(0.0));
+    assert_eq!(result.columns[1].values[3], Scalar::F64(8.0));
+}
+
+#[test]
+fn test_histogram_observed_bounds_and_nulls() {
+    let hist = Histogram {
+        column: "x".to_string(),
+        bins: 2,
+        min: None,
+        max: None,
+    };
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "x".to_string(),
+            values: vec![
+                Scalar::F64(10.0),
+                Scalar::Null, // ignored
+                Scalar::F64(20.0),
+                Scalar::F64(14.0),
+            ],
+        }],
+    };
+    let result = hist
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024))
+        .expect("histogram");
+    assert_eq!(counts(&result), vec![2, 1]); // [10,15): {10, 14}; [15,20]: {20}
+}
+
+#[test]
+fn test_histogram_plan_validation() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    let schema = Schema::new(vec![Field::new("x", DataType::Float64, false)]);
+
+    assert!(Histogram::default().plan(std::slice::from_ref(&schema)).is_err());
+    let zero_bins = Histogram {
+        column: "x".into(),
+        bins: 0,
+        ..Default::default()
+    };
+    assert!(zero_bins.plan(std::slice::from_ref(&schema)).is_err());
+}
@@ -0,0 +1,57 @@
+//! JSONL nested flattening and JSON path projection tests.
+
+use emsqrt_core::types::Scalar;
+use emsqrt_io::readers::jsonl::JsonlReader;
+
+const NESTED: &str = r#"{"id": 1, "user": {"name": "alice", "address": {"city": "berlin"}}, "tags": [1, 2]}
+{"id": 2, "user": {"name": "bob"}}
+"#;
+
+fn column<'a>(batch: &'a emsqrt_core::types::RowBatch, name: &str) -> &'a [Scalar] {
+    &batch
+        .columns
+        .iter()
+        .find(|c| c.name == name)
+        .unwrap_or_else(|| panic!("missing column {}", name))
+        .values
+}
+
+#[test]
+fn test_nested_objects_flatten_to_dotted_columns() {
+    let mut reader = JsonlReader::from_reader(NESTED.as_bytes())
+        .unwrap()
+        .with_flattening();
+    let batch = reader.next_batch(10).unwrap().expect("batch");
+
+    assert_eq!(
+        column(&batch, "user.name"),
+        &[Scalar::Str("alice".into()), Scalar::Str("bob".into())]
+    );
+    assert_eq!(
+        column(&batch, "user.address.city"),
+        &[Scalar::Str("berlin".into()), Scalar::Null]
+    );
+    // Arrays stay as JSON-rendered strings at their path.
+    assert!(matches!(column(&batch, "tags")[0], Scalar::Str(_)));
+}
+
+#[test]
+fn test_json_path_projection() {
+    let mut reader = JsonlReader::from_reader(NESTED.as_bytes())
+        .unwrap()
+        .with_projection(vec!["id".into(), "user.address.city".into()]);
+    let batch = reader.next_batch(10).unwrap().expect("batch");
+
+    let names: Vec<&str> = batch.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "user.address.city"]);
+    assert_eq!(column(&batch, "id")[1], Scalar::I64(2));
+}
+
+#[test]
+fn test_unflattened_reader_keeps_top_level_keys() {
+    let mut reader = JsonlReader::from_reader(NESTED.as_bytes()).unwrap();
+    let batch = reader.next_batch(10).unwrap().expect("batch");
+    // Nested object renders as a string value under its top-level key.
+    assert!(batch.columns.iter().any(|c| c.name == "user"));
+    assert!(batch.columns.iter().all(|c| c.name != "user.name"));
+}
@@ -0,0 +1,152 @@
+//! Kafka connector tests via a stubbed kcat binary.
+
+use emsqrt_core::schema::{DataType, Field, Schema};
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_exec::{Connector, KafkaConnector};
+
+/// Stub kcat: `-P` appends stdin to <dir>/<topic>.log, `-C` replays it
+/// honoring `-o <offset>` and `-c <count>`.
+fn write_stub(dir: &str) -> String {
+    let stub = format!("{}/stub-kcat", dir);
+    let script = format!(
+        concat!(
+            "#!/bin/sh\n",
+            "mode=$1\ntopic=\"\"\noffset=beginning\ncount=\"\"\n",
+            "while [ $# -gt 0 ]; do\n",
+            "  case $1 in\n",
+            "    -t) topic=$2; shift ;;\n",
+            "    -o) offset=$2; shift ;;\n",
+            "    -c) count=$2; shift ;;\n",
+            "  esac\n",
+            "  shift\n",
+            "done\n",
+            "case $mode in\n",
+            "  -P) cat >> {dir}/$topic.log ;;\n",
+            "  -C)\n",
+            "    [ \"$offset\" = beginning ] && offset=0\n",
+            "    start=$((offset + 1))\n",
+            "    if [ -n \"$count\" ]; then\n",
+            "      tail -n +$start {dir}/$topic.log 2>/dev/null | head -n $count\n",
+            "    else\n",
+            "      tail -n +$start {dir}/$topic.log 2>/dev/null\n",
+            "    fi ;;\n",
+            "esac\n",
+        ),
+        dir = dir
+    );
+    std::fs::write(&stub, script).unwrap();
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+    stub
+}
+
+#[test]
+fn test_kafka_sink_then_source_round_trip() {
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_kafka_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let stub = write_stub(&dir);
+
+    let connector = KafkaConnector::with_command(&stub);
+
+    // Produce two rows.
+    {
+        let mut sink = connector
+            .open_sink("kafka://broker1:9092/events", "csv")
+            .expect("open sink");
+        sink.write_batch(&RowBatch {
+            columns: vec![
+                Column {
+                    name: "id".to_string(),
+                    values: vec![Scalar::I64(1), Scalar::I64(2)],
+                },
+                Column {
+                    name: "msg".to_string(),
+                    values: vec![Scalar::Str("hello".into()), Scalar::Null],
+                },
+            ],
+        })
+        .expect("produce");
+        // Dropping the sink closes the producer and flushes.
+    }
+
+    // Consume them back with a declared schema.
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("msg", DataType::Utf8, true),
+    ]);
+    let mut source = connector
+        .open_source("kafka://broker1:9092/events", &schema)
+        .expect("open source");
+    let batch = source.next_batch().expect("batch");
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.columns[0].values[1], Scalar::I64(2));
+    assert_eq!(batch.columns[1].values[0], Scalar::Str("hello".into()));
+    assert_eq!(batch.columns[1].values[1], Scalar::Null);
+    assert!(source.next_batch().is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_kafka_uri_validation() {
+    let connector = KafkaConnector::default();
+    assert!(connector.matches("kafka://broker/topic"));
+    assert!(connector
+        .open_sink("kafka://broker-without-topic", "csv")
+        .is_err());
+}
+
+#[test]
+fn test_kafka_offset_range_and_keys() {
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_kafka_range_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let stub = write_stub(&dir);
+
+    let connector = KafkaConnector::with_command(&stub);
+
+    // Produce keyed messages: the stub records key<US>payload lines.
+    {
+        let mut sink = connector
+            .open_sink("kafka://broker1:9092/keyed", "id")
+            .expect("open sink");
+        sink.write_batch(&RowBatch {
+            columns: vec![Column {
+                name: "id".to_string(),
+                values: (0..5).map(|i| Scalar::Str(format!("k{}", i))).collect(),
+            }],
+        })
+        .expect("produce");
+    }
+    let log = std::fs::read_to_string(format!("{}/keyed.log", dir)).unwrap();
+    let first = log.lines().next().unwrap();
+    let (key, payload) = first.split_once('\u{1f}').expect("keyed message");
+    assert_eq!(key, "k0");
+    assert!(payload.starts_with('{'));
+
+    // Bounded range: offset=2, count=2 consumes exactly messages 2 and 3.
+    // (Keyed log lines aren't JSON objects, so they map onto a declared
+    // "value" column.)
+    let schema = Schema::new(vec![Field::new("value", DataType::Utf8, true)]);
+    let mut source = connector
+        .open_source("kafka://broker1:9092/keyed?offset=2&count=2", &schema)
+        .expect("open source");
+    let batch = source.next_batch().expect("batch");
+    assert_eq!(batch.num_rows(), 2);
+    assert!(matches!(&batch.columns[0].values[0], Scalar::Str(s) if s.contains("k2")));
+    assert!(source.next_batch().is_none());
+
+    // Bad query options are rejected up front.
+    assert!(connector
+        .open_source("kafka://broker/topic?offset=abc", &schema)
+        .is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -89,7 +89,7 @@ fn test_budget_concurrent_access() {
     let mut handles = vec![];
 
     // Spawn 10 threads, each acquiring and releasing 50KB
-    for i in 0..10 {
+    for _i in 0..10 {
         let budget_clone: Arc<MemoryBudgetImpl> = Arc::clone(&budget);
         let handle = thread::spawn(move || {
             // Try to acquire 50KB
@@ -275,3 +275,230 @@ fn test_budget_high_contention() {
     // All memory should be released
     assert_eq!(budget.used_bytes(), 0);
 }
+
+#[test]
+fn test_plan_time_infeasible_memory_cap() {
+    use emsqrt_core::dag::LogicalPlan as L;
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_planner::{estimate_work, lower_to_physical, WorkHint};
+    use emsqrt_te::plan_te;
+
+    let scan = L::Scan {
+        source: "data/huge.csv".to_string(),
+        schema: Schema::new(vec![Field::new("payload", DataType::Utf8, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "out.csv".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys = lower_to_physical(&sink);
+    // Hint: 1M rows of ~1KB each.
+    let hints = WorkHint {
+        source_rows: vec![("data/huge.csv".to_string(), 1_000_000)],
+        source_bytes: vec![("data/huge.csv".to_string(), 1_000_000_000)],
+    };
+    let work = estimate_work(&sink, Some(&hints));
+
+    // A 1KB cap cannot hold even one row per resident block.
+    let err = plan_te(&phys.plan, &work, 1024).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("memory cap"), "message: {}", msg);
+    assert!(msg.contains("suggestions:"), "message: {}", msg);
+    assert!(msg.contains("memory-cap"), "message: {}", msg);
+
+    // A generous cap plans fine.
+    assert!(plan_te(&phys.plan, &work, 512 * 1024 * 1024).is_ok());
+}
+
+#[test]
+fn test_sub_budget_reservations() {
+    use emsqrt_core::budget::MemoryBudget;
+
+    let parent = MemoryBudgetImpl::new(1000);
+
+    // Reservation counts against the parent immediately.
+    let sub = parent.reserve(600, "operator_a").expect("reserve");
+    assert_eq!(parent.used_bytes(), 600);
+    assert_eq!(sub.capacity_bytes(), 600);
+
+    // Allocations inside the sub-budget are capped at the reservation...
+    let g1 = sub.try_acquire(500, "build_table").expect("within sub");
+    assert!(sub.try_acquire(200, "too_much").is_none());
+    drop(g1);
+    assert_eq!(sub.used_bytes(), 0);
+
+    // ...and don't double-count against the parent.
+    let _g2 = sub.try_acquire(600, "all_of_it").expect("full sub");
+    assert_eq!(parent.used_bytes(), 600);
+
+    // A second reservation can't exceed what's left of the parent.
+    assert!(parent.reserve(500, "operator_b").is_none());
+    let sub_b = parent.reserve(400, "operator_b").expect("fits");
+    assert_eq!(parent.used_bytes(), 1000);
+
+    // Dropping a sub-budget returns its reservation.
+    drop(sub_b);
+    assert_eq!(parent.used_bytes(), 600);
+    drop(sub);
+    assert_eq!(parent.used_bytes(), 0);
+}
+
+#[test]
+fn test_acquire_timeout_blocks_until_release() {
+    use emsqrt_core::budget::MemoryBudget;
+    use std::time::Duration;
+
+    let budget = MemoryBudgetImpl::new(1000);
+    let guard = budget.try_acquire(1000, "holder").expect("initial acquire");
+
+    // Times out while the budget is fully held.
+    let start = std::time::Instant::now();
+    assert!(budget
+        .acquire_timeout(500, "waiter", Duration::from_millis(50))
+        .is_none());
+    assert!(start.elapsed() >= Duration::from_millis(45));
+
+    // Release from another thread unblocks a waiting acquire.
+    let budget_for_release = budget.clone();
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        drop(guard);
+        let _ = budget_for_release; // keep clone alive until after drop
+    });
+
+    let acquired = budget.acquire_timeout(500, "waiter", Duration::from_secs(5));
+    assert!(acquired.is_some());
+    releaser.join().unwrap();
+}
+
+#[test]
+fn test_pressure_notifications_on_rising_edge() {
+    use emsqrt_core::budget::MemoryBudget;
+
+    let budget = MemoryBudgetImpl::new(1000);
+    let rx = budget.subscribe_pressure(0.8);
+
+    // Below the watermark: no event.
+    let g1 = budget.try_acquire(500, "a").unwrap();
+    assert!(rx.try_recv().is_err());
+
+    // Crossing 80%: one event with the observed usage.
+    let g2 = budget.try_acquire(400, "b").unwrap();
+    let event = rx.try_recv().expect("pressure event");
+    assert_eq!(event.used_bytes, 900);
+    assert_eq!(event.capacity_bytes, 1000);
+
+    // Watchers fire once; staying above the watermark emits nothing more.
+    let _g3 = budget.try_acquire(50, "c").unwrap();
+    assert!(rx.try_recv().is_err());
+
+    drop(g1);
+    drop(g2);
+}
+
+#[test]
+fn test_tag_tracking_and_leak_detection() {
+    use emsqrt_core::budget::MemoryBudget;
+
+    let budget = MemoryBudgetImpl::new_tracked(10_000);
+
+    let g1 = budget.try_acquire(4_000, "hash_table").unwrap();
+    let g2 = budget.try_acquire(2_000, "sort_buffer").unwrap();
+    let g3 = budget.try_acquire(1_000, "hash_table").unwrap();
+
+    let snapshot = budget.tag_snapshot();
+    let hash = snapshot
+        .iter()
+        .find(|(tag, _)| *tag == "hash_table")
+        .expect("hash_table tracked");
+    assert_eq!(hash.1.current_bytes, 5_000);
+    assert_eq!(hash.1.acquires, 2);
+
+    // Releasing brings the balance down; peak is retained.
+    drop(g3);
+    drop(g2);
+    let snapshot = budget.tag_snapshot();
+    let hash = snapshot
+        .iter()
+        .find(|(tag, _)| *tag == "hash_table")
+        .unwrap();
+    assert_eq!(hash.1.current_bytes, 4_000);
+    assert_eq!(hash.1.peak_bytes, 5_000);
+
+    // g1 is still live: it shows up as the only leak.
+    let leaks = budget.leaked_tags();
+    assert_eq!(leaks, vec![("hash_table", 4_000)]);
+
+    drop(g1);
+    assert!(budget.leaked_tags().is_empty());
+}
+
+#[test]
+fn test_untracked_budget_reports_nothing() {
+    use emsqrt_core::budget::MemoryBudget;
+
+    let budget = MemoryBudgetImpl::new(1_000);
+    let _g = budget.try_acquire(500, "x").unwrap();
+    assert!(budget.tag_snapshot().is_empty());
+    assert!(budget.leaked_tags().is_empty());
+}
+
+#[test]
+fn test_per_operator_budgets_cap_each_operator() {
+    use emsqrt_core::config::EngineConfig;
+    use emsqrt_core::dag::{Aggregation, LogicalPlan as L};
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_exec::runtime::planned_budget_split;
+    use emsqrt_exec::Engine;
+    use emsqrt_planner::{estimate_work, lower_to_physical};
+    use emsqrt_te::plan_te;
+
+    let temp_dir = std::env::temp_dir()
+        .join(format!("emsqrt_subbudget_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let input = format!("{}/input.csv", temp_dir);
+    let output = format!("{}/out.csv", temp_dir);
+    std::fs::write(&input, "k,v\na,1\nb,2\na,3\n").unwrap();
+
+    let scan = L::Scan {
+        source: input,
+        schema: Schema::new(vec![
+            Field::new("k", DataType::Utf8, false),
+            Field::new("v", DataType::Float64, false),
+        ]),
+    };
+    let agg = L::Aggregate {
+        input: Box::new(scan),
+        group_by: vec!["k".to_string()],
+        aggs: vec![Aggregation::Sum("v".to_string())],
+    };
+    let sink = L::Sink {
+        input: Box::new(agg),
+        destination: output.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys = lower_to_physical(&sink);
+    // The planner carves half the cap across the one heavy operator.
+    let split = planned_budget_split(&phys.bindings, 1024 * 1024);
+    assert_eq!(split.len(), 1);
+    assert_eq!(split[0].1, "aggregate");
+    assert_eq!(split[0].2, 512 * 1024);
+
+    // A run with per-operator budgets enabled still completes.
+    let te = plan_te(&phys.plan, &estimate_work(&sink, None), 64 * 1024 * 1024).unwrap();
+    let config = EngineConfig {
+        per_operator_budgets: true,
+        spill_dir: format!("{}/spill", temp_dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).unwrap();
+    eng.run(&phys, &te).expect("run with sub-budgets");
+    assert!(std::fs::read_to_string(&output).unwrap().lines().count() >= 3);
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
@@ -0,0 +1,65 @@
+//! Operator registry documentation tests.
+
+use emsqrt_operators::registry::Registry;
+
+#[test]
+fn test_docs_cover_all_registered_operators() {
+    let registry = Registry::new();
+    let docs = registry.docs();
+
+    for key in [
+        "filter",
+        "map",
+        "project",
+        "aggregate",
+        "sort_external",
+        "join_hash",
+        "join_merge",
+        "sample",
+        "window",
+        "lateral_explode",
+    ] {
+        let doc = docs
+            .iter()
+            .find(|d| d.key == key)
+            .unwrap_or_else(|| panic!("missing docs for '{}'", key));
+        assert!(!doc.summary.is_empty(), "empty summary for '{}'", key);
+        // Every documented operator must actually be constructible
+        assert!(registry.make(key).is_some(), "cannot make '{}'", key);
+    }
+}
+
+#[test]
+fn test_docs_markdown_is_sorted_and_structured() {
+    let markdown = Registry::new().docs_markdown();
+    assert!(markdown.starts_with("# Operators"));
+
+    let headers: Vec<&str> = markdown
+        .lines()
+        .filter(|l| l.starts_with("## "))
+        .map(|l| l.trim_start_matches("## ").trim_matches('`'))
+        .collect();
+    let mut sorted = headers.clone();
+    sorted.sort();
+    assert_eq!(headers, sorted, "operator sections should be sorted");
+    assert!(markdown.contains("- `expr`:"));
+}
+
+#[test]
+fn test_deprecated_operator_keys_resolve() {
+    let registry = Registry::new();
+
+    for (old, new) in [
+        ("sort", "sort_external"),
+        ("hash_join", "join_hash"),
+        ("merge_join", "join_merge"),
+        ("explode", "lateral_explode"),
+    ] {
+        assert_eq!(registry.resolve(old), Some((new, true)), "alias {}", old);
+        assert!(registry.make(old).is_some(), "make via alias {}", old);
+    }
+
+    // Canonical keys resolve to themselves, undeprecated.
+    assert_eq!(registry.resolve("filter"), Some(("filter", false)));
+    assert_eq!(registry.resolve("no_such_operator"), None);
+}
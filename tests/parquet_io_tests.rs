@@ -327,3 +327,241 @@ fn test_parquet_multi_batch_read() {
 fn test_parquet_feature_required() {
     // This test file requires the parquet feature to be enabled
 }
+
+/// End-to-end: the runtime SourceOp scans a Parquet file through a full
+/// engine run (write with ParquetWriter, read back via a pipeline).
+#[cfg(feature = "parquet")]
+#[test]
+fn test_runtime_source_scans_parquet() {
+    use emsqrt_core::config::EngineConfig;
+    use emsqrt_core::dag::LogicalPlan as L;
+    use emsqrt_exec::Engine;
+    use emsqrt_planner::{estimate_work, lower_to_physical};
+    use emsqrt_te::plan_te;
+
+    let dir = create_temp_spill_dir();
+    fs::create_dir_all(&dir).unwrap();
+    let parquet_path = format!("{}/input.parquet", dir);
+    let output_path = format!("{}/output.csv", dir);
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]);
+    let mut writer = ParquetWriter::from_emsqrt_schema(&parquet_path, &schema).unwrap();
+    writer
+        .write_row_batch(&RowBatch {
+            columns: vec![
+                Column {
+                    name: "id".to_string(),
+                    values: vec![Scalar::I64(1), Scalar::I64(2), Scalar::I64(3)],
+                },
+                Column {
+                    name: "name".to_string(),
+                    values: vec![
+                        Scalar::Str("a".into()),
+                        Scalar::Str("b".into()),
+                        Scalar::Str("c".into()),
+                    ],
+                },
+            ],
+        })
+        .unwrap();
+    writer.close().unwrap();
+
+    let scan = L::Scan {
+        source: parquet_path,
+        schema,
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "id > 1".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: output_path.clone(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+    let config = EngineConfig {
+        spill_dir: format!("{}/spill", dir),
+        ..Default::default()
+    };
+    let mut eng = Engine::new(config).unwrap();
+    let manifest = eng.run(&phys_prog, &te).unwrap();
+
+    assert_eq!(manifest.records_written, 2);
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(output.lines().count(), 3); // header + 2 rows
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+/// Delta table sink: data files plus a readable _delta_log.
+#[cfg(feature = "parquet")]
+#[test]
+fn test_delta_sink_writes_table_and_log() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::{Connector, DeltaConnector};
+
+    let dir = create_temp_spill_dir();
+    let table = format!("{}/events", dir);
+
+    let mut sink = DeltaConnector
+        .open_sink(&format!("delta://{}", table), "csv")
+        .expect("open sink");
+
+    let batch = |offset: i64| RowBatch {
+        columns: vec![Column {
+            name: "id".to_string(),
+            values: vec![Scalar::I64(offset), Scalar::I64(offset + 1)],
+        }],
+    };
+    sink.write_batch(&batch(0)).expect("commit 0");
+    sink.write_batch(&batch(10)).expect("commit 1");
+
+    // Two versioned commits exist; the first carries protocol + metaData.
+    let log0 = std::fs::read_to_string(format!(
+        "{}/_delta_log/00000000000000000000.json",
+        table
+    ))
+    .expect("commit 0 log");
+    assert!(log0.contains("\"protocol\""));
+    assert!(log0.contains("\"schemaString\""));
+    assert!(log0.contains("\"add\""));
+    let log1 = std::fs::read_to_string(format!(
+        "{}/_delta_log/00000000000000000001.json",
+        table
+    ))
+    .expect("commit 1 log");
+    assert!(!log1.contains("\"protocol\""));
+    assert!(log1.contains("\"add\""));
+
+    // Each referenced data file exists and reads back as Parquet.
+    for log in [&log0, &log1] {
+        let add: serde_json::Value = log
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .find(|v| v.get("add").is_some())
+            .unwrap();
+        let path = format!("{}/{}", table, add["add"]["path"].as_str().unwrap());
+        let mut reader = ParquetReader::from_path(&path, None, 1024).expect("read data file");
+        let data = reader.next_batch().expect("batch").expect("rows");
+        assert_eq!(data.num_rows(), 2);
+    }
+
+    // Iceberg is explicitly unsupported.
+    assert!(DeltaConnector
+        .open_sink("iceberg:///tmp/never", "csv")
+        .is_err());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Re-opening an existing Delta table resumes the version sequence.
+#[cfg(feature = "parquet")]
+#[test]
+fn test_delta_sink_resumes_version_from_existing_log() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::{Connector, DeltaConnector};
+
+    let dir = create_temp_spill_dir();
+    let table = format!("{}/resume", dir);
+    let uri = format!("delta://{}", table);
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "id".to_string(),
+            values: vec![Scalar::I64(1)],
+        }],
+    };
+
+    let mut sink = DeltaConnector.open_sink(&uri, "csv").expect("open sink");
+    sink.write_batch(&batch).expect("commit 0");
+    drop(sink);
+
+    // A second writer appends commit 1 and must not re-emit protocol or
+    // metaData (the table already carries them).
+    let mut sink = DeltaConnector.open_sink(&uri, "csv").expect("reopen sink");
+    sink.write_batch(&batch).expect("commit 1");
+
+    let log1 = std::fs::read_to_string(format!(
+        "{}/_delta_log/00000000000000000001.json",
+        table
+    ))
+    .expect("commit 1 log");
+    assert!(!log1.contains("\"protocol\""));
+    assert!(log1.contains("\"add\""));
+    assert!(!std::path::Path::new(&format!(
+        "{}/_delta_log/00000000000000000002.json",
+        table
+    ))
+    .exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Partitioned Delta table: hive-style data paths, partitionValues in the
+/// add actions, and the partition column kept out of the data files.
+#[cfg(feature = "parquet")]
+#[test]
+fn test_delta_sink_partitioned_layout() {
+    use emsqrt_core::types::{Column, RowBatch, Scalar};
+    use emsqrt_exec::{Connector, DeltaConnector};
+
+    let dir = create_temp_spill_dir();
+    let table = format!("{}/by_region", dir);
+
+    let mut sink = DeltaConnector
+        .open_sink(&format!("delta://{}?partition=region", table), "csv")
+        .expect("open sink");
+    sink.write_batch(&RowBatch {
+        columns: vec![
+            Column {
+                name: "region".to_string(),
+                values: vec![
+                    Scalar::Str("eu".into()),
+                    Scalar::Str("us".into()),
+                    Scalar::Str("eu".into()),
+                ],
+            },
+            Column {
+                name: "amount".to_string(),
+                values: vec![Scalar::I64(1), Scalar::I64(2), Scalar::I64(3)],
+            },
+        ],
+    })
+    .expect("commit 0");
+
+    let log0 = std::fs::read_to_string(format!(
+        "{}/_delta_log/00000000000000000000.json",
+        table
+    ))
+    .expect("commit 0 log");
+    assert!(log0.contains("\"partitionColumns\":[\"region\"]"));
+
+    let adds: Vec<serde_json::Value> = log0
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .filter(|v| v.get("add").is_some())
+        .collect();
+    assert_eq!(adds.len(), 2);
+    for add in &adds {
+        let path = add["add"]["path"].as_str().unwrap();
+        let region = add["add"]["partitionValues"]["region"].as_str().unwrap();
+        assert!(path.starts_with(&format!("region={}/", region)));
+
+        // Data files carry only the non-partition columns.
+        let mut reader =
+            ParquetReader::from_path(&format!("{}/{}", table, path), None, 1024)
+                .expect("read data file");
+        let data = reader.next_batch().expect("batch").expect("rows");
+        let names: Vec<&str> = data.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["amount"]);
+        assert_eq!(data.num_rows(), if region == "eu" { 2 } else { 1 });
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,89 @@
+//! Redis sink tests against a minimal in-process RESP server.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_exec::{Connector, RedisConnector};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+type SharedStore = Arc<Mutex<HashMap<String, String>>>;
+
+/// Fake Redis: parses RESP SET commands, stores them, replies +OK. Shuts
+/// down after `expected` SETs.
+fn spawn_fake_redis(expected: usize) -> (u16, SharedStore, std::thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let store: SharedStore = Arc::new(Mutex::new(HashMap::new()));
+
+    let server_store = store.clone();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut stream = stream;
+
+        let read_bulk = |reader: &mut BufReader<std::net::TcpStream>| -> String {
+            let mut len_line = String::new();
+            reader.read_line(&mut len_line).unwrap();
+            let len: usize = len_line.trim_start_matches('$').trim().parse().unwrap();
+            let mut buf = vec![0u8; len + 2];
+            reader.read_exact(&mut buf).unwrap();
+            String::from_utf8_lossy(&buf[..len]).to_string()
+        };
+
+        for _ in 0..expected {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap(); // *3
+            let command = read_bulk(&mut reader);
+            assert_eq!(command, "SET");
+            let key = read_bulk(&mut reader);
+            let value = read_bulk(&mut reader);
+            server_store.lock().unwrap().insert(key, value);
+            stream.write_all(b"+OK\r\n").unwrap();
+        }
+    });
+
+    (port, store, handle)
+}
+
+#[test]
+fn test_redis_sink_writes_keyed_json_rows() {
+    let (port, store, server) = spawn_fake_redis(2);
+
+    let connector = RedisConnector;
+    let mut sink = connector
+        .open_sink(&format!("redis://127.0.0.1:{}/users", port), "user_id")
+        .expect("open sink");
+
+    let batch = RowBatch {
+        columns: vec![
+            Column {
+                name: "user_id".to_string(),
+                values: vec![Scalar::Str("u1".into()), Scalar::Str("u2".into())],
+            },
+            Column {
+                name: "tier".to_string(),
+                values: vec![Scalar::Str("gold".into()), Scalar::Null],
+            },
+        ],
+    };
+    sink.write_batch(&batch).expect("write");
+    server.join().unwrap();
+
+    let store = store.lock().unwrap();
+    assert_eq!(store.len(), 2);
+    let u1: serde_json::Value = serde_json::from_str(&store["users:u1"]).unwrap();
+    assert_eq!(u1["tier"], "gold");
+    let u2: serde_json::Value = serde_json::from_str(&store["users:u2"]).unwrap();
+    assert_eq!(u2["tier"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_redis_uri_parsing() {
+    let connector = RedisConnector;
+    assert!(connector.matches("redis://localhost/x"));
+    assert!(!connector.matches("rediss://localhost/x"));
+    assert!(connector
+        .open_sink("redis://localhost:notaport/x", "csv")
+        .is_err());
+}
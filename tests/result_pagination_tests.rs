@@ -0,0 +1,90 @@
+//! ResultSet pagination tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_exec::ResultSet;
+
+fn batch(values: Vec<i64>) -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: values.into_iter().map(Scalar::I64).collect(),
+        }],
+    }
+}
+
+fn column_as_i64(batch: &RowBatch) -> Vec<i64> {
+    batch.columns[0]
+        .values
+        .iter()
+        .map(|v| match v {
+            Scalar::I64(i) => *i,
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_pages_cross_batch_boundaries() {
+    let set = ResultSet::from_batches(vec![batch(vec![1, 2, 3]), batch(vec![4, 5]), batch(vec![6, 7, 8])]);
+
+    assert_eq!(set.num_rows(), 8);
+    assert_eq!(set.num_pages(3), 3);
+
+    assert_eq!(column_as_i64(&set.page(0, 3)), vec![1, 2, 3]);
+    assert_eq!(column_as_i64(&set.page(1, 3)), vec![4, 5, 6]);
+    assert_eq!(column_as_i64(&set.page(2, 3)), vec![7, 8]); // short last page
+}
+
+#[test]
+fn test_out_of_range_page_is_empty_with_columns() {
+    let set = ResultSet::from_batches(vec![batch(vec![1, 2])]);
+    let page = set.page(5, 10);
+    assert_eq!(page.num_rows(), 0);
+    assert_eq!(page.columns.len(), 1);
+    assert_eq!(page.columns[0].name, "n");
+}
+
+#[test]
+fn test_empty_batches_are_skipped() {
+    let set = ResultSet::from_batches(vec![batch(vec![]), batch(vec![9])]);
+    assert_eq!(set.num_rows(), 1);
+    assert_eq!(column_as_i64(&set.rows(0, 10)), vec![9]);
+}
+
+#[test]
+fn test_collect_from_channel_sink() {
+    use emsqrt_core::config::EngineConfig;
+    use emsqrt_core::dag::LogicalPlan as L;
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    use emsqrt_exec::{Engine, MemorySource};
+    use emsqrt_planner::{estimate_work, lower_to_physical};
+    use emsqrt_te::plan_te;
+
+    let scan = L::Scan {
+        source: "mem://rows".to_string(),
+        schema: Schema::new(vec![Field::new("n", DataType::Int64, false)]),
+    };
+    let sink = L::Sink {
+        input: Box::new(scan),
+        destination: "callback://pages".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    eng.register_source("rows", MemorySource::new(vec![batch(vec![1, 2, 3, 4])]));
+    let rx = eng.register_channel_sink("pages", 16);
+
+    let engine_thread = std::thread::spawn(move || eng.run(&phys_prog, &te));
+    let set = ResultSet::collect(rx);
+    engine_thread
+        .join()
+        .expect("engine thread")
+        .expect("engine run");
+
+    assert_eq!(set.num_rows(), 4);
+    assert_eq!(column_as_i64(&set.page(1, 2)), vec![3, 4]);
+}
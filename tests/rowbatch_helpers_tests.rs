@@ -2,9 +2,7 @@
 
 mod test_data_gen;
 
-use emsqrt_core::schema::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch, Scalar};
-use test_data_gen::generate_sorted_batch;
 
 #[test]
 fn test_sort_by_single_column() {
@@ -373,3 +371,50 @@ fn test_sort_preserves_row_order_across_columns() {
     assert_eq!(batch.columns[0].values[2], Scalar::I32(30));
     assert_eq!(batch.columns[1].values[2], Scalar::Str("third".to_string()));
 }
+
+#[test]
+fn test_seed_stable_pluggable_partitioning() {
+    use emsqrt_core::hash::{HashKind, RowHasher};
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "k".to_string(),
+            values: (0..100).map(|i| Scalar::Str(format!("key{}", i))).collect(),
+        }],
+    };
+    let keys = vec!["k".to_string()];
+
+    // Default hasher matches the legacy unseeded blake3 path.
+    let legacy = batch.hash_columns(&keys, 8).unwrap();
+    let default = batch
+        .hash_columns_with(&keys, 8, &RowHasher::default())
+        .unwrap();
+    assert_eq!(legacy, default);
+
+    // Same seed -> identical assignment; different seed -> (almost surely)
+    // different assignment.
+    let seeded_a = batch
+        .hash_columns_with(&keys, 8, &RowHasher::new(HashKind::Blake3, 42))
+        .unwrap();
+    let seeded_b = batch
+        .hash_columns_with(&keys, 8, &RowHasher::new(HashKind::Blake3, 42))
+        .unwrap();
+    assert_eq!(seeded_a, seeded_b);
+    assert_ne!(seeded_a, legacy);
+
+    // FNV-1a is a valid alternative with a full spread of partitions.
+    let fnv = batch
+        .hash_columns_with(&keys, 8, &RowHasher::new(HashKind::Fnv1a, 7))
+        .unwrap();
+    assert!(fnv.iter().all(|p| *p < 8));
+    let distinct: std::collections::HashSet<_> = fnv.iter().collect();
+    assert!(distinct.len() > 1);
+}
+
+#[test]
+fn test_hash_kind_parse() {
+    use emsqrt_core::hash::HashKind;
+    assert_eq!(HashKind::parse("blake3").unwrap(), HashKind::Blake3);
+    assert_eq!(HashKind::parse("fnv1a").unwrap(), HashKind::Fnv1a);
+    assert!(HashKind::parse("md5").is_err());
+}
@@ -0,0 +1,103 @@
+//! Sample operator tests (Bernoulli and reservoir).
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{sample::Sample, Operator};
+
+fn numbers_batch(n: usize) -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: (0..n).map(|i| Scalar::I64(i as i64)).collect(),
+        }],
+    }
+}
+
+#[test]
+fn test_reservoir_sample_is_bounded_and_deterministic() {
+    let sample = Sample {
+        fraction: None,
+        rows: Some(10),
+        seed: Some(42),
+    };
+
+    let batch = numbers_batch(1000);
+    let a = sample
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("sample execution");
+    let b = sample
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("sample execution");
+
+    assert_eq!(a.num_rows(), 10);
+    // Same seed, same input -> same sample
+    assert_eq!(a.columns[0].values, b.columns[0].values);
+
+    // Input order is preserved within the sample
+    let picked: Vec<i64> = a.columns[0]
+        .values
+        .iter()
+        .map(|v| match v {
+            Scalar::I64(i) => *i,
+            other => panic!("unexpected scalar {:?}", other),
+        })
+        .collect();
+    let mut sorted = picked.clone();
+    sorted.sort_unstable();
+    assert_eq!(picked, sorted);
+}
+
+#[test]
+fn test_reservoir_smaller_input_passes_through() {
+    let sample = Sample {
+        fraction: None,
+        rows: Some(100),
+        seed: Some(1),
+    };
+
+    let batch = numbers_batch(5);
+    let out = sample
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("sample execution");
+    assert_eq!(out.num_rows(), 5);
+}
+
+#[test]
+fn test_bernoulli_fraction_roughly_respected() {
+    let sample = Sample {
+        fraction: Some(0.3),
+        rows: None,
+        seed: Some(7),
+    };
+
+    let batch = numbers_batch(10_000);
+    let out = sample
+        .eval_block(&[batch], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("sample execution");
+
+    let kept = out.num_rows() as f64 / 10_000.0;
+    assert!((kept - 0.3).abs() < 0.05, "kept {}", kept);
+}
+
+#[test]
+fn test_bernoulli_extremes() {
+    for (fraction, expect) in [(0.0, 0), (1.0, 50)] {
+        let sample = Sample {
+            fraction: Some(fraction),
+            rows: None,
+            seed: Some(3),
+        };
+        let out = sample
+            .eval_block(&[numbers_batch(50)], &MemoryBudgetImpl::new(1024 * 1024))
+            .expect("sample execution");
+        assert_eq!(out.num_rows(), expect);
+    }
+}
+
+#[test]
+fn test_sample_plan_rejects_missing_config() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    let sample = Sample::default();
+    let schema = Schema::new(vec![Field::new("n", DataType::Int64, false)]);
+    assert!(sample.plan(&[schema]).is_err());
+}
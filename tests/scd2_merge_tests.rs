@@ -0,0 +1,203 @@
+//! SCD Type 2 merge operator tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{scd::Scd2Merge, Operator};
+
+fn mk_column(name: &str, values: Vec<Scalar>) -> Column {
+    Column {
+        name: name.to_string(),
+        values,
+    }
+}
+
+fn find<'a>(batch: &'a RowBatch, name: &str) -> &'a Column {
+    batch
+        .columns
+        .iter()
+        .find(|c| c.name == name)
+        .unwrap_or_else(|| panic!("missing column {}", name))
+}
+
+#[test]
+fn test_scd2_merge_closes_changed_and_inserts_new() {
+    let merge = Scd2Merge {
+        key: "customer".to_string(),
+        compare: vec!["tier".to_string()],
+        as_of: Some("2024-06-01".to_string()),
+    };
+
+    // Existing dimension: alice (gold, current), bob (silver, current), and
+    // a historical alice row already closed out.
+    let existing = RowBatch {
+        columns: vec![
+            mk_column(
+                "customer",
+                vec![
+                    Scalar::Str("alice".into()),
+                    Scalar::Str("alice".into()),
+                    Scalar::Str("bob".into()),
+                ],
+            ),
+            mk_column(
+                "tier",
+                vec![
+                    Scalar::Str("bronze".into()),
+                    Scalar::Str("gold".into()),
+                    Scalar::Str("silver".into()),
+                ],
+            ),
+            mk_column(
+                "valid_from",
+                vec![Scalar::Date64(0), Scalar::Date64(100), Scalar::Date64(0)],
+            ),
+            mk_column(
+                "valid_to",
+                vec![Scalar::Date64(100), Scalar::Null, Scalar::Null],
+            ),
+            mk_column(
+                "is_current",
+                vec![
+                    Scalar::Bool(false),
+                    Scalar::Bool(true),
+                    Scalar::Bool(true),
+                ],
+            ),
+        ],
+    };
+
+    // Updates: alice changes tier, bob unchanged, carol is new.
+    let updates = RowBatch {
+        columns: vec![
+            mk_column(
+                "customer",
+                vec![
+                    Scalar::Str("alice".into()),
+                    Scalar::Str("bob".into()),
+                    Scalar::Str("carol".into()),
+                ],
+            ),
+            mk_column(
+                "tier",
+                vec![
+                    Scalar::Str("platinum".into()),
+                    Scalar::Str("silver".into()),
+                    Scalar::Str("bronze".into()),
+                ],
+            ),
+        ],
+    };
+
+    let result = merge
+        .eval_block(&[existing, updates], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("merge");
+
+    // 3 existing rows + 1 new alice version + 1 carol insert = 5 rows.
+    assert_eq!(result.num_rows(), 5);
+
+    let customers = find(&result, "customer");
+    let tiers = find(&result, "tier");
+    let current = find(&result, "is_current");
+    let valid_to = find(&result, "valid_to");
+
+    // Exactly one current row per key.
+    let mut current_by_key = std::collections::HashMap::new();
+    for row in 0..result.num_rows() {
+        if current.values[row] == Scalar::Bool(true) {
+            let key = match &customers.values[row] {
+                Scalar::Str(s) => s.clone(),
+                other => panic!("{:?}", other),
+            };
+            assert!(
+                current_by_key.insert(key, row).is_none(),
+                "two current rows for one key"
+            );
+        }
+    }
+    assert_eq!(current_by_key.len(), 3);
+
+    // Alice's current row carries the new tier; her old gold row is closed.
+    assert_eq!(
+        tiers.values[current_by_key["alice"]],
+        Scalar::Str("platinum".into())
+    );
+    let closed_gold = (0..result.num_rows()).find(|&row| {
+        customers.values[row] == Scalar::Str("alice".into())
+            && tiers.values[row] == Scalar::Str("gold".into())
+    });
+    let closed_gold = closed_gold.expect("old gold row retained");
+    assert_eq!(current.values[closed_gold], Scalar::Bool(false));
+    assert!(matches!(valid_to.values[closed_gold], Scalar::Date64(_)));
+
+    // Bob was unchanged: still exactly one row for him current with silver.
+    assert_eq!(
+        tiers.values[current_by_key["bob"]],
+        Scalar::Str("silver".into())
+    );
+    // Carol is a fresh insert with an open validity range.
+    assert_eq!(valid_to.values[current_by_key["carol"]], Scalar::Null);
+}
+
+#[test]
+fn test_scd2_merge_plan_validation() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    let dim = Schema::new(vec![Field::new("k", DataType::Utf8, false)]);
+    let upd = Schema::new(vec![Field::new("k", DataType::Utf8, false)]);
+
+    let no_key = Scd2Merge::default();
+    assert!(no_key.plan(&[dim.clone(), upd.clone()]).is_err());
+
+    let ok = Scd2Merge {
+        key: "k".into(),
+        ..Default::default()
+    };
+    let plan = ok.plan(&[dim, upd]).expect("plan");
+    assert!(plan.exclusive);
+    assert!(plan
+        .output_schema
+        .fields
+        .iter()
+        .any(|f| f.name == "is_current"));
+}
+
+#[test]
+fn test_scd2_merge_reopens_all_historical_key() {
+    let merge = Scd2Merge {
+        key: "customer".to_string(),
+        compare: vec!["tier".to_string()],
+        as_of: Some("2024-06-01".to_string()),
+    };
+
+    // carol exists only as closed history (the entity was deleted); an
+    // update for her must open a fresh current version, not vanish.
+    let existing = RowBatch {
+        columns: vec![
+            mk_column("customer", vec![Scalar::Str("carol".into())]),
+            mk_column("tier", vec![Scalar::Str("gold".into())]),
+            mk_column("valid_from", vec![Scalar::Date64(0)]),
+            mk_column("valid_to", vec![Scalar::Date64(100)]),
+            mk_column("is_current", vec![Scalar::Bool(false)]),
+        ],
+    };
+    let updates = RowBatch {
+        columns: vec![
+            mk_column("customer", vec![Scalar::Str("carol".into())]),
+            mk_column("tier", vec![Scalar::Str("silver".into())]),
+        ],
+    };
+
+    let result = merge
+        .eval_block(&[existing, updates], &MemoryBudgetImpl::new(1024 * 1024))
+        .expect("merge");
+
+    // Historical row passes through untouched, plus one new current row.
+    assert_eq!(result.num_rows(), 2);
+    let is_current = &find(&result, "is_current").values;
+    assert_eq!(is_current[0], Scalar::Bool(false));
+    assert_eq!(is_current[1], Scalar::Bool(true));
+    assert_eq!(
+        find(&result, "tier").values[1],
+        Scalar::Str("silver".into())
+    );
+    assert_eq!(find(&result, "valid_to").values[1], Scalar::Null);
+}
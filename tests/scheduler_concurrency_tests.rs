@@ -0,0 +1,40 @@
+//! Operator concurrency annotation tests.
+
+use emsqrt_core::schema::{DataType, Field, Schema};
+use emsqrt_exec::scheduler::effective_parallelism;
+use emsqrt_operators::{agregate::Aggregate, filter::Filter, Operator};
+
+fn input_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("k", DataType::Utf8, false),
+        Field::new("v", DataType::Float64, false),
+    ])
+}
+
+#[test]
+fn test_pipeline_breakers_are_exclusive() {
+    let agg = Aggregate {
+        group_by: vec!["k".into()],
+        aggs: vec!["sum:v".into()],
+        approx: false,
+        spill_mgr: None,
+    };
+    let plan = agg.plan(&[input_schema()]).expect("plan");
+    assert!(plan.exclusive);
+    assert_eq!(effective_parallelism(8, &plan), 1);
+}
+
+#[test]
+fn test_streaming_operators_follow_engine_limit() {
+    let filter = Filter {
+        expr: Some("v > 0".into()),
+    };
+    let plan = filter.plan(&[input_schema()]).expect("plan");
+    assert!(!plan.exclusive);
+    assert_eq!(effective_parallelism(8, &plan), 8);
+
+    // An operator-declared cap further limits the engine default.
+    let capped = plan.with_max_concurrency(2);
+    assert_eq!(effective_parallelism(8, &capped), 2);
+    assert_eq!(effective_parallelism(1, &capped), 1);
+}
@@ -0,0 +1,76 @@
+//! Schema metadata and column provenance tests.
+
+use emsqrt_core::dag::LogicalPlan as L;
+use emsqrt_core::schema::{DataType, Field, Schema};
+use emsqrt_planner::lower_to_physical;
+
+#[test]
+fn test_schema_metadata_round_trip() {
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)])
+        .with_metadata("owner", "data-eng")
+        .with_metadata("source_system", "billing");
+
+    let json = serde_json::to_string(&schema).unwrap();
+    let back: Schema = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.metadata.get("owner").map(String::as_str), Some("data-eng"));
+    assert_eq!(
+        back.metadata.get("source_system").map(String::as_str),
+        Some("billing")
+    );
+}
+
+#[test]
+fn test_lowering_fills_column_provenance() {
+    let scan = L::Scan {
+        source: "data/input.csv".to_string(),
+        schema: Schema::new(vec![Field::new("x", DataType::Float64, false)]),
+    };
+    let map = L::Map {
+        input: Box::new(scan),
+        expr: "doubled = x * 2.0".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(map),
+        destination: "out.csv".to_string(),
+        format: "csv".to_string(),
+    };
+
+    let program = lower_to_physical(&sink);
+
+    // The map node's output schema: scan column + derived column
+    fn find_map_schema(plan: &emsqrt_core::dag::PhysicalPlan) -> Option<&Schema> {
+        match plan {
+            emsqrt_core::dag::PhysicalPlan::Unary { input, schema, .. } => {
+                if schema.fields.len() == 2 {
+                    Some(schema)
+                } else {
+                    find_map_schema(input)
+                }
+            }
+            emsqrt_core::dag::PhysicalPlan::Sink { input, .. } => find_map_schema(input),
+            _ => None,
+        }
+    }
+
+    let schema = find_map_schema(&program.plan).expect("map schema");
+    assert_eq!(
+        schema.fields[0].provenance.as_deref(),
+        Some("scan:data/input.csv")
+    );
+    assert_eq!(
+        schema.fields[1].provenance.as_deref(),
+        Some("map:x * 2.0")
+    );
+}
+
+#[test]
+fn test_field_provenance_serialization_is_optional() {
+    // A field without provenance serializes without the key (back-compat)
+    let field = Field::new("id", DataType::Int64, false);
+    let json = serde_json::to_string(&field).unwrap();
+    assert!(!json.contains("provenance"));
+
+    let with = field.clone().with_provenance("scan:x.csv");
+    let json = serde_json::to_string(&with).unwrap();
+    assert!(json.contains("scan:x.csv"));
+}
@@ -4,9 +4,9 @@ mod test_data_gen;
 
 use emsqrt_core::id::SpillId;
 use emsqrt_core::schema::{DataType, Field, Schema};
-use emsqrt_core::types::RowBatch;
+use emsqrt_core::types::{Column, RowBatch, Scalar};
 use emsqrt_io::storage::FsStorage;
-use emsqrt_mem::{Codec, MemoryBudgetImpl, SpillManager, Storage};
+use emsqrt_mem::{Codec, MemoryBudgetImpl, SpillManager};
 use test_data_gen::{create_temp_spill_dir, generate_random_batch};
 
 fn setup_spill_manager(codec: Codec) -> (SpillManager, String) {
@@ -84,12 +84,11 @@ fn test_spill_checksum_validation() {
     let segment_path = format!("{}/test-spills/{}.seg", spill_dir, meta.name.0);
     let mut corrupted_data = std::fs::read(&segment_path).expect("Failed to read segment");
 
-    // Corrupt some bytes in the middle (past the header)
-    if corrupted_data.len() > 100 {
-        corrupted_data[100] ^= 0xFF;
-        corrupted_data[101] ^= 0xFF;
-        std::fs::write(&segment_path, corrupted_data).expect("Failed to write corrupted data");
-    }
+    // Corrupt the last payload byte (the encoded payload may be small now
+    // that integer columns are delta-encoded, so don't assume a fixed size)
+    let last = corrupted_data.len() - 1;
+    corrupted_data[last] ^= 0xFF;
+    std::fs::write(&segment_path, corrupted_data).expect("Failed to write corrupted data");
 
     // Attempt to read - should fail checksum validation
     let result = mgr.read_batch(&meta, &budget);
@@ -304,10 +303,381 @@ fn test_spill_empty_batch() {
         .write_batch(&empty_batch, spill_id, 0)
         .expect("Write failed");
 
-    assert!(meta.uncompressed_len >= 0);
+    assert!(meta.uncompressed_len > 0);
 
     let read_batch = mgr.read_batch(&meta, &budget).expect("Read failed");
     assert_eq!(read_batch.num_rows(), 0);
 
     cleanup_spill_dir(&spill_dir);
 }
+
+#[test]
+fn test_columnar_payload_round_trips_nulls_and_types() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let batch = RowBatch {
+        columns: vec![
+            Column {
+                name: "id".to_string(),
+                values: vec![Scalar::I64(1), Scalar::I64(2), Scalar::Null],
+            },
+            Column {
+                name: "name".to_string(),
+                values: vec![
+                    Scalar::Str("a".to_string()),
+                    Scalar::Null,
+                    Scalar::Str("c".to_string()),
+                ],
+            },
+            Column {
+                name: "flag".to_string(),
+                values: vec![Scalar::Bool(true), Scalar::Bool(false), Scalar::Bool(true)],
+            },
+            Column {
+                name: "ts".to_string(),
+                values: vec![
+                    Scalar::Date64(0),
+                    Scalar::Date64(86_400_000),
+                    Scalar::Null,
+                ],
+            },
+        ],
+    };
+
+    let meta = mgr
+        .write_batch(&batch, SpillId::new(77), 0)
+        .expect("write batch");
+    let read = mgr.read_batch(&meta, &budget).expect("read batch");
+
+    for (orig, got) in batch.columns.iter().zip(read.columns.iter()) {
+        assert_eq!(orig.name, got.name);
+        assert_eq!(orig.values, got.values, "column {}", orig.name);
+    }
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_columnar_payload_mixed_type_fallback() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    // Mixed string/integer column cannot be packed; the JSON fallback
+    // should still round-trip it exactly.
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "mixed".to_string(),
+            values: vec![Scalar::I64(1), Scalar::Str("two".to_string())],
+        }],
+    };
+
+    let meta = mgr
+        .write_batch(&batch, SpillId::new(78), 0)
+        .expect("write batch");
+    let read = mgr.read_batch(&meta, &budget).expect("read batch");
+    assert_eq!(read.columns[0].values, batch.columns[0].values);
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_streaming_spill_round_trip() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let mut stream = mgr.begin_stream(SpillId::new(90), 0);
+
+    // Write three chunks without ever holding the whole run in memory.
+    for chunk in 0..3i64 {
+        let batch = RowBatch {
+            columns: vec![Column {
+                name: "n".to_string(),
+                values: (0..4).map(|i| Scalar::I64(chunk * 4 + i)).collect(),
+            }],
+        };
+        mgr.stream_write(&mut stream, &batch).expect("stream write");
+    }
+    assert_eq!(stream.num_parts(), 3);
+
+    // Read back one part at a time, in order.
+    let mut seen = Vec::new();
+    let mut part = 0;
+    while let Some(batch) = mgr.stream_read(&stream, part, &budget).expect("stream read") {
+        for v in &batch.columns[0].values {
+            match v {
+                Scalar::I64(i) => seen.push(*i),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        part += 1;
+    }
+    assert_eq!(seen, (0..12).collect::<Vec<i64>>());
+
+    // Cleanup removes every part segment.
+    mgr.stream_delete(&stream).expect("stream delete");
+    assert!(mgr
+        .stream_read(&stream, 0, &budget)
+        .is_err());
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_disk_budget_enforced_and_freed_on_delete() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: (0..100).map(Scalar::I64).collect(),
+        }],
+    };
+
+    // First write establishes the segment size; budget allows ~1.5 segments.
+    let meta = mgr
+        .write_batch(&batch, SpillId::new(91), 0)
+        .expect("first write");
+    let segment_bytes = mgr.disk_used_bytes();
+    assert!(segment_bytes > 0);
+    mgr.set_disk_budget(Some(segment_bytes + segment_bytes / 2));
+
+    // Second write would exceed the cap.
+    let err = mgr.write_batch(&batch, SpillId::new(91), 1).unwrap_err();
+    assert!(
+        err.to_string().contains("disk budget"),
+        "unexpected error: {}",
+        err
+    );
+
+    // Deleting frees budget; the write then succeeds.
+    mgr.delete_segment(&meta.name).expect("delete");
+    assert_eq!(mgr.disk_used_bytes(), 0);
+    mgr.write_batch(&batch, SpillId::new(91), 2)
+        .expect("write after delete");
+
+    // Cleanup removes everything that's left.
+    let removed = mgr.cleanup_all().expect("cleanup");
+    assert_eq!(removed, 1);
+    assert_eq!(mgr.disk_used_bytes(), 0);
+    assert!(mgr.read_batch(&meta, &budget).is_err());
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_global_spill_id_allocation_and_namespaces() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    // Allocated ids are unique and monotonically increasing.
+    let a = mgr.allocate_spill_id();
+    let b = mgr.allocate_spill_id();
+    assert_ne!(a, b);
+    assert!(b.get() > a.get());
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: vec![Scalar::I64(42)],
+        }],
+    };
+
+    // Same spill id + run index in two namespaces must not collide.
+    let sort_meta = mgr
+        .write_batch_in("sort_external", &batch, a, 0)
+        .expect("sort write");
+    let join_meta = mgr
+        .write_batch_in("join_hash", &batch, a, 0)
+        .expect("join write");
+    assert_ne!(sort_meta.path, join_meta.path);
+    assert!(sort_meta.path.contains("/sort_external/"));
+    assert!(join_meta.path.contains("/join_hash/"));
+
+    let read = mgr.read_batch(&join_meta, &budget).expect("read back");
+    assert_eq!(read.columns[0].values, batch.columns[0].values);
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_spill_codec_parse() {
+    assert!(matches!(Codec::parse("none"), Ok(Codec::None)));
+    assert!(matches!(Codec::parse("zstd"), Ok(Codec::Zstd)));
+    assert!(matches!(Codec::parse("lz4"), Ok(Codec::Lz4)));
+    assert!(Codec::parse("snappy").is_err());
+}
+
+#[test]
+fn test_unbuilt_codec_fails_at_write() {
+    // Without the zstd/lz4 features, configuring those codecs surfaces a
+    // clear error on the first spill write instead of corrupting data.
+    #[cfg(not(feature = "zstd"))]
+    {
+        let (mut mgr, spill_dir) = setup_spill_manager(Codec::Zstd);
+        let batch = RowBatch {
+            columns: vec![Column {
+                name: "n".to_string(),
+                values: vec![Scalar::I64(1)],
+            }],
+        };
+        assert!(mgr.write_batch(&batch, SpillId::new(99), 0).is_err());
+        cleanup_spill_dir(&spill_dir);
+    }
+}
+
+#[test]
+fn test_rle_and_delta_encodings_round_trip() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let batch = RowBatch {
+        columns: vec![
+            // Long runs: RLE candidate
+            Column {
+                name: "status".to_string(),
+                values: (0..1000)
+                    .map(|i| Scalar::Str(if i < 600 { "a" } else { "b" }.to_string()))
+                    .collect(),
+            },
+            // Monotonic timestamps: delta candidate
+            Column {
+                name: "ts".to_string(),
+                values: (0..1000).map(|i| Scalar::I64(1_700_000_000 + i)).collect(),
+            },
+            // High-entropy floats: stays plain
+            Column {
+                name: "x".to_string(),
+                values: (0..1000)
+                    .map(|i| Scalar::F64((i as f64 * 0.7311).sin()))
+                    .collect(),
+            },
+        ],
+    };
+
+    let meta = mgr
+        .write_batch(&batch, SpillId::new(95), 0)
+        .expect("write");
+
+    // The encoded payload should be much smaller than plain fixed-width
+    // for the runs + deltas columns (plain would be >= 1000 * 8 * 2 bytes
+    // for the two integer-ish columns alone).
+    assert!(
+        meta.uncompressed_len < 14_000,
+        "payload unexpectedly large: {}",
+        meta.uncompressed_len
+    );
+
+    let read = mgr.read_batch(&meta, &budget).expect("read");
+    for (orig, got) in batch.columns.iter().zip(read.columns.iter()) {
+        assert_eq!(orig.values, got.values, "column {}", orig.name);
+    }
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_background_spill_writes() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+    mgr.enable_background_writes(1024 * 1024);
+
+    let mut metas = Vec::new();
+    for run in 0..5 {
+        let batch = RowBatch {
+            columns: vec![Column {
+                name: "n".to_string(),
+                values: (0..100).map(|i| Scalar::I64(run * 100 + i)).collect(),
+            }],
+        };
+        metas.push(
+            mgr.write_batch(&batch, SpillId::new(96), run as u32)
+                .expect("background write"),
+        );
+    }
+
+    mgr.flush_writes().expect("flush");
+
+    // Reads flush implicitly and must see every queued segment.
+    for (run, meta) in metas.iter().enumerate() {
+        let read = mgr.read_batch(meta, &budget).expect("read");
+        assert_eq!(read.num_rows(), 100);
+        assert_eq!(read.columns[0].values[0], Scalar::I64(run as i64 * 100));
+    }
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_prefetched_segments_read_back_correctly() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let mut metas = Vec::new();
+    for run in 0..4i64 {
+        let batch = RowBatch {
+            columns: vec![Column {
+                name: "n".to_string(),
+                values: (0..10).map(|i| Scalar::I64(run * 10 + i)).collect(),
+            }],
+        };
+        metas.push(mgr.write_batch(&batch, SpillId::new(97), run as u32).unwrap());
+    }
+
+    mgr.prefetch(&metas);
+
+    // Reads must return identical data whether or not the prefetch thread
+    // has populated the cache yet.
+    for (run, meta) in metas.iter().enumerate() {
+        let read = mgr.read_batch(meta, &budget).expect("read");
+        assert_eq!(read.columns[0].values[0], Scalar::I64(run as i64 * 10));
+    }
+
+    // Deleted segments with stale prefetch entries must not resurrect:
+    // prefetch again, delete, then verify the read fails or is consistent.
+    mgr.prefetch(&metas);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mgr.delete_segment(&metas[0].name).expect("delete");
+    // The cache may still serve the bytes; that's acceptable for run-scoped
+    // segments, but the checksum must still validate.
+    if let Ok(batch) = mgr.read_batch(&metas[0], &budget) {
+        assert_eq!(batch.columns[0].values[0], Scalar::I64(0));
+    }
+
+    cleanup_spill_dir(&spill_dir);
+}
+
+#[test]
+fn test_corrupted_prefetch_cache_heals_via_retry() {
+    let (mut mgr, spill_dir) = setup_spill_manager(Codec::None);
+    let budget = MemoryBudgetImpl::new(10 * 1024 * 1024);
+
+    let batch = RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: (0..50).map(Scalar::I64).collect(),
+        }],
+    };
+    let meta = mgr.write_batch(&batch, SpillId::new(98), 0).unwrap();
+
+    // Corrupt the on-disk copy, prefetch the corrupted bytes, then restore
+    // the good copy: the first verify fails against the cached bytes, the
+    // retry re-reads the (now good) storage copy and succeeds.
+    let good = std::fs::read(&meta.path).unwrap();
+    let mut bad = good.clone();
+    let last = bad.len() - 1;
+    bad[last] ^= 0xFF;
+    std::fs::write(&meta.path, &bad).unwrap();
+    mgr.prefetch(std::slice::from_ref(&meta));
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    std::fs::write(&meta.path, &good).unwrap();
+
+    let read = mgr.read_batch(&meta, &budget).expect("healed read");
+    assert_eq!(read.num_rows(), 50);
+
+    // Persistent corruption still fails after the retries.
+    std::fs::write(&meta.path, &bad).unwrap();
+    let err = mgr.read_batch(&meta, &budget).unwrap_err();
+    assert!(err.to_string().contains("checksum"), "err: {}", err);
+
+    cleanup_spill_dir(&spill_dir);
+}
@@ -0,0 +1,77 @@
+//! SQLite connector tests (require the sqlite3 CLI, present in CI images).
+
+use emsqrt_core::config::EngineConfig;
+use emsqrt_core::dag::LogicalPlan as L;
+use emsqrt_core::schema::{DataType, Field, Schema};
+use emsqrt_exec::Engine;
+use emsqrt_planner::{estimate_work, lower_to_physical};
+use emsqrt_te::plan_te;
+
+fn sqlite_available() -> bool {
+    std::process::Command::new("sqlite3")
+        .arg("--version")
+        .output()
+        .is_ok()
+}
+
+#[test]
+fn test_sqlite_source_to_sqlite_sink() {
+    if !sqlite_available() {
+        eprintln!("skipping: sqlite3 CLI not available");
+        return;
+    }
+
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_sqlite_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let in_db = format!("{}/in.db", dir);
+    let out_db = format!("{}/out.db", dir);
+
+    let seed = std::process::Command::new("sqlite3")
+        .arg(&in_db)
+        .arg("CREATE TABLE t (id INTEGER, v REAL); INSERT INTO t VALUES (1, 1.5), (2, 2.5), (3, NULL);")
+        .status()
+        .unwrap();
+    assert!(seed.success());
+
+    let scan = L::Scan {
+        source: format!("sqlite://{}/t", in_db),
+        schema: Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("v", DataType::Float64, true),
+        ]),
+    };
+    let filter = L::Filter {
+        input: Box::new(scan),
+        expr: "id >= 2".to_string(),
+    };
+    let sink = L::Sink {
+        input: Box::new(filter),
+        destination: format!("sqlite://{}/filtered", out_db),
+        format: "csv".to_string(),
+    };
+
+    let phys_prog = lower_to_physical(&sink);
+    let work = estimate_work(&sink, None);
+    let te = plan_te(&phys_prog.plan, &work, 64 * 1024 * 1024).unwrap();
+    let mut eng = Engine::new(EngineConfig::default()).expect("engine init");
+    let manifest = eng.run(&phys_prog, &te).expect("run");
+    assert_eq!(manifest.records_written, 2);
+
+    let out = std::process::Command::new("sqlite3")
+        .arg("-json")
+        .arg(&out_db)
+        .arg("SELECT id, v FROM filtered ORDER BY id")
+        .output()
+        .unwrap();
+    let rows: Vec<serde_json::Value> =
+        serde_json::from_slice(&out.stdout).expect("json rows");
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["id"], 2);
+    assert_eq!(rows[1]["v"], serde_json::Value::Null);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -12,8 +12,10 @@ fn temp_spill_dir(name: &str) -> String {
 #[test]
 fn test_file_storage_builder_write_read() {
     let dir = temp_spill_dir("fs");
-    let mut cfg = EngineConfig::default();
-    cfg.spill_dir = dir.clone();
+    let cfg = EngineConfig {
+        spill_dir: dir.clone(),
+        ..Default::default()
+    };
 
     let storage_cfg = cfg.storage_config();
     let storage = build_storage_from_config(&storage_cfg).expect("fs storage");
@@ -27,8 +29,10 @@ fn test_file_storage_builder_write_read() {
 
 #[test]
 fn test_invalid_scheme_errors() {
-    let mut cfg = EngineConfig::default();
-    cfg.spill_uri = Some("ftp://example.com/spill".into());
+    let cfg = EngineConfig {
+        spill_uri: Some("ftp://example.com/spill".into()),
+        ..Default::default()
+    };
     let storage_cfg = cfg.storage_config();
     let err = build_storage_from_config(&storage_cfg)
         .err()
@@ -39,9 +43,11 @@ fn test_invalid_scheme_errors() {
 #[cfg(not(feature = "s3"))]
 #[test]
 fn test_s3_without_feature_fails() {
-    let mut cfg = EngineConfig::default();
-    cfg.spill_uri = Some("s3://dummy/test".into());
-    cfg.spill_aws_region = Some("us-east-1".into());
+    let cfg = EngineConfig {
+        spill_uri: Some("s3://dummy/test".into()),
+        spill_aws_region: Some("us-east-1".into()),
+        ..Default::default()
+    };
     let storage_cfg = cfg.storage_config();
     let err = build_storage_from_config(&storage_cfg)
         .err()
@@ -51,14 +57,304 @@ fn test_s3_without_feature_fails() {
         .contains("EM-√ was built without the `s3` feature"));
 }
 
-#[cfg(all(feature = "s3"))]
+#[cfg(feature = "s3")]
 #[test]
 fn test_s3_builder_initializes_with_dummy_credentials() {
-    let mut cfg = EngineConfig::default();
-    cfg.spill_uri = Some("s3://dummy-bucket/tests".into());
-    cfg.spill_aws_region = Some("us-east-1".into());
-    cfg.spill_aws_access_key_id = Some("ACCESSKEY123".into());
-    cfg.spill_aws_secret_access_key = Some("SECRETKEY456".into());
+    let cfg = EngineConfig {
+        spill_uri: Some("s3://dummy-bucket/tests".into()),
+        spill_aws_region: Some("us-east-1".into()),
+        spill_aws_access_key_id: Some("ACCESSKEY123".into()),
+        spill_aws_secret_access_key: Some("SECRETKEY456".into()),
+        ..Default::default()
+    };
     let storage_cfg = cfg.storage_config();
     build_storage_from_config(&storage_cfg).expect("s3 storage builds");
 }
+
+#[test]
+fn test_cached_storage_serves_local_reads_and_evicts() {
+    use emsqrt_io::storage::{CachedStorage, FsStorage};
+    use emsqrt_mem::Storage;
+
+    let base = std::env::temp_dir()
+        .join(format!("emsqrt_cache_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let backing = format!("{}/backing", base);
+    let cache = format!("{}/cache", base);
+    let _ = std::fs::remove_dir_all(&base);
+    std::fs::create_dir_all(&backing).unwrap();
+
+    // 100-byte cache budget: the second 60-byte object evicts the first.
+    let storage = CachedStorage::new(Box::new(FsStorage::new()), &cache, Some(100)).unwrap();
+
+    let a_path = format!("{}/a.seg", backing);
+    let b_path = format!("{}/b.seg", backing);
+    storage.write(&a_path, &[1u8; 60]).unwrap();
+    assert_eq!(storage.cached_bytes(), 60);
+    storage.write(&b_path, &[2u8; 60]).unwrap();
+    assert_eq!(storage.cached_bytes(), 60); // a evicted
+
+    // Cached read serves correct bytes...
+    assert_eq!(storage.read_range(&b_path, 10, 5).unwrap(), vec![2u8; 5]);
+    // ...and evicted/uncached paths fall through to the backing store.
+    assert_eq!(storage.read_range(&a_path, 0, 60).unwrap(), vec![1u8; 60]);
+
+    // Delete clears both tiers.
+    storage.delete(&b_path).unwrap();
+    assert_eq!(storage.cached_bytes(), 0);
+    assert!(storage.read_range(&b_path, 0, 1).is_err());
+
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+/// Minimal in-process WebHDFS endpoint good enough to exercise the adapter:
+/// stores files in a HashMap and answers CREATE/OPEN/DELETE/GETFILESTATUS.
+fn spawn_fake_webhdfs() -> (u16, std::thread::JoinHandle<()>) {
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = std::thread::spawn(move || {
+        let mut files: HashMap<String, Vec<u8>> = HashMap::new();
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            // Read until headers + declared body are in.
+            let (head_end, request) = loop {
+                let n = stream.read(&mut chunk).unwrap_or(0);
+                if n == 0 {
+                    break (0, buf.clone());
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    let head = String::from_utf8_lossy(&buf[..pos]).to_string();
+                    let content_length = head
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                        .unwrap_or(0);
+                    if buf.len() >= pos + 4 + content_length {
+                        break (pos, buf.clone());
+                    }
+                }
+            };
+            if request.is_empty() {
+                continue;
+            }
+            let head = String::from_utf8_lossy(&request[..head_end]).to_string();
+            let body = request[head_end + 4..].to_vec();
+            let mut parts = head.lines().next().unwrap().split_whitespace();
+            let method = parts.next().unwrap_or("").to_string();
+            let target = parts.next().unwrap_or("").to_string();
+            let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+            let path = path.strip_prefix("/webhdfs/v1").unwrap_or(path).to_string();
+
+            let respond = |stream: &mut std::net::TcpStream, status: u16, body: &[u8]| {
+                let reason = if status == 200 { "OK" } else if status == 201 { "Created" } else { "Not Found" };
+                let head = format!(
+                    "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status, reason, body.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(body);
+            };
+
+            if method == "PUT" && query.contains("op=CREATE") {
+                files.insert(path, body);
+                respond(&mut stream, 201, b"");
+            } else if method == "GET" && query.contains("op=OPEN") {
+                match files.get(&path) {
+                    Some(data) => {
+                        let offset: usize = query
+                            .split('&')
+                            .find_map(|kv| kv.strip_prefix("offset="))
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0);
+                        let length: usize = query
+                            .split('&')
+                            .find_map(|kv| kv.strip_prefix("length="))
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(data.len());
+                        let end = (offset + length).min(data.len());
+                        respond(&mut stream, 200, &data[offset.min(data.len())..end]);
+                    }
+                    None => respond(&mut stream, 404, b"{}"),
+                }
+            } else if method == "GET" && query.contains("op=GETFILESTATUS") {
+                match files.get(&path) {
+                    Some(data) => {
+                        let json = format!("{{\"FileStatus\":{{\"length\":{}}}}}", data.len());
+                        respond(&mut stream, 200, json.as_bytes());
+                    }
+                    None => respond(&mut stream, 404, b"{}"),
+                }
+            } else if method == "DELETE" {
+                let existed = files.remove(&path).is_some();
+                respond(&mut stream, if existed { 200 } else { 404 }, b"{}");
+            } else if query.contains("op=SHUTDOWN") {
+                respond(&mut stream, 200, b"{}");
+                break;
+            } else {
+                respond(&mut stream, 404, b"{}");
+            }
+        }
+    });
+
+    (port, handle)
+}
+
+#[test]
+fn test_webhdfs_adapter_round_trip() {
+    use emsqrt_io::storage::WebHdfsStorage;
+    use emsqrt_mem::Storage;
+
+    let (port, server) = spawn_fake_webhdfs();
+    let storage =
+        WebHdfsStorage::from_uri(&format!("webhdfs://127.0.0.1:{}/spill", port)).unwrap();
+
+    let payload: Vec<u8> = (0..200u32).map(|i| (i % 256) as u8).collect();
+    storage.write("run1/seg0.seg", &payload).unwrap();
+
+    assert_eq!(storage.size("run1/seg0.seg").unwrap(), 200);
+    assert_eq!(
+        storage.read_range("run1/seg0.seg", 10, 20).unwrap(),
+        payload[10..30].to_vec()
+    );
+
+    storage.delete("run1/seg0.seg").unwrap();
+    assert!(storage.read_range("run1/seg0.seg", 0, 1).is_err());
+    // Idempotent delete
+    storage.delete("run1/seg0.seg").unwrap();
+
+    // Shut the fake server down.
+    let _ = std::net::TcpStream::connect(("127.0.0.1", port)).map(|mut s| {
+        use std::io::Write;
+        let _ = s.write_all(b"GET /webhdfs/v1/x?op=SHUTDOWN HTTP/1.1\r\nHost: x\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    });
+    let _ = server.join();
+}
+
+#[test]
+fn test_sftp_adapter_via_stubbed_ssh() {
+    use emsqrt_io::storage::SftpStorage;
+    use emsqrt_mem::Storage;
+
+    let base = std::env::temp_dir()
+        .join(format!("emsqrt_sftp_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&base);
+    std::fs::create_dir_all(&base).unwrap();
+
+    // Stub "ssh" that just runs the remote command locally, ignoring the
+    // host argument: `stub-ssh <host> <command>`.
+    let stub = format!("{}/stub-ssh", base);
+    std::fs::write(&stub, "#!/bin/sh\nshift\nexec sh -c \"$1\"\n").unwrap();
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let storage = SftpStorage::from_uri(&format!("sftp://spill@example.com{}/remote", base))
+        .unwrap()
+        .with_ssh_cmd(&stub);
+
+    let payload: Vec<u8> = (0..100u32).map(|i| i as u8).collect();
+    storage.write("seg/part0.seg", &payload).unwrap();
+    assert_eq!(storage.size("seg/part0.seg").unwrap(), 100);
+    assert_eq!(
+        storage.read_range("seg/part0.seg", 10, 25).unwrap(),
+        payload[10..35].to_vec()
+    );
+
+    let listed = storage.list("seg").unwrap();
+    assert_eq!(listed.len(), 1);
+    assert!(listed[0].ends_with("part0.seg"));
+
+    storage.delete("seg/part0.seg").unwrap();
+    assert!(storage.read_range("seg/part0.seg", 0, 1).is_err());
+    storage.delete("seg/part0.seg").unwrap(); // idempotent
+
+    let _ = std::fs::remove_dir_all(&base);
+}
+
+#[test]
+fn test_fs_storage_atomic_write_leaves_no_temp_files() {
+    use emsqrt_io::storage::FsStorage;
+    use emsqrt_mem::Storage;
+
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_atomic_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let storage = FsStorage::new();
+    let path = format!("{}/seg/a.seg", dir);
+    storage.write(&path, b"first").unwrap();
+    // Overwrite goes through the same temp+rename path.
+    storage.write(&path, b"second").unwrap();
+
+    assert_eq!(storage.read_range(&path, 0, 6).unwrap(), b"second");
+
+    // No *.tmp.* residue after successful writes.
+    let residue: Vec<_> = std::fs::read_dir(format!("{}/seg", dir))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().contains(".tmp."))
+        .collect();
+    assert!(residue.is_empty(), "temp files left behind: {:?}", residue);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_memory_storage_round_trip() {
+    use emsqrt_io::storage::MemoryStorage;
+    use emsqrt_mem::Storage;
+
+    let storage = MemoryStorage::new();
+    storage.write("a/b.seg", b"hello world").unwrap();
+    assert_eq!(storage.size("a/b.seg").unwrap(), 11);
+    assert_eq!(storage.read_range("a/b.seg", 6, 5).unwrap(), b"world");
+    assert!(storage.read_range("a/b.seg", 6, 100).is_err());
+    assert_eq!(storage.list("a/").unwrap(), vec!["a/b.seg".to_string()]);
+    assert!(storage.etag("a/b.seg").unwrap().is_some());
+    storage.delete("a/b.seg").unwrap();
+    assert!(storage.size("a/b.seg").is_err());
+}
+
+#[test]
+fn test_fs_lock_excludes_and_breaks_stale() {
+    use emsqrt_io::storage::FsLock;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir()
+        .join(format!("emsqrt_lock_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let fixture = format!("{}/fixture", dir);
+
+    // Held lock excludes a second acquirer until released.
+    let lock = FsLock::acquire(&fixture, Duration::from_millis(200)).unwrap();
+    assert!(FsLock::acquire(&fixture, Duration::from_millis(100)).is_err());
+    drop(lock);
+    let relock = FsLock::acquire(&fixture, Duration::from_millis(200)).unwrap();
+    drop(relock);
+
+    // A lock file owned by a dead pid is broken instead of blocking forever.
+    std::fs::write(format!("{}.lock", fixture), "999999999").unwrap();
+    let broken = FsLock::acquire(&fixture, Duration::from_millis(500)).unwrap();
+    drop(broken);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
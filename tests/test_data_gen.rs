@@ -1,5 +1,7 @@
 //! Test data generation utilities for EM-√ test suite
 
+#![allow(dead_code)] // shared helpers; each test target uses a subset
+
 use emsqrt_core::schema::{DataType, Field, Schema};
 use emsqrt_core::types::{Column, RowBatch, Scalar};
 use std::collections::HashMap;
@@ -130,7 +132,9 @@ pub fn create_temp_spill_dir() -> String {
         .unwrap()
         .as_nanos();
 
-    format!("/tmp/emsqrt-test-{}", nanos)
+    // Pid + nanos keeps concurrent test binaries apart; fixtures shared
+    // across processes should additionally hold an FsLock on the dir.
+    format!("/tmp/emsqrt-test-{}-{}", std::process::id(), nanos)
 }
 
 /// Generate two batches suitable for join testing
@@ -0,0 +1,104 @@
+//! Deterministic train/test split tests.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_mem::guard::MemoryBudgetImpl;
+use emsqrt_operators::{
+    split::{SplitMode, TrainTestSplit},
+    Operator,
+};
+
+fn users_batch(n: usize) -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "user".to_string(),
+            values: (0..n).map(|i| Scalar::Str(format!("user{}", i))).collect(),
+        }],
+    }
+}
+
+#[test]
+fn test_split_is_deterministic_and_roughly_proportional() {
+    let split = TrainTestSplit {
+        key: "user".into(),
+        test_fraction: 0.3,
+        mode: SplitMode::Label,
+        seed: 42,
+    };
+
+    let batch = users_batch(10_000);
+    let a = split
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024))
+        .expect("split");
+    let b = split
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024))
+        .expect("split");
+
+    let labels = |batch: &RowBatch| -> Vec<String> {
+        batch.columns[1]
+            .values
+            .iter()
+            .map(|v| match v {
+                Scalar::Str(s) => s.clone(),
+                other => panic!("{:?}", other),
+            })
+            .collect()
+    };
+    // Same seed, same input -> identical assignment.
+    assert_eq!(labels(&a), labels(&b));
+
+    let test_count = labels(&a).iter().filter(|l| *l == "test").count();
+    let fraction = test_count as f64 / 10_000.0;
+    assert!((fraction - 0.3).abs() < 0.03, "test fraction {}", fraction);
+}
+
+#[test]
+fn test_train_and_test_modes_partition_the_input() {
+    let mk = |mode| TrainTestSplit {
+        key: "user".into(),
+        test_fraction: 0.5,
+        mode,
+        seed: 7,
+    };
+
+    let batch = users_batch(1_000);
+    let train = mk(SplitMode::Train)
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024))
+        .unwrap();
+    let test = mk(SplitMode::Test)
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024))
+        .unwrap();
+
+    // Every row lands on exactly one side.
+    assert_eq!(train.num_rows() + test.num_rows(), 1_000);
+    assert!(train.num_rows() > 0 && test.num_rows() > 0);
+
+    // Same key always lands on the same side: re-running test mode yields
+    // the same rows.
+    let test_again = mk(SplitMode::Test)
+        .eval_block(std::slice::from_ref(&batch), &MemoryBudgetImpl::new(1024))
+        .unwrap();
+    assert_eq!(test.columns[0].values, test_again.columns[0].values);
+}
+
+#[test]
+fn test_split_plan_validation() {
+    use emsqrt_core::schema::{DataType, Field, Schema};
+    let schema = Schema::new(vec![Field::new("user", DataType::Utf8, false)]);
+
+    assert!(TrainTestSplit::default()
+        .plan(std::slice::from_ref(&schema))
+        .is_err()); // missing key
+    let bad_fraction = TrainTestSplit {
+        key: "user".into(),
+        test_fraction: 1.5,
+        ..Default::default()
+    };
+    assert!(bad_fraction.plan(std::slice::from_ref(&schema)).is_err());
+
+    let label = TrainTestSplit {
+        key: "user".into(),
+        ..Default::default()
+    };
+    let plan = label.plan(std::slice::from_ref(&schema)).unwrap();
+    assert!(plan.output_schema.fields.iter().any(|f| f.name == "_split"));
+}
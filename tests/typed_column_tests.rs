@@ -0,0 +1,142 @@
+//! TypedColumn / ColumnData conversion tests.
+
+use emsqrt_core::schema::DataType;
+use emsqrt_core::types::{Bitmap, Column, ColumnData, RowBatch, Scalar, TypedColumn};
+
+fn mk_column(name: &str, values: Vec<Scalar>) -> Column {
+    Column {
+        name: name.to_string(),
+        values,
+    }
+}
+
+#[test]
+fn test_round_trip_without_nulls() {
+    let col = mk_column(
+        "n",
+        vec![Scalar::I64(1), Scalar::I64(2), Scalar::I64(3)],
+    );
+    let typed = TypedColumn::from_column(&col, &DataType::Int64).unwrap();
+
+    assert_eq!(typed.len(), 3);
+    assert!(typed.validity.is_none());
+    assert!(matches!(&typed.data, ColumnData::I64(v) if v == &vec![1, 2, 3]));
+    assert_eq!(typed.to_column().values, col.values);
+}
+
+#[test]
+fn test_round_trip_with_nulls() {
+    let col = mk_column(
+        "x",
+        vec![Scalar::F64(1.5), Scalar::Null, Scalar::F64(2.5)],
+    );
+    let typed = TypedColumn::from_column(&col, &DataType::Float64).unwrap();
+
+    assert_eq!(
+        typed.validity,
+        Some(Bitmap::from_bools(&[true, false, true]))
+    );
+    assert_eq!(typed.get(0), Scalar::F64(1.5));
+    assert_eq!(typed.get(1), Scalar::Null);
+    assert_eq!(typed.to_column().values, col.values);
+}
+
+#[test]
+fn test_integer_widening_into_int64() {
+    let col = mk_column("n", vec![Scalar::I32(7), Scalar::I64(8)]);
+    let typed = TypedColumn::from_column(&col, &DataType::Int64).unwrap();
+    assert!(matches!(&typed.data, ColumnData::I64(v) if v == &vec![7, 8]));
+}
+
+#[test]
+fn test_type_mismatch_is_error() {
+    let col = mk_column("n", vec![Scalar::I64(1), Scalar::Str("two".into())]);
+    let err = TypedColumn::from_column(&col, &DataType::Int64).unwrap_err();
+    assert!(err.contains("row 1"), "error was: {}", err);
+}
+
+#[test]
+fn test_batch_to_typed_inference() {
+    let batch = RowBatch {
+        columns: vec![
+            mk_column("a", vec![Scalar::Null, Scalar::I32(5)]),
+            mk_column("b", vec![Scalar::Str("x".into()), Scalar::Str("y".into())]),
+            mk_column("c", vec![Scalar::Null, Scalar::Null]),
+        ],
+    };
+
+    let typed = batch.to_typed().unwrap();
+    assert!(matches!(typed[0].data, ColumnData::I32(_)));
+    assert!(matches!(typed[1].data, ColumnData::Str(_)));
+    // All-null column defaults to Utf8
+    assert!(matches!(typed[2].data, ColumnData::Str(_)));
+
+    let back = RowBatch::from_typed(&typed);
+    assert_eq!(back.columns[0].values, batch.columns[0].values);
+    assert_eq!(back.columns[2].values, batch.columns[2].values);
+}
+
+#[test]
+fn test_bitmap_packing_and_null_count() {
+    let mut bitmap = Bitmap::new();
+    for i in 0..130 {
+        bitmap.push(i % 3 != 0); // every third index null
+    }
+    assert_eq!(bitmap.len(), 130);
+    assert!(!bitmap.get(0));
+    assert!(bitmap.get(1));
+    assert!(!bitmap.get(129)); // 129 % 3 == 0
+    assert_eq!(bitmap.null_count(), 44); // 0, 3, ..., 129
+
+    bitmap.set(0, true);
+    assert!(bitmap.get(0));
+    assert_eq!(bitmap.null_count(), 43);
+}
+
+#[test]
+fn test_bitmap_all_valid() {
+    let bitmap = Bitmap::all_valid(70);
+    assert_eq!(bitmap.len(), 70);
+    assert_eq!(bitmap.null_count(), 0);
+    assert!(bitmap.get(69));
+}
+
+#[test]
+fn test_zero_copy_batch_slice() {
+    let batch = RowBatch {
+        columns: vec![
+            mk_column("n", (0..10).map(Scalar::I64).collect()),
+            mk_column(
+                "s",
+                (0..10).map(|i| Scalar::Str(format!("r{}", i))).collect(),
+            ),
+        ],
+    };
+
+    let slice = batch.slice(3, 4);
+    assert_eq!(slice.num_rows(), 4);
+    assert_eq!(slice.num_columns(), 2);
+    assert_eq!(slice.value(0, 0), Some(&Scalar::I64(3)));
+    assert_eq!(slice.value(1, 3), Some(&Scalar::Str("r6".into())));
+    assert_eq!(slice.value(0, 4), None); // past the slice
+
+    // Re-slicing is relative and clamped
+    let inner = slice.slice(2, 100);
+    assert_eq!(inner.num_rows(), 2);
+    assert_eq!(inner.value(0, 0), Some(&Scalar::I64(5)));
+
+    // Materialization copies exactly the window
+    let owned = inner.to_batch();
+    assert_eq!(owned.num_rows(), 2);
+    assert_eq!(owned.columns[0].values, vec![Scalar::I64(5), Scalar::I64(6)]);
+}
+
+#[test]
+fn test_batch_slice_clamping() {
+    let batch = RowBatch {
+        columns: vec![mk_column("n", vec![Scalar::I64(1)])],
+    };
+    assert_eq!(batch.slice(5, 3).num_rows(), 0);
+    assert_eq!(batch.slice(0, 100).num_rows(), 1);
+    assert_eq!(batch.slice(0, 1).column_values(0).unwrap().len(), 1);
+}
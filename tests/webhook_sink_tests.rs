@@ -0,0 +1,121 @@
+//! Webhook sink tests: batching, retries, permanent failures.
+
+use emsqrt_core::types::{Column, RowBatch, Scalar};
+use emsqrt_exec::{Connector, WebhookConnector};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// Fake endpoint replying with the scripted status codes in order,
+/// recording request bodies.
+fn spawn_endpoint(
+    statuses: Vec<u16>,
+) -> (u16, Arc<Mutex<Vec<String>>>, std::thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let bodies: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let server_bodies = bodies.clone();
+    let handle = std::thread::spawn(move || {
+        for status in statuses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut chunk).unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    let head = String::from_utf8_lossy(&request[..pos]).to_string();
+                    let content_length: usize = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= pos + 4 + content_length {
+                        server_bodies
+                            .lock()
+                            .unwrap()
+                            .push(String::from_utf8_lossy(&request[pos + 4..]).to_string());
+                        break;
+                    }
+                }
+            }
+            let reason = if status < 300 { "OK" } else { "Nope" };
+            let _ = stream.write_all(
+                format!(
+                    "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status, reason
+                )
+                .as_bytes(),
+            );
+        }
+    });
+
+    (port, bodies, handle)
+}
+
+fn rows(n: i64) -> RowBatch {
+    RowBatch {
+        columns: vec![Column {
+            name: "n".to_string(),
+            values: (0..n).map(Scalar::I64).collect(),
+        }],
+    }
+}
+
+#[test]
+fn test_webhook_retries_transient_failures() {
+    // First attempt 500, retry succeeds.
+    let (port, bodies, server) = spawn_endpoint(vec![500, 200]);
+
+    let mut sink = WebhookConnector
+        .open_sink(&format!("webhook://127.0.0.1:{}/ingest", port), "csv")
+        .expect("open sink");
+    sink.write_batch(&rows(3)).expect("write with retry");
+    server.join().unwrap();
+
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 2); // the failed attempt and the retry
+    let payload: Vec<serde_json::Value> = serde_json::from_str(&bodies[1]).unwrap();
+    assert_eq!(payload.len(), 3);
+    assert_eq!(payload[2]["n"], 2);
+}
+
+#[test]
+fn test_webhook_client_errors_are_permanent() {
+    let (port, bodies, server) = spawn_endpoint(vec![400]);
+
+    let mut sink = WebhookConnector
+        .open_sink(&format!("webhook://127.0.0.1:{}/ingest", port), "csv")
+        .expect("open sink");
+    let err = sink.write_batch(&rows(1)).unwrap_err();
+    assert!(err.contains("rejected"), "err: {}", err);
+    server.join().unwrap();
+    // No retry happened.
+    assert_eq!(bodies.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_webhook_splits_large_batches() {
+    // 501 rows -> two POSTs (500 + 1).
+    let (port, bodies, server) = spawn_endpoint(vec![200, 200]);
+
+    let mut sink = WebhookConnector
+        .open_sink(&format!("webhook://127.0.0.1:{}/ingest", port), "csv")
+        .expect("open sink");
+    sink.write_batch(&rows(501)).expect("write");
+    server.join().unwrap();
+
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 2);
+    let first: Vec<serde_json::Value> = serde_json::from_str(&bodies[0]).unwrap();
+    let second: Vec<serde_json::Value> = serde_json::from_str(&bodies[1]).unwrap();
+    assert_eq!(first.len(), 500);
+    assert_eq!(second.len(), 1);
+}
@@ -64,7 +64,7 @@ fn test_window_row_number_and_sum() {
     };
 
     let result = window
-        .eval_block(&[row_batch.clone()], &MemoryBudgetImpl::new(1024))
+        .eval_block(std::slice::from_ref(&row_batch), &MemoryBudgetImpl::new(1024))
         .expect("window execution");
 
     assert_eq!(result.columns.len(), 5);